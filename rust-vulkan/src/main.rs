@@ -1,16 +1,18 @@
 use std::sync::Arc;
+use tracing::{error, info};
+use tracing_subscriber::EnvFilter;
+use vulkan_context::{
+    clear_values, create_framebuffers, create_render_pass, select_sample_count,
+    PresentModePreference, VulkanContext,
+};
 use vulkano::{
     buffer::{Buffer, BufferContents, BufferCreateInfo, BufferUsage},
     command_buffer::{
         allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
         RenderPassBeginInfo, SubpassBeginInfo, SubpassContents,
     },
-    device::{
-        physical::PhysicalDeviceType, Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo,
-        QueueFlags,
-    },
-    image::{view::ImageView, Image, ImageUsage},
-    instance::{Instance, InstanceCreateFlags, InstanceCreateInfo},
+    format::NumericFormat,
+    image::Image,
     memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
     pipeline::{
         graphics::{
@@ -25,32 +27,37 @@ use vulkano::{
         layout::PipelineDescriptorSetLayoutCreateInfo,
         DynamicState, GraphicsPipeline, PipelineLayout, PipelineShaderStageCreateInfo,
     },
-    render_pass::{Framebuffer, FramebufferCreateInfo, Subpass},
-    swapchain::{
-        acquire_next_image, Surface, Swapchain, SwapchainCreateInfo, SwapchainPresentInfo,
-    },
+    render_pass::{Framebuffer, RenderPass, Subpass},
+    shader::SpecializationConstant,
+    swapchain::{acquire_next_image, SwapchainPresentInfo},
     sync::{self, GpuFuture},
-    Validated, VulkanError, VulkanLibrary,
+    Validated, VulkanError,
 };
 use winit::{
-    event::{Event, WindowEvent},
+    event::{ElementState, Event, KeyEvent, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
+    keyboard::{KeyCode, PhysicalKey},
     window::WindowBuilder,
 };
 
+// --msaa를 주지 않았을 때의 샘플 수
+const DEFAULT_MSAA: u32 = 4;
+
 fn main() {
-    // Vulkan 라이브러리 로드
-    let library = VulkanLibrary::new().expect("Vulkan 라이브러리를 로드할 수 없습니다");
-
-    // Instance 생성
-    let instance = Instance::new(
-        library,
-        InstanceCreateInfo {
-            flags: InstanceCreateFlags::ENUMERATE_PORTABILITY,
-            ..Default::default()
-        },
-    )
-    .expect("Instance 생성 실패");
+    // vulkan-context의 경고와 이 예제의 상태 출력 (RUST_LOG로 조절, 기본 info)
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    tracing_subscriber::fmt().with_env_filter(filter).init();
+
+    // --msaa <1|2|4|8>로 멀티샘플 안티앨리어싱 샘플 수 지정 (장치가 지원하는 가장 큰 값 이하로 낮춤)
+    let requested_samples = std::env::args()
+        .skip_while(|arg| arg != "--msaa")
+        .nth(1)
+        .map(|value| {
+            value
+                .parse()
+                .expect("--msaa 값은 1, 2, 4, 8 중 하나여야 합니다")
+        })
+        .unwrap_or(DEFAULT_MSAA);
 
     // 윈도우 생성
     let event_loop = EventLoop::new();
@@ -60,91 +67,23 @@ fn main() {
             .build(&event_loop)
             .unwrap(),
     );
-    let surface = Surface::from_window(instance.clone(), window.clone()).unwrap();
-
-    // Physical Device 선택
-    let device_extensions = DeviceExtensions {
-        khr_swapchain: true,
-        ..DeviceExtensions::empty()
-    };
 
-    let (physical_device, queue_family_index) = instance
-        .enumerate_physical_devices()
-        .expect("Physical device 열거 실패")
-        .filter(|p| p.supported_extensions().contains(&device_extensions))
-        .filter_map(|p| {
-            p.queue_family_properties()
-                .iter()
-                .enumerate()
-                .position(|(i, q)| {
-                    q.queue_flags.intersects(QueueFlags::GRAPHICS)
-                        && p.surface_support(i as u32, &surface).unwrap_or(false)
-                })
-                .map(|i| (p, i as u32))
-        })
-        .min_by_key(|(p, _)| match p.properties().device_type {
-            PhysicalDeviceType::DiscreteGpu => 0,
-            PhysicalDeviceType::IntegratedGpu => 1,
-            PhysicalDeviceType::VirtualGpu => 2,
-            PhysicalDeviceType::Cpu => 3,
-            PhysicalDeviceType::Other => 4,
-            _ => 5,
-        })
-        .expect("사용 가능한 Physical device가 없습니다");
+    // Instance, Device, Queue, Swapchain 생성 (vulkan-context)
+    let mut context = VulkanContext::new(window.clone()).expect("Vulkan 초기화 실패");
+    let device = context.device.clone();
+    let queue = context.queues.graphics.clone();
+    let mut present_mode = PresentModePreference::Vsync;
 
-    println!(
-        "사용 중인 디바이스: {} (타입: {:?})",
-        physical_device.properties().device_name,
-        physical_device.properties().device_type,
+    info!(
+        "Surface Format: {:?} ({:?})",
+        context.swapchain.image_format(),
+        context.swapchain.image_color_space()
+    );
+    info!(
+        "Present Mode: {:?} (요청: {})",
+        context.swapchain.present_mode(),
+        present_mode.name()
     );
-
-    // Logical Device와 Queue 생성
-    let (device, mut queues) = Device::new(
-        physical_device.clone(),
-        DeviceCreateInfo {
-            queue_create_infos: vec![QueueCreateInfo {
-                queue_family_index,
-                ..Default::default()
-            }],
-            enabled_extensions: device_extensions,
-            ..Default::default()
-        },
-    )
-    .expect("Device 생성 실패");
-
-    let queue = queues.next().unwrap();
-
-    // Swapchain 생성
-    let (mut swapchain, images) = {
-        let surface_capabilities = device
-            .physical_device()
-            .surface_capabilities(&surface, Default::default())
-            .expect("Surface capabilities 가져오기 실패");
-
-        let image_format = device
-            .physical_device()
-            .surface_formats(&surface, Default::default())
-            .unwrap()[0]
-            .0;
-
-        Swapchain::new(
-            device.clone(),
-            surface,
-            SwapchainCreateInfo {
-                min_image_count: surface_capabilities.min_image_count.max(2),
-                image_format,
-                image_extent: window.inner_size().into(),
-                image_usage: ImageUsage::COLOR_ATTACHMENT,
-                composite_alpha: surface_capabilities
-                    .supported_composite_alpha
-                    .into_iter()
-                    .next()
-                    .unwrap(),
-                ..Default::default()
-            },
-        )
-        .unwrap()
-    };
 
     // 메모리 할당자
     let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
@@ -215,12 +154,20 @@ fn main() {
             src: r"
                 #version 460
 
+                // sRGB 스왑체인이면 하드웨어가 출력을 다시 sRGB로 인코딩하므로 선형으로 바꿔 출력
+                layout(constant_id = 0) const bool LINEAR_OUTPUT = false;
+
                 layout(location = 0) in vec3 fragColor;
 
                 layout(location = 0) out vec4 outColor;
 
+                vec3 srgbToLinear(vec3 color) {
+                    return mix(color / 12.92, pow((color + 0.055) / 1.055, vec3(2.4)), step(0.04045, color));
+                }
+
                 void main() {
-                    outColor = vec4(fragColor, 1.0);
+                    // 정점 색은 sRGB 값
+                    outColor = vec4(LINEAR_OUTPUT ? srgbToLinear(fragColor) : fragColor, 1.0);
                 }
             ",
         }
@@ -230,28 +177,24 @@ fn main() {
         .expect("Vertex shader 로드 실패")
         .entry_point("main")
         .unwrap();
+    let linear_output =
+        context.swapchain.image_format().numeric_format_color() == Some(NumericFormat::SRGB);
     let fs = fs::load(device.clone())
         .expect("Fragment shader 로드 실패")
+        .specialize(
+            [(0, SpecializationConstant::Bool(linear_output))]
+                .into_iter()
+                .collect(),
+        )
+        .expect("Fragment shader 특수화 실패")
         .entry_point("main")
         .unwrap();
 
-    // Render Pass 생성
-    let render_pass = vulkano::single_pass_renderpass!(
-        device.clone(),
-        attachments: {
-            color: {
-                format: swapchain.image_format(),
-                samples: 1,
-                load_op: Clear,
-                store_op: Store,
-            },
-        },
-        pass: {
-            color: [color],
-            depth_stencil: {},
-        },
-    )
-    .unwrap();
+    // Render Pass 생성 (MSAA면 멀티샘플 이미지에 그린 뒤 스왑체인 이미지로 resolve)
+    let samples = select_sample_count(device.physical_device(), requested_samples);
+    info!("MSAA: {}x", samples as u32);
+    let render_pass = create_render_pass(&device, context.swapchain.image_format(), samples)
+        .expect("Render pass 생성 실패");
 
     // Graphics Pipeline 생성
     let pipeline = {
@@ -283,7 +226,10 @@ fn main() {
                 input_assembly_state: Some(InputAssemblyState::default()),
                 viewport_state: Some(ViewportState::default()),
                 rasterization_state: Some(RasterizationState::default()),
-                multisample_state: Some(MultisampleState::default()),
+                multisample_state: Some(MultisampleState {
+                    rasterization_samples: samples,
+                    ..Default::default()
+                }),
                 color_blend_state: Some(ColorBlendState::with_attachment_states(
                     subpass.num_color_attachments(),
                     ColorBlendAttachmentState::default(),
@@ -303,7 +249,12 @@ fn main() {
         depth_range: 0.0..=1.0,
     };
 
-    let mut framebuffers = window_size_dependent_setup(&images, render_pass.clone(), &mut viewport);
+    let mut framebuffers = window_size_dependent_setup(
+        &context.images,
+        render_pass.clone(),
+        &memory_allocator,
+        &mut viewport,
+    );
 
     // Command Buffer 할당자
     let command_buffer_allocator =
@@ -320,6 +271,24 @@ fn main() {
         } => {
             *control_flow = ControlFlow::Exit;
         }
+        Event::WindowEvent {
+            event:
+                WindowEvent::KeyboardInput {
+                    event:
+                        KeyEvent {
+                            physical_key: PhysicalKey::Code(KeyCode::KeyV),
+                            state: ElementState::Pressed,
+                            ..
+                        },
+                    ..
+                },
+            ..
+        } => {
+            // 프레젠트 모드 전환 (스왑체인 재생성 시 적용)
+            present_mode = present_mode.next();
+            recreate_swapchain = true;
+            info!("프레젠트 모드 요청: {}", present_mode.name());
+        }
         Event::WindowEvent {
             event: WindowEvent::Resized(_),
             ..
@@ -336,18 +305,25 @@ fn main() {
             previous_frame_end.as_mut().unwrap().cleanup_finished();
 
             if recreate_swapchain {
-                let (new_swapchain, new_images) = swapchain
-                    .recreate(SwapchainCreateInfo {
-                        image_extent,
-                        ..swapchain.create_info()
-                    })
-                    .expect("Swapchain 재생성 실패");
-
-                swapchain = new_swapchain;
-                framebuffers =
-                    window_size_dependent_setup(&new_images, render_pass.clone(), &mut viewport);
+                let previous_present_mode = context.swapchain.present_mode();
+
+                // 그릴 수 없는 크기면 recreate_swapchain을 그대로 두고 다음 프레임에 다시 시도
+                if !context.resize(present_mode).expect("Swapchain 재생성 실패") {
+                    return;
+                }
+                if context.swapchain.present_mode() != previous_present_mode {
+                    info!("Present Mode: {:?}", context.swapchain.present_mode());
+                }
+
+                framebuffers = window_size_dependent_setup(
+                    &context.images,
+                    render_pass.clone(),
+                    &memory_allocator,
+                    &mut viewport,
+                );
                 recreate_swapchain = false;
             }
+            let swapchain = context.swapchain.clone();
 
             let (image_index, suboptimal, acquire_future) =
                 match acquire_next_image(swapchain.clone(), None).map_err(Validated::unwrap) {
@@ -373,7 +349,7 @@ fn main() {
             builder
                 .begin_render_pass(
                     RenderPassBeginInfo {
-                        clear_values: vec![Some([0.0, 0.0, 0.0, 1.0].into())],
+                        clear_values: clear_values(&render_pass, [0.0, 0.0, 0.0, 1.0]),
                         ..RenderPassBeginInfo::framebuffer(
                             framebuffers[image_index as usize].clone(),
                         )
@@ -405,7 +381,7 @@ fn main() {
                 .unwrap()
                 .then_swapchain_present(
                     queue.clone(),
-                    SwapchainPresentInfo::swapchain_image_index(swapchain.clone(), image_index),
+                    SwapchainPresentInfo::swapchain_image_index(swapchain, image_index),
                 )
                 .then_signal_fence_and_flush();
 
@@ -418,7 +394,7 @@ fn main() {
                     previous_frame_end = Some(sync::now(device.clone()).boxed());
                 }
                 Err(e) => {
-                    println!("렌더링 실패: {e}");
+                    error!("렌더링 실패: {e}");
                     previous_frame_end = Some(sync::now(device.clone()).boxed());
                 }
             }
@@ -427,26 +403,16 @@ fn main() {
     });
 }
 
+// 스왑체인 이미지마다 framebuffer를 만들고 viewport를 이미지 크기에 맞춤
 fn window_size_dependent_setup(
     images: &[Arc<Image>],
-    render_pass: Arc<vulkano::render_pass::RenderPass>,
+    render_pass: Arc<RenderPass>,
+    memory_allocator: &Arc<StandardMemoryAllocator>,
     viewport: &mut Viewport,
 ) -> Vec<Arc<Framebuffer>> {
     let extent = images[0].extent();
     viewport.extent = [extent[0] as f32, extent[1] as f32];
 
-    images
-        .iter()
-        .map(|image| {
-            let view = ImageView::new_default(image.clone()).unwrap();
-            Framebuffer::new(
-                render_pass.clone(),
-                FramebufferCreateInfo {
-                    attachments: vec![view],
-                    ..Default::default()
-                },
-            )
-            .unwrap()
-        })
-        .collect::<Vec<_>>()
+    // MSAA 멀티샘플 이미지는 모든 framebuffer가 공유 (resolve 후에는 내용이 필요 없음)
+    create_framebuffers(&render_pass, images, memory_allocator).expect("Framebuffer 생성 실패")
 }
@@ -0,0 +1,35 @@
+#version 460
+
+layout(location = 0) in vec2 fragLocal;
+layout(location = 0) out vec4 outColor;
+
+layout(constant_id = 0) const bool LINEAR_OUTPUT = false;
+
+layout(push_constant) uniform BackgroundPushConstants {
+    // 투영 행렬과 모델 행렬의 곱
+    mat4 transform;
+    vec4 color;
+    vec2 origin;
+    vec2 size;
+    float radius;
+} pc;
+
+// 둥근 사각형까지의 부호 있는 거리 (픽셀, 안쪽이 음수)
+float roundedBox(vec2 p, vec2 halfSize, float radius) {
+    vec2 q = abs(p) - halfSize + radius;
+    return length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - radius;
+}
+
+vec3 srgbToLinear(vec3 color) {
+    return mix(color / 12.92, pow((color + 0.055) / 1.055, vec3(2.4)), step(0.04045, color));
+}
+
+void main() {
+    float radius = min(pc.radius, min(pc.size.x, pc.size.y) * 0.5);
+    float distance = roundedBox(fragLocal, pc.size * 0.5, radius);
+    // 가장자리 1픽셀에 걸쳐 안티앨리어싱
+    float alpha = pc.color.a * clamp(0.5 - distance, 0.0, 1.0);
+
+    vec3 rgb = LINEAR_OUTPUT ? srgbToLinear(pc.color.rgb) : pc.color.rgb;
+    outColor = vec4(rgb * alpha, alpha);
+}
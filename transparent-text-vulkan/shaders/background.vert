@@ -0,0 +1,20 @@
+#version 460
+
+layout(location = 0) in vec2 corner;
+
+// 상자 중심 기준 픽셀 좌표
+layout(location = 0) out vec2 fragLocal;
+
+layout(push_constant) uniform BackgroundPushConstants {
+    // 투영 행렬과 모델 행렬의 곱
+    mat4 transform;
+    vec4 color;
+    vec2 origin;
+    vec2 size;
+    float radius;
+} pc;
+
+void main() {
+    gl_Position = pc.transform * vec4(pc.origin + corner * pc.size, 0.0, 1.0);
+    fragLocal = (corner - 0.5) * pc.size;
+}
@@ -0,0 +1,15 @@
+#version 460
+
+layout(location = 0) out vec4 outColor;
+
+// 텍스트를 먼저 그린 오프스크린 레이어 (premultiplied alpha, 대상과 같은 크기와 포맷)
+layout(set = 0, binding = 0) uniform sampler2D layerSampler;
+
+layout(push_constant) uniform CompositePushConstants {
+    float opacity;
+} pc;
+
+void main() {
+    // 레이어와 대상의 픽셀이 일대일이므로 필터링 없이 같은 위치의 텍셀을 읽음
+    outColor = texelFetch(layerSampler, ivec2(gl_FragCoord.xy), 0) * pc.opacity;
+}
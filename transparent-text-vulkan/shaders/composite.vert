@@ -0,0 +1,7 @@
+#version 460
+
+// 화면 전체를 덮는 삼각형 하나 (정점 버퍼 없이 gl_VertexIndex로 만듦)
+void main() {
+    vec2 position = vec2((gl_VertexIndex << 1) & 2, gl_VertexIndex & 2);
+    gl_Position = vec4(position * 2.0 - 1.0, 0.0, 1.0);
+}
@@ -0,0 +1,75 @@
+#version 460
+
+// 사용자 효과 셰이더 예제: 홀로그램 (흐르는 스캔라인 + 가장자리 발광 + 깜빡임)
+// 내장 text.frag와 같은 인터페이스를 선언합니다 (CustomShader 문서 참고).
+
+layout(location = 0) in vec2 fragTexCoords;
+layout(location = 1) in vec4 fragColor;
+layout(location = 2) flat in int fragEffect;
+layout(location = 3) flat in uint fragColored;
+layout(location = 4) in float fragGradient;
+layout(location = 5) flat in uint fragLayer;
+layout(location = 0) out vec4 outColor;
+
+layout(set = 0, binding = 0) uniform sampler2DArray texSampler;
+layout(set = 0, binding = 1) uniform sampler2DArray shadowSampler;
+
+layout(constant_id = 0) const bool LINEAR_OUTPUT = false;
+
+layout(push_constant) uniform PushConstants {
+    mat4 projection;
+    float opacity;
+    int effects;
+} pc;
+
+layout(set = 1, binding = 0) uniform EffectParams {
+    vec4 outline_color;
+    vec4 glow_color;
+    vec4 shadow_color;
+    vec4 dissolve_color;
+    vec4 gradient_stops[4];
+    vec2 shadow_offset;
+    vec2 text_extent;
+    float outline_width;
+    float outline_softness;
+    float glow_radius;
+    float glow_intensity;
+    float time;
+    int gradient_mode;
+    int gradient_count;
+    float gradient_speed;
+    float wave_amplitude;
+    float wave_speed;
+    float dissolve_progress;
+    float dissolve_edge;
+    float glitch_intensity;
+} params;
+
+// R8 아틀라스는 R에만, 서브픽셀 RGBA는 채널 중 최댓값, 컬러 글리프는 알파가 커버리지
+float coverage(vec2 texel) {
+    vec4 tex = texture(texSampler, vec3(texel / vec2(textureSize(texSampler, 0).xy), fragLayer));
+    return fragColored != 0u ? tex.a : max(tex.r, max(tex.g, tex.b));
+}
+
+vec3 srgbToLinear(vec3 color) {
+    return mix(color / 12.92, pow((color + 0.055) / 1.055, vec3(2.4)), step(0.04045, color));
+}
+
+void main() {
+    float alpha = coverage(fragTexCoords);
+
+    // 블러된 그림자 아틀라스를 가장자리 발광으로 사용
+    vec2 uv = fragTexCoords / vec2(textureSize(shadowSampler, 0).xy);
+    float halo = texture(shadowSampler, vec3(uv, fragLayer)).a;
+    float rim = clamp(halo - alpha, 0.0, 1.0) * 0.6;
+
+    // 화면 좌표 기준으로 아래로 흐르는 스캔라인
+    float scan = 0.75 + 0.25 * sin(gl_FragCoord.y * 1.5 - params.time * 8.0);
+    float flicker = 0.9 + 0.1 * sin(params.time * 37.0) * sin(params.time * 11.0);
+
+    vec3 tint = mix(vec3(0.3, 0.9, 1.0), fragColor.rgb, 0.3);
+    float a = clamp((alpha * scan + rim) * flicker, 0.0, 1.0) * fragColor.a * pc.opacity;
+
+    vec3 rgb = LINEAR_OUTPUT ? srgbToLinear(tint) : tint;
+    outColor = vec4(rgb * a, a);
+}
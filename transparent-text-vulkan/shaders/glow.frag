@@ -0,0 +1,36 @@
+#version 460
+
+layout(location = 0) out vec4 outColor;
+
+// 블룸 체인의 결과 (대상의 절반 크기, 모든 채널에 같은 값)
+layout(set = 0, binding = 0) uniform sampler2D bloomSampler;
+
+layout(constant_id = 0) const bool LINEAR_OUTPUT = false;
+// 부동소수점 HDR 대상이면 발광이 1.0보다 밝아질 수 있음
+layout(constant_id = 1) const bool HDR_OUTPUT = false;
+
+layout(push_constant) uniform GlowPushConstants {
+    // 발광 색 (straight sRGB)
+    vec4 color;
+    // 대상 크기 (픽셀)
+    vec2 target_size;
+    // 발광 세기 × 투명도 ÷ 블룸 단계 수
+    float strength;
+} pc;
+
+vec3 srgbToLinear(vec3 color) {
+    return mix(color / 12.92, pow((color + 0.055) / 1.055, vec3(2.4)), step(0.04045, color));
+}
+
+void main() {
+    float glow = texture(bloomSampler, gl_FragCoord.xy / pc.target_size).a * pc.strength;
+    vec3 rgb = LINEAR_OUTPUT ? srgbToLinear(pc.color.rgb) : pc.color.rgb;
+    if (HDR_OUTPUT) {
+        // 밝기는 자르지 않고 더하고, 덮는 정도(알파)만 1.0까지
+        outColor = vec4(rgb * glow * pc.color.a, clamp(glow, 0.0, 1.0) * pc.color.a);
+        return;
+    }
+    // SDR에서는 1.0에서 잘리는 대신 지수 톤 매핑으로 밝은 중심이 부드럽게 포화
+    float alpha = (1.0 - exp(-glow)) * pc.color.a;
+    outColor = vec4(rgb * alpha, alpha);
+}
@@ -0,0 +1,25 @@
+#version 460
+
+// text.vert의 출력 중 필요한 것만 받음
+layout(location = 0) in vec2 fragTexCoords;
+layout(location = 1) in vec4 fragColor;
+layout(location = 2) flat in int fragEffect;
+layout(location = 3) flat in uint fragColored;
+layout(location = 5) flat in uint fragLayer;
+layout(location = 0) out vec4 outColor;
+
+layout(set = 0, binding = 0) uniform sampler2DArray texSampler;
+
+const int EFFECT_GLOW = 4;
+
+// 발광을 켠 글리프의 커버리지만 블룸 원본 이미지에 그림
+void main() {
+    if ((fragEffect & EFFECT_GLOW) == 0) {
+        discard;
+    }
+    vec2 uv = fragTexCoords / vec2(textureSize(texSampler, 0).xy);
+    vec4 tex = texture(texSampler, vec3(uv, fragLayer));
+    // R8 아틀라스는 R에만, 서브픽셀 RGBA는 채널 중 최댓값, 컬러 글리프는 알파가 커버리지
+    float alpha = fragColored != 0u ? tex.a : max(tex.r, max(tex.g, tex.b));
+    outColor = vec4(alpha * fragColor.a);
+}
@@ -0,0 +1,24 @@
+#version 460
+
+layout(location = 0) in vec2 fragUv;
+layout(location = 1) in vec4 fragColor;
+layout(location = 0) out vec4 outColor;
+
+// egui 텍스처 (폰트 아틀라스나 사용자 이미지, sRGB premultiplied)
+layout(set = 0, binding = 0) uniform sampler2D panelTexture;
+
+// 대상이 sRGB나 부동소수점 포맷이면 선형 색으로 출력
+layout(constant_id = 0) const bool LINEAR_OUTPUT = false;
+
+vec3 srgbToLinear(vec3 color) {
+    return mix(color / 12.92, pow((color + 0.055) / 1.055, vec3(2.4)), step(0.04045, color));
+}
+
+void main() {
+    vec4 color = fragColor * texture(panelTexture, fragUv);
+    // premultiplied 색을 그대로 변환 (반투명 가장자리만 약간 어두워짐)
+    if (LINEAR_OUTPUT) {
+        color.rgb = srgbToLinear(color.rgb);
+    }
+    outColor = color;
+}
@@ -0,0 +1,21 @@
+#version 460
+
+// egui 메시 정점 (논리 픽셀 좌표, 색은 sRGB premultiplied)
+layout(location = 0) in vec2 position;
+layout(location = 1) in vec2 uv;
+layout(location = 2) in vec4 color;
+
+layout(location = 0) out vec2 fragUv;
+layout(location = 1) out vec4 fragColor;
+
+layout(push_constant) uniform PanelPushConstants {
+    // 화면 크기 (논리 픽셀)
+    vec2 screenSize;
+} pc;
+
+void main() {
+    // 왼쪽 위가 원점인 논리 픽셀을 NDC로 (Vulkan은 y가 아래로 증가)
+    gl_Position = vec4(position / pc.screenSize * 2.0 - 1.0, 0.0, 1.0);
+    fragUv = uv;
+    fragColor = color;
+}
@@ -0,0 +1,87 @@
+#version 460
+
+// 단위 사각형 꼭짓점 (정점마다)
+layout(location = 0) in vec2 corner;
+// 글리프 인스턴스 (글리프마다)
+layout(location = 1) in vec2 position;
+layout(location = 2) in vec2 size;
+layout(location = 3) in vec4 uv_rect;
+layout(location = 4) in vec4 color;
+layout(location = 5) in int effect;
+layout(location = 6) in uint colored;
+// 텍스트 블록의 모델 행렬과 블록 크기 (텍스트 객체마다, mat4는 location 7 ~ 10)
+layout(location = 7) in mat4 transform;
+layout(location = 11) in vec2 extent;
+// 블록 안의 글자 번호 (결합 문자처럼 글리프 여러 개로 된 글자는 같은 번호)
+layout(location = 12) in uint character;
+// 글리프가 든 아틀라스 레이어
+layout(location = 13) in uint layer;
+
+layout(location = 0) out vec2 fragTexCoords;
+layout(location = 1) out vec4 fragColor;
+// 효과 비트마스크 (1: 외곽선, 2: 그림자, 4: 발광, 8: 그라데이션, 16: 물결, 32: 소멸, 64: 글리치)
+layout(location = 2) flat out int fragEffect;
+layout(location = 3) flat out uint fragColored;
+// 그라데이션 위치 (Linear: 텍스트 왼쪽 0 ~ 오른쪽 1, Rainbow: 색상)
+layout(location = 4) out float fragGradient;
+layout(location = 5) flat out uint fragLayer;
+
+layout(push_constant) uniform PushConstants {
+    mat4 projection;
+    float opacity;
+    int effects;
+} pc;
+
+layout(set = 1, binding = 0) uniform EffectParams {
+    vec4 outline_color;
+    vec4 glow_color;
+    vec4 shadow_color;
+    vec4 dissolve_color;
+    vec4 gradient_stops[4];
+    vec2 shadow_offset;
+    vec2 text_extent;
+    float outline_width;
+    float outline_softness;
+    float glow_radius;
+    float glow_intensity;
+    float time;
+    int gradient_mode;
+    int gradient_count;
+    float gradient_speed;
+    float wave_amplitude;
+    float wave_speed;
+    float dissolve_progress;
+    float dissolve_edge;
+    float glitch_intensity;
+} params;
+
+const int EFFECT_WAVE = 16;
+
+void main() {
+    fragEffect = effect < 0 ? pc.effects : effect;
+
+    // 물결: 글자마다 위상을 달리해 세로로 흔듦
+    vec2 wave = vec2(0.0);
+    if ((fragEffect & EFFECT_WAVE) != 0) {
+        float phase = params.time * params.wave_speed + float(character) * 0.5;
+        wave.y = sin(phase) * params.wave_amplitude;
+    }
+
+    // 텍스트 블록 중심 기준 픽셀 → 모델 행렬 → 화면 픽셀 → 직교 투영으로 NDC
+    vec4 local = vec4(position + wave + corner * size, 0.0, 1.0);
+    gl_Position = pc.projection * (transform * local);
+    fragTexCoords = uv_rect.xy + corner * uv_rect.zw;
+    fragLayer = layer;
+    fragColor = color;
+    fragColored = colored;
+
+    // 시간에 따라 흐르도록 speed * time만큼 이동
+    float flow = params.time * params.gradient_speed;
+    if (params.gradient_mode == 1) {
+        // 글자 번호로 글자마다 색상을 바꿈
+        fragGradient = float(character) * 0.08 + flow;
+    } else {
+        float x = position.x + corner.x * size.x;
+        fragGradient = x / max(extent.x, 1.0) + 0.5 + flow;
+    }
+}
@@ -0,0 +1,210 @@
+#version 460
+
+layout(location = 0) in vec2 fragTexCoords;
+layout(location = 1) in vec4 fragColor;
+layout(location = 2) flat in int fragEffect;
+layout(location = 3) flat in uint fragColored;
+layout(location = 4) in float fragGradient;
+// 글리프가 든 아틀라스 레이어 (그림자, 외곽선 아틀라스도 같은 레이어)
+layout(location = 5) flat in uint fragLayer;
+layout(location = 0, index = 0) out vec4 outColor;
+layout(location = 0, index = 1) out vec4 outBlend;
+
+layout(set = 0, binding = 0) uniform sampler2DArray texSampler;
+// 아틀라스를 가우시안 블러한 그림자 아틀라스 (같은 배치)
+layout(set = 0, binding = 1) uniform sampler2DArray shadowSampler;
+// 아틀라스를 외곽선 두께만큼 팽창한 외곽선 아틀라스 (같은 배치)
+layout(set = 0, binding = 2) uniform sampler2DArray outlineSampler;
+
+layout(constant_id = 0) const bool LINEAR_OUTPUT = false;
+
+layout(push_constant) uniform PushConstants {
+    mat4 projection;
+    float opacity;
+    int effects;
+} pc;
+
+layout(set = 1, binding = 0) uniform EffectParams {
+    vec4 outline_color;
+    vec4 glow_color;
+    vec4 shadow_color;
+    vec4 dissolve_color;
+    vec4 gradient_stops[4];
+    vec2 shadow_offset;
+    vec2 text_extent;
+    float outline_width;
+    float outline_softness;
+    float glow_radius;
+    float glow_intensity;
+    float time;
+    int gradient_mode;
+    int gradient_count;
+    float gradient_speed;
+    float wave_amplitude;
+    float wave_speed;
+    float dissolve_progress;
+    float dissolve_edge;
+    float glitch_intensity;
+} params;
+
+const int EFFECT_OUTLINE = 1;
+const int EFFECT_SHADOW = 2;
+const int EFFECT_GRADIENT = 8;
+const int EFFECT_DISSOLVE = 32;
+const int EFFECT_GLITCH = 64;
+// 채널별 커버리지로 그릴 수 없는 효과 (그라데이션, 물결은 색과 위치만 바꾸고 발광은 레이어 뒤에 그림)
+const int LAYER_EFFECTS = EFFECT_OUTLINE | EFFECT_SHADOW | EFFECT_DISSOLVE | EFFECT_GLITCH;
+
+// 서브픽셀 모드의 아틀라스는 항상 RGBA (RGB에 채널별 커버리지, 알파에 그 최댓값)
+vec4 sampleAtlas(vec2 texel) {
+    return texture(texSampler, vec3(texel / vec2(textureSize(texSampler, 0).xy), fragLayer));
+}
+
+float sampleShadow(vec2 texel) {
+    return texture(shadowSampler, vec3(texel / vec2(textureSize(shadowSampler, 0).xy), fragLayer)).a;
+}
+
+// 컴퓨트 셰이더(dilate.comp)가 미리 계산한 외곽선 커버리지
+float outlineCoverage(vec2 texel) {
+    return texture(outlineSampler, vec3(texel / vec2(textureSize(outlineSampler, 0).xy), fragLayer)).a;
+}
+
+vec3 srgbToLinear(vec3 color) {
+    return mix(color / 12.92, pow((color + 0.055) / 1.055, vec3(2.4)), step(0.04045, color));
+}
+
+vec4 outputColor(vec3 color) {
+    return vec4(LINEAR_OUTPUT ? srgbToLinear(color) : color, 1.0);
+}
+
+vec3 hueToRgb(float hue) {
+    vec3 k = abs(fract(hue + vec3(0.0, 2.0, 1.0) / 3.0) * 6.0 - 3.0);
+    return clamp(k - 1.0, 0.0, 1.0);
+}
+
+// 그라데이션 채우기 색 (straight sRGB)
+vec4 gradientColor() {
+    if (params.gradient_mode == 1) {
+        return vec4(hueToRgb(fract(fragGradient)), 1.0);
+    }
+    // 끝에서 되돌아오도록 0 → 1 → 0으로 반복
+    float t = 1.0 - abs(1.0 - fract(fragGradient * 0.5) * 2.0);
+    int last = clamp(params.gradient_count, 2, 4) - 1;
+    float x = t * float(last);
+    int i = min(int(x), last - 1);
+    return mix(params.gradient_stops[i], params.gradient_stops[i + 1], x - float(i));
+}
+
+float hash(vec2 p) {
+    return fract(sin(dot(p, vec2(127.1, 311.7))) * 43758.5453);
+}
+
+// 격자 꼭짓점의 해시를 부드럽게 보간한 값 노이즈 (0 ~ 1)
+float valueNoise(vec2 p) {
+    vec2 i = floor(p);
+    vec2 f = fract(p);
+    vec2 u = f * f * (3.0 - 2.0 * f);
+    float top = mix(hash(i), hash(i + vec2(1.0, 0.0)), u.x);
+    float bottom = mix(hash(i + vec2(0.0, 1.0)), hash(i + vec2(1.0, 1.0)), u.x);
+    return mix(top, bottom, u.y);
+}
+
+// 소멸: 노이즈가 기준값보다 작은 곳은 지우고, 그 위 dissolve_edge 폭은 타는 색으로
+// (progress 0이면 모두 보이고 1이면 모두 사라지도록 경계 폭만큼 기준값 범위를 넓힘)
+vec4 dissolve(vec4 layer) {
+    float edge = max(params.dissolve_edge, 0.001);
+    float cut = params.dissolve_progress * (1.0 + edge) - edge;
+    float noise = valueNoise(gl_FragCoord.xy / 6.0);
+    float burn = 1.0 - smoothstep(cut, cut + edge, noise);
+    vec3 color = mix(layer.rgb, params.dissolve_color.rgb, burn * params.dissolve_color.a);
+    return vec4(color, layer.a * step(cut, noise));
+}
+
+// 글리치: 초당 12번 바뀌는 가로 띠 일부를 옆으로 밀고, R/B 채널을 좌우로 어긋나게 샘플링
+// 밀리는 거리와 채널 간격의 합이 글리프 여백(8픽셀)을 넘지 않도록 함
+vec4 glitchSample(vec2 texel) {
+    float intensity = clamp(params.glitch_intensity, 0.0, 1.0);
+    float frame = floor(params.time * 12.0);
+    float slice = floor(gl_FragCoord.y / 6.0);
+    if (hash(vec2(slice, frame)) < intensity * 0.3) {
+        texel.x += (hash(vec2(frame, slice)) - 0.5) * 12.0 * intensity;
+    }
+
+    vec2 shift = vec2(2.0 * intensity, 0.0);
+    float r = sampleAtlas(texel + shift).a;
+    float g = sampleAtlas(texel).a;
+    float b = sampleAtlas(texel - shift).a;
+    float alpha = max(g, max(r, b));
+    return vec4(alpha > 0.0 ? vec3(r, g, b) / alpha : vec3(0.0), alpha);
+}
+
+// straight 색 레이어를 아래 레이어 위에 덮음 (over 합성)
+vec4 over(vec4 below, vec3 color, float alpha) {
+    float a = alpha + below.a * (1.0 - alpha);
+    vec3 rgb = color * alpha + below.rgb * below.a * (1.0 - alpha);
+    return vec4(a > 0.0 ? rgb / a : vec3(0.0), a);
+}
+
+// 켜진 효과를 그림자, 외곽선, 글자 순서로 겹치고 소멸을 적용
+// (발광은 TextLayer가 레이어 뒤에 블룸으로 합성)
+vec4 composeEffects(vec3 color, float alpha) {
+    vec4 layer = vec4(0.0);
+    if ((fragEffect & EFFECT_SHADOW) != 0) {
+        // 오프셋만큼 오른쪽 아래에 그려지도록 반대 방향에서 블러된 아틀라스를 샘플링
+        float shadow = sampleShadow(fragTexCoords - params.shadow_offset);
+        layer = over(layer, params.shadow_color.rgb, shadow * params.shadow_color.a);
+    }
+    if ((fragEffect & EFFECT_OUTLINE) != 0) {
+        float outline = outlineCoverage(fragTexCoords);
+        layer = over(layer, params.outline_color.rgb, outline * params.outline_color.a);
+    }
+    layer = over(layer, color, alpha);
+    if ((fragEffect & EFFECT_DISSOLVE) != 0) {
+        layer = dissolve(layer);
+    }
+    return layer;
+}
+
+// 채널 구분 없이 색과 알파로 합성
+void writeGrayscale(vec3 color, float alpha) {
+    outColor = outputColor(color);
+    outBlend = vec4(alpha);
+}
+
+void main() {
+    vec4 tex = sampleAtlas(fragTexCoords);
+
+    // 컬러 글리프는 RGB가 커버리지가 아니라 실제 색 (premultiplied)
+    if (fragColored != 0u) {
+        vec3 color = tex.a > 0.0 ? tex.rgb / tex.a : vec3(0.0);
+        writeGrayscale(color * fragColor.rgb, tex.a * fragColor.a * pc.opacity);
+        return;
+    }
+
+    // 그라데이션은 글자 색을 대신함
+    vec4 fill = fragColor;
+    if ((fragEffect & EFFECT_GRADIENT) != 0) {
+        vec4 gradient = gradientColor();
+        fill = vec4(gradient.rgb, fragColor.a * gradient.a);
+    }
+
+    if ((fragEffect & LAYER_EFFECTS) == 0) {
+        // 겹쳐 그릴 효과가 없으면 채널별 커버리지
+        vec3 coverage = tex.rgb * fill.a * pc.opacity;
+        outColor = outputColor(fill.rgb);
+        outBlend = vec4(coverage, max(coverage.r, max(coverage.g, coverage.b)));
+        return;
+    }
+
+    // 알파에는 채널별 커버리지의 최댓값이 들어 있음
+    vec3 base = vec3(1.0);
+    float alpha = tex.a;
+    if ((fragEffect & EFFECT_GLITCH) != 0) {
+        vec4 glitch = glitchSample(fragTexCoords);
+        base = glitch.rgb;
+        alpha = glitch.a;
+    }
+
+    vec4 color = composeEffects(base * fill.rgb, alpha * fill.a);
+    writeGrayscale(color.rgb, color.a * pc.opacity);
+}
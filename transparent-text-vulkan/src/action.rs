@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use winit::keyboard::KeyCode;
+
+/// 키에 연결할 수 있는 예제 프로그램 동작 (config.toml `[keys]`의 이름)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    Quit,
+    TextEntry,
+    #[serde(rename = "opacity-10")]
+    Opacity10,
+    #[serde(rename = "opacity-20")]
+    Opacity20,
+    #[serde(rename = "opacity-30")]
+    Opacity30,
+    #[serde(rename = "opacity-40")]
+    Opacity40,
+    #[serde(rename = "opacity-50")]
+    Opacity50,
+    #[serde(rename = "opacity-60")]
+    Opacity60,
+    #[serde(rename = "opacity-70")]
+    Opacity70,
+    #[serde(rename = "opacity-80")]
+    Opacity80,
+    #[serde(rename = "opacity-90")]
+    Opacity90,
+    #[serde(rename = "opacity-100")]
+    Opacity100,
+    NextEffect,
+    NextOutlineColor,
+    OutlineThinner,
+    OutlineThicker,
+    OutlineSharper,
+    OutlineSofter,
+    GlowSmaller,
+    GlowLarger,
+    GlowDimmer,
+    GlowBrighter,
+    ShadowSharper,
+    ShadowBlurrier,
+    Dissolve,
+    Pause,
+    SlowDown,
+    SpeedUp,
+    Typewriter,
+    Gradient,
+    Background,
+    StatusLine,
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    Rotate,
+    ScaleUp,
+    ScaleDown,
+    ClickThrough,
+    Visibility,
+    PresentMode,
+    Fullscreen,
+    Screenshot,
+    Stats,
+    Panel,
+    TimerToggle,
+    TimerReset,
+    #[serde(rename = "preset-1")]
+    Preset1,
+    #[serde(rename = "preset-2")]
+    Preset2,
+    #[serde(rename = "preset-3")]
+    Preset3,
+    #[serde(rename = "preset-4")]
+    Preset4,
+    #[serde(rename = "preset-5")]
+    Preset5,
+    #[serde(rename = "preset-6")]
+    Preset6,
+    #[serde(rename = "preset-7")]
+    Preset7,
+    #[serde(rename = "preset-8")]
+    Preset8,
+}
+
+impl Action {
+    /// 투명도 동작이면 그 값
+    pub fn opacity(self) -> Option<f32> {
+        let tenths = match self {
+            Action::Opacity10 => 1,
+            Action::Opacity20 => 2,
+            Action::Opacity30 => 3,
+            Action::Opacity40 => 4,
+            Action::Opacity50 => 5,
+            Action::Opacity60 => 6,
+            Action::Opacity70 => 7,
+            Action::Opacity80 => 8,
+            Action::Opacity90 => 9,
+            Action::Opacity100 => 10,
+            _ => return None,
+        };
+        Some(tenths as f32 / 10.0)
+    }
+
+    /// 프리셋 동작이면 F 키 번호 (1-8)
+    pub fn preset_slot(self) -> Option<usize> {
+        let slot = match self {
+            Action::Preset1 => 1,
+            Action::Preset2 => 2,
+            Action::Preset3 => 3,
+            Action::Preset4 => 4,
+            Action::Preset5 => 5,
+            Action::Preset6 => 6,
+            Action::Preset7 => 7,
+            Action::Preset8 => 8,
+            _ => return None,
+        };
+        Some(slot)
+    }
+}
+
+/// 기본 키 배치 (config.toml의 `[keys]`로 동작별로 바꿀 수 있음)
+pub fn default_bindings() -> HashMap<KeyCode, Action> {
+    HashMap::from([
+        (KeyCode::Escape, Action::Quit),
+        (KeyCode::Enter, Action::TextEntry),
+        (KeyCode::Digit1, Action::Opacity10),
+        (KeyCode::Digit2, Action::Opacity20),
+        (KeyCode::Digit3, Action::Opacity30),
+        (KeyCode::Digit4, Action::Opacity40),
+        (KeyCode::Digit5, Action::Opacity50),
+        (KeyCode::Digit6, Action::Opacity60),
+        (KeyCode::Digit7, Action::Opacity70),
+        (KeyCode::Digit8, Action::Opacity80),
+        (KeyCode::Digit9, Action::Opacity90),
+        (KeyCode::Digit0, Action::Opacity100),
+        (KeyCode::KeyE, Action::NextEffect),
+        (KeyCode::KeyO, Action::NextOutlineColor),
+        (KeyCode::Minus, Action::OutlineThinner),
+        (KeyCode::Equal, Action::OutlineThicker),
+        (KeyCode::Comma, Action::OutlineSharper),
+        (KeyCode::Period, Action::OutlineSofter),
+        (KeyCode::BracketLeft, Action::GlowSmaller),
+        (KeyCode::BracketRight, Action::GlowLarger),
+        (KeyCode::Semicolon, Action::GlowDimmer),
+        (KeyCode::Quote, Action::GlowBrighter),
+        (KeyCode::KeyK, Action::ShadowSharper),
+        (KeyCode::KeyL, Action::ShadowBlurrier),
+        (KeyCode::KeyD, Action::Dissolve),
+        (KeyCode::KeyP, Action::Pause),
+        (KeyCode::KeyZ, Action::SlowDown),
+        (KeyCode::KeyX, Action::SpeedUp),
+        (KeyCode::KeyT, Action::Typewriter),
+        (KeyCode::KeyG, Action::Gradient),
+        (KeyCode::KeyB, Action::Background),
+        (KeyCode::KeyI, Action::StatusLine),
+        (KeyCode::ArrowLeft, Action::MoveLeft),
+        (KeyCode::ArrowRight, Action::MoveRight),
+        (KeyCode::ArrowUp, Action::MoveUp),
+        (KeyCode::ArrowDown, Action::MoveDown),
+        (KeyCode::KeyR, Action::Rotate),
+        (KeyCode::NumpadAdd, Action::ScaleUp),
+        (KeyCode::NumpadSubtract, Action::ScaleDown),
+        (KeyCode::KeyC, Action::ClickThrough),
+        (KeyCode::KeyH, Action::Visibility),
+        (KeyCode::KeyV, Action::PresentMode),
+        (KeyCode::Space, Action::TimerToggle),
+        (KeyCode::Backspace, Action::TimerReset),
+        (KeyCode::F1, Action::Preset1),
+        (KeyCode::F2, Action::Preset2),
+        (KeyCode::F3, Action::Preset3),
+        (KeyCode::F4, Action::Preset4),
+        (KeyCode::F5, Action::Preset5),
+        (KeyCode::F6, Action::Preset6),
+        (KeyCode::F7, Action::Preset7),
+        (KeyCode::F8, Action::Preset8),
+        (KeyCode::F9, Action::Stats),
+        (KeyCode::F10, Action::Panel),
+        (KeyCode::F11, Action::Fullscreen),
+        (KeyCode::F12, Action::Screenshot),
+    ])
+}
+
+/// 기본 배치에 동작별 키를 덮어씀 (동작의 원래 키는 해제)
+pub fn bindings(overrides: &HashMap<Action, KeyCode>) -> HashMap<KeyCode, Action> {
+    let mut bindings = default_bindings();
+    for (&action, &key) in overrides {
+        bindings.retain(|_, bound| *bound != action);
+        bindings.insert(key, action);
+    }
+    bindings
+}
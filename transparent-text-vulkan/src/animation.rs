@@ -0,0 +1,391 @@
+use std::{ops::Range, time::Instant};
+
+/// 글자를 하나씩 드러내는 타자기 애니메이션
+///
+/// 글리프 인스턴스의 알파를 글자 순서대로 0에서 원래 값까지 올립니다.
+/// 결합 문자나 이모지 시퀀스처럼 글리프 여러 개로 된 글자는 함께 나타납니다.
+/// 진행 시간은 `TextParams::time`을 기준으로 하므로, 프레임마다 경과 시간을 넘겨야 합니다.
+///
+/// ```ignore
+/// text_renderer.start_typewriter(Typewriter {
+///     duration: 3.0,
+///     ..Typewriter::default()
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Typewriter {
+    /// 마지막 글자가 나타나기 시작할 때까지 걸리는 시간 (초)
+    pub duration: f32,
+    /// 글자 하나가 투명에서 불투명해지는 시간 (초, 0이면 바로 나타남)
+    pub fade: f32,
+}
+
+impl Default for Typewriter {
+    fn default() -> Self {
+        Self {
+            duration: 2.0,
+            fade: 0.1,
+        }
+    }
+}
+
+impl Typewriter {
+    // 시작 후 elapsed초일 때 count개 중 index번째 글자의 알파 배율
+    pub(crate) fn alpha(&self, index: usize, count: usize, elapsed: f32) -> f32 {
+        let start = self.duration * index as f32 / count.max(1) as f32;
+        if self.fade <= 0.0 {
+            return if elapsed >= start { 1.0 } else { 0.0 };
+        }
+        ((elapsed - start) / self.fade).clamp(0.0, 1.0)
+    }
+
+    pub(crate) fn is_finished(&self, elapsed: f32) -> bool {
+        elapsed >= self.duration + self.fade.max(0.0)
+    }
+}
+
+/// 가사를 시간에 맞춰 강조 색으로 칠해 나가는 노래방 효과
+///
+/// 음절마다 시작부터 끝까지 그 음절의 글자를 앞에서부터 하나씩 강조 색으로 바꾸고, 다 칠한 글자는
+/// 강조 색으로 남습니다. 시간은 타자기 애니메이션처럼 시작 후 첫 `draw`의 `TextParams::time`부터 셉니다.
+/// 컬러 글리프는 칠하지 않습니다.
+///
+/// ```ignore
+/// text_renderer.set_text("반짝 반짝 작은 별")?;
+/// text_renderer.start_karaoke(Karaoke {
+///     color: [255, 204, 0, 255],
+///     syllables: vec![
+///         KaraokeSyllable { range: 0..6, start: 0.0, end: 0.5 },
+///         KaraokeSyllable { range: 7..13, start: 0.5, end: 1.0 },
+///         KaraokeSyllable { range: 14..24, start: 1.0, end: 2.0 },
+///     ],
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Karaoke {
+    /// 강조 색 (RGBA8, 글자의 원래 알파는 유지하고 RGB만 바꿈)
+    pub color: [u8; 4],
+    pub syllables: Vec<KaraokeSyllable>,
+}
+
+/// 노래방 효과의 음절 하나
+#[derive(Debug, Clone, PartialEq)]
+pub struct KaraokeSyllable {
+    /// 기본 텍스트의 바이트 범위 (마크업을 해석한 뒤의 텍스트 기준)
+    pub range: Range<usize>,
+    /// 칠하기 시작하는 시각과 다 칠하는 시각 (초, 효과를 시작한 때부터)
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Karaoke {
+    /// 텍스트 전체를 `duration`초 동안 글자 순서대로 고르게 칠함
+    pub fn uniform(color: [u8; 4], duration: f32) -> Self {
+        Self {
+            color,
+            syllables: vec![KaraokeSyllable {
+                range: 0..usize::MAX,
+                start: 0.0,
+                end: duration,
+            }],
+        }
+    }
+
+    /// 마지막 음절을 다 칠하는 시각 (초)
+    pub fn duration(&self) -> f32 {
+        self.syllables
+            .iter()
+            .map(|syllable| syllable.end)
+            .fold(0.0, f32::max)
+    }
+
+    // 시작 후 elapsed초일 때 글리프마다 칠해진 정도 (0 ~ 1, clusters는 글리프가 속한 글자의 바이트 위치)
+    // 음절 안의 글자 n개는 음절 시간을 n등분해 차례로 칠함 (한 글자의 글리프는 함께)
+    pub(crate) fn fill(&self, clusters: &[usize], elapsed: f32) -> Vec<f32> {
+        let mut fill = vec![0.0; clusters.len()];
+        for syllable in &self.syllables {
+            let glyphs: Vec<usize> = (0..clusters.len())
+                .filter(|&index| syllable.range.contains(&clusters[index]))
+                .collect();
+            let characters: Vec<usize> = glyphs.iter().map(|&index| clusters[index]).collect();
+            let (order, count) = character_order(&characters);
+            let length = syllable.end - syllable.start;
+            let progress = if length > 0.0 {
+                (elapsed - syllable.start) / length * count as f32
+            } else if elapsed >= syllable.start {
+                count as f32
+            } else {
+                0.0
+            };
+            for (index, order) in glyphs.into_iter().zip(order) {
+                fill[index] = (progress - order as f32).clamp(0.0, 1.0);
+            }
+        }
+        fill
+    }
+}
+
+// 글리프의 바이트 위치가 속한 글자(확장 grapheme cluster)의 시작 (graphemes는 글자 시작 위치, 오름차순)
+pub(crate) fn grapheme_start(graphemes: &[usize], cluster: usize) -> usize {
+    let grapheme = graphemes.partition_point(|&start| start <= cluster);
+    graphemes[grapheme.saturating_sub(1)]
+}
+
+// 글리프마다 논리 순서의 글자 번호와 글자 수 (clusters는 글리프가 속한 글자의 바이트 위치)
+// 같은 글자에 속한 글리프는 같은 번호
+pub(crate) fn character_order(clusters: &[usize]) -> (Vec<usize>, usize) {
+    let mut starts = clusters.to_vec();
+    starts.sort_unstable();
+    starts.dedup();
+    let order = clusters
+        .iter()
+        .map(|cluster| starts.partition_point(|start| start < cluster))
+        .collect();
+    (order, starts.len())
+}
+
+/// 애니메이션 시계 (일시 정지와 배속 지원)
+///
+/// 프레임마다 `tick`을 한 번 호출하고 `elapsed`를 `TextParams::time`으로 넘기면
+/// 셰이더 효과와 타자기 애니메이션이 같은 시간으로 움직입니다.
+///
+/// ```ignore
+/// clock.tick();
+/// let params = TextParams {
+///     time: clock.elapsed(),
+///     ..TextParams::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct Clock {
+    last_tick: Instant,
+    elapsed: f32,
+    delta: f32,
+    speed: f32,
+    paused: bool,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Self {
+            last_tick: Instant::now(),
+            elapsed: 0.0,
+            delta: 0.0,
+            speed: 1.0,
+            paused: false,
+        }
+    }
+
+    /// 지난 `tick` 이후 흐른 실제 시간에 배속을 곱해 시계를 진행하고, 그 증가량(초)을 돌려줍니다.
+    pub fn tick(&mut self) -> f32 {
+        let now = Instant::now();
+        let real = now.duration_since(self.last_tick).as_secs_f32();
+        self.last_tick = now;
+
+        self.delta = if self.paused { 0.0 } else { real * self.speed };
+        self.elapsed += self.delta;
+        self.delta
+    }
+
+    /// 애니메이션 시간 (초, 일시 정지 중에는 멈춤)
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// 마지막 `tick`에서 진행한 시간 (초)
+    pub fn delta(&self) -> f32 {
+        self.delta
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// 배속 (1.0이 실제 속도, 음수는 0으로 취급)
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// 시간을 0으로 되돌림 (배속과 일시 정지 상태는 유지)
+    pub fn reset(&mut self) {
+        self.last_tick = Instant::now();
+        self.elapsed = 0.0;
+        self.delta = 0.0;
+    }
+}
+
+/// 트윈의 진행도 곡선
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Easing {
+    Linear,
+    /// 천천히 시작해 천천히 멈춤
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    /// 진행도 t (0..=1)를 곡선에 따라 바꿈
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// 값 하나를 목표값까지 정해진 시간 동안 부드럽게 바꾸는 트윈
+///
+/// 프레임마다 `advance`에 경과 시간을 넘기면 `value`가 목표값으로 다가갑니다.
+/// 진행 중에 새 목표를 주면 현재 값에서 다시 시작하므로 값이 튀지 않습니다.
+///
+/// ```ignore
+/// let mut opacity = Tween::new(1.0);
+/// opacity.animate_to(0.3, 0.25);
+///
+/// // 프레임마다
+/// opacity.advance(clock.tick());
+/// let params = TextParams {
+///     opacity: opacity.value(),
+///     ..TextParams::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tween {
+    from: f32,
+    to: f32,
+    value: f32,
+    elapsed: f32,
+    duration: f32,
+    easing: Easing,
+}
+
+impl Tween {
+    /// 움직이지 않는 값 (`Easing::EaseInOut`)
+    pub fn new(value: f32) -> Self {
+        Self {
+            from: value,
+            to: value,
+            value,
+            elapsed: 0.0,
+            duration: 0.0,
+            easing: Easing::default(),
+        }
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// 현재 값
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// 목표값 (끝났으면 현재 값과 같음)
+    pub fn target(&self) -> f32 {
+        self.to
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// 현재 값에서 target까지 duration초 동안 바꿈 (0 이하면 바로 바뀜)
+    pub fn animate_to(&mut self, target: f32, duration: f32) {
+        self.from = self.value;
+        self.to = target;
+        self.elapsed = 0.0;
+        self.duration = duration.max(0.0);
+        if self.duration == 0.0 {
+            self.value = target;
+        }
+    }
+
+    /// 애니메이션 없이 바로 바꿈
+    pub fn set(&mut self, value: f32) {
+        self.animate_to(value, 0.0);
+    }
+
+    /// delta초 진행하고 현재 값을 돌려줍니다.
+    pub fn advance(&mut self, delta: f32) -> f32 {
+        if self.is_finished() {
+            return self.value;
+        }
+        self.elapsed = (self.elapsed + delta.max(0.0)).min(self.duration);
+        let t = self.easing.apply(self.elapsed / self.duration);
+        self.value = self.from + (self.to - self.from) * t;
+        self.value
+    }
+}
+
+impl Default for Tween {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    use super::*;
+
+    // 글리프의 바이트 위치를 글자 시작으로 묶은 뒤 글자 번호와 글자 수
+    fn order(text: &str, clusters: &[usize]) -> (Vec<usize>, usize) {
+        let graphemes: Vec<usize> = text
+            .grapheme_indices(true)
+            .map(|(index, _)| index)
+            .collect();
+        let starts: Vec<usize> = clusters
+            .iter()
+            .map(|&cluster| grapheme_start(&graphemes, cluster))
+            .collect();
+        character_order(&starts)
+    }
+
+    #[test]
+    fn combining_marks_share_a_character() {
+        // e + U+0301 (2바이트), x
+        let text = "e\u{301}x";
+        assert_eq!(grapheme_start(&[0, 3], 1), 0);
+        assert_eq!(order(text, &[0, 1, 3]), (vec![0, 0, 1], 2));
+    }
+
+    #[test]
+    fn zwj_emoji_is_one_character() {
+        // 가족 이모지는 👨 ZWJ 👩 ZWJ 👧 (18바이트), 합자가 없는 폰트는 글리프가 여러 개
+        let text = "a\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}b";
+        assert_eq!(order(text, &[0, 1, 19]), (vec![0, 1, 2], 3));
+        assert_eq!(order(text, &[0, 1, 8, 15, 19]), (vec![0, 1, 1, 1, 2], 3));
+    }
+
+    #[test]
+    fn hangul_jamo_compose_one_character() {
+        // 첫가끝 자모 ᄀ ᅡ ᆨ (각 3바이트)는 한 글자 '각', 뒤의 '한'은 완성형
+        let text = "\u{1100}\u{1161}\u{11A8}한";
+        assert_eq!(order(text, &[0, 3, 6, 9]), (vec![0, 0, 0, 1], 2));
+    }
+
+    #[test]
+    fn order_follows_logical_position() {
+        // 오른쪽→왼쪽 줄은 글리프가 시각 순서라 바이트 위치가 거꾸로 나옴
+        assert_eq!(character_order(&[6, 3, 3, 0]), (vec![2, 1, 1, 0], 3));
+        assert_eq!(character_order(&[]), (vec![], 0));
+    }
+}
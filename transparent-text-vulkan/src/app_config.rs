@@ -0,0 +1,285 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use transparent_text_vulkan::{
+    format_color, format_effects, parse_color, parse_effects, TextEffects,
+};
+use winit::keyboard::KeyCode;
+
+use crate::{action::Action, backdrop::Backdrop, file_watch::FileWatcher, placement::Anchor};
+
+/// `--config`가 없을 때 현재 디렉터리에서 찾는 설정 파일
+pub const DEFAULT_CONFIG_PATH: &str = "config.toml";
+
+/// 프리셋과 저장한 상태를 두는 디렉터리 (`<사용자 설정 디렉터리>/transparent-text-vulkan`)
+///
+/// Linux: `~/.config`, macOS: `~/Library/Application Support`, Windows: `%APPDATA%`
+pub fn user_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")))
+}
+
+/// 설정 파일 오류
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("설정 파일을 읽을 수 없습니다 ({path}): {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("설정 파일 형식 오류: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("설정 파일 감시 실패: {0}")]
+    Watch(#[from] notify::Error),
+}
+
+/// 예제 프로그램 설정 (config.toml)
+///
+/// 모든 항목은 생략할 수 있고, 생략한 항목은 기본값이나 명령줄 인자를 따릅니다.
+///
+/// ```toml
+/// text = "<b>현재 재생 중</b>\n노래 제목"
+/// font = "Noto Sans KR"
+/// font_size = 40
+/// opacity = 0.8
+/// color = "#ffffff"
+/// effects = "outline+shadow"
+/// outline_color = "#000000cc"
+///
+/// [window]
+/// x = 100
+/// y = 100
+/// width = 640
+/// height = 200
+/// click_through = true
+/// monitor = 1
+/// anchor = "bottom-center"
+/// margin = 40
+///
+/// [keys]
+/// next-effect = "KeyN"
+/// quit = "KeyQ"
+///
+/// [global_keys]
+/// visibility = "ctrl+shift+KeyH"
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    /// 표시할 텍스트 (마크업 사용 가능)
+    pub text: Option<String>,
+    /// 텍스트 대신 표시할 현재 시각의 strftime 형식 (`"%H:%M:%S"`, 마크업 사용 가능)
+    pub clock: Option<String>,
+    /// 텍스트 대신 시간에 맞춰 보여 줄 SRT 자막 파일
+    pub subtitles: Option<String>,
+    /// 자막이 서서히 나타나고 사라지는 시간 (초, 기본 0.25)
+    pub subtitle_fade: Option<f32>,
+    /// 자막의 `{\k}` 음절 시간이나 `karaoke` 메시지에 맞춰 글자를 칠하는 노래방 강조 색 (기본 `#ffcc00`)
+    pub karaoke_color: Option<Color>,
+    /// 창보다 넓은 텍스트를 가로로 흘리는 전광판 모드의 속도 (초당 논리 픽셀, 음수면 오른쪽으로)
+    pub marquee: Option<f32>,
+    /// 전광판 모드에서 텍스트 끝과 다음 복사본 사이의 간격 (논리 픽셀, 기본 80)
+    pub marquee_gap: Option<f32>,
+    /// 세로쓰기 (글자는 위에서 아래로, 줄은 오른쪽에서 왼쪽으로)
+    pub vertical: Option<bool>,
+    /// 탭 간격 (기본 폰트의 공백 너비 배수, 기본 4)
+    pub tab_width: Option<f32>,
+    /// 탭으로 나뉜 칸을 열로 맞춤 (이름과 값을 탭으로 나눈 표)
+    pub align_tabs: Option<bool>,
+    /// 텍스트 대신 표시할 카운트다운 시간 (`"10:00"`, `"1:30:00"`, `"90"`)
+    pub countdown: Option<String>,
+    /// 텍스트 대신 스톱워치를 표시 (countdown이 있으면 무시)
+    pub stopwatch: Option<bool>,
+    /// 타이머의 소수점 아래 자릿수 (0-3, 기본 3: 밀리초)
+    pub timer_decimals: Option<u32>,
+    /// 텍스트 대신 표시할 시스템 통계 템플릿 (`"CPU {cpu}%  RAM {mem_used}/{mem_total}"`, system-stats 기능)
+    pub system_stats: Option<String>,
+    /// 시스템 통계를 다시 읽는 간격 (초, 기본 1초)
+    pub system_stats_interval: Option<f32>,
+    /// 기본 폰트 (파일 경로 또는 시스템 폰트 패밀리 이름)
+    pub font: Option<String>,
+    /// 대체 폰트 (파일 경로 또는 패밀리 이름, 우선순위 순서)
+    pub fallback_fonts: Vec<String>,
+    /// 글자 크기 (픽셀)
+    pub font_size: Option<f32>,
+    pub opacity: Option<f32>,
+    /// 투명도와 효과 파라미터를 바꿀 때 부드럽게 바뀌는 시간 (초, 0이면 바로 바뀜)
+    pub fade_duration: Option<f32>,
+    /// Ctrl+Z로 되돌릴 수 있는 변경 수 (0이면 기록하지 않음)
+    pub history_depth: Option<usize>,
+    /// 멀티샘플 안티앨리어싱 샘플 수 (1, 2, 4, 8, 시작할 때만 적용)
+    pub msaa: Option<u32>,
+    /// 글리프 아틀라스 밉맵 (작게 줄인 글자의 계단 현상 방지, 시작할 때만 적용)
+    pub mipmaps: Option<bool>,
+    /// HDR 스왑체인 (scRGB, 지원하지 않으면 SDR에서 발광을 톤 매핑, 시작할 때만 적용)
+    pub hdr: Option<bool>,
+    /// render pass와 framebuffer 대신 dynamic rendering으로 그림 (지원하지 않으면 render pass, 시작할 때만 적용)
+    pub dynamic_rendering: Option<bool>,
+    /// F11 전체 화면을 독점 모드로 (Windows에서 지원할 때만, 아니면 테두리 없는 전체 화면, 시작할 때만 적용)
+    pub exclusive_fullscreen: Option<bool>,
+    /// 시작할 때 egui 컨트롤 패널을 띄움 (egui-panel 기능, F10으로 켜고 끔)
+    pub panel: Option<bool>,
+    /// 프레임마다 실행할 rhai 스크립트 경로 (scripting 기능, 생략하면 현재 디렉터리의 overlay.rhai, 시작할 때만 적용)
+    pub script: Option<String>,
+    /// 초당 최대 프레임 수 (0이나 생략이면 제한 없음, 수직 동기화면 모니터 주사율)
+    pub max_fps: Option<f32>,
+    /// 바뀐 것이나 진행 중인 애니메이션이 있을 때만 다시 그림 (가만히 있는 오버레이의 GPU/전력 절약)
+    pub lazy_redraw: Option<bool>,
+    /// 밉맵 단계 선택에 더하는 값 (양수면 더 부드럽게, 음수면 더 선명하게)
+    pub lod_bias: Option<f32>,
+    /// 텍스트 전체의 기본 색 (마크업 `<color>`가 우선)
+    pub color: Option<Color>,
+    /// 효과 조합 (`outline+shadow`처럼 `+`로 이음)
+    pub effects: Option<Effects>,
+    pub outline_color: Option<Color>,
+    pub outline_width: Option<f32>,
+    pub glow_color: Option<Color>,
+    pub glow_radius: Option<f32>,
+    pub shadow_color: Option<Color>,
+    pub shadow_blur: Option<f32>,
+    /// 텍스트 뒤 배경 상자
+    pub background: Option<bool>,
+    pub window: WindowConfig,
+    /// 동작별 키 (winit `KeyCode` 이름, 예: `"KeyE"`, `"Digit1"`, `"F2"`)
+    pub keys: HashMap<Action, KeyCode>,
+    /// 동작별 전역 단축키 (`"ctrl+alt+KeyE"` 형식, global-hotkey 기능 필요)
+    pub global_keys: HashMap<Action, String>,
+}
+
+/// 창 위치와 크기 (논리 픽셀)
+///
+/// `monitor`나 `anchor`를 지정하면 `x`, `y` 대신 모니터 안의 위치로 창을 놓습니다.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WindowConfig {
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// 마우스 클릭을 창 아래로 통과시킴
+    pub click_through: Option<bool>,
+    /// 창을 놓을 모니터 번호 (0부터, 시작할 때 목록 출력)
+    pub monitor: Option<usize>,
+    /// 모니터 안의 위치 (`bottom-center` 등)
+    pub anchor: Option<Anchor>,
+    /// 모니터 가장자리와의 간격
+    pub margin: Option<f64>,
+    /// 합성기가 투명 창을 지원하지 않을 때 채우는 배경 색 (알파는 무시)
+    pub opaque_background: Option<Color>,
+    /// 창 뒤의 Windows 배경 (`blur`, `acrylic`, `mica`, backdrop 기능 필요)
+    pub backdrop: Option<Backdrop>,
+    /// blur, acrylic 배경에 섞는 색 (알파가 진할수록 색이 강해짐)
+    pub backdrop_tint: Option<Color>,
+}
+
+impl WindowConfig {
+    pub fn position(&self) -> Option<[i32; 2]> {
+        Some([self.x?, self.y?])
+    }
+
+    pub fn size(&self) -> Option<[u32; 2]> {
+        Some([self.width?, self.height?])
+    }
+}
+
+/// `#rrggbb` 형식 색
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Color(pub [u8; 4]);
+
+impl TryFrom<String> for Color {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse_color(&value)
+            .map(Color)
+            .ok_or_else(|| format!("색 형식이 아닙니다 (#rgb, #rrggbb, #rrggbbaa): {value}"))
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        format_color(color.0)
+    }
+}
+
+/// `outline+shadow` 형식 효과 조합
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Effects(pub TextEffects);
+
+impl TryFrom<String> for Effects {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse_effects(&value)
+            .map(Effects)
+            .ok_or_else(|| format!("알 수 없는 효과: {value}"))
+    }
+}
+
+impl From<Effects> for String {
+    fn from(effects: Effects) -> Self {
+        format_effects(effects.0)
+    }
+}
+
+impl AppConfig {
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.display().to_string(),
+            source,
+        })?;
+        let mut config: Self = toml::from_str(&text)?;
+        config.sanitize();
+        Ok(config)
+    }
+
+    // Duration으로 바꿀 수 없는 값은 경고하고 버림 (다시 읽을 때도 거침)
+    fn sanitize(&mut self) {
+        if let Some(interval) = self
+            .system_stats_interval
+            .filter(|interval| !interval.is_finite() || *interval <= 0.0)
+        {
+            warn!("system_stats_interval이 올바르지 않아 무시합니다: {interval}");
+            self.system_stats_interval = None;
+        }
+        if let Some(max_fps) = self.max_fps.filter(|max_fps| !max_fps.is_finite()) {
+            warn!("max_fps가 올바르지 않아 무시합니다: {max_fps}");
+            self.max_fps = None;
+        }
+    }
+
+    /// 폰트를 다시 불러와야 하는 변경인지
+    pub fn font_changed(&self, other: &AppConfig) -> bool {
+        self.font != other.font
+            || self.fallback_fonts != other.fallback_fonts
+            || self.font_size != other.font_size
+    }
+}
+
+/// 설정 파일이 바뀌면 다시 읽어 돌려줌
+pub struct ConfigWatcher {
+    file: FileWatcher,
+}
+
+impl ConfigWatcher {
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self, ConfigError> {
+        Ok(Self {
+            file: FileWatcher::new(path)?,
+        })
+    }
+
+    /// 마지막 확인 이후 파일이 바뀌었으면 새 설정
+    pub fn poll(&self) -> Option<Result<AppConfig, ConfigError>> {
+        self.file
+            .changed()
+            .then(|| AppConfig::load(self.file.path()))
+    }
+}
@@ -0,0 +1,1270 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+use vulkano::{
+    command_buffer::{
+        allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, BlitImageInfo,
+        BufferImageCopy, CommandBufferUsage, CopyBufferToImageInfo, ImageBlit,
+    },
+    device::Queue,
+    format::Format,
+    image::{
+        sampler::{ComponentMapping, ComponentSwizzle, Filter},
+        view::{ImageView, ImageViewCreateInfo, ImageViewType},
+        Image, ImageCreateInfo, ImageSubresourceLayers, ImageSubresourceRange, ImageType,
+        ImageUsage,
+    },
+    memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    sync::{self, GpuFuture, Sharing},
+};
+use fontdue::{layout::GlyphRasterConfig, Font};
+use tracing::warn;
+
+use crate::{
+    color::ColorBitmap,
+    error::RendererError,
+    font::FontFace,
+    frame::FrameFence,
+    staging::StagingRing,
+    variation::{Coverage, Variation},
+};
+
+// 효과 셰이더가 글리프 주변을 샘플링할 수 있도록 각 글리프 둘레에 두는 여백 (픽셀)
+pub(crate) const GLYPH_PADDING: u32 = 8;
+
+const INITIAL_ATLAS_SIZE: u32 = 256;
+// 레이어 한 장의 최대 변 (모든 Vulkan 장치가 지원하는 2D 이미지 크기)
+const MAX_ATLAS_SIZE: u32 = 4096;
+// 최대 크기의 레이어가 가득 차면 더하는 레이어 수의 상한 (4096² × 4 = 8192² 면적)
+const MAX_ATLAS_LAYERS: u32 = 4;
+
+// 스테이징 버퍼 링의 슬롯 수 (한 프레임에 여러 번 업로드해도 GPU가 읽는 버퍼를 기다리지 않게)
+const STAGING_SLOTS: usize = 3;
+
+// 밉맵 단계 수 (0단계 포함)
+// 여백이 1텍셀 이상 남는 단계까지만 만들어 축소해도 옆 글리프가 번지지 않게 함 (8 → 4 → 2 → 1)
+const MAX_MIP_LEVELS: u32 = GLYPH_PADDING.ilog2() + 1;
+
+// 아틀라스 안의 글리프 영역 (여백 포함, 텍셀 단위)
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AtlasEntry {
+    // 배열 이미지의 레이어
+    pub layer: u32,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    // 펜 위치/베이스라인 기준 비트맵 왼쪽 위 좌표 (여백 제외, Y는 아래 방향)
+    pub offset: [f32; 2],
+    // 컬러 글리프(이모지 등)인지 여부
+    pub colored: bool,
+}
+
+impl AtlasEntry {
+    /// 공백처럼 그릴 픽셀이 없는 글리프
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+}
+
+/// 글리프 아틀라스의 캐시 통계 (`TextRenderer::glyph_cache_stats`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GlyphCacheStats {
+    /// 아틀라스에서 바로 찾은 글리프 수
+    pub hits: u64,
+    /// 아틀라스에 없어 새로 래스터라이즈한 글리프 수
+    pub misses: u64,
+    /// 최대 크기에서 공간이 모자라 내보낸 글리프 수
+    pub evictions: u64,
+    /// 지금 아틀라스에 든 글리프 수
+    pub glyphs: usize,
+    /// 배열 이미지의 레이어 수
+    pub layers: u32,
+}
+
+// 아틀라스에 든 글리프와 마지막으로 쓴 레이아웃 패스 (LRU 제거용)
+struct CachedGlyph {
+    entry: AtlasEntry,
+    // 글리프가 차지한 영역 (다시 쓴 빈 영역에서 떼어 낸 얇은 자투리를 포함할 수 있음, 빈 글리프는 None)
+    slot: Option<Slot>,
+    last_used: u64,
+}
+
+// 레이어와 그 안의 영역 [x, y, 너비, 높이]
+#[derive(Debug, Clone, Copy)]
+struct Slot {
+    layer: u32,
+    rect: [u32; 4],
+}
+
+impl Slot {
+    fn area(&self) -> u64 {
+        self.rect[2] as u64 * self.rect[3] as u64
+    }
+
+    fn fits(&self, width: u32, height: u32) -> bool {
+        self.rect[2] >= width && self.rect[3] >= height
+    }
+
+    // 왼쪽 위에서 width x height를 떼어 내고 남는 오른쪽과 아래 영역
+    // (남는 쪽이 어떤 글리프도 들어가지 못할 만큼 얇으면 떼어 낸 영역에 붙여 둠)
+    fn split(self, width: u32, height: u32) -> (Slot, Vec<Slot>) {
+        let [x, y, slot_width, slot_height] = self.rect;
+        let usable = |extent: u32| extent > GLYPH_PADDING * 2;
+        let width = if usable(slot_width - width) {
+            width
+        } else {
+            slot_width
+        };
+        let height = if usable(slot_height - height) {
+            height
+        } else {
+            slot_height
+        };
+        let (rest_width, rest_height) = (slot_width - width, slot_height - height);
+        // 오른쪽 모서리 부분은 더 넓게 남는 쪽에 붙임
+        let (right_height, bottom_width) = if rest_width >= rest_height {
+            (slot_height, width)
+        } else {
+            (height, slot_width)
+        };
+        let rest = [
+            [x + width, y, rest_width, right_height],
+            [x, y + height, bottom_width, rest_height],
+        ]
+        .into_iter()
+        .filter(|rect| rect[2] > 0 && rect[3] > 0)
+        .map(|rect| Slot {
+            layer: self.layer,
+            rect,
+        })
+        .collect();
+        let slot = Slot {
+            layer: self.layer,
+            rect: [x, y, width, height],
+        };
+        (slot, rest)
+    }
+}
+
+// 내보낸 글리프가 비운 영역
+#[derive(Debug, Clone, Copy)]
+struct FreeSlot {
+    slot: Slot,
+    // 이 번호 앞의 프레임이 모두 끝나야 다시 씀 (내보낸 글리프를 그렸을 수 있는 프레임)
+    ready_at: u64,
+}
+
+// 배열 이미지의 레이어 한 장 (CPU 사본, 패커, 아직 업로드하지 않은 영역 [x0, y0, x1, y1])
+struct AtlasLayer {
+    pixels: Vec<u8>,
+    packer: ShelfPacker,
+    dirty: Option<[u32; 4]>,
+    // 통째로 비운 레이어는 이 번호 앞의 프레임이 모두 끝나야 패커에서 자리를 줌
+    ready_at: u64,
+    // 비운 뒤 아직 GPU 이미지에 지운 내용을 올리지 않음 (처음 자리를 줄 때 전체를 업로드)
+    cleared: bool,
+}
+
+impl AtlasLayer {
+    // 새 레이어는 내용이 정의되지 않았으므로 전체를 업로드
+    fn new(size: u32, channels: u32) -> Self {
+        Self {
+            pixels: vec![0u8; (size * size * channels) as usize],
+            packer: ShelfPacker::new(size, size),
+            dirty: Some([0, 0, size, size]),
+            ready_at: 0,
+            cleared: false,
+        }
+    }
+
+    // 기존 픽셀을 new_size 크기의 버퍼로 옮기고 패커 공간을 늘림
+    fn grow(&mut self, size: u32, new_size: u32, channels: u32) {
+        let mut pixels = vec![0u8; (new_size * new_size * channels) as usize];
+        let row_bytes = (size * channels) as usize;
+        for y in 0..size as usize {
+            let src = y * row_bytes;
+            let dst = y * (new_size * channels) as usize;
+            pixels[dst..dst + row_bytes].copy_from_slice(&self.pixels[src..src + row_bytes]);
+        }
+        self.pixels = pixels;
+        self.packer.grow(new_size, new_size);
+    }
+
+    fn mark_dirty(&mut self, x: u32, y: u32, width: u32, height: u32) {
+        let rect = [x, y, x + width, y + height];
+        self.dirty = Some(match self.dirty {
+            Some(d) => [d[0].min(rect[0]), d[1].min(rect[1]), d[2].max(rect[2]), d[3].max(rect[3])],
+            None => rect,
+        });
+    }
+}
+
+// 한 줄(shelf)씩 채워 나가는 단순한 사각형 패커
+struct ShelfPacker {
+    width: u32,
+    height: u32,
+    shelves: Vec<Shelf>,
+    next_y: u32,
+}
+
+struct Shelf {
+    y: u32,
+    height: u32,
+    next_x: u32,
+}
+
+impl ShelfPacker {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            shelves: Vec::new(),
+            next_y: 0,
+        }
+    }
+
+    fn allocate(&mut self, width: u32, height: u32) -> Option<[u32; 2]> {
+        // 높이가 맞는 기존 shelf 중 가장 낭비가 적은 곳 선택
+        let max_width = self.width;
+        let best = self
+            .shelves
+            .iter_mut()
+            .filter(|shelf| shelf.height >= height && shelf.next_x + width <= max_width)
+            .min_by_key(|shelf| shelf.height - height);
+
+        if let Some(shelf) = best {
+            let position = [shelf.next_x, shelf.y];
+            shelf.next_x += width;
+            return Some(position);
+        }
+
+        if width > self.width || self.next_y + height > self.height {
+            return None;
+        }
+
+        let y = self.next_y;
+        self.next_y += height;
+        self.shelves.push(Shelf {
+            y,
+            height,
+            next_x: width,
+        });
+
+        Some([0, y])
+    }
+
+    // 기존 배치는 그대로 두고 오른쪽/아래로 공간만 늘림
+    fn grow(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+    }
+}
+
+/// 필요할 때마다 글리프를 래스터라이즈해 채워 넣는 GPU 글리프 아틀라스
+///
+/// CPU 쪽 사본을 함께 유지하며, 변경된 영역만 GPU로 업로드합니다.
+/// 일반 글리프는 커버리지만 `R8_UNORM` 한 채널에 저장하고 색은 인스턴스에서 입힙니다.
+/// 서브픽셀 모드(채널별 커버리지)는 처음부터, 일반 모드는 컬러 글리프가 처음 들어올 때 `R8G8B8A8_UNORM`으로
+/// 바꾸며, 이때 일반 글리프는 흰색 premultiplied alpha로 펼쳐 셰이더가 어느 형식이든 `.r`을 커버리지로 읽습니다.
+/// RGBA 픽셀은 premultiplied alpha로 저장해 선형 필터링 시 가장자리에 색이 번지지 않게 합니다.
+/// 레이아웃 결과로 필요한 면적을 미리 계산해 한 번에 알맞은 크기로 키우고,
+/// 그래도 부족하면 두 배로 커집니다. 레이어가 최대 크기에 이르면 같은 크기의 레이어를 더하며
+/// (2D 배열 이미지), 글리프마다 레이어 번호를 인스턴스에 실어 보내므로 배치를 나누지 않습니다.
+/// 커지거나 레이어가 늘 때마다 이미지를 새로 만듭니다 (`generation` 증가).
+/// 밉맵을 켜면 업로드할 때 바뀐 영역의 하위 단계를 blit으로 줄여 채웁니다.
+/// 레이어를 모두 써도 자리가 없으면 이번 레이아웃 패스에 쓰지 않은 글리프 중 새 글리프가 들어가는 영역을
+/// 가진 것을 가장 오래전에 쓴 것부터 내보내고 (LRU), 그 영역을 잘라 새 글리프에 다시 씁니다.
+/// 그런 글리프가 없으면 이번 패스에 쓴 글리프가 없는 레이어 중 가장 오래전에 쓴 레이어를 통째로 비웁니다.
+/// 비운 영역은 `frame_submitted`로 받은 펜스로 그 글리프를 그렸을 수 있는 프레임이 끝난 것을 확인한 뒤에 씁니다.
+/// 업로드는 기다리지 않고 제출하며, 그 future를 `take_pending`으로 가져가 그리기 전에 기다립니다.
+pub(crate) struct GlyphAtlas {
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    staging: StagingRing,
+    // 복사를 맡는 전송 전용 큐 (없으면 밉맵 blit과 같은 그래픽 큐에서 복사)
+    transfer_queue: Option<Arc<Queue>>,
+    // 제출했지만 아직 아무도 기다리지 않은 업로드나 아틀라스를 읽는 컴퓨트 패스 (완료되면 세마포어가 신호됨)
+    pending: Option<Box<dyn GpuFuture>>,
+    // 이미지를 함께 쓰는 큐 패밀리 (여러 패밀리면 concurrent 공유, 비어 있으면 exclusive)
+    queue_families: Vec<u32>,
+    // 레이어 한 장의 변 (모든 레이어가 같은 크기)
+    size: u32,
+    mip_levels: u32,
+    // 텍셀당 바이트 (R8이면 1, RGBA8이면 4)
+    channels: u32,
+    layers: Vec<AtlasLayer>,
+    glyphs: HashMap<GlyphRasterConfig, CachedGlyph>,
+    // 내보낸 글리프가 비운 영역
+    free_slots: Vec<FreeSlot>,
+    // 레이아웃 패스 번호 (이번 패스에 쓴 글리프는 내보내지 않음)
+    pass: u64,
+    // 아틀라스를 그린 프레임의 번호와 펜스 (제출 순서, 끝난 앞쪽은 정리)
+    frames: VecDeque<(u64, FrameFence)>,
+    // 지금까지 제출한 프레임 수 (다음 프레임의 번호)
+    submitted: u64,
+    stats: GlyphCacheStats,
+    // 글리프가 차지한 면적 (여백 포함)
+    used_area: u64,
+    image: Arc<Image>,
+    // 모든 레이어와 밉맵 단계 (텍스트 셰이더가 샘플링)
+    view: Arc<ImageView>,
+    // 0단계만, 알파에 커버리지가 오도록 R8은 모든 성분을 R로 (그림자 블러와 외곽선 컴퓨트 셰이더가 읽음)
+    base_view: Arc<ImageView>,
+    generation: u64,
+    // 업로드할 때마다 증가 (그림자 블러 갱신 판단용)
+    revision: u64,
+    // 외곽선 글리프를 RGB 서브픽셀 커버리지로 래스터라이즈
+    subpixel: bool,
+}
+
+impl GlyphAtlas {
+    pub fn new(
+        memory_allocator: Arc<StandardMemoryAllocator>,
+        subpixel: bool,
+        mipmaps: bool,
+    ) -> Result<Self, RendererError> {
+        let size = INITIAL_ATLAS_SIZE;
+        let mip_levels = if mipmaps { MAX_MIP_LEVELS } else { 1 };
+        let channels = if subpixel { 4 } else { 1 };
+        let (image, view, base_view) =
+            create_atlas_image(&memory_allocator, size, 1, channels, mip_levels, &[])?;
+
+        Ok(Self {
+            staging: StagingRing::new(memory_allocator.clone(), STAGING_SLOTS),
+            memory_allocator,
+            transfer_queue: None,
+            pending: None,
+            queue_families: Vec::new(),
+            size,
+            mip_levels,
+            channels,
+            layers: vec![AtlasLayer::new(size, channels)],
+            glyphs: HashMap::new(),
+            free_slots: Vec::new(),
+            pass: 0,
+            frames: VecDeque::new(),
+            submitted: 0,
+            stats: GlyphCacheStats::default(),
+            used_area: 0,
+            image,
+            view,
+            base_view,
+            generation: 0,
+            revision: 0,
+            subpixel,
+        })
+    }
+
+    pub fn view(&self) -> Arc<ImageView> {
+        self.view.clone()
+    }
+
+    /// 밉맵 없이 0단계만 보는 뷰 (형식과 관계없이 알파가 커버리지)
+    pub fn base_view(&self) -> Arc<ImageView> {
+        self.base_view.clone()
+    }
+
+    /// 밉맵 단계 수 (밉맵을 끄면 1)
+    pub fn mip_levels(&self) -> u32 {
+        self.mip_levels
+    }
+
+    /// 이미지가 다시 만들어질 때마다 증가 (디스크립터 셋 갱신 판단용)
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// 내용이 GPU로 업로드될 때마다 증가
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// 레이어 한 장의 변 (텍셀)
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// 배열 이미지의 레이어 수
+    pub fn layers(&self) -> u32 {
+        self.layers.len() as u32
+    }
+
+    /// 업로드의 복사를 전송 전용 큐에서 실행 (`None`이면 밉맵 blit과 같은 그래픽 큐에서 복사)
+    ///
+    /// 이미지를 전송 큐 패밀리와 함께 쓰도록 `set_queue_families`도 호출해야 합니다.
+    pub fn set_transfer_queue(&mut self, transfer_queue: Option<Arc<Queue>>) {
+        let family = |queue: &Option<Arc<Queue>>| queue.as_ref().map(|q| q.queue_family_index());
+        if family(&transfer_queue) != family(&self.transfer_queue) {
+            // 스테이징 버퍼는 exclusive이므로 새 패밀리에서 처음 쓰도록 새로 할당
+            self.staging.clear();
+        }
+        self.transfer_queue = transfer_queue;
+    }
+
+    pub fn transfer_queue(&self) -> Option<&Arc<Queue>> {
+        self.transfer_queue.as_ref()
+    }
+
+    /// 아틀라스와 그림자/외곽선 이미지를 함께 쓰는 큐 패밀리 (하나 이하면 exclusive)
+    ///
+    /// 바뀌면 이미지를 다시 만들고 (`generation` 증가) 전체를 다시 올립니다.
+    pub fn set_queue_families(&mut self, queue_families: Vec<u32>) -> Result<(), RendererError> {
+        let queue_families = if queue_families.len() > 1 {
+            queue_families
+        } else {
+            Vec::new()
+        };
+        if queue_families != self.queue_families {
+            self.queue_families = queue_families;
+            self.resize(self.size, self.layers())?;
+        }
+        Ok(())
+    }
+
+    pub fn queue_families(&self) -> &[u32] {
+        &self.queue_families
+    }
+
+    /// 아직 기다리지 않은 업로드와 컴퓨트 패스 (가져간 쪽이 아틀라스를 읽는 명령보다 먼저 기다려야 함)
+    pub fn take_pending(&mut self) -> Option<Box<dyn GpuFuture>> {
+        self.pending.take()
+    }
+
+    /// 아틀라스를 읽는 GPU 작업을 기다리지 않고 남겨 둠 (`take_pending`과 다음 업로드가 그 뒤에 이어짐)
+    pub fn set_pending(&mut self, future: Box<dyn GpuFuture>) {
+        self.pending = Some(future);
+    }
+
+    /// 아직 없는 글리프들이 모두 들어갈 만큼 아틀라스를 미리 키움
+    ///
+    /// 한 글자씩 넣으며 여러 번 두 배로 키우는 대신 이미지를 한 번만 다시 만듭니다.
+    /// 최대 크기까지 키운 뒤에도 모자라면 레이어를 더합니다.
+    pub fn reserve<'a>(
+        &mut self,
+        glyphs: impl IntoIterator<Item = (&'a Font, GlyphRasterConfig)>,
+    ) -> Result<(), RendererError> {
+        let missing: HashMap<GlyphRasterConfig, &Font> = glyphs
+            .into_iter()
+            .filter(|(_, key)| !self.glyphs.contains_key(key))
+            .map(|(font, key)| (key, font))
+            .collect();
+
+        let needed: u64 = missing
+            .iter()
+            .map(|(key, font)| {
+                let metrics = font.metrics_indexed(key.glyph_index, key.px);
+                if metrics.width == 0 || metrics.height == 0 {
+                    return 0;
+                }
+                let width = metrics.width as u64 + GLYPH_PADDING as u64 * 2;
+                let height = metrics.height as u64 + GLYPH_PADDING as u64 * 2;
+                width * height
+            })
+            .sum();
+        if needed == 0 {
+            return Ok(());
+        }
+
+        // shelf 패킹의 빈 공간을 감안해 면적의 3/4까지만 채운다고 보고 크기와 레이어 수를 정함
+        let required = self.used_area + needed;
+        let capacity = |size: u32, layers: u32| (size as u64 * size as u64) * 3 / 4 * layers as u64;
+        let mut size = self.size;
+        let mut layers = self.layers();
+        while capacity(size, layers) < required && size < MAX_ATLAS_SIZE {
+            size *= 2;
+        }
+        while capacity(size, layers) < required && layers < MAX_ATLAS_LAYERS {
+            layers += 1;
+        }
+
+        if size > self.size || layers > self.layers() {
+            self.resize(size, layers)?;
+        }
+
+        Ok(())
+    }
+
+    /// 캐시 적중, 실패, 제거 횟수와 지금 든 글리프 수
+    pub fn stats(&self) -> GlyphCacheStats {
+        GlyphCacheStats {
+            glyphs: self.glyphs.len(),
+            layers: self.layers(),
+            ..self.stats
+        }
+    }
+
+    /// 새 레이아웃 패스를 시작하고, 화면에 남아 있는 글리프를 이번 패스에 쓴 것으로 표시
+    ///
+    /// 이번 패스에 쓴 글리프는 공간이 모자라도 내보내지 않습니다.
+    pub fn begin_pass<'a>(&mut self, live: impl IntoIterator<Item = &'a GlyphRasterConfig>) {
+        self.pass += 1;
+        for key in live {
+            if let Some(glyph) = self.glyphs.get_mut(key) {
+                glyph.last_used = self.pass;
+            }
+        }
+    }
+
+    /// 아틀라스를 그린 프레임을 제출한 뒤 그 펜스를 기록
+    ///
+    /// 내보낸 글리프의 영역은 그때까지 제출한 프레임의 펜스가 모두 신호된 뒤에 다시 씁니다.
+    /// 프레임마다 펜스를 기다린 뒤 다음 프레임을 그린다면 호출하지 않아도 됩니다.
+    pub fn frame_submitted(&mut self, fence: FrameFence) {
+        self.frames.push_back((self.submitted, fence));
+        self.submitted += 1;
+        self.completed_frames();
+    }
+
+    /// 이미 아틀라스에 있는 글리프 (찾으면 이번 패스에 쓴 것으로 표시)
+    pub fn get(&mut self, key: &GlyphRasterConfig) -> Option<AtlasEntry> {
+        match self.glyphs.get_mut(key) {
+            Some(glyph) => {
+                glyph.last_used = self.pass;
+                self.stats.hits += 1;
+                Some(glyph.entry)
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// 글리프를 아틀라스에서 찾고, 없으면 래스터라이즈해서 추가
+    ///
+    /// `embolden`이 0보다 크면 커버리지를 그 픽셀 수만큼 가로로 넓혀 굵게 만듭니다.
+    /// `sideways`면 비트맵을 시계 방향으로 90° 돌려 넣습니다 (세로쓰기에서 눕힌 글자).
+    /// 가변 폰트는 `variation`의 축 좌표를 적용한 인스턴스로 래스터라이즈합니다.
+    pub fn glyph(
+        &mut self,
+        face: &FontFace,
+        key: GlyphRasterConfig,
+        variation: &Variation,
+        embolden: usize,
+        sideways: bool,
+    ) -> Result<AtlasEntry, RendererError> {
+        if let Some(glyph) = self.glyphs.get(&key) {
+            return Ok(glyph.entry);
+        }
+
+        // 서브픽셀 모드는 픽셀마다 RGB 세 개의 커버리지
+        let Coverage {
+            xmin,
+            ymin,
+            mut width,
+            height,
+            pixels: mut coverage,
+        } = face
+            .rasterize_variation(key.glyph_index, key.px, variation, self.subpixel)
+            .unwrap_or_else(|| {
+                let (metrics, pixels) = if self.subpixel {
+                    face.font.rasterize_config_subpixel(key)
+                } else {
+                    face.font.rasterize_config(key)
+                };
+                Coverage {
+                    xmin: metrics.xmin,
+                    ymin: metrics.ymin,
+                    width: metrics.width,
+                    height: metrics.height,
+                    pixels,
+                }
+            });
+        let channels = if self.subpixel { 3 } else { 1 };
+
+        if embolden > 0 && width > 0 {
+            // 한 줄의 서브픽셀은 가로로 나란하므로 채널 수만큼 넓은 한 평면으로 다룸
+            coverage = dilate_horizontal(&coverage, width * channels, height, embolden * channels);
+            width += embolden;
+        }
+
+        // 일반 모드는 커버리지만 저장 (색은 셰이더에서 입힘, RGBA 아틀라스면 흰색 premultiplied)
+        // 서브픽셀 모드는 RGB에 채널별 커버리지, 알파에 그 최댓값을 저장
+        let texels: Vec<u8> = if self.subpixel {
+            coverage
+                .chunks_exact(3)
+                .flat_map(|c| [c[0], c[1], c[2], c[0].max(c[1]).max(c[2])])
+                .collect()
+        } else if self.channels == 4 {
+            coverage.iter().flat_map(|&c| [c, c, c, c]).collect()
+        } else {
+            coverage
+        };
+        let offset = [xmin as f32, -(ymin as f32 + height as f32)];
+
+        self.insert(key, [width, height], offset, false, sideways, &texels)
+    }
+
+    /// 컬러 글리프(이모지 등)의 RGBA 비트맵을 premultiplied alpha로 바꿔 추가
+    ///
+    /// 아틀라스가 아직 R8이면 먼저 RGBA로 바꿉니다 (`generation` 증가).
+    pub fn insert_color(
+        &mut self,
+        key: GlyphRasterConfig,
+        bitmap: &ColorBitmap,
+        sideways: bool,
+    ) -> Result<AtlasEntry, RendererError> {
+        if self.channels == 1 {
+            self.expand_to_rgba()?;
+        }
+        let rgba: Vec<u8> = bitmap
+            .pixels
+            .chunks_exact(4)
+            .flat_map(|p| {
+                let premultiply = |c: u8| ((c as u32 * p[3] as u32 + 127) / 255) as u8;
+                [premultiply(p[0]), premultiply(p[1]), premultiply(p[2]), p[3]]
+            })
+            .collect();
+
+        self.insert(
+            key,
+            [bitmap.width, bitmap.height],
+            bitmap.offset,
+            true,
+            sideways,
+            &rgba,
+        )
+    }
+
+    // 여백을 포함한 공간을 확보하고 아틀라스 형식의 픽셀을 복사 (sideways면 글리프 원점을 중심으로 돌린 뒤)
+    fn insert(
+        &mut self,
+        key: GlyphRasterConfig,
+        [bitmap_width, bitmap_height]: [usize; 2],
+        offset: [f32; 2],
+        colored: bool,
+        sideways: bool,
+        texels: &[u8],
+    ) -> Result<AtlasEntry, RendererError> {
+        if sideways {
+            let channels = self.channels as usize;
+            let rotated = rotate_clockwise(texels, bitmap_width, bitmap_height, channels);
+            // 원점 기준 (x, y)는 (-y, x)로 가므로 왼쪽 위 모서리는 원래 왼쪽 아래 모서리에서 옴
+            let offset = [-(offset[1] + bitmap_height as f32), offset[0]];
+            return self.insert(
+                key,
+                [bitmap_height, bitmap_width],
+                offset,
+                colored,
+                false,
+                &rotated,
+            );
+        }
+
+        // 그릴 픽셀이 없으면 공간을 쓰지 않고 기록만 해 둠
+        if bitmap_width == 0 || bitmap_height == 0 {
+            let entry = AtlasEntry {
+                layer: 0,
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+                offset,
+                colored,
+            };
+            self.glyphs.insert(
+                key,
+                CachedGlyph {
+                    entry,
+                    slot: None,
+                    last_used: self.pass,
+                },
+            );
+            return Ok(entry);
+        }
+
+        let width = bitmap_width as u32 + GLYPH_PADDING * 2;
+        let height = bitmap_height as u32 + GLYPH_PADDING * 2;
+        if width > MAX_ATLAS_SIZE || height > MAX_ATLAS_SIZE {
+            return Err(RendererError::texture(format!(
+                "글리프가 아틀라스 레이어보다 큽니다 ({width}x{height}, 최대 {MAX_ATLAS_SIZE}x{MAX_ATLAS_SIZE})"
+            )));
+        }
+
+        // 비운 영역, 레이어마다 남은 공간, 아틀라스 키우기, 레이어 더하기, 그리는 중인 프레임이 끝나기를
+        // 기다려 비운 영역 쓰기, 오래된 글리프 내보내기, 오래된 레이어 비우기 순서로 자리를 찾음
+        let slot = loop {
+            let completed = self.completed_frames();
+            if let Some(slot) = self.take_free_slot(width, height, completed) {
+                self.clear_slot(slot);
+                break slot;
+            }
+            if let Some(slot) = self.allocate(width, height, completed) {
+                break slot;
+            }
+            if self.size < MAX_ATLAS_SIZE {
+                self.resize(self.size * 2, self.layers())?;
+            } else if self.layers() < MAX_ATLAS_LAYERS {
+                self.resize(self.size, self.layers() + 1)?;
+            } else if let Some(ready_at) = self.pending_space(width, height, completed) {
+                self.wait_frames(ready_at);
+            } else if !self.evict_fitting(width, height) && !self.reset_oldest_layer() {
+                return Err(RendererError::texture(format!(
+                    "글리프 아틀라스 공간 부족 (최대 {MAX_ATLAS_SIZE}x{MAX_ATLAS_SIZE} {MAX_ATLAS_LAYERS}장, 모두 사용 중)"
+                )));
+            }
+        };
+        let Slot {
+            layer,
+            rect: [x, y, ..],
+        } = slot;
+
+        let (size, channels) = (self.size, self.channels);
+        let pixels = &mut self.layers[layer as usize].pixels;
+        let row_bytes = bitmap_width * channels as usize;
+        for row in 0..bitmap_height {
+            let px = x + GLYPH_PADDING;
+            let py = y + GLYPH_PADDING + row as u32;
+            let idx = ((py * size + px) * channels) as usize;
+            pixels[idx..idx + row_bytes]
+                .copy_from_slice(&texels[row * row_bytes..(row + 1) * row_bytes]);
+        }
+
+        let entry = AtlasEntry {
+            layer,
+            x,
+            y,
+            width,
+            height,
+            offset,
+            colored,
+        };
+        self.layers[layer as usize].mark_dirty(x, y, width, height);
+        self.glyphs.insert(
+            key,
+            CachedGlyph {
+                entry,
+                slot: Some(slot),
+                last_used: self.pass,
+            },
+        );
+        self.used_area += slot.area();
+
+        Ok(entry)
+    }
+
+    // 앞 레이어부터 남은 공간을 찾음 (비운 뒤 그리는 중인 프레임이 남은 레이어는 건너뜀)
+    fn allocate(&mut self, width: u32, height: u32, completed: u64) -> Option<Slot> {
+        let size = self.size;
+        self.layers
+            .iter_mut()
+            .zip(0..)
+            .filter(|(layer, _)| layer.ready_at <= completed)
+            .find_map(|(layer, index)| {
+                let [x, y] = layer.packer.allocate(width, height)?;
+                if std::mem::take(&mut layer.cleared) {
+                    layer.mark_dirty(0, 0, size, size);
+                }
+                Some(Slot {
+                    layer: index,
+                    rect: [x, y, width, height],
+                })
+            })
+    }
+
+    // 다시 쓸 수 있는 비운 영역 중 글리프가 들어가는 가장 작은 영역 (남는 부분은 잘라 비운 영역으로 되돌림)
+    fn take_free_slot(&mut self, width: u32, height: u32, completed: u64) -> Option<Slot> {
+        let index = self
+            .free_slots
+            .iter()
+            .enumerate()
+            .filter(|(_, free)| free.ready_at <= completed && free.slot.fits(width, height))
+            .min_by_key(|(_, free)| free.slot.area())
+            .map(|(index, _)| index)?;
+        let FreeSlot { slot, ready_at } = self.free_slots.swap_remove(index);
+        let (slot, rest) = slot.split(width, height);
+        self.free_slots
+            .extend(rest.into_iter().map(|slot| FreeSlot { slot, ready_at }));
+        Some(slot)
+    }
+
+    // 글리프가 들어가지만 그리는 중인 프레임이 남아 아직 쓰지 못하는 영역이나 레이어 중
+    // 가장 먼저 쓸 수 있게 되는 프레임 번호
+    fn pending_space(&self, width: u32, height: u32, completed: u64) -> Option<u64> {
+        let slots = self
+            .free_slots
+            .iter()
+            .filter(|free| free.slot.fits(width, height))
+            .map(|free| free.ready_at);
+        let layers = self.layers.iter().map(|layer| layer.ready_at);
+        slots
+            .chain(layers)
+            .filter(|&ready_at| ready_at > completed)
+            .min()
+    }
+
+    // 이번 패스에 쓰지 않았고 영역에 글리프가 들어가는 것 중 가장 오래전에 쓴 글리프를 내보냄
+    // (그런 글리프가 없으면 더 내보내도 자리가 생기지 않으므로 false)
+    fn evict_fitting(&mut self, width: u32, height: u32) -> bool {
+        let oldest = self
+            .glyphs
+            .iter()
+            .filter(|(_, glyph)| {
+                glyph.last_used < self.pass
+                    && glyph.slot.is_some_and(|slot| slot.fits(width, height))
+            })
+            .min_by_key(|(_, glyph)| glyph.last_used)
+            .map(|(key, _)| *key);
+        let Some(slot) = oldest.and_then(|key| self.glyphs.remove(&key)?.slot) else {
+            return false;
+        };
+        self.used_area -= slot.area();
+        self.free_slots.push(FreeSlot {
+            slot,
+            ready_at: self.submitted,
+        });
+        self.stats.evictions += 1;
+        true
+    }
+
+    // 이번 패스에 쓴 글리프가 없는 레이어 중 가장 오래전에 쓴 레이어의 글리프를 모두 내보내고 패커를 비움
+    // (작은 영역만 흩어져 있어 큰 글리프가 들어갈 곳이 없을 때, 비울 레이어가 없으면 false)
+    fn reset_oldest_layer(&mut self) -> bool {
+        let mut last_used = vec![None::<u64>; self.layers.len()];
+        for glyph in self.glyphs.values() {
+            if let Some(slot) = glyph.slot {
+                let used = &mut last_used[slot.layer as usize];
+                *used = Some(used.map_or(glyph.last_used, |used| used.max(glyph.last_used)));
+            }
+        }
+        let Some(index) = (0..)
+            .zip(last_used)
+            .filter_map(|(index, used)| Some((index, used?)))
+            .filter(|&(_, used)| used < self.pass)
+            .min_by_key(|&(_, used)| used)
+            .map(|(index, _)| index)
+        else {
+            return false;
+        };
+
+        let mut evicted_area = 0;
+        let before = self.glyphs.len();
+        self.glyphs.retain(|_, glyph| match glyph.slot {
+            Some(slot) if slot.layer == index => {
+                evicted_area += slot.area();
+                false
+            }
+            _ => true,
+        });
+        self.used_area -= evicted_area;
+        self.stats.evictions += (before - self.glyphs.len()) as u64;
+        self.free_slots.retain(|free| free.slot.layer != index);
+
+        let size = self.size;
+        let layer = &mut self.layers[index as usize];
+        layer.pixels.fill(0);
+        layer.packer = ShelfPacker::new(size, size);
+        layer.dirty = None;
+        layer.ready_at = self.submitted;
+        layer.cleared = true;
+        true
+    }
+
+    // 앞에서부터 신호된 프레임 펜스를 정리하고, 모두 끝난 프레임 수 (이 번호 앞의 프레임은 모두 끝남)
+    fn completed_frames(&mut self) -> u64 {
+        while let Some((_, fence)) = self.frames.front() {
+            match fence.is_signaled() {
+                Ok(false) => break,
+                Ok(true) => {}
+                Err(e) => warn!("프레임 펜스 확인 실패: {e}"),
+            }
+            self.frames.pop_front();
+        }
+        self.frames
+            .front()
+            .map_or(self.submitted, |&(frame, _)| frame)
+    }
+
+    // ready_at 앞의 프레임이 끝날 때까지 그 펜스를 기다림
+    // (아틀라스가 가득 차 비운 영역밖에 없을 때만, 큐 전체가 아니라 그 영역을 그렸을 수 있는 프레임만)
+    fn wait_frames(&mut self, ready_at: u64) {
+        while let Some((frame, fence)) = self.frames.front() {
+            if *frame >= ready_at {
+                break;
+            }
+            if let Err(e) = fence.wait(None) {
+                warn!("프레임 펜스 대기 실패: {e}");
+            }
+            self.frames.pop_front();
+        }
+    }
+
+    // 다시 쓰는 영역에 남은 이전 글리프를 지움 (여백은 비어 있어야 함)
+    fn clear_slot(&mut self, slot: Slot) {
+        let [x, y, width, height] = slot.rect;
+        let layer = &mut self.layers[slot.layer as usize];
+        let row_bytes = (width * self.channels) as usize;
+        for row in y..y + height {
+            let start = ((row * self.size + x) * self.channels) as usize;
+            layer.pixels[start..start + row_bytes].fill(0);
+        }
+        layer.mark_dirty(x, y, width, height);
+    }
+
+    /// 레이어마다 변경된 영역을 스테이징 버퍼를 거쳐 GPU 이미지로 복사 (밉맵이 있으면 하위 단계까지 갱신)
+    ///
+    /// 기다리지 않고 제출만 하며, 완료는 `take_pending`의 future로 기다립니다.
+    pub fn flush(
+        &mut self,
+        command_buffer_allocator: &StandardCommandBufferAllocator,
+        queue: Arc<Queue>,
+    ) -> Result<(), RendererError> {
+        // (레이어, [x0, y0, x1, y1])
+        let dirty: Vec<(u32, [u32; 4])> = self
+            .layers
+            .iter_mut()
+            .zip(0..)
+            .filter_map(|(layer, index)| Some((index, layer.dirty.take()?)))
+            .collect();
+        if dirty.is_empty() {
+            return Ok(());
+        }
+
+        let image = self.image.clone();
+        let subresource = |mip_level, layer: u32| ImageSubresourceLayers {
+            mip_level,
+            array_layers: layer..layer + 1,
+            ..image.subresource_layers()
+        };
+
+        // 모든 레이어의 영역을 한 스테이징 버퍼에 이어 담고 영역마다 복사 구역 하나
+        // (전송 전용 큐의 복사는 버퍼 오프셋이 4의 배수여야 하므로 R8 영역 사이를 띄움)
+        let channels = self.channels;
+        let row_bytes_of = |[x0, _, x1, _]: [u32; 4]| ((x1 - x0) * channels) as usize;
+        let upload_size: usize = dirty
+            .iter()
+            .map(|&(_, rect)| {
+                (row_bytes_of(rect) * (rect[3] - rect[1]) as usize).next_multiple_of(4)
+            })
+            .sum();
+        let upload_buffer = self.staging.allocate(upload_size as u64)?;
+        let mut regions = Vec::with_capacity(dirty.len());
+        {
+            let mut contents = upload_buffer.write().map_err(RendererError::buffer)?;
+            let mut buffer_offset = 0;
+            for &(layer, rect) in &dirty {
+                let [x0, y0, x1, y1] = rect;
+                let pixels = &self.layers[layer as usize].pixels;
+                let row_bytes = row_bytes_of(rect);
+                let end = buffer_offset + row_bytes * (y1 - y0) as usize;
+                for (row, y) in contents[buffer_offset..end]
+                    .chunks_exact_mut(row_bytes)
+                    .zip(y0..y1)
+                {
+                    let start = ((y * self.size + x0) * channels) as usize;
+                    row.copy_from_slice(&pixels[start..start + row_bytes]);
+                }
+                regions.push(BufferImageCopy {
+                    buffer_offset: buffer_offset as u64,
+                    image_subresource: subresource(0, layer),
+                    image_offset: [x0, y0, 0],
+                    image_extent: [x1 - x0, y1 - y0, 1],
+                    ..Default::default()
+                });
+                buffer_offset = end.next_multiple_of(4);
+            }
+        }
+
+        // 이전 업로드나 컴퓨트 패스를 아무도 가져가지 않았으면 그 뒤에 이어서 실행
+        let previous = self
+            .pending
+            .take()
+            .unwrap_or_else(|| sync::now(queue.device().clone()).boxed());
+
+        // 전송 큐가 있으면 복사만 거기서 하고, 세마포어로 이어 그래픽 큐에서 밉맵을 채움
+        let copy_queue = self.transfer_queue.clone().unwrap_or_else(|| queue.clone());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            command_buffer_allocator,
+            copy_queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .map_err(RendererError::command)?;
+
+        builder
+            .copy_buffer_to_image(CopyBufferToImageInfo {
+                regions: regions.into(),
+                ..CopyBufferToImageInfo::buffer_image(upload_buffer, image.clone())
+            })
+            .map_err(RendererError::texture)?;
+
+        let mut future = previous;
+        if self.transfer_queue.is_some() {
+            // 복사가 끝나면 세마포어를 신호하고, 이어지는 그래픽 큐 제출이 그것을 기다림
+            let command_buffer = builder.build().map_err(RendererError::command)?;
+            future = future
+                .then_execute(copy_queue, command_buffer)
+                .map_err(RendererError::command)?
+                .then_signal_semaphore_and_flush()
+                .map_err(RendererError::command)?
+                .boxed();
+            builder = AutoCommandBufferBuilder::primary(
+                command_buffer_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .map_err(RendererError::command)?;
+        }
+
+        // 레이어마다 바로 위 단계를 선형 필터로 절반씩 줄여 다음 단계를 채움
+        // (홀수 경계는 바깥으로 넓혀 원본 영역이 정확히 두 배가 되게 함)
+        let mut rects = dirty;
+        for level in 1..self.mip_levels {
+            let regions = rects
+                .iter_mut()
+                .map(|(layer, rect)| {
+                    let dst = [
+                        rect[0] / 2,
+                        rect[1] / 2,
+                        rect[2].div_ceil(2),
+                        rect[3].div_ceil(2),
+                    ];
+                    *rect = dst;
+                    ImageBlit {
+                        src_subresource: subresource(level - 1, *layer),
+                        src_offsets: [[dst[0] * 2, dst[1] * 2, 0], [dst[2] * 2, dst[3] * 2, 1]],
+                        dst_subresource: subresource(level, *layer),
+                        dst_offsets: [[dst[0], dst[1], 0], [dst[2], dst[3], 1]],
+                        ..Default::default()
+                    }
+                })
+                .collect();
+            builder
+                .blit_image(BlitImageInfo {
+                    regions,
+                    filter: Filter::Linear,
+                    ..BlitImageInfo::images(image.clone(), image.clone())
+                })
+                .map_err(RendererError::texture)?;
+        }
+
+        if self.transfer_queue.is_none() || self.mip_levels > 1 {
+            let command_buffer = builder.build().map_err(RendererError::command)?;
+            future = future
+                .then_execute(queue, command_buffer)
+                .map_err(RendererError::command)?
+                .then_signal_semaphore_and_flush()
+                .map_err(RendererError::command)?
+                .boxed();
+        }
+        self.pending = Some(future);
+
+        self.revision += 1;
+        Ok(())
+    }
+
+    // 레이어를 new_size로 키우고 모자란 레이어를 더한 뒤 이미지를 새로 만듦 (모든 레이어를 다시 업로드)
+    fn resize(&mut self, new_size: u32, layers: u32) -> Result<(), RendererError> {
+        if new_size > MAX_ATLAS_SIZE || layers > MAX_ATLAS_LAYERS {
+            return Err(RendererError::texture(format!(
+                "글리프 아틀라스 공간 부족 (최대 {MAX_ATLAS_SIZE}x{MAX_ATLAS_SIZE} {MAX_ATLAS_LAYERS}장)"
+            )));
+        }
+
+        let (image, view, base_view) = create_atlas_image(
+            &self.memory_allocator,
+            new_size,
+            layers,
+            self.channels,
+            self.mip_levels,
+            &self.queue_families,
+        )?;
+
+        let channels = self.channels;
+        for layer in &mut self.layers {
+            if new_size > self.size {
+                layer.grow(self.size, new_size, channels);
+            }
+            layer.dirty = Some([0, 0, new_size, new_size]);
+        }
+        self.layers
+            .resize_with(layers as usize, || AtlasLayer::new(new_size, channels));
+        self.size = new_size;
+        self.image = image;
+        self.view = view;
+        self.base_view = base_view;
+        self.generation += 1;
+
+        Ok(())
+    }
+
+    // R8 아틀라스를 RGBA로 바꿈 (이미 든 커버리지는 흰색 premultiplied로 펼치고 이미지를 새로 만듦)
+    fn expand_to_rgba(&mut self) -> Result<(), RendererError> {
+        let (image, view, base_view) = create_atlas_image(
+            &self.memory_allocator,
+            self.size,
+            self.layers(),
+            4,
+            self.mip_levels,
+            &self.queue_families,
+        )?;
+
+        for layer in &mut self.layers {
+            layer.pixels = layer.pixels.iter().flat_map(|&c| [c, c, c, c]).collect();
+            layer.dirty = Some([0, 0, self.size, self.size]);
+        }
+        self.channels = 4;
+        self.image = image;
+        self.view = view;
+        self.base_view = base_view;
+        self.generation += 1;
+
+        Ok(())
+    }
+}
+
+// 각 픽셀을 왼쪽 amount개 픽셀과의 최댓값으로 (너비가 amount만큼 늘어남)
+fn dilate_horizontal(coverage: &[u8], width: usize, height: usize, amount: usize) -> Vec<u8> {
+    let out_width = width + amount;
+    let mut out = vec![0u8; out_width * height];
+
+    for row in 0..height {
+        let src = &coverage[row * width..(row + 1) * width];
+        let dst = &mut out[row * out_width..(row + 1) * out_width];
+        for (x, value) in dst.iter_mut().enumerate() {
+            let start = x.saturating_sub(amount);
+            let end = (x + 1).min(width);
+            *value = src
+                .get(start..end)
+                .and_then(|window| window.iter().max().copied())
+                .unwrap_or(0);
+        }
+    }
+
+    out
+}
+
+// 픽셀당 channels 바이트인 비트맵을 시계 방향으로 90° 돌림 (너비와 높이가 바뀜)
+fn rotate_clockwise(texels: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+    let mut out = vec![0u8; texels.len()];
+    for y in 0..width {
+        for x in 0..height {
+            let src = ((height - 1 - x) * width + y) * channels;
+            let dst = (y * height + x) * channels;
+            out[dst..dst + channels].copy_from_slice(&texels[src..src + channels]);
+        }
+    }
+    out
+}
+
+// 텍셀당 바이트 수에 맞는 아틀라스 형식
+fn atlas_format(channels: u32) -> Format {
+    if channels == 1 {
+        Format::R8_UNORM
+    } else {
+        Format::R8G8B8A8_UNORM
+    }
+}
+
+// 이미지, 전체 밉맵 뷰, 0단계 뷰 (모든 레이어)
+type AtlasImage = (Arc<Image>, Arc<ImageView>, Arc<ImageView>);
+
+fn create_atlas_image(
+    memory_allocator: &Arc<StandardMemoryAllocator>,
+    size: u32,
+    layers: u32,
+    channels: u32,
+    mip_levels: u32,
+    queue_families: &[u32],
+) -> Result<AtlasImage, RendererError> {
+    let image = Image::new(
+        memory_allocator.clone(),
+        ImageCreateInfo {
+            image_type: ImageType::Dim2d,
+            format: atlas_format(channels),
+            extent: [size, size, 1],
+            array_layers: layers,
+            mip_levels,
+            // 그림자 블러와 외곽선 컴퓨트 셰이더도 샘플링으로 읽고 (R8은 storage 지원이 필수가 아님),
+            // 밉맵은 단계 사이 blit으로 만듦
+            usage: ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST | ImageUsage::SAMPLED,
+            sharing: image_sharing(queue_families),
+            ..Default::default()
+        },
+        AllocationCreateInfo::default(),
+    )
+    .map_err(RendererError::texture)?;
+
+    let view = array_view(image.clone(), mip_levels, ComponentMapping::identity())?;
+    // R8은 모든 성분을 R로 보게 해 컴퓨트 셰이더가 RGBA 아틀라스와 같이 알파에서 커버리지를 읽음
+    let coverage = if channels == 1 {
+        let red = ComponentSwizzle::Red;
+        ComponentMapping {
+            r: red,
+            g: red,
+            b: red,
+            a: red,
+        }
+    } else {
+        ComponentMapping::identity()
+    };
+    let base_view = array_view(image.clone(), 1, coverage)?;
+
+    Ok((image, view, base_view))
+}
+
+// 0단계부터 mip_levels개 단계의 배열 뷰
+// (레이어가 하나여도 셰이더의 sampler2DArray/image2DArray와 맞도록 종류를 지정)
+pub(crate) fn array_view(
+    image: Arc<Image>,
+    mip_levels: u32,
+    component_mapping: ComponentMapping,
+) -> Result<Arc<ImageView>, RendererError> {
+    ImageView::new(
+        image.clone(),
+        ImageViewCreateInfo {
+            view_type: ImageViewType::Dim2dArray,
+            component_mapping,
+            subresource_range: ImageSubresourceRange {
+                mip_levels: 0..mip_levels,
+                ..image.subresource_range()
+            },
+            ..ImageViewCreateInfo::from_image(&image)
+        },
+    )
+    .map_err(RendererError::texture)
+}
+
+// 여러 큐 패밀리가 함께 쓰는 이미지는 concurrent 공유 (소유권 이전 없이 세마포어만으로 동기화)
+pub(crate) fn image_sharing<I>(queue_families: &[u32]) -> Sharing<I>
+where
+    I: FromIterator<u32> + IntoIterator<Item = u32>,
+{
+    if queue_families.len() > 1 {
+        Sharing::Concurrent(queue_families.iter().copied().collect())
+    } else {
+        Sharing::Exclusive
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(rect: [u32; 4], width: u32, height: u32) -> ([u32; 4], Vec<[u32; 4]>) {
+        let (slot, rest) = Slot { layer: 1, rect }.split(width, height);
+        assert!(rest.iter().all(|slot| slot.layer == 1));
+        (slot.rect, rest.iter().map(|slot| slot.rect).collect())
+    }
+
+    #[test]
+    fn split_returns_the_remainder() {
+        assert_eq!(
+            split([0, 0, 100, 100], 20, 20),
+            ([0, 0, 20, 20], vec![[20, 0, 80, 100], [0, 20, 20, 80]])
+        );
+        assert_eq!(
+            split([10, 0, 100, 40], 30, 30),
+            ([10, 0, 30, 40], vec![[40, 0, 70, 40]])
+        );
+    }
+
+    #[test]
+    fn split_keeps_thin_remainders_in_the_slot() {
+        assert_eq!(split([0, 0, 40, 40], 30, 30), ([0, 0, 40, 40], vec![]));
+        assert_eq!(
+            split([0, 0, 40, 100], 30, 30),
+            ([0, 0, 40, 30], vec![[0, 30, 40, 70]])
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use std::str::FromStr;
+
+use serde::Deserialize;
+use tracing::warn;
+use winit::window::Window;
+
+/// 투명한 창 뒤에 그리는 Windows DWM 배경 (`--backdrop`, 설정 파일 `window.backdrop`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Backdrop {
+    /// 바탕화면이 그대로 보이는 완전한 투명
+    #[default]
+    None,
+    /// 창 뒤를 흐리게 (Windows 7/10/11)
+    Blur,
+    /// 반투명 유리 질감 (Windows 10/11)
+    Acrylic,
+    /// 바탕화면 색을 옅게 비추는 배경 (Windows 11)
+    Mica,
+}
+
+impl Backdrop {
+    pub const ALL: [Backdrop; 4] = [
+        Backdrop::None,
+        Backdrop::Blur,
+        Backdrop::Acrylic,
+        Backdrop::Mica,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Backdrop::None => "none",
+            Backdrop::Blur => "blur",
+            Backdrop::Acrylic => "acrylic",
+            Backdrop::Mica => "mica",
+        }
+    }
+}
+
+impl FromStr for Backdrop {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|backdrop| backdrop.name() == value)
+            .ok_or_else(|| format!("알 수 없는 배경: {value} (none, blur, acrylic, mica)"))
+    }
+}
+
+impl TryFrom<String> for Backdrop {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// 창 뒤 배경을 바꿈 (`tint`는 blur, acrylic에 섞는 색, 적용하지 못하면 출력하고 그대로 둠)
+#[cfg(all(feature = "backdrop", windows))]
+pub fn apply(window: &Window, backdrop: Backdrop, tint: Option<[u8; 4]>) {
+    use window_vibrancy::{
+        apply_acrylic, apply_blur, apply_mica, clear_acrylic, clear_blur, clear_mica,
+    };
+
+    // 다른 종류로 바꿀 때 이전 배경이 남지 않도록 모두 지운 뒤 적용 (적용하지 않은 것은 실패해도 무시)
+    let _ = clear_blur(window);
+    let _ = clear_acrylic(window);
+    let _ = clear_mica(window);
+
+    let tint = tint.map(|[r, g, b, a]| (r, g, b, a));
+    let applied = match backdrop {
+        Backdrop::None => Ok(()),
+        Backdrop::Blur => apply_blur(window, tint),
+        Backdrop::Acrylic => apply_acrylic(window, tint),
+        Backdrop::Mica => apply_mica(window, None),
+    };
+    if let Err(e) = applied {
+        warn!("창 배경({})을 적용할 수 없습니다: {e}", backdrop.name());
+    }
+}
+
+#[cfg(not(all(feature = "backdrop", windows)))]
+pub fn apply(_window: &Window, backdrop: Backdrop, _tint: Option<[u8; 4]>) {
+    if backdrop != Backdrop::None {
+        warn!(
+            "창 배경({}): Windows에서 backdrop 기능으로 빌드해야 합니다",
+            backdrop.name()
+        );
+    }
+}
@@ -0,0 +1,501 @@
+use std::sync::Arc;
+use vulkano::{
+    buffer::BufferContents,
+    command_buffer::{
+        AutoCommandBufferBuilder, RenderPassBeginInfo, SubpassBeginInfo, SubpassContents,
+        SubpassEndInfo,
+    },
+    descriptor_set::{
+        allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
+    },
+    device::Device,
+    format::Format,
+    image::{
+        sampler::{Filter, Sampler, SamplerAddressMode, SamplerCreateInfo},
+        view::ImageView,
+        Image, ImageCreateInfo, ImageType, ImageUsage, SampleCount,
+    },
+    memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    pipeline::{
+        compute::ComputePipelineCreateInfo,
+        graphics::{
+            color_blend::{AttachmentBlend, BlendFactor, BlendOp},
+            vertex_input::VertexInputState,
+            viewport::Viewport,
+        },
+        layout::PipelineDescriptorSetLayoutCreateInfo,
+        ComputePipeline, GraphicsPipeline, Pipeline, PipelineBindPoint, PipelineLayout,
+        PipelineShaderStageCreateInfo,
+    },
+    render_pass::{Framebuffer, FramebufferCreateInfo, RenderPass, Subpass},
+};
+
+use crate::{
+    effect::TextParams,
+    error::RendererError,
+    graph::{ImageDesc, RenderGraph},
+    renderer::{build_pipeline, specialize_fragment, TextRenderer},
+    shaders,
+    target::RenderTarget,
+};
+
+const WORKGROUP_SIZE: u32 = 16;
+
+// 블룸 이미지 단계 수 (대상의 1/2 ~ 1/64 크기, 발광 반지름이 클수록 작은 단계까지 씀)
+const MAX_LEVELS: usize = 6;
+
+// 블룸 이미지는 여러 단계를 더해도 잘리지 않도록 부동소수점
+const BLOOM_FORMAT: Format = Format::R16G16B16A16_SFLOAT;
+
+// bloom.comp의 push constant 블록과 같은 레이아웃
+#[derive(BufferContents, Clone, Copy)]
+#[repr(C)]
+struct BloomParams {
+    upsample: i32,
+}
+
+// glow.frag의 push constant 블록과 같은 레이아웃
+#[derive(BufferContents, Clone, Copy)]
+#[repr(C)]
+struct GlowPushConstants {
+    color: [f32; 4],
+    target_size: [f32; 2],
+    strength: f32,
+}
+
+// 대상 크기에 맞춘 0단계 이미지와 디스크립터 셋 (1단계부터는 렌더 그래프의 임시 이미지)
+struct BloomTargets {
+    extent: [u32; 2],
+    // 0단계 (발광 마스크를 그리고 마지막에 블룸 결과가 남음)
+    mask: Arc<ImageView>,
+    framebuffer: Arc<Framebuffer>,
+    sizes: Vec<[u32; 2]>,
+    composite: Arc<PersistentDescriptorSet>,
+    chain: Option<BloomChain>,
+}
+
+// 단계 이미지로 만든 디스크립터 셋 (그래프가 같은 이미지를 돌려주는 동안 다시 씀)
+struct BloomChain {
+    views: Vec<Arc<ImageView>>,
+    // downsample[i]: i단계 → i + 1단계, upsample[i]: i + 1단계 → i단계에 더함
+    downsample: Vec<Arc<PersistentDescriptorSet>>,
+    upsample: Vec<Arc<PersistentDescriptorSet>>,
+}
+
+/// 발광 효과의 블룸 후처리
+///
+/// 발광을 켠 글리프의 커버리지를 대상의 절반 크기 이미지에 그린 뒤, 컴퓨트 셰이더로
+/// 13탭 필터로 절반씩 줄이고 텐트 필터로 키우며 더해 넓게 번진 빛을 만듭니다.
+/// 결과는 `TextLayer`가 레이어를 합성하기 전에 발광 색으로 대상에 더합니다.
+pub(crate) struct Bloom {
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    // 발광 마스크를 그리는 render pass (0단계 이미지 하나)
+    mask_render_pass: Arc<RenderPass>,
+    compute_pipeline: Arc<ComputePipeline>,
+    // 대상 render pass에 블룸을 더하는 파이프라인
+    composite_pipeline: Arc<GraphicsPipeline>,
+    sampler: Arc<Sampler>,
+    targets: Option<BloomTargets>,
+    // 마지막으로 만든 블룸을 합성할 설정 (발광을 쓰지 않으면 None)
+    glow: Option<GlowSettings>,
+}
+
+#[derive(Clone, Copy)]
+struct GlowSettings {
+    color: [f32; 4],
+    intensity: f32,
+    levels: usize,
+}
+
+impl Bloom {
+    /// `target`은 블룸을 합성할 대상
+    pub fn new(
+        device: Arc<Device>,
+        memory_allocator: Arc<StandardMemoryAllocator>,
+        target: RenderTarget,
+    ) -> Result<Self, RendererError> {
+        let mask_render_pass = vulkano::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                mask: {
+                    format: BLOOM_FORMAT,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: Store,
+                },
+            },
+            pass: {
+                color: [mask],
+                depth_stencil: {},
+            },
+        )
+        .map_err(RendererError::pipeline)?;
+
+        let cs = shaders::bloom_cs::load(device.clone())
+            .map_err(|e| RendererError::shader("bloom compute", e))?
+            .entry_point("main")
+            .ok_or_else(|| RendererError::shader("bloom compute", "main 진입점 없음"))?;
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
+                .into_pipeline_layout_create_info(device.clone())
+                .map_err(RendererError::pipeline)?,
+        )
+        .map_err(RendererError::pipeline)?;
+        let compute_pipeline = ComputePipeline::new(
+            device.clone(),
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .map_err(RendererError::pipeline)?;
+
+        let vs = shaders::composite_vs::load(device.clone())
+            .map_err(|e| RendererError::shader("composite vertex", e))?
+            .entry_point("main")
+            .ok_or_else(|| RendererError::shader("composite vertex", "main 진입점 없음"))?;
+        let fs = shaders::glow_fs::load(device.clone())
+            .map_err(|e| RendererError::shader("glow fragment", e))?;
+        let fs = specialize_fragment(&fs, &target)?;
+        let composite_pipeline = build_pipeline(
+            device.clone(),
+            target,
+            [vs, fs],
+            VertexInputState::new(),
+            additive_blend(),
+        )?;
+
+        let sampler = Sampler::new(
+            device,
+            SamplerCreateInfo {
+                mag_filter: Filter::Linear,
+                min_filter: Filter::Linear,
+                address_mode: [SamplerAddressMode::ClampToEdge; 3],
+                ..Default::default()
+            },
+        )
+        .map_err(RendererError::texture)?;
+
+        Ok(Self {
+            memory_allocator,
+            mask_render_pass,
+            compute_pipeline,
+            composite_pipeline,
+            sampler,
+            targets: None,
+            glow: None,
+        })
+    }
+
+    /// 장면의 발광 마스크와 블룸 체인 패스를 `graph`에 더함 (발광을 쓰지 않으면 합성하지 않도록 표시만 함)
+    ///
+    /// 같은 `params`로 `draw_scene`을 기록하는 패스 다음에 더합니다.
+    pub fn add_passes<'a, L>(
+        &'a mut self,
+        graph: &mut RenderGraph<'a, L, TextRenderer>,
+        text_renderer: &TextRenderer,
+        params: &TextParams,
+        extent: [u32; 2],
+        descriptor_set_allocator: &'a StandardDescriptorSetAllocator,
+    ) -> Result<(), RendererError> {
+        if !text_renderer.uses_glow(params) {
+            self.glow = None;
+            return Ok(());
+        }
+
+        if self.targets.as_ref().map(|targets| targets.extent) != Some(extent) {
+            self.targets = Some(self.create_targets(extent, descriptor_set_allocator)?);
+        }
+        let Some(targets) = &self.targets else {
+            return Ok(());
+        };
+
+        // 0단계 텍셀이 물리 2픽셀이고 단계마다 두 배씩 넓어지므로 반지름에 맞는 단계까지만 사용
+        let radius = params.glow_radius * text_renderer.scale_factor();
+        let levels = (radius.max(2.0).log2().ceil() as usize).clamp(1, targets.sizes.len());
+
+        let mask = graph.import(targets.mask.clone());
+        graph.output(mask);
+        let chain: Vec<_> = std::iter::once(mask)
+            .chain(targets.sizes[1..levels].iter().map(|&extent| {
+                graph.transient(ImageDesc {
+                    format: BLOOM_FORMAT,
+                    extent,
+                    usage: ImageUsage::STORAGE | ImageUsage::SAMPLED,
+                    samples: SampleCount::Sample1,
+                })
+            }))
+            .collect();
+
+        let framebuffer = targets.framebuffer.clone();
+        let mask_subpass = Subpass::from(self.mask_render_pass.clone(), 0)
+            .ok_or_else(|| RendererError::pipeline("render pass에 서브패스가 없습니다"))?;
+        let params = *params;
+        graph.add_pass(
+            "발광 마스크",
+            &[],
+            &[mask],
+            move |builder, _, text_renderer| {
+                let [width, height] = framebuffer.extent();
+                builder
+                    .begin_render_pass(
+                        RenderPassBeginInfo {
+                            clear_values: vec![Some([0.0; 4].into())],
+                            ..RenderPassBeginInfo::framebuffer(framebuffer.clone())
+                        },
+                        SubpassBeginInfo {
+                            contents: SubpassContents::Inline,
+                            ..Default::default()
+                        },
+                    )
+                    .map_err(RendererError::command)?
+                    .set_viewport(
+                        0,
+                        [Viewport {
+                            offset: [0.0, 0.0],
+                            extent: [width as f32, height as f32],
+                            depth_range: 0.0..=1.0,
+                        }]
+                        .into_iter()
+                        .collect(),
+                    )
+                    .map_err(RendererError::command)?;
+                text_renderer.record_glow_mask(builder, &mask_subpass, &params)?;
+                builder
+                    .end_render_pass(SubpassEndInfo::default())
+                    .map_err(RendererError::command)?;
+                Ok(())
+            },
+        );
+
+        self.glow = Some(GlowSettings {
+            color: params.glow_color.map(|c| c as f32 / 255.0),
+            intensity: params.glow_intensity,
+            levels,
+        });
+
+        if levels > 1 {
+            // 단계 이미지는 이 패스 안에서 줄이며 쓰고 키우며 읽음
+            let images = chain.clone();
+            graph.add_pass("블룸", &[mask], &images, move |builder, resources, _| {
+                let views = chain
+                    .iter()
+                    .map(|&image| resources.view(image).cloned())
+                    .collect::<Result<Vec<_>, _>>()?;
+                self.record_chain(builder, views, descriptor_set_allocator)
+            });
+        }
+        Ok(())
+    }
+
+    // 줄이면서 단계마다 저장하고, 작은 단계부터 키우며 바로 위 단계에 더함
+    fn record_chain<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        views: Vec<Arc<ImageView>>,
+        descriptor_set_allocator: &StandardDescriptorSetAllocator,
+    ) -> Result<(), RendererError> {
+        let Some(targets) = &mut self.targets else {
+            return Ok(());
+        };
+        let reusable = targets.chain.as_ref().is_some_and(|chain| {
+            chain.views.len() == views.len()
+                && chain
+                    .views
+                    .iter()
+                    .zip(&views)
+                    .all(|(a, b)| Arc::ptr_eq(a, b))
+        });
+        if !reusable {
+            targets.chain = Some(create_chain(
+                &self.compute_pipeline,
+                &self.sampler,
+                views,
+                descriptor_set_allocator,
+            )?);
+        }
+        let Some(chain) = &targets.chain else {
+            return Ok(());
+        };
+
+        builder
+            .bind_pipeline_compute(self.compute_pipeline.clone())
+            .map_err(RendererError::command)?;
+        let levels = chain.views.len();
+        let passes = (0..levels - 1)
+            .map(|level| (&chain.downsample[level], level + 1, 0))
+            .chain(
+                (0..levels - 1)
+                    .rev()
+                    .map(|level| (&chain.upsample[level], level, 1)),
+            );
+        for (set, target_level, upsample) in passes {
+            let [width, height] = targets.sizes[target_level];
+            builder
+                .bind_descriptor_sets(
+                    PipelineBindPoint::Compute,
+                    self.compute_pipeline.layout().clone(),
+                    0,
+                    set.clone(),
+                )
+                .map_err(RendererError::command)?
+                .push_constants(
+                    self.compute_pipeline.layout().clone(),
+                    0,
+                    BloomParams { upsample },
+                )
+                .map_err(RendererError::command)?
+                .dispatch([
+                    width.div_ceil(WORKGROUP_SIZE),
+                    height.div_ceil(WORKGROUP_SIZE),
+                    1,
+                ])
+                .map_err(RendererError::command)?;
+        }
+        Ok(())
+    }
+
+    /// 마지막 `add_passes`의 블룸을 발광 색으로 대상에 더함 (대상 render pass 안에서, 레이어보다 먼저)
+    pub fn composite<L>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        opacity: f32,
+    ) -> Result<(), RendererError> {
+        let (Some(targets), Some(glow)) = (&self.targets, self.glow) else {
+            return Ok(());
+        };
+
+        // 단계마다 원본 세기만큼 더해지므로 단계 수로 나눔
+        let push_constants = GlowPushConstants {
+            color: glow.color,
+            target_size: targets.extent.map(|size| size as f32),
+            strength: glow.intensity * opacity / glow.levels as f32,
+        };
+
+        builder
+            .bind_pipeline_graphics(self.composite_pipeline.clone())
+            .map_err(RendererError::command)?
+            .bind_descriptor_sets(
+                PipelineBindPoint::Graphics,
+                self.composite_pipeline.layout().clone(),
+                0,
+                targets.composite.clone(),
+            )
+            .map_err(RendererError::command)?
+            .push_constants(self.composite_pipeline.layout().clone(), 0, push_constants)
+            .map_err(RendererError::command)?
+            .draw(3, 1, 0, 0)
+            .map_err(RendererError::command)?;
+
+        Ok(())
+    }
+
+    // 대상의 절반 크기부터 단계마다 절반씩 (최소 1픽셀)
+    fn create_targets(
+        &self,
+        extent: [u32; 2],
+        descriptor_set_allocator: &StandardDescriptorSetAllocator,
+    ) -> Result<BloomTargets, RendererError> {
+        let mut sizes = vec![extent.map(|size| size.div_ceil(2).max(1))];
+        while sizes.len() < MAX_LEVELS {
+            let [width, height] = sizes[sizes.len() - 1];
+            if width == 1 && height == 1 {
+                break;
+            }
+            sizes.push([width.div_ceil(2), height.div_ceil(2)]);
+        }
+
+        // 0단계는 발광 마스크를 그리는 색 첨부이기도 함
+        let image = Image::new(
+            self.memory_allocator.clone(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: BLOOM_FORMAT,
+                extent: [sizes[0][0], sizes[0][1], 1],
+                usage: ImageUsage::STORAGE | ImageUsage::SAMPLED | ImageUsage::COLOR_ATTACHMENT,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .map_err(RendererError::texture)?;
+        let mask = ImageView::new_default(image).map_err(RendererError::texture)?;
+
+        let framebuffer = Framebuffer::new(
+            self.mask_render_pass.clone(),
+            FramebufferCreateInfo {
+                attachments: vec![mask.clone()],
+                ..Default::default()
+            },
+        )
+        .map_err(RendererError::texture)?;
+
+        let composite = PersistentDescriptorSet::new(
+            descriptor_set_allocator,
+            self.composite_pipeline.layout().set_layouts()[0].clone(),
+            [WriteDescriptorSet::image_view_sampler(
+                0,
+                mask.clone(),
+                self.sampler.clone(),
+            )],
+            [],
+        )
+        .map_err(RendererError::texture)?;
+
+        Ok(BloomTargets {
+            extent,
+            mask,
+            framebuffer,
+            sizes,
+            composite,
+            chain: None,
+        })
+    }
+}
+
+// 이웃한 단계 이미지 쌍마다 줄이기와 키우기 디스크립터 셋
+fn create_chain(
+    compute_pipeline: &ComputePipeline,
+    sampler: &Arc<Sampler>,
+    views: Vec<Arc<ImageView>>,
+    descriptor_set_allocator: &StandardDescriptorSetAllocator,
+) -> Result<BloomChain, RendererError> {
+    let set_layout = compute_pipeline.layout().set_layouts()[0].clone();
+    let descriptor_set = |source: &Arc<ImageView>, target: &Arc<ImageView>| {
+        PersistentDescriptorSet::new(
+            descriptor_set_allocator,
+            set_layout.clone(),
+            [
+                WriteDescriptorSet::image_view_sampler(0, source.clone(), sampler.clone()),
+                WriteDescriptorSet::image_view(1, target.clone()),
+            ],
+            [],
+        )
+        .map_err(RendererError::texture)
+    };
+    let downsample = views
+        .windows(2)
+        .map(|pair| descriptor_set(&pair[0], &pair[1]))
+        .collect::<Result<Vec<_>, _>>()?;
+    let upsample = views
+        .windows(2)
+        .map(|pair| descriptor_set(&pair[1], &pair[0]))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(BloomChain {
+        views,
+        downsample,
+        upsample,
+    })
+}
+
+// 빛은 아래 색에 더함 (premultiplied)
+fn additive_blend() -> AttachmentBlend {
+    AttachmentBlend {
+        src_color_blend_factor: BlendFactor::One,
+        dst_color_blend_factor: BlendFactor::One,
+        color_blend_op: BlendOp::Add,
+        src_alpha_blend_factor: BlendFactor::One,
+        dst_alpha_blend_factor: BlendFactor::One,
+        alpha_blend_op: BlendOp::Add,
+    }
+}
@@ -0,0 +1,216 @@
+use std::sync::Arc;
+use vulkano::{
+    device::{physical::PhysicalDevice, Device, Queue},
+    image::SampleCount,
+};
+
+use crate::{
+    config::{Antialiasing, RendererConfig},
+    effect::TextEffects,
+    error::RendererError,
+    font::FontData,
+    renderer::{TextRenderer, DEFAULT_COLOR, DEFAULT_FONT, DEFAULT_FONT_SIZE},
+    target::RenderTarget,
+};
+
+// frames_in_flight 상한 (정점/효과 버퍼 링과 펜스를 이만큼 만듦)
+const MAX_FRAMES_IN_FLIGHT: usize = 8;
+
+/// 옵션을 모아 `TextRenderer`를 만드는 빌더
+///
+/// `build`할 때 옵션을 장치 기능과 그리기 대상에 맞춰 확인하고, 지원하지 않으면 조용히 대체하지 않고
+/// `RendererError::InvalidConfig`를 돌려줍니다.
+///
+/// ```ignore
+/// let builder = TextRenderer::builder()
+///     .font_bytes(std::fs::read("font.ttf")?)
+///     .font_size(32.0)
+///     .color([255, 220, 120, 255])
+///     .effect(TextEffect::Glow)
+///     .msaa(4)
+///     .frames_in_flight(2);
+/// // render pass는 빌더가 확인한 샘플 수로 만듦
+/// let samples = builder.sample_count(device.physical_device())?;
+/// let render_pass = create_render_pass(&device, format, samples)?;
+/// let text_renderer = builder.build(device, queue, render_pass)?;
+/// let mut frames = FramesInFlight::new(device.clone(), &text_renderer.config());
+/// let params = text_renderer.params();
+/// ```
+#[derive(Clone)]
+pub struct TextRendererBuilder {
+    font: Option<FontData>,
+    font_size: f32,
+    color: [u8; 4],
+    effects: TextEffects,
+    msaa: Option<u32>,
+    config: RendererConfig,
+}
+
+impl Default for TextRendererBuilder {
+    fn default() -> Self {
+        Self {
+            font: None,
+            font_size: DEFAULT_FONT_SIZE,
+            color: DEFAULT_COLOR,
+            effects: TextEffects::NONE,
+            msaa: None,
+            config: RendererConfig::default(),
+        }
+    }
+}
+
+impl TextRenderer {
+    /// 내장 폰트(Noto Sans KR, 48px)와 기본 설정에서 시작하는 빌더
+    pub fn builder() -> TextRendererBuilder {
+        TextRendererBuilder::default()
+    }
+}
+
+impl TextRendererBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 폰트 파일 내용 (TTF/OTF, 없으면 내장 폰트)
+    pub fn font_bytes(self, bytes: impl Into<Vec<u8>>) -> Self {
+        self.font(FontData::new(bytes.into()))
+    }
+
+    pub fn font(mut self, font: FontData) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// 글자 크기 (논리 픽셀)
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// 스타일에 색이 없는 글자의 색 (RGBA8)
+    pub fn color(mut self, color: [u8; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// `TextRenderer::params`가 켜는 효과 (`TextEffect` 하나나 `TextEffects` 조합)
+    pub fn effect(mut self, effects: impl Into<TextEffects>) -> Self {
+        self.effects = effects.into();
+        self
+    }
+
+    /// 멀티샘플 안티앨리어싱 샘플 수 (1, 2, 4, 8, 16 중 장치가 지원하는 값이고 그리기 대상과 같아야 함)
+    pub fn msaa(mut self, samples: u32) -> Self {
+        self.msaa = Some(samples);
+        self
+    }
+
+    /// 동시에 GPU에서 처리 중일 수 있는 프레임 수 (1..=8)
+    pub fn frames_in_flight(mut self, frames_in_flight: usize) -> Self {
+        self.config.frames_in_flight = frames_in_flight;
+        self
+    }
+
+    /// 글리프 안티앨리어싱 방식 (서브픽셀은 장치에서 `dual_src_blend`를 켜야 함)
+    pub fn antialiasing(mut self, antialiasing: Antialiasing) -> Self {
+        self.config.antialiasing = antialiasing;
+        self
+    }
+
+    /// 글리프 아틀라스 밉맵
+    pub fn mipmaps(mut self, mipmaps: bool) -> Self {
+        self.config.mipmaps = mipmaps;
+        self
+    }
+
+    /// 나머지 설정을 한 번에 (이미 정한 `frames_in_flight`, `antialiasing`, `mipmaps`도 덮어씀)
+    pub fn config(mut self, config: RendererConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// `msaa`로 고른 샘플 수 (주지 않았으면 1, 장치가 지원하지 않으면 오류)
+    ///
+    /// 텍스트를 그릴 render pass나 dynamic rendering 대상을 이 샘플 수로 만드세요.
+    pub fn sample_count(&self, device: &PhysicalDevice) -> Result<SampleCount, RendererError> {
+        let Some(requested) = self.msaa else {
+            return Ok(SampleCount::Sample1);
+        };
+        let samples = match requested {
+            1 => SampleCount::Sample1,
+            2 => SampleCount::Sample2,
+            4 => SampleCount::Sample4,
+            8 => SampleCount::Sample8,
+            16 => SampleCount::Sample16,
+            _ => {
+                return Err(RendererError::InvalidConfig(format!(
+                    "MSAA 샘플 수는 1, 2, 4, 8, 16 중 하나여야 합니다 ({requested})"
+                )))
+            }
+        };
+        let supported = device.properties().framebuffer_color_sample_counts;
+        if !supported.contains_enum(samples) {
+            return Err(RendererError::InvalidConfig(format!(
+                "이 장치는 MSAA {requested}x를 지원하지 않습니다"
+            )));
+        }
+        Ok(samples)
+    }
+
+    /// 옵션을 확인하고 `target`에 그리는 렌더러를 만듭니다.
+    pub fn build(
+        self,
+        device: Arc<Device>,
+        queue: Arc<Queue>,
+        target: impl Into<RenderTarget>,
+    ) -> Result<TextRenderer, RendererError> {
+        let target = target.into();
+        self.validate(&device, &target)?;
+
+        let font = self
+            .font
+            .unwrap_or_else(|| FontData::new(DEFAULT_FONT.to_vec()));
+        let mut renderer =
+            TextRenderer::with_font_data(device, queue, target, font, self.font_size, self.config)?;
+        renderer.set_defaults(self.color, self.effects);
+        Ok(renderer)
+    }
+
+    fn validate(&self, device: &Device, target: &RenderTarget) -> Result<(), RendererError> {
+        if !(self.font_size.is_finite() && self.font_size > 0.0) {
+            return Err(RendererError::InvalidConfig(format!(
+                "글자 크기는 0보다 커야 합니다 ({})",
+                self.font_size
+            )));
+        }
+        if !(1..=MAX_FRAMES_IN_FLIGHT).contains(&self.config.frames_in_flight) {
+            return Err(RendererError::InvalidConfig(format!(
+                "frames_in_flight는 1에서 {MAX_FRAMES_IN_FLIGHT} 사이여야 합니다 ({})",
+                self.config.frames_in_flight
+            )));
+        }
+        if self.config.antialiasing == Antialiasing::Subpixel
+            && !device.enabled_features().dual_src_blend
+        {
+            return Err(RendererError::InvalidConfig(
+                "서브픽셀 안티앨리어싱은 장치에서 dual_src_blend 기능을 켜야 합니다".to_string(),
+            ));
+        }
+        if target.is_dynamic() && !device.enabled_features().dynamic_rendering {
+            return Err(RendererError::InvalidConfig(
+                "dynamic rendering 대상은 장치에서 dynamic_rendering 기능을 켜야 합니다"
+                    .to_string(),
+            ));
+        }
+
+        let samples = self.sample_count(device.physical_device())?;
+        let (_, target_samples) = target.color_attachment()?;
+        if self.msaa.is_some() && samples != target_samples {
+            return Err(RendererError::InvalidConfig(format!(
+                "MSAA {}x가 그리기 대상의 샘플 수({}x)와 다릅니다",
+                samples as u32, target_samples as u32
+            )));
+        }
+        Ok(())
+    }
+}
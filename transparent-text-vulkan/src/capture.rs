@@ -0,0 +1,167 @@
+use std::{fs::File, io::BufWriter, path::Path, sync::Arc};
+use vulkano::{
+    buffer::{Buffer, BufferCreateInfo, BufferUsage, Subbuffer},
+    command_buffer::{AutoCommandBufferBuilder, CopyImageToBufferInfo},
+    format::{Format, NumericFormat},
+    image::Image,
+    memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+};
+
+use crate::error::{BoxError, RendererError};
+
+/// 그린 이미지를 호스트에서 읽을 수 있는 버퍼로 복사해 PNG로 저장
+///
+/// 이미지는 `TRANSFER_SRC` 용도로 만들어야 합니다 (스왑체인이면 `image_usage`에 추가).
+/// 8비트 RGBA/BGRA 포맷만 지원하고, premultiplied alpha를 straight alpha로 되돌려 저장합니다.
+///
+/// ```ignore
+/// let capture = ImageCapture::new(memory_allocator, swapchain_image)?;
+/// // render pass를 닫은 다음
+/// capture.record(&mut builder)?;
+/// // 제출한 command buffer가 끝날 때까지 기다린 뒤
+/// capture.read()?.save_png("text.png")?;
+/// ```
+pub struct ImageCapture {
+    image: Arc<Image>,
+    buffer: Subbuffer<[u8]>,
+}
+
+impl ImageCapture {
+    pub fn new(
+        memory_allocator: Arc<StandardMemoryAllocator>,
+        image: Arc<Image>,
+    ) -> Result<Self, RendererError> {
+        if channel_order(image.format()).is_none() {
+            return Err(RendererError::texture(format!(
+                "캡처할 수 없는 이미지 포맷: {:?}",
+                image.format()
+            )));
+        }
+
+        let [width, height, _] = image.extent();
+        let buffer = Buffer::new_slice::<u8>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            width as u64 * height as u64 * 4,
+        )
+        .map_err(RendererError::buffer)?;
+
+        Ok(Self { image, buffer })
+    }
+
+    /// 이미지를 버퍼로 복사하는 명령 기록 (render pass 밖에서)
+    pub fn record<L>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+    ) -> Result<(), RendererError> {
+        builder
+            .copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(
+                self.image.clone(),
+                self.buffer.clone(),
+            ))
+            .map_err(RendererError::command)?;
+        Ok(())
+    }
+
+    /// 복사된 픽셀 (기록한 command buffer가 끝난 뒤에 호출)
+    pub fn read(&self) -> Result<Screenshot, RendererError> {
+        let [width, height, _] = self.image.extent();
+        let format = self.image.format();
+        let (red, blue) = channel_order(format).unwrap_or((0, 2));
+        let srgb = format.numeric_format_color() == Some(NumericFormat::SRGB);
+
+        let data = self.buffer.read().map_err(RendererError::buffer)?;
+        let pixels = data
+            .chunks_exact(4)
+            .flat_map(|texel| {
+                let premultiplied = [texel[red], texel[1], texel[blue], texel[3]];
+                unpremultiply(premultiplied, srgb)
+            })
+            .collect();
+
+        Ok(Screenshot {
+            width,
+            height,
+            pixels,
+        })
+    }
+}
+
+/// 캡처한 이미지 (straight alpha sRGB RGBA8, 위에서 아래로)
+#[derive(Debug, Clone)]
+pub struct Screenshot {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Screenshot {
+    /// 알파 채널을 포함한 RGBA PNG로 저장
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), RendererError> {
+        let path = path.as_ref();
+        let error = |source: BoxError| RendererError::Capture {
+            path: path.display().to_string(),
+            source,
+        };
+
+        let file = File::create(path).map_err(|e| error(e.into()))?;
+        let mut encoder = png::Encoder::new(BufWriter::new(file), self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels))
+            .map_err(|e| error(e.into()))
+    }
+}
+
+// 텍셀 안에서 빨강, 파랑 채널의 위치 (지원하지 않는 포맷이면 None)
+fn channel_order(format: Format) -> Option<(usize, usize)> {
+    match format {
+        Format::R8G8B8A8_UNORM | Format::R8G8B8A8_SRGB => Some((0, 2)),
+        Format::B8G8R8A8_UNORM | Format::B8G8R8A8_SRGB => Some((2, 0)),
+        _ => None,
+    }
+}
+
+// sRGB 포맷은 선형 공간에서 알파를 곱했으므로 선형으로 풀어서 나눈 뒤 다시 인코딩
+fn unpremultiply([r, g, b, a]: [u8; 4], srgb: bool) -> [u8; 4] {
+    if a == 0 {
+        return [0; 4];
+    }
+    let alpha = a as f32 / 255.0;
+    let channel = |c: u8| {
+        let c = c as f32 / 255.0;
+        let c = if srgb {
+            linear_to_srgb((srgb_to_linear(c) / alpha).min(1.0))
+        } else {
+            (c / alpha).min(1.0)
+        };
+        (c * 255.0).round() as u8
+    };
+    [channel(r), channel(g), channel(b), a]
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
@@ -0,0 +1,318 @@
+use std::sync::Arc;
+use fontdue::Font;
+use ttf_parser::{Face, GlyphId, RasterGlyphImage, RasterImageFormat, Tag};
+
+const COLR: Tag = Tag::from_bytes(b"COLR");
+const CPAL: Tag = Tag::from_bytes(b"CPAL");
+
+// COLR 레이어의 팔레트 번호가 이 값이면 텍스트 색
+const FOREGROUND_PALETTE_INDEX: u16 = 0xFFFF;
+
+/// 컬러 글리프 비트맵 (RGBA, 스트레이트 알파)
+pub(crate) struct ColorBitmap {
+    pub width: usize,
+    pub height: usize,
+    // 펜 위치/베이스라인 기준 비트맵 왼쪽 위 좌표 (Y는 아래 방향)
+    pub offset: [f32; 2],
+    pub pixels: Vec<u8>,
+}
+
+/// 컬러 글리프 테이블(CBDT/sbix 비트맵, COLR 레이어)을 가진 폰트 데이터
+///
+/// 레이아웃과 메트릭은 같은 데이터로 만든 fontdue `Font`를 쓰고,
+/// 래스터라이즈만 여기서 RGBA로 합니다.
+pub(crate) struct ColorFont {
+    data: Arc<[u8]>,
+    index: u32,
+}
+
+impl ColorFont {
+    /// 컬러 글리프 테이블이 없으면 `None`
+    pub fn new(data: Arc<[u8]>, index: u32) -> Option<Self> {
+        let face = Face::parse(&data, index).ok()?;
+        let tables = face.tables();
+        let has_color =
+            tables.cbdt.is_some() || tables.sbix.is_some() || face.raw_face().table(COLR).is_some();
+
+        has_color.then_some(Self { data, index })
+    }
+
+    /// 컬러 글리프면 RGBA로 래스터라이즈, 일반 외곽선 글리프면 `None`
+    pub fn rasterize(&self, font: &Font, glyph_index: u16, px: f32) -> Option<ColorBitmap> {
+        let face = Face::parse(&self.data, self.index).ok()?;
+        let glyph_id = GlyphId(glyph_index);
+
+        if let Some(image) = face.glyph_raster_image(glyph_id, px.round() as u16) {
+            return decode_raster_image(&image, px);
+        }
+
+        rasterize_layers(&face, font, glyph_id, px)
+    }
+}
+
+// CBDT/sbix의 PNG 비트맵을 디코딩해 요청한 크기로 축소/확대
+fn decode_raster_image(image: &RasterGlyphImage, px: f32) -> Option<ColorBitmap> {
+    if image.format != RasterImageFormat::PNG {
+        return None;
+    }
+
+    let mut decoder = png::Decoder::new(image.data);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().ok()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).ok()?;
+
+    let src_width = info.width as usize;
+    let src_height = info.height as usize;
+    let rgba: Vec<u8> = match info.color_type {
+        png::ColorType::Rgba => buffer[..src_width * src_height * 4].to_vec(),
+        png::ColorType::Rgb => buffer[..src_width * src_height * 3]
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer[..src_width * src_height * 2]
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => buffer[..src_width * src_height]
+            .iter()
+            .flat_map(|&g| [g, g, g, 255])
+            .collect(),
+        png::ColorType::Indexed => return None,
+    };
+
+    let scale = px / image.pixels_per_em.max(1) as f32;
+    let width = ((src_width as f32 * scale).round() as usize).max(1);
+    let height = ((src_height as f32 * scale).round() as usize).max(1);
+
+    Some(ColorBitmap {
+        width,
+        height,
+        // 이미지의 y는 베이스라인에서 비트맵 아래쪽까지 (위쪽이 양수)
+        offset: [
+            image.x as f32 * scale,
+            -(image.y as f32 + image.height as f32) * scale,
+        ],
+        pixels: resize_rgba(&rgba, src_width, src_height, width, height),
+    })
+}
+
+// 알파 가중 박스 필터로 크기 변경 (가장자리 색 번짐 방지)
+fn resize_rgba(
+    src: &[u8],
+    src_width: usize,
+    src_height: usize,
+    width: usize,
+    height: usize,
+) -> Vec<u8> {
+    let mut pixels = vec![0u8; width * height * 4];
+    let sx = src_width as f32 / width as f32;
+    let sy = src_height as f32 / height as f32;
+
+    for y in 0..height {
+        let y0 = (y as f32 * sy) as usize;
+        let y1 = (((y + 1) as f32 * sy).ceil() as usize).clamp(y0 + 1, src_height);
+        for x in 0..width {
+            let x0 = (x as f32 * sx) as usize;
+            let x1 = (((x + 1) as f32 * sx).ceil() as usize).clamp(x0 + 1, src_width);
+
+            let mut sum = [0.0f32; 4];
+            for row in y0..y1 {
+                for col in x0..x1 {
+                    let p = &src[(row * src_width + col) * 4..][..4];
+                    let a = p[3] as f32;
+                    sum[0] += p[0] as f32 * a;
+                    sum[1] += p[1] as f32 * a;
+                    sum[2] += p[2] as f32 * a;
+                    sum[3] += a;
+                }
+            }
+
+            let count = ((y1 - y0) * (x1 - x0)) as f32;
+            let out = &mut pixels[(y * width + x) * 4..][..4];
+            if sum[3] > 0.0 {
+                out[0] = (sum[0] / sum[3]) as u8;
+                out[1] = (sum[1] / sum[3]) as u8;
+                out[2] = (sum[2] / sum[3]) as u8;
+                out[3] = (sum[3] / count) as u8;
+            }
+        }
+    }
+
+    pixels
+}
+
+// COLR 레이어 (외곽선 글리프 + CPAL 첫 팔레트의 색, None이면 텍스트 색)
+// ttf-parser 0.19에는 COLR 파서가 없어 버전 0 레코드를 직접 읽음 (버전 1 페인트 그래프는 지원하지 않음)
+fn color_layers(
+    colr: &[u8],
+    cpal: Option<&[u8]>,
+    glyph_id: u16,
+) -> Option<Vec<(u16, Option<[u8; 4]>)>> {
+    let base_count = read_u16(colr, 2)? as usize;
+    let base_offset = read_u32(colr, 4)? as usize;
+    let layer_offset = read_u32(colr, 8)? as usize;
+    let layer_count = read_u16(colr, 12)? as usize;
+
+    // 베이스 글리프 레코드 (글리프, 첫 레이어, 레이어 수)는 글리프 번호 순서
+    let records: Vec<&[u8]> = colr
+        .get(base_offset..base_offset + base_count * 6)?
+        .chunks_exact(6)
+        .collect();
+    let record = records
+        .binary_search_by_key(&glyph_id, |record| {
+            u16::from_be_bytes([record[0], record[1]])
+        })
+        .ok()
+        .map(|index| records[index])?;
+    let first = read_u16(record, 2)? as usize;
+    let count = read_u16(record, 4)? as usize;
+    if first + count > layer_count {
+        return None;
+    }
+
+    // 레이어 레코드 (외곽선 글리프, 팔레트 번호)
+    (first..first + count)
+        .map(|layer| {
+            let offset = layer_offset + layer * 4;
+            let glyph = read_u16(colr, offset)?;
+            let color = match read_u16(colr, offset + 2)? {
+                FOREGROUND_PALETTE_INDEX => None,
+                index => cpal.and_then(|cpal| palette_color(cpal, index)),
+            };
+            Some((glyph, color))
+        })
+        .collect()
+}
+
+// CPAL 첫 팔레트의 index번째 색 (RGBA, 레코드는 BGRA 순서)
+fn palette_color(cpal: &[u8], index: u16) -> Option<[u8; 4]> {
+    if index >= read_u16(cpal, 2)? {
+        return None;
+    }
+    let records_offset = read_u32(cpal, 8)? as usize;
+    let first = read_u16(cpal, 12)? as usize;
+    let offset = records_offset + (first + index as usize) * 4;
+    let [b, g, r, a]: [u8; 4] = cpal.get(offset..offset + 4)?.try_into().ok()?;
+    Some([r, g, b, a])
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+// COLR 레이어를 각각 fontdue로 래스터라이즈해 팔레트 색으로 합성
+fn rasterize_layers(face: &Face, font: &Font, glyph_id: GlyphId, px: f32) -> Option<ColorBitmap> {
+    let raw = face.raw_face();
+    let layers = color_layers(raw.table(COLR)?, raw.table(CPAL), glyph_id.0)?;
+    if layers.is_empty() {
+        return None;
+    }
+
+    let layers: Vec<_> = layers
+        .iter()
+        .map(|&(index, color)| (font.rasterize_indexed(index, px), color))
+        .collect();
+
+    // 모든 레이어를 덮는 영역 (Y 위쪽이 양수)
+    let (xmin, ymin, xmax, ymax) = layers.iter().fold(
+        (i32::MAX, i32::MAX, i32::MIN, i32::MIN),
+        |(x0, y0, x1, y1), ((m, _), _)| {
+            (
+                x0.min(m.xmin),
+                y0.min(m.ymin),
+                x1.max(m.xmin + m.width as i32),
+                y1.max(m.ymin + m.height as i32),
+            )
+        },
+    );
+    if xmin >= xmax || ymin >= ymax {
+        return None;
+    }
+
+    let width = (xmax - xmin) as usize;
+    let height = (ymax - ymin) as usize;
+    let mut pixels = vec![0u8; width * height * 4];
+
+    for ((metrics, coverage), color) in &layers {
+        let [r, g, b, a] = match color {
+            Some(color) => *color,
+            None => [255, 255, 255, 255],
+        };
+        let left = (metrics.xmin - xmin) as usize;
+        let top = (ymax - (metrics.ymin + metrics.height as i32)) as usize;
+
+        for row in 0..metrics.height {
+            for col in 0..metrics.width {
+                let src_a = coverage[row * metrics.width + col] as f32 / 255.0 * a as f32 / 255.0;
+                if src_a <= 0.0 {
+                    continue;
+                }
+
+                let dst = &mut pixels[((top + row) * width + left + col) * 4..][..4];
+                let dst_a = dst[3] as f32 / 255.0;
+                let out_a = src_a + dst_a * (1.0 - src_a);
+                for (channel, src) in dst.iter_mut().take(3).zip([r, g, b]) {
+                    let blended =
+                        src as f32 * src_a + *channel as f32 * dst_a * (1.0 - src_a);
+                    *channel = (blended / out_a) as u8;
+                }
+                dst[3] = (out_a * 255.0) as u8;
+            }
+        }
+    }
+
+    Some(ColorBitmap {
+        width,
+        height,
+        offset: [xmin as f32, -ymax as f32],
+        pixels,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 베이스 글리프 5 → 레이어 (7, 팔레트 1), (8, 텍스트 색)
+    fn colr() -> Vec<u8> {
+        let mut table = vec![0, 0, 0, 1, 0, 0, 0, 14, 0, 0, 0, 20, 0, 2];
+        table.extend([0, 5, 0, 0, 0, 2]);
+        table.extend([0, 7, 0, 1, 0, 8, 0xFF, 0xFF]);
+        table
+    }
+
+    // 팔레트 1개, 색 2개 (BGRA)
+    fn cpal() -> Vec<u8> {
+        let mut table = vec![0, 0, 0, 2, 0, 1, 0, 2, 0, 0, 0, 14, 0, 0];
+        table.extend([0, 0, 255, 255, 10, 20, 30, 128]);
+        table
+    }
+
+    #[test]
+    fn reads_layers_with_palette_colors() {
+        let layers = color_layers(&colr(), Some(&cpal()), 5).unwrap();
+        assert_eq!(layers, vec![(7, Some([30, 20, 10, 128])), (8, None)]);
+    }
+
+    #[test]
+    fn glyph_without_base_record_has_no_layers() {
+        assert_eq!(color_layers(&colr(), Some(&cpal()), 6), None);
+    }
+
+    #[test]
+    fn truncated_tables_are_ignored() {
+        let colr = colr();
+        assert_eq!(color_layers(&colr[..20], Some(&cpal()), 5), None);
+        assert_eq!(palette_color(&cpal()[..16], 1), None);
+        assert_eq!(palette_color(&cpal(), 2), None);
+    }
+}
@@ -0,0 +1,38 @@
+use vulkan_context::PresentModePreference;
+
+/// 글리프 안티앨리어싱 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Antialiasing {
+    /// 픽셀 단위 커버리지 (투명한 배경에서도 올바르게 합성됨)
+    #[default]
+    Grayscale,
+    /// RGB 서브픽셀 단위 커버리지 (불투명한 배경의 작은 글자를 더 선명하게)
+    ///
+    /// 장치에서 `dual_src_blend` 기능을 켜야 하며, 꺼져 있으면 `Grayscale`로 대체됩니다.
+    /// 가로 RGB 배열 LCD를 가정합니다.
+    Subpixel,
+}
+
+/// 렌더러 동작 설정
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RendererConfig {
+    /// 동시에 GPU에서 처리 중일 수 있는 프레임 수 (최소 1)
+    pub frames_in_flight: usize,
+    /// 스왑체인 프레젠트 모드 선호
+    pub present_mode: PresentModePreference,
+    /// 글리프 안티앨리어싱 방식
+    pub antialiasing: Antialiasing,
+    /// 글리프 아틀라스 밉맵 (텍스트를 작게 축소해도 계단 현상 없이 트라이리니어 필터링)
+    pub mipmaps: bool,
+}
+
+impl Default for RendererConfig {
+    fn default() -> Self {
+        Self {
+            frames_in_flight: 2,
+            present_mode: PresentModePreference::Vsync,
+            antialiasing: Antialiasing::Grayscale,
+            mipmaps: true,
+        }
+    }
+}
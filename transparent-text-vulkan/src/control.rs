@@ -0,0 +1,207 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+use serde::Deserialize;
+
+use crate::app_config::{Color, Effects};
+
+/// 외부 프로그램이 보내는 제어 메시지 (JSON 객체 하나)
+///
+/// ```text
+/// {"type": "set_text", "text": "<b>안녕</b>"}
+/// {"type": "subtitle", "text": "안녕하세요", "duration": 2.5, "delay": 0.5}
+/// {"type": "set_opacity", "opacity": 0.5}
+/// {"type": "set_effect", "effect": "outline+glow"}
+/// {"type": "set_style", "color": "#ffcc00", "outline_width": 3}
+/// {"type": "set_position", "x": 100, "y": 200}
+/// {"type": "typewriter", "duration": 1.5}
+/// {"type": "karaoke", "duration": 4, "color": "#ffcc00"}
+/// {"type": "dissolve", "out": true}
+/// {"type": "hide"}
+/// {"type": "save_preset", "name": "자막"}
+/// {"type": "load_preset", "name": "자막"}
+/// {"type": "screenshot"}
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ControlMessage {
+    /// 표시할 텍스트 (마크업 사용 가능)
+    SetText {
+        text: String,
+    },
+    /// `delay`초 뒤에 `duration`초 동안 보일 자막 (자막 대기열에 추가, 서서히 나타나고 사라짐)
+    Subtitle {
+        text: String,
+        duration: f32,
+        #[serde(default)]
+        delay: f32,
+    },
+    SetOpacity {
+        opacity: f32,
+    },
+    /// 효과 조합 (`outline+shadow`처럼 `+`로 이음)
+    SetEffect {
+        effect: Effects,
+    },
+    /// 색과 효과 파라미터 (생략한 항목은 그대로)
+    SetStyle(StyleUpdate),
+    /// 창 위치 (논리 픽셀)
+    SetPosition {
+        x: i32,
+        y: i32,
+    },
+    /// 타자기 애니메이션 다시 시작 (시간을 생략하면 기본값)
+    Typewriter {
+        #[serde(default)]
+        duration: Option<f32>,
+    },
+    /// 지금 텍스트를 `duration`초 동안 글자 순서대로 강조 색으로 칠함 (색을 생략하면 설정 파일의 `karaoke_color`)
+    Karaoke {
+        duration: f32,
+        #[serde(default)]
+        color: Option<Color>,
+    },
+    /// 텍스트를 태워 사라지게(`out: true`) 또는 나타나게
+    Dissolve {
+        out: bool,
+    },
+    /// 창을 서서히 나타나게
+    Show,
+    /// 창을 서서히 사라지게 한 뒤 숨김
+    Hide,
+    /// 현재 폰트, 색, 효과, 투명도, 위치를 이름을 붙여 프리셋 파일에 저장 (같은 이름이면 바꿈)
+    SavePreset {
+        name: String,
+    },
+    /// 이름으로 프리셋 불러오기
+    LoadPreset {
+        name: String,
+    },
+    /// 다음 프레임을 알파 채널을 포함한 PNG로 현재 디렉터리에 저장 (F12와 같음)
+    ///
+    /// 인증 없는 원격 연결도 받으므로 저장 경로는 메시지로 정할 수 없습니다.
+    Screenshot,
+}
+
+/// `set_style` 메시지의 항목
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StyleUpdate {
+    /// 텍스트 전체의 기본 색
+    pub color: Option<Color>,
+    pub outline_color: Option<Color>,
+    pub outline_width: Option<f32>,
+    pub glow_color: Option<Color>,
+    pub glow_radius: Option<f32>,
+    pub shadow_color: Option<Color>,
+    pub shadow_blur: Option<f32>,
+    pub background: Option<bool>,
+}
+
+/// 제어 메시지를 받는 서버
+///
+/// 연결마다 스레드에서 메시지를 읽어 이벤트 루프로 넘기고,
+/// 메시지마다 `{"ok":true}` 또는 `{"ok":false,"error":"..."}`로 답합니다.
+/// 여러 주소에서 받을 수 있고, 이벤트 루프는 프레임마다 쌓인 메시지만 가져갑니다.
+pub struct ControlServer {
+    sender: Sender<ControlMessage>,
+    messages: Receiver<ControlMessage>,
+}
+
+impl Default for ControlServer {
+    fn default() -> Self {
+        let (sender, messages) = mpsc::channel();
+        Self { sender, messages }
+    }
+}
+
+impl ControlServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 로컬 소켓에서 한 줄에 JSON 하나씩 받음
+    ///
+    /// Unix에서는 Unix 도메인 소켓 경로, 그 밖에서는 `127.0.0.1:포트` 주소입니다.
+    #[cfg(unix)]
+    pub fn listen_local(&self, path: &str) -> std::io::Result<()> {
+        use std::os::unix::fs::FileTypeExt;
+
+        // 이전 실행이 남긴 소켓 파일은 지우고 다시 만듦 (소켓이 아닌 파일은 지우지 않음)
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if !metadata.file_type().is_socket() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    format!("소켓이 아닌 파일이 이미 있습니다: {path}"),
+                ));
+            }
+            std::fs::remove_file(path)?;
+        }
+        let listener = std::os::unix::net::UnixListener::bind(path)?;
+        self.spawn(
+            move || listener.accept().map(|(stream, _)| stream),
+            serve_lines,
+        );
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    pub fn listen_local(&self, address: &str) -> std::io::Result<()> {
+        let listener = std::net::TcpListener::bind(address)?;
+        self.spawn(
+            move || listener.accept().map(|(stream, _)| stream),
+            serve_lines,
+        );
+        Ok(())
+    }
+
+    // 리스너를 옮겨 받은 accept로 연결을 받는 스레드를 띄우고, 연결마다 serve를 별도 스레드에서 실행
+    pub(crate) fn spawn<A, S>(&self, mut accept: A, serve: fn(S, Sender<ControlMessage>))
+    where
+        A: FnMut() -> std::io::Result<S> + Send + 'static,
+        S: Send + 'static,
+    {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            while let Ok(stream) = accept() {
+                let sender = sender.clone();
+                thread::spawn(move || serve(stream, sender));
+            }
+        });
+    }
+
+    /// 마지막 확인 이후 받은 메시지
+    pub fn poll(&self) -> impl Iterator<Item = ControlMessage> + '_ {
+        self.messages.try_iter()
+    }
+}
+
+/// JSON 메시지 하나를 처리해 답할 내용을 돌려줌 (이벤트 루프가 끝났으면 `None`)
+pub fn handle_message(json: &str, sender: &Sender<ControlMessage>) -> Option<serde_json::Value> {
+    match serde_json::from_str::<ControlMessage>(json.trim()) {
+        Ok(message) => sender
+            .send(message)
+            .ok()
+            .map(|()| serde_json::json!({ "ok": true })),
+        Err(e) => Some(serde_json::json!({ "ok": false, "error": e.to_string() })),
+    }
+}
+
+// 연결이 끊기거나 이벤트 루프가 끝날 때까지 한 줄씩 처리
+fn serve_lines<S: std::io::Read + Write>(stream: S, sender: Sender<ControlMessage>) {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    while matches!(reader.read_line(&mut line), Ok(read) if read > 0) {
+        let Some(reply) = handle_message(&line, &sender) else {
+            break;
+        };
+        line.clear();
+
+        if writeln!(reader.get_mut(), "{reply}").is_err() {
+            break;
+        }
+    }
+}
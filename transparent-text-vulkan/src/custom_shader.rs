@@ -0,0 +1,112 @@
+use std::{path::Path, sync::Arc};
+use vulkano::{device::Device, shader::ShaderModule};
+
+use crate::{error::RendererError, shaders};
+
+const SPIRV_MAGIC: u32 = 0x0723_0203;
+
+/// 내장 텍스트 프래그먼트 셰이더를 대신하는 사용자 효과 셰이더
+///
+/// 내장 `shaders/text.frag`와 같은 인터페이스를 받습니다.
+/// - 입력: `fragTexCoords`(0), `fragColor`(1), `fragEffect`(2), `fragColored`(3), `fragGradient`(4),
+///   `fragLayer`(5, flat uint)
+/// - set 0: 글리프 아틀라스 `texSampler`(0), 블러된 그림자 아틀라스 `shadowSampler`(1),
+///   팽창한 외곽선 아틀라스 `outlineSampler`(2), 모두 `fragLayer`를 레이어로 읽는 `sampler2DArray`
+///   (글리프 아틀라스는 일반 글리프면 `.r`이 커버리지, 컬러 글리프면 premultiplied RGBA)
+/// - set 1: `EffectParams` UBO, push constant: `PushConstants`
+/// - 출력: premultiplied alpha `outColor`(0)
+///
+/// `constant_id = 0`으로 bool을 선언하면 대상이 sRGB일 때 `true`가 됩니다(선형 색 출력).
+/// 서브픽셀 안티앨리어싱에서도 일반 블렌딩으로 합성됩니다.
+///
+/// ```ignore
+/// let shader = CustomShader::from_file("shaders/custom/scanline.frag")?;
+/// text_renderer.set_custom_shader(Some(&shader))?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct CustomShader {
+    source: Source,
+}
+
+#[derive(Debug, Clone)]
+enum Source {
+    Spirv(Arc<[u32]>),
+    #[cfg(feature = "glsl")]
+    Glsl(Arc<str>),
+}
+
+impl CustomShader {
+    /// 컴파일된 SPIR-V 바이너리 (리틀 엔디언)
+    pub fn from_spirv(bytes: &[u8]) -> Result<Self, RendererError> {
+        if !bytes.len().is_multiple_of(4) {
+            return Err(RendererError::shader(
+                "custom fragment",
+                "SPIR-V 크기가 4바이트 단위가 아닙니다",
+            ));
+        }
+        let words: Arc<[u32]> = bytes
+            .chunks_exact(4)
+            .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+            .collect();
+        if words.first() != Some(&SPIRV_MAGIC) {
+            return Err(RendererError::shader(
+                "custom fragment",
+                "SPIR-V 매직 넘버가 아닙니다",
+            ));
+        }
+
+        Ok(Self {
+            source: Source::Spirv(words),
+        })
+    }
+
+    /// GLSL 소스 (렌더러에 적용할 때 컴파일됨)
+    #[cfg(feature = "glsl")]
+    pub fn from_glsl(source: impl Into<String>) -> Self {
+        Self {
+            source: Source::Glsl(source.into().into()),
+        }
+    }
+
+    /// 셰이더 파일을 읽습니다. 확장자가 `.spv`면 SPIR-V, 아니면 GLSL로 취급합니다
+    /// (GLSL은 `glsl` 기능이 필요).
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RendererError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|e| {
+            RendererError::shader("custom fragment", format!("{}: {e}", path.display()))
+        })?;
+
+        if path.extension().is_some_and(|ext| ext == "spv") {
+            return Self::from_spirv(&bytes);
+        }
+        Self::from_glsl_bytes(bytes)
+    }
+
+    #[cfg(feature = "glsl")]
+    fn from_glsl_bytes(bytes: Vec<u8>) -> Result<Self, RendererError> {
+        String::from_utf8(bytes)
+            .map(Self::from_glsl)
+            .map_err(|e| RendererError::shader("custom fragment", e))
+    }
+
+    #[cfg(not(feature = "glsl"))]
+    fn from_glsl_bytes(_bytes: Vec<u8>) -> Result<Self, RendererError> {
+        Err(RendererError::shader(
+            "custom fragment",
+            "GLSL 셰이더는 glsl 기능이 필요합니다 (.spv 파일을 사용하세요)",
+        ))
+    }
+
+    pub(crate) fn load(&self, device: &Arc<Device>) -> Result<Arc<ShaderModule>, RendererError> {
+        match &self.source {
+            Source::Spirv(words) => shaders::load_spirv(device, words, "custom fragment"),
+            #[cfg(feature = "glsl")]
+            Source::Glsl(source) => crate::glsl::compile(
+                device,
+                source,
+                shaderc::ShaderKind::Fragment,
+                "custom fragment",
+            ),
+        }
+    }
+}
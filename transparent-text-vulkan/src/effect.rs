@@ -0,0 +1,446 @@
+use glam::{Mat4, Vec3};
+use std::ops::{BitOr, BitOrAssign};
+use vulkano::buffer::BufferContents;
+
+// Push Constants (드로우마다 바뀌는 투영 행렬, 투명도와 효과)
+#[derive(BufferContents, Clone, Copy)]
+#[repr(C)]
+pub(crate) struct PushConstants {
+    pub projection: [[f32; 4]; 4],
+    pub opacity: f32,
+    pub effects: i32, // 비트마스크 (1: outline, 2: shadow, 4: glow, 8: gradient, 16: wave, 32: dissolve, 64: glitch)
+}
+
+// 효과 파라미터 UBO (descriptor set 1)
+// GLSL std140 블록과 오프셋이 일치하도록 vec4, vec2 필드를 앞에 배치
+#[derive(BufferContents, Clone, Copy)]
+#[repr(C)]
+pub(crate) struct EffectParams {
+    pub outline_color: [f32; 4],
+    pub glow_color: [f32; 4],
+    pub shadow_color: [f32; 4],
+    pub dissolve_color: [f32; 4],
+    pub gradient_stops: [[f32; 4]; 4],
+    pub shadow_offset: [f32; 2],
+    pub text_extent: [f32; 2],
+    pub outline_width: f32,
+    pub outline_softness: f32,
+    pub glow_radius: f32,
+    pub glow_intensity: f32,
+    pub time: f32,
+    pub gradient_mode: i32, // 0: linear, 1: rainbow
+    pub gradient_count: i32,
+    pub gradient_speed: f32,
+    pub wave_amplitude: f32,
+    pub wave_speed: f32,
+    pub dissolve_progress: f32,
+    pub dissolve_edge: f32,
+    pub glitch_intensity: f32,
+}
+
+// 배경 상자 Push Constants (mat4, vec4를 맨 앞에 두어 GLSL 오프셋과 일치)
+// (투영 행렬과 모델 행렬을 곱해 넘겨 push constant 최소 보장 크기 128바이트 안에 맞춤)
+#[derive(BufferContents, Clone, Copy)]
+#[repr(C)]
+pub(crate) struct BackgroundPushConstants {
+    pub transform: [[f32; 4]; 4],
+    pub color: [f32; 4],
+    // 상자 왼쪽 위 (픽셀, 텍스트 중심 기준)
+    pub origin: [f32; 2],
+    pub size: [f32; 2],
+    pub radius: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextEffect {
+    Normal,
+    Outline,
+    Shadow,
+    Glow,
+    Gradient,
+    Wave,
+    Dissolve,
+    Glitch,
+}
+
+impl TextEffect {
+    pub fn next(&self) -> Self {
+        match self {
+            TextEffect::Normal => TextEffect::Outline,
+            TextEffect::Outline => TextEffect::Shadow,
+            TextEffect::Shadow => TextEffect::Glow,
+            TextEffect::Glow => TextEffect::Gradient,
+            TextEffect::Gradient => TextEffect::Wave,
+            TextEffect::Wave => TextEffect::Dissolve,
+            TextEffect::Dissolve => TextEffect::Glitch,
+            TextEffect::Glitch => TextEffect::Normal,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            TextEffect::Normal => "일반",
+            TextEffect::Outline => "외곽선",
+            TextEffect::Shadow => "그림자",
+            TextEffect::Glow => "발광",
+            TextEffect::Gradient => "그라데이션",
+            TextEffect::Wave => "물결",
+            TextEffect::Dissolve => "소멸",
+            TextEffect::Glitch => "글리치",
+        }
+    }
+}
+
+/// 함께 켤 수 있는 효과 조합 (셰이더에는 비트마스크로 전달)
+///
+/// 켜진 효과는 그림자, 발광, 외곽선, 글자 순서로 아래에서 위로 겹쳐 그려지고,
+/// 그라데이션은 글자 색을, 물결은 글자 위치를, 글리치는 글자 샘플링을 바꾸고,
+/// 소멸은 마지막에 전체를 노이즈로 지웁니다.
+///
+/// ```ignore
+/// let effects = TextEffects::OUTLINE | TextEffects::SHADOW;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextEffects(u32);
+
+impl TextEffects {
+    pub const NONE: Self = Self(0);
+    pub const OUTLINE: Self = Self(1);
+    pub const SHADOW: Self = Self(2);
+    pub const GLOW: Self = Self(4);
+    pub const GRADIENT: Self = Self(8);
+    pub const WAVE: Self = Self(16);
+    pub const DISSOLVE: Self = Self(32);
+    pub const GLITCH: Self = Self(64);
+
+    const ALL: [TextEffect; 7] = [
+        TextEffect::Outline,
+        TextEffect::Shadow,
+        TextEffect::Glow,
+        TextEffect::Gradient,
+        TextEffect::Wave,
+        TextEffect::Dissolve,
+        TextEffect::Glitch,
+    ];
+
+    /// 두 조합을 합침 (상수에서 쓸 수 있는 `|`)
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// 효과 하나를 켜거나 끔
+    pub fn toggle(&mut self, effect: TextEffect) {
+        self.0 ^= Self::from(effect).0;
+    }
+
+    pub fn name(&self) -> String {
+        if self.is_empty() {
+            return TextEffect::Normal.name().to_string();
+        }
+        Self::ALL
+            .iter()
+            .filter(|&&effect| self.contains(effect.into()))
+            .map(TextEffect::name)
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+
+    pub(crate) fn bits(self) -> i32 {
+        self.0 as i32
+    }
+}
+
+impl From<TextEffect> for TextEffects {
+    fn from(effect: TextEffect) -> Self {
+        match effect {
+            TextEffect::Normal => Self::NONE,
+            TextEffect::Outline => Self::OUTLINE,
+            TextEffect::Shadow => Self::SHADOW,
+            TextEffect::Glow => Self::GLOW,
+            TextEffect::Gradient => Self::GRADIENT,
+            TextEffect::Wave => Self::WAVE,
+            TextEffect::Dissolve => Self::DISSOLVE,
+            TextEffect::Glitch => Self::GLITCH,
+        }
+    }
+}
+
+impl BitOr for TextEffects {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl BitOrAssign for TextEffects {
+    fn bitor_assign(&mut self, other: Self) {
+        *self = self.union(other);
+    }
+}
+
+// 텍스트 한 번 그릴 때 사용하는 파라미터
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextParams {
+    pub opacity: f32,
+    // 켤 효과 조합 (구간 스타일에 효과가 없으면 이 값을 씀)
+    pub effects: TextEffects,
+    // 외곽선 색 (RGBA8)
+    pub outline_color: [u8; 4],
+    // 외곽선 두께 (픽셀, 최대 8)
+    pub outline_width: f32,
+    // 외곽선 바깥쪽이 흐려지는 폭 (픽셀, 0이면 선명한 경계)
+    pub outline_softness: f32,
+    // 발광 색 (RGBA8)
+    pub glow_color: [u8; 4],
+    // 발광이 퍼지는 반지름 (픽셀, 블룸 단계 수를 정함, 64 근처까지 넓어짐)
+    pub glow_radius: f32,
+    // 발광 세기 (블룸 밝기 배율)
+    pub glow_intensity: f32,
+    // 그림자 오프셋 (픽셀)
+    pub shadow_offset: [f32; 2],
+    // 그림자 색 (RGBA8)
+    pub shadow_color: [u8; 4],
+    // 그림자 가우시안 블러 표준편차 (픽셀, 0이면 블러 없음)
+    // 오프셋 + 3σ가 글리프 여백(8픽셀)을 넘으면 그림자 가장자리가 잘림
+    pub shadow_blur: f32,
+    // 그라데이션 채우기 (`TextEffects::GRADIENT`)
+    pub gradient: TextGradient,
+    // 물결 높이 (픽셀)
+    pub wave_amplitude: f32,
+    // 물결 속도 (초당 라디안)
+    pub wave_speed: f32,
+    // 소멸 진행도 (0이면 모두 보이고 1이면 모두 사라짐, 시간에 따라 바꿔 페이드 인/아웃)
+    pub dissolve_progress: f32,
+    // 소멸 경계의 타는 띠 폭 (노이즈 값 단위, 0 ~ 1)
+    pub dissolve_edge: f32,
+    // 소멸 경계 색 (RGBA8)
+    pub dissolve_color: [u8; 4],
+    // 글리치 세기 (0 ~ 1, 채널 어긋남과 가로 띠가 밀리는 정도)
+    pub glitch_intensity: f32,
+    // 애니메이션 시간 (초, 보통 `Clock::elapsed()`)
+    pub time: f32,
+    // 화면 픽셀 좌표를 NDC로 바꾸는 투영 행렬 (보통 `TextParams::pixel_projection(스왑체인 크기)`)
+    pub projection: Mat4,
+    // 기본 텍스트와 배경 상자의 모델 행렬 (텍스트 블록 중심 기준 픽셀 → 화면 픽셀)
+    pub transform: Mat4,
+    // 텍스트 뒤에 그릴 배경 상자 (자막, 오버레이용)
+    pub background: Option<TextBackground>,
+}
+
+/// 그라데이션 채우기 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GradientMode {
+    /// 색 지점을 텍스트 왼쪽에서 오른쪽으로 보간 (speed만큼 시간에 따라 흐름)
+    #[default]
+    Linear,
+    /// 글자마다 색상(hue)을 바꾸고 시간에 따라 순환
+    Rainbow,
+}
+
+/// 글자 색을 대신하는 그라데이션 (컬러 글리프에는 적용되지 않음)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextGradient {
+    /// 색 지점 (RGBA8, 앞의 `stop_count`개만 사용)
+    pub stops: [[u8; 4]; 4],
+    /// 사용할 색 지점 수 (2 ~ 4)
+    pub stop_count: usize,
+    pub mode: GradientMode,
+    /// 초당 흐르는 양 (Linear: 텍스트 너비 배율, Rainbow: 색상환 바퀴 수)
+    pub speed: f32,
+}
+
+impl TextGradient {
+    /// 색 지점 2 ~ 4개의 선형 그라데이션 (4개를 넘는 지점은 무시)
+    pub fn linear(stops: &[[u8; 4]]) -> Self {
+        let mut gradient = Self {
+            stop_count: stops.len().clamp(2, 4),
+            ..Self::default()
+        };
+        for (stop, color) in gradient.stops.iter_mut().zip(stops) {
+            *stop = *color;
+        }
+        gradient
+    }
+
+    /// 글자마다 색상이 바뀌며 순환하는 무지개
+    pub fn rainbow() -> Self {
+        Self {
+            mode: GradientMode::Rainbow,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for TextGradient {
+    fn default() -> Self {
+        Self {
+            stops: [
+                [255, 90, 170, 255],
+                [255, 220, 60, 255],
+                [60, 220, 255, 255],
+                [255, 255, 255, 255],
+            ],
+            stop_count: 3,
+            mode: GradientMode::Linear,
+            speed: 0.25,
+        }
+    }
+}
+
+/// 텍스트 뒤에 그리는 둥근 배경 상자
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextBackground {
+    /// 배경 색 (RGBA8, 알파는 `TextParams::opacity`와 곱해짐)
+    pub color: [u8; 4],
+    /// 모서리 반지름 (픽셀)
+    pub corner_radius: f32,
+    /// 텍스트 영역 바깥 여백 [가로, 세로] (픽셀)
+    pub padding: [f32; 2],
+}
+
+impl Default for TextBackground {
+    fn default() -> Self {
+        Self {
+            color: [0, 0, 0, 160],
+            corner_radius: 12.0,
+            padding: [16.0, 8.0],
+        }
+    }
+}
+
+/// 기본 텍스트 위에 겹쳐 그리는 사각형 (커서, 선택 영역 등)
+///
+/// 배경 상자와 같은 파이프라인으로 글리프보다 먼저 그려집니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextHighlight {
+    /// [x, y, 너비, 높이] (텍스트 블록 중심 기준 픽셀, `TextParams::transform`이 적용됨)
+    pub rect: [f32; 4],
+    /// 색 (RGBA8, 알파는 `TextParams::opacity`와 곱해짐)
+    pub color: [u8; 4],
+}
+
+impl Default for TextParams {
+    fn default() -> Self {
+        Self {
+            opacity: 1.0,
+            effects: TextEffects::NONE,
+            outline_color: [255, 255, 0, 204],
+            outline_width: 3.0,
+            outline_softness: 1.0,
+            glow_color: [51, 204, 255, 255],
+            glow_radius: 6.0,
+            glow_intensity: 2.0,
+            shadow_offset: [3.0, 3.0],
+            shadow_color: [0, 0, 0, 153],
+            shadow_blur: 1.5,
+            gradient: TextGradient::default(),
+            wave_amplitude: 6.0,
+            wave_speed: 6.0,
+            dissolve_progress: 0.0,
+            dissolve_edge: 0.08,
+            dissolve_color: [255, 120, 20, 255],
+            glitch_intensity: 0.5,
+            time: 0.0,
+            // 800x600 화면의 가운데
+            projection: Self::pixel_projection([800.0, 600.0]),
+            transform: Mat4::from_translation(Vec3::new(400.0, 300.0, 0.0)),
+            background: None,
+        }
+    }
+}
+
+impl TextParams {
+    /// 왼쪽 위가 (0, 0)이고 Y가 아래로 커지는 픽셀 좌표의 직교 투영
+    ///
+    /// `extent`는 보통 스왑체인 이미지 크기이며, 창 크기가 바뀌면 새 크기로 다시 만듭니다.
+    pub fn pixel_projection(extent: [f32; 2]) -> Mat4 {
+        // Vulkan NDC는 Y가 아래쪽이 +1이므로 bottom = 0, top = 높이
+        Mat4::orthographic_rh(0.0, extent[0], 0.0, extent[1], -1.0, 1.0)
+    }
+
+    pub(crate) fn push_constants(&self) -> PushConstants {
+        PushConstants {
+            projection: self.projection.to_cols_array_2d(),
+            opacity: self.opacity,
+            effects: self.effects.bits(),
+        }
+    }
+
+    // extent는 텍스트 크기 (논리 픽셀)
+    // 외곽선, 발광, 그림자는 아틀라스 텍셀 단위로 샘플링하므로 HiDPI 배율(texel_scale)을 곱함
+    pub(crate) fn effect_params(&self, extent: [f32; 2], texel_scale: f32) -> EffectParams {
+        let color = |c: [u8; 4]| c.map(|c| c as f32 / 255.0);
+
+        EffectParams {
+            outline_color: color(self.outline_color),
+            glow_color: color(self.glow_color),
+            shadow_color: color(self.shadow_color),
+            dissolve_color: color(self.dissolve_color),
+            gradient_stops: self.gradient.stops.map(color),
+            shadow_offset: self.shadow_offset.map(|offset| offset * texel_scale),
+            text_extent: extent,
+            outline_width: self.outline_width * texel_scale,
+            outline_softness: self.outline_softness * texel_scale,
+            glow_radius: self.glow_radius * texel_scale,
+            glow_intensity: self.glow_intensity,
+            time: self.time,
+            gradient_mode: match self.gradient.mode {
+                GradientMode::Linear => 0,
+                GradientMode::Rainbow => 1,
+            },
+            gradient_count: self.gradient.stop_count.clamp(2, 4) as i32,
+            gradient_speed: self.gradient.speed,
+            wave_amplitude: self.wave_amplitude,
+            wave_speed: self.wave_speed,
+            dissolve_progress: self.dissolve_progress.clamp(0.0, 1.0),
+            dissolve_edge: self.dissolve_edge,
+            glitch_intensity: self.glitch_intensity.clamp(0.0, 1.0),
+        }
+    }
+
+    pub(crate) fn highlight_push_constants(
+        &self,
+        highlight: &TextHighlight,
+    ) -> BackgroundPushConstants {
+        let [x, y, width, height] = highlight.rect;
+        let [r, g, b, a] = highlight.color.map(|c| c as f32 / 255.0);
+
+        BackgroundPushConstants {
+            transform: (self.projection * self.transform).to_cols_array_2d(),
+            color: [r, g, b, a * self.opacity],
+            origin: [x, y],
+            size: [width, height],
+            radius: 0.0,
+        }
+    }
+
+    // 텍스트 크기(extent)에 여백을 더한 배경 상자, 배경이 없으면 None
+    pub(crate) fn background_push_constants(
+        &self,
+        extent: [f32; 2],
+    ) -> Option<BackgroundPushConstants> {
+        let background = self.background?;
+        let size = [
+            extent[0] + background.padding[0] * 2.0,
+            extent[1] + background.padding[1] * 2.0,
+        ];
+        let [r, g, b, a] = background.color.map(|c| c as f32 / 255.0);
+
+        Some(BackgroundPushConstants {
+            transform: (self.projection * self.transform).to_cols_array_2d(),
+            color: [r, g, b, a * self.opacity],
+            origin: [-size[0] / 2.0, -size[1] / 2.0],
+            size,
+            radius: background.corner_radius,
+        })
+    }
+}
@@ -0,0 +1,117 @@
+use vulkano::{LoadingError, Validated, VulkanError};
+use vulkan_context::ContextError;
+
+pub(crate) type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// 렌더러 초기화와 렌더링 중 발생할 수 있는 오류
+#[derive(Debug, thiserror::Error)]
+pub enum RendererError {
+    #[error("Vulkan 라이브러리 로드 실패: {0}")]
+    Library(#[from] LoadingError),
+
+    #[error("Instance 생성 실패: {0}")]
+    Instance(#[source] Validated<VulkanError>),
+
+    #[error("윈도우 생성 실패: {0}")]
+    Window(#[source] BoxError),
+
+    #[error("사용 가능한 GPU가 없습니다")]
+    NoSuitableDevice,
+
+    #[error("Device 생성 실패: {0}")]
+    Device(#[source] Validated<VulkanError>),
+
+    #[error("Swapchain 오류: {0}")]
+    Swapchain(#[source] Validated<VulkanError>),
+
+    #[error("셰이더 로드 실패 ({name}): {source}")]
+    Shader {
+        name: &'static str,
+        #[source]
+        source: BoxError,
+    },
+
+    #[error("파이프라인 생성 실패: {0}")]
+    Pipeline(#[source] BoxError),
+
+    #[error("폰트 로드 실패: {0}")]
+    Font(&'static str),
+
+    #[error("폰트 파일 로드 실패 ({path}): {source}")]
+    FontFile {
+        path: String,
+        #[source]
+        source: BoxError,
+    },
+
+    #[error("시스템 폰트를 찾을 수 없습니다: {0}")]
+    FontNotFound(String),
+
+    #[error("텍스처 생성/업로드 실패: {0}")]
+    Texture(#[source] BoxError),
+
+    #[error("버퍼 할당 실패: {0}")]
+    Buffer(#[source] BoxError),
+
+    #[error("명령 기록/제출 실패: {0}")]
+    Command(#[source] BoxError),
+
+    #[error("렌더러 설정 오류: {0}")]
+    InvalidConfig(String),
+
+    #[error("스크린샷 저장 실패 ({path}): {source}")]
+    Capture {
+        path: String,
+        #[source]
+        source: BoxError,
+    },
+}
+
+impl RendererError {
+    pub(crate) fn shader(name: &'static str, error: impl Into<BoxError>) -> Self {
+        RendererError::Shader {
+            name,
+            source: error.into(),
+        }
+    }
+
+    pub(crate) fn pipeline(error: impl Into<BoxError>) -> Self {
+        RendererError::Pipeline(error.into())
+    }
+
+    pub(crate) fn texture(error: impl Into<BoxError>) -> Self {
+        RendererError::Texture(error.into())
+    }
+
+    pub(crate) fn buffer(error: impl Into<BoxError>) -> Self {
+        RendererError::Buffer(error.into())
+    }
+
+    pub(crate) fn command(error: impl Into<BoxError>) -> Self {
+        RendererError::Command(error.into())
+    }
+
+    /// 스왑체인을 다시 만들면 복구되는 오류인지 여부
+    pub fn is_out_of_date(&self) -> bool {
+        matches!(
+            self,
+            RendererError::Swapchain(Validated::Error(VulkanError::OutOfDate))
+        )
+    }
+}
+
+// vulkan-context의 초기화 오류는 같은 이름의 변형으로 (render pass는 파이프라인, 이미지는 텍스처 오류)
+impl From<ContextError> for RendererError {
+    fn from(error: ContextError) -> Self {
+        match error {
+            ContextError::Library(e) => RendererError::Library(e),
+            ContextError::Instance(e) => RendererError::Instance(e),
+            ContextError::Window(e) => RendererError::Window(e),
+            ContextError::NoSuitableDevice => RendererError::NoSuitableDevice,
+            ContextError::Device(e) => RendererError::Device(e),
+            ContextError::Swapchain(e) => RendererError::Swapchain(e),
+            ContextError::RenderPass(e) => RendererError::Pipeline(e),
+            ContextError::Image(e) => RendererError::Texture(e),
+        }
+    }
+}
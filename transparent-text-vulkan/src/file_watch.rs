@@ -0,0 +1,53 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+/// 파일 하나의 변경 감시
+///
+/// 편집기나 다른 프로그램은 임시 파일을 rename해 저장하기도 하므로 파일이 있는 디렉터리를 감시합니다.
+/// 파일이 아직 없어도 디렉터리가 있으면 감시할 수 있습니다.
+pub struct FileWatcher {
+    path: PathBuf,
+    events: Receiver<notify::Result<notify::Event>>,
+    // 드롭되면 감시가 끝나므로 보관만 함
+    _watcher: RecommendedWatcher,
+}
+
+impl FileWatcher {
+    pub fn new(path: impl Into<PathBuf>) -> notify::Result<Self> {
+        let path = path.into();
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        Ok(Self {
+            path,
+            events,
+            _watcher: watcher,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 마지막 확인 이후 파일이 생성/수정되었는지 (쌓인 이벤트는 모두 비움)
+    pub fn changed(&self) -> bool {
+        let file_name = self.path.file_name();
+        self.events.try_iter().fold(false, |changed, event| {
+            let touched = event.is_ok_and(|event| {
+                (event.kind.is_modify() || event.kind.is_create())
+                    && event.paths.iter().any(|path| path.file_name() == file_name)
+            });
+            changed || touched
+        })
+    }
+}
@@ -0,0 +1,158 @@
+use std::{path::Path, sync::Arc};
+use fontdue::{Font, FontSettings};
+
+use crate::{
+    color::ColorFont,
+    error::RendererError,
+    variation::{self, Coverage, Variation},
+};
+
+/// 원본 폰트 파일 데이터
+///
+/// fontdue `Font`만으로는 셰이핑과 컬러 글리프를 처리할 수 없으므로,
+/// 이 데이터로 폰트를 등록하면 rustybuzz 셰이핑과 컬러 이모지가 함께 적용됩니다.
+/// 가변 폰트의 축 좌표(`SpanStyle::weight` 등)도 이 데이터로 등록한 폰트에만 적용됩니다.
+#[derive(Clone)]
+pub struct FontData {
+    bytes: Arc<[u8]>,
+    index: u32,
+}
+
+impl FontData {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self::with_index(bytes, 0)
+    }
+
+    /// 폰트 컬렉션(TTC)에서 `index`번째 폰트를 사용
+    pub fn with_index(bytes: Vec<u8>, index: u32) -> Self {
+        Self {
+            bytes: bytes.into(),
+            index,
+        }
+    }
+
+    /// 폰트 파일(TTF/OTF/TTC)을 읽습니다.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RendererError> {
+        let path = path.as_ref();
+        std::fs::read(path)
+            .map(Self::new)
+            .map_err(|e| RendererError::FontFile {
+                path: path.display().to_string(),
+                source: e.into(),
+            })
+    }
+
+    /// 시스템에 설치된 폰트를 패밀리 이름으로 찾습니다. (예: "Noto Sans KR", "Segoe UI")
+    ///
+    /// 같은 패밀리에 여러 스타일이 있으면 일반 굵기/스타일을 우선합니다.
+    #[cfg(feature = "system-fonts")]
+    pub fn from_system(family: &str) -> Result<Self, RendererError> {
+        let mut database = fontdb::Database::new();
+        database.load_system_fonts();
+
+        let id = database
+            .query(&fontdb::Query {
+                families: &[fontdb::Family::Name(family)],
+                ..fontdb::Query::default()
+            })
+            .ok_or_else(|| RendererError::FontNotFound(family.to_owned()))?;
+
+        database
+            .with_face_data(id, |data, index| Self::with_index(data.to_vec(), index))
+            .ok_or_else(|| RendererError::FontNotFound(family.to_owned()))
+    }
+
+    /// 레이아웃/래스터라이즈용 fontdue 폰트
+    pub fn to_font(&self) -> Result<Font, RendererError> {
+        Font::from_bytes(
+            &self.bytes[..],
+            FontSettings {
+                collection_index: self.index,
+                ..FontSettings::default()
+            },
+        )
+        .map_err(RendererError::Font)
+    }
+}
+
+/// 폰트 파일(TTF/OTF)을 읽어 로드합니다.
+pub fn load_font_file(path: impl AsRef<Path>) -> Result<Font, RendererError> {
+    FontData::from_file(path)?.to_font()
+}
+
+/// 시스템에 설치된 폰트를 패밀리 이름으로 찾아 로드합니다.
+#[cfg(feature = "system-fonts")]
+pub fn load_system_font(family: &str) -> Result<Font, RendererError> {
+    FontData::from_system(family)?.to_font()
+}
+
+// 렌더러에 등록된 폰트 하나 (원본 데이터가 있으면 셰이핑/컬러 글리프 사용)
+pub(crate) struct FontFace {
+    pub font: Font,
+    data: Option<FontData>,
+    pub color: Option<ColorFont>,
+    // 가변 폰트 (fvar 축이 있음)
+    variable: bool,
+}
+
+impl FontFace {
+    pub fn from_font(font: Font) -> Self {
+        Self {
+            font,
+            data: None,
+            color: None,
+            variable: false,
+        }
+    }
+
+    pub fn from_data(data: FontData) -> Result<Self, RendererError> {
+        Ok(Self {
+            font: data.to_font()?,
+            color: ColorFont::new(data.bytes.clone(), data.index),
+            variable: ttf_parser::Face::parse(&data.bytes, data.index)
+                .is_ok_and(|face| face.is_variable()),
+            data: Some(data),
+        })
+    }
+
+    /// 가변 폰트인지 (축 좌표를 적용할 수 있는지)
+    pub fn is_variable(&self) -> bool {
+        self.variable
+    }
+
+    /// 셰이핑용 rustybuzz 페이스 (원본 데이터가 없으면 `None`, 가변 폰트면 축 좌표를 적용)
+    pub fn shaper(&self, variation: &Variation) -> Option<rustybuzz::Face<'_>> {
+        let data = self.data.as_ref()?;
+        let mut face = rustybuzz::Face::from_slice(&data.bytes, data.index)?;
+        if self.variable {
+            let variations: Vec<_> = variation
+                .axes()
+                .map(|(tag, value)| rustybuzz::Variation { tag, value })
+                .collect();
+            face.set_variations(&variations);
+        }
+        Some(face)
+    }
+
+    /// 축 좌표를 적용한 인스턴스로 래스터라이즈 (가변 폰트가 아니거나 기본 인스턴스면 `None`, fontdue 사용)
+    pub fn rasterize_variation(
+        &self,
+        glyph_index: u16,
+        px: f32,
+        variation: &Variation,
+        subpixel: bool,
+    ) -> Option<Coverage> {
+        if !self.variable || *variation == Variation::default() {
+            return None;
+        }
+        let data = self.data.as_ref()?;
+        let face = ttf_parser::Face::parse(&data.bytes, data.index).ok()?;
+        Some(variation::rasterize(
+            &face,
+            glyph_index,
+            px,
+            variation,
+            subpixel,
+        ))
+    }
+}
@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use vulkano::{
+    command_buffer::allocator::StandardCommandBufferAllocator,
+    device::Device,
+    sync::{self, future::FenceSignalFuture, GpuFuture},
+};
+use tracing::warn;
+
+use crate::config::RendererConfig;
+
+/// 제출한 프레임이 GPU에서 끝나면 신호되는 펜스
+pub type FrameFence = Arc<FenceSignalFuture<Box<dyn GpuFuture>>>;
+
+/// N개의 프레임을 동시에 GPU에 올려 두기 위한 프레임별 동기화 상태
+///
+/// 프레임마다 펜스와 command buffer 할당자를 따로 두어, CPU는 N 프레임 전의
+/// 같은 슬롯이 끝났을 때만 기다립니다.
+///
+/// ```ignore
+/// frames.wait_current();
+/// let builder = AutoCommandBufferBuilder::primary(frames.command_buffer_allocator(), ...);
+/// let future = frames.previous_future().join(acquire_future)...boxed().then_signal_fence_and_flush();
+/// frames.finish_frame(future.ok());
+/// ```
+pub struct FramesInFlight {
+    device: Arc<Device>,
+    fences: Vec<Option<FrameFence>>,
+    command_buffer_allocators: Vec<StandardCommandBufferAllocator>,
+    current: usize,
+    previous: usize,
+}
+
+impl FramesInFlight {
+    pub fn new(device: Arc<Device>, config: &RendererConfig) -> Self {
+        let count = config.frames_in_flight.max(1);
+        let command_buffer_allocators = (0..count)
+            .map(|_| StandardCommandBufferAllocator::new(device.clone(), Default::default()))
+            .collect();
+
+        Self {
+            device,
+            fences: vec![None; count],
+            command_buffer_allocators,
+            current: 0,
+            previous: 0,
+        }
+    }
+
+    /// 현재 프레임 슬롯 번호 (0..frames_in_flight)
+    pub fn frame_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn frames_in_flight(&self) -> usize {
+        self.fences.len()
+    }
+
+    /// 현재 프레임 전용 command buffer 할당자
+    pub fn command_buffer_allocator(&self) -> &StandardCommandBufferAllocator {
+        &self.command_buffer_allocators[self.current]
+    }
+
+    /// 현재 슬롯을 마지막으로 사용한 프레임이 GPU에서 끝날 때까지 대기
+    pub fn wait_current(&mut self) {
+        if let Some(fence) = &self.fences[self.current] {
+            if let Err(e) = fence.wait(None) {
+                warn!("프레임 펜스 대기 실패: {e}");
+            }
+        }
+    }
+
+    /// 다음 제출이 이어 붙을 이전 프레임의 future
+    pub fn previous_future(&mut self) -> Box<dyn GpuFuture> {
+        match self.fences[self.previous].clone() {
+            Some(fence) => fence.boxed(),
+            None => {
+                let mut now = sync::now(self.device.clone());
+                now.cleanup_finished();
+                now.boxed()
+            }
+        }
+    }
+
+    /// 현재 프레임의 펜스를 기록하고 다음 슬롯으로 이동
+    ///
+    /// 제출에 실패했다면 `None`을 넘겨 슬롯을 비웁니다.
+    pub fn finish_frame(&mut self, fence: Option<FenceSignalFuture<Box<dyn GpuFuture>>>) {
+        self.fences[self.current] = fence.map(Arc::new);
+        self.previous = self.current;
+        self.current = (self.current + 1) % self.fences.len();
+    }
+
+    /// 마지막으로 제출한 프레임의 펜스 (`TextRenderer::frame_submitted`에 넘김)
+    pub fn last_fence(&self) -> Option<FrameFence> {
+        self.fences[self.previous].clone()
+    }
+
+    /// 모든 프레임이 끝날 때까지 대기 (리소스 재생성 전 등)
+    pub fn wait_all(&mut self) {
+        for fence in self.fences.iter().flatten() {
+            if let Err(e) = fence.wait(None) {
+                warn!("프레임 펜스 대기 실패: {e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use vulkano::{
+    device::{physical::PhysicalDevice, DeviceExtensions},
+    instance::InstanceExtensions,
+    swapchain::{FullScreenExclusive, Swapchain, SwapchainCreateInfo, Win32Monitor},
+};
+use tracing::{info, warn};
+use winit::window::{Fullscreen, Window};
+use transparent_text_vulkan::RendererError;
+
+/// F11로 켜고 끄는 전체 화면
+///
+/// 기본은 창이 있는 모니터를 덮는 테두리 없는 전체 화면이고, `--exclusive-fullscreen`이면
+/// (Windows, `VK_EXT_full_screen_exclusive`) 스왑체인을 앱이 제어하는 독점 모드로 다시 만들어 합성기를 거치지 않고 표시합니다.
+/// 독점 모드는 스왑체인마다 얻어야 하므로 스왑체인을 다시 만들 때마다 `acquire`를 부릅니다.
+pub struct FullscreenToggle {
+    // 독점 전체 화면을 쓸 수 있는지 (요청했고 Windows와 장치가 지원)
+    exclusive: bool,
+    active: bool,
+}
+
+impl FullscreenToggle {
+    pub fn new(exclusive: bool) -> Self {
+        Self {
+            exclusive,
+            active: false,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// 전체 화면을 켜거나 끔 (창 크기가 바뀌므로 스왑체인은 다음 프레임에 다시 만듦)
+    pub fn toggle(&mut self, window: &Window) {
+        self.active = !self.active;
+        window.set_fullscreen(
+            self.active
+                .then(|| Fullscreen::Borderless(window.current_monitor())),
+        );
+        info!(
+            "전체 화면: {}",
+            match (self.active, self.exclusive) {
+                (false, _) => "꺼짐",
+                (true, false) => "테두리 없음",
+                (true, true) => "독점",
+            }
+        );
+    }
+
+    /// 독점 모드를 켜거나 껐으면 그 설정으로 스왑체인을 다시 만듦 (크기는 뒤에서 `resize_swapchain`으로 맞춤)
+    pub fn switch_exclusive(
+        &self,
+        window: &Window,
+        swapchain: &Arc<Swapchain>,
+    ) -> Result<Option<Arc<Swapchain>>, RendererError> {
+        if !self.exclusive {
+            return Ok(None);
+        }
+        let (full_screen_exclusive, win32_monitor) = match self.active {
+            true => match win32_monitor(window) {
+                Some(monitor) => (FullScreenExclusive::ApplicationControlled, Some(monitor)),
+                None => return Ok(None),
+            },
+            false => (FullScreenExclusive::Default, None),
+        };
+        let create_info = swapchain.create_info();
+        if create_info.full_screen_exclusive == full_screen_exclusive {
+            return Ok(None);
+        }
+        let (swapchain, _) = swapchain
+            .recreate(SwapchainCreateInfo {
+                full_screen_exclusive,
+                win32_monitor,
+                ..create_info
+            })
+            .map_err(RendererError::Swapchain)?;
+        Ok(Some(swapchain))
+    }
+
+    /// 독점 전체 화면이면 새 스왑체인의 독점 모드를 얻음 (실패하면 테두리 없는 전체 화면으로 그림)
+    pub fn acquire(&self, swapchain: &Swapchain) {
+        if !self.active
+            || swapchain.create_info().full_screen_exclusive
+                != FullScreenExclusive::ApplicationControlled
+        {
+            return;
+        }
+        if let Err(e) = swapchain.acquire_full_screen_exclusive_mode() {
+            warn!("독점 전체 화면을 얻지 못해 테두리 없는 전체 화면으로 그립니다: {e}");
+        }
+    }
+}
+
+/// 독점 전체 화면에 필요한 instance 확장 (`requested`이고 Windows일 때만, 지원하지 않으면 켜지 않음)
+pub fn exclusive_instance_extensions(requested: bool) -> InstanceExtensions {
+    InstanceExtensions {
+        khr_get_surface_capabilities2: requested && cfg!(windows),
+        ..InstanceExtensions::empty()
+    }
+}
+
+/// `requested`이고 장치가 지원하면 `VK_EXT_full_screen_exclusive`를 켜고 true
+///
+/// 지원하지 않으면(Windows가 아니거나 instance 확장이 없는 경우 포함) 경고하고 테두리 없는 전체 화면만 씁니다.
+pub fn enable_exclusive_fullscreen(
+    device: &PhysicalDevice,
+    requested: bool,
+    extensions: &mut DeviceExtensions,
+) -> bool {
+    if !requested {
+        return false;
+    }
+    let instance_extensions = device.instance().enabled_extensions();
+    if !cfg!(windows)
+        || !instance_extensions.khr_get_surface_capabilities2
+        || !device.supported_extensions().ext_full_screen_exclusive
+    {
+        warn!("독점 전체 화면을 지원하지 않아 F11은 테두리 없는 전체 화면으로 전환합니다");
+        return false;
+    }
+    extensions.ext_full_screen_exclusive = true;
+    true
+}
+
+// 창이 있는 모니터의 HMONITOR (독점 모드를 앱이 제어할 때 필요)
+#[cfg(windows)]
+fn win32_monitor(window: &Window) -> Option<Win32Monitor> {
+    use winit::platform::windows::MonitorHandleExtWindows;
+    let monitor = window.current_monitor()?;
+    // SAFETY: winit이 돌려준 모니터 핸들은 모니터가 연결되어 있는 동안 유효함
+    Some(unsafe { Win32Monitor::new(monitor.hmonitor() as *const std::ffi::c_void) })
+}
+
+#[cfg(not(windows))]
+fn win32_monitor(_window: &Window) -> Option<Win32Monitor> {
+    None
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use global_hotkey::{hotkey::HotKey, GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+use tracing::warn;
+
+use crate::action::Action;
+
+/// 기본 전역 단축키 (config.toml의 `[global_keys]`로 동작별로 바꿀 수 있음)
+///
+/// 창에 포커스가 없어도 동작하도록 운영체제에 등록하므로, 다른 프로그램과 겹치지 않게 Ctrl+Alt 조합을 씁니다.
+pub const DEFAULT_GLOBAL_KEYS: [(Action, &str); 14] = [
+    (Action::Opacity10, "ctrl+alt+Digit1"),
+    (Action::Opacity20, "ctrl+alt+Digit2"),
+    (Action::Opacity30, "ctrl+alt+Digit3"),
+    (Action::Opacity40, "ctrl+alt+Digit4"),
+    (Action::Opacity50, "ctrl+alt+Digit5"),
+    (Action::Opacity60, "ctrl+alt+Digit6"),
+    (Action::Opacity70, "ctrl+alt+Digit7"),
+    (Action::Opacity80, "ctrl+alt+Digit8"),
+    (Action::Opacity90, "ctrl+alt+Digit9"),
+    (Action::Opacity100, "ctrl+alt+Digit0"),
+    (Action::NextEffect, "ctrl+alt+KeyE"),
+    (Action::Visibility, "ctrl+alt+KeyH"),
+    (Action::ClickThrough, "ctrl+alt+KeyC"),
+    (Action::Quit, "ctrl+alt+KeyQ"),
+];
+
+/// 운영체제에 등록한 전역 단축키
+///
+/// 눌림 이벤트는 global-hotkey의 전역 채널로 오므로 이벤트 루프에서 `poll`로 가져갑니다.
+/// Linux에서는 X11에서만 동작합니다.
+pub struct GlobalKeys {
+    manager: GlobalHotKeyManager,
+    registered: Vec<HotKey>,
+    actions: HashMap<u32, Action>,
+}
+
+impl GlobalKeys {
+    pub fn new() -> global_hotkey::Result<Self> {
+        Ok(Self {
+            manager: GlobalHotKeyManager::new()?,
+            registered: Vec::new(),
+            actions: HashMap::new(),
+        })
+    }
+
+    /// 기본 단축키에 동작별 단축키를 덮어써 다시 등록
+    /// (형식이 틀리거나 다른 프로그램이 이미 쓰는 단축키는 출력하고 건너뜀)
+    pub fn set_bindings(&mut self, overrides: &HashMap<Action, String>) {
+        if let Err(e) = self.manager.unregister_all(&self.registered) {
+            warn!("전역 단축키 해제 실패: {e}");
+        }
+        self.registered.clear();
+        self.actions.clear();
+
+        let mut bindings: HashMap<Action, &str> = HashMap::from(DEFAULT_GLOBAL_KEYS);
+        bindings.extend(
+            overrides
+                .iter()
+                .map(|(&action, key)| (action, key.as_str())),
+        );
+        for (action, key) in bindings {
+            let hotkey = match key.parse::<HotKey>() {
+                Ok(hotkey) => hotkey,
+                Err(e) => {
+                    warn!("전역 단축키 형식 오류 ({key}): {e}");
+                    continue;
+                }
+            };
+            match self.manager.register(hotkey) {
+                Ok(()) => {
+                    self.registered.push(hotkey);
+                    self.actions.insert(hotkey.id(), action);
+                }
+                Err(e) => warn!("전역 단축키를 등록할 수 없습니다 ({key}): {e}"),
+            }
+        }
+    }
+
+    /// 마지막 확인 이후 눌린 단축키의 동작
+    pub fn poll(&self) -> impl Iterator<Item = Action> + '_ {
+        GlobalHotKeyEvent::receiver()
+            .try_iter()
+            .filter(|event| event.state == HotKeyState::Pressed)
+            .filter_map(|event| self.actions.get(&event.id).copied())
+    }
+}
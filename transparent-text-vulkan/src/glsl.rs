@@ -0,0 +1,37 @@
+use std::path::Path;
+use std::sync::Arc;
+use vulkano::{device::Device, shader::ShaderModule};
+use shaderc::{CompileOptions, Compiler, EnvVersion, ShaderKind, TargetEnv};
+
+use crate::{error::RendererError, shaders};
+
+// GLSL 소스를 런타임에 SPIR-V로 컴파일해 셰이더 모듈 생성
+// (vulkano-shaders와 같은 대상 환경: Vulkan 1.0)
+pub(crate) fn compile(
+    device: &Arc<Device>,
+    source: &str,
+    kind: ShaderKind,
+    name: &'static str,
+) -> Result<Arc<ShaderModule>, RendererError> {
+    let compiler =
+        Compiler::new().ok_or_else(|| RendererError::shader(name, "shaderc 컴파일러 생성 실패"))?;
+    let mut options = CompileOptions::new()
+        .ok_or_else(|| RendererError::shader(name, "shaderc 옵션 생성 실패"))?;
+    options.set_target_env(TargetEnv::Vulkan, EnvVersion::Vulkan1_0 as u32);
+
+    let artifact = compiler
+        .compile_into_spirv(source, kind, name, "main", Some(&options))
+        .map_err(|e| RendererError::shader(name, e))?;
+
+    shaders::load_spirv(device, artifact.as_binary(), name)
+}
+
+// 확장자로 셰이더 스테이지 판별 (.vert, .frag, .comp)
+pub(crate) fn shader_kind(path: &Path) -> Option<ShaderKind> {
+    match path.extension()?.to_str()? {
+        "vert" => Some(ShaderKind::Vertex),
+        "frag" => Some(ShaderKind::Fragment),
+        "comp" => Some(ShaderKind::Compute),
+        _ => None,
+    }
+}
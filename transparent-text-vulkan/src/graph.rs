@@ -0,0 +1,280 @@
+use std::{collections::HashMap, sync::Arc};
+use vulkano::{
+    command_buffer::AutoCommandBufferBuilder,
+    format::Format,
+    image::{view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage, SampleCount},
+    memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+};
+use tracing::debug_span;
+
+use crate::error::RendererError;
+
+/// 그래프 안에서 이미지를 가리키는 번호 (`import`나 `transient`로 받음)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct GraphImage(usize);
+
+/// 그래프가 할당하는 임시 이미지의 설명 (같은 설명이면 이미지를 다시 씀)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct ImageDesc {
+    pub format: Format,
+    pub extent: [u32; 2],
+    pub usage: ImageUsage,
+    pub samples: SampleCount,
+}
+
+enum ImageSource {
+    // 그래프 밖에서 만든 이미지 (레이어 이미지, 블룸 결과처럼 그래프가 끝난 뒤에도 쓰는 것)
+    Imported(Arc<ImageView>),
+    Transient(ImageDesc),
+}
+
+type RecordFn<'a, L, C> = Box<
+    dyn FnOnce(
+            &mut AutoCommandBufferBuilder<L>,
+            &GraphResources,
+            &mut C,
+        ) -> Result<(), RendererError>
+        + 'a,
+>;
+
+struct Pass<'a, L, C> {
+    name: &'static str,
+    reads: Vec<GraphImage>,
+    writes: Vec<GraphImage>,
+    record: RecordFn<'a, L, C>,
+}
+
+/// 여러 패스로 이루어진 효과를 선언하고 순서대로 기록하는 작은 렌더 그래프
+///
+/// 패스마다 읽고 쓰는 이미지를 선언하면 `execute`가 출력(`output`)에 쓰이지 않는 패스를 빼고,
+/// 임시 이미지를 `TransientImages`에서 할당(수명이 겹치지 않으면 같은 이미지를 나눠 씀)한 뒤
+/// 선언한 순서대로 기록합니다. 패스 사이의 장벽과 레이아웃 전환은 `AutoCommandBufferBuilder`가
+/// 이미지 사용을 보고 넣습니다. `C`는 패스가 함께 빌려 쓰는 값입니다 (`TextRenderer` 등).
+pub(crate) struct RenderGraph<'a, L, C> {
+    images: Vec<ImageSource>,
+    outputs: Vec<GraphImage>,
+    passes: Vec<Pass<'a, L, C>>,
+}
+
+impl<'a, L, C> RenderGraph<'a, L, C> {
+    pub fn new() -> Self {
+        Self {
+            images: Vec::new(),
+            outputs: Vec::new(),
+            passes: Vec::new(),
+        }
+    }
+
+    /// 그래프 밖의 이미지를 씀
+    pub fn import(&mut self, view: Arc<ImageView>) -> GraphImage {
+        self.images.push(ImageSource::Imported(view));
+        GraphImage(self.images.len() - 1)
+    }
+
+    /// 그래프가 할당하는 임시 이미지 (먼저 쓰는 패스가 있어야 읽을 수 있음)
+    pub fn transient(&mut self, desc: ImageDesc) -> GraphImage {
+        self.images.push(ImageSource::Transient(desc));
+        GraphImage(self.images.len() - 1)
+    }
+
+    /// 그래프가 끝난 뒤 쓰는 이미지 (이 이미지에 이어지는 패스만 기록함)
+    pub fn output(&mut self, image: GraphImage) {
+        self.outputs.push(image);
+    }
+
+    /// `reads`를 읽고 `writes`에 쓰는 패스 (선언한 순서대로 기록)
+    pub fn add_pass(
+        &mut self,
+        name: &'static str,
+        reads: &[GraphImage],
+        writes: &[GraphImage],
+        record: impl FnOnce(
+                &mut AutoCommandBufferBuilder<L>,
+                &GraphResources,
+                &mut C,
+            ) -> Result<(), RendererError>
+            + 'a,
+    ) {
+        self.passes.push(Pass {
+            name,
+            reads: reads.to_vec(),
+            writes: writes.to_vec(),
+            record: Box::new(record),
+        });
+    }
+
+    /// 출력에 쓰이는 패스를 순서대로 기록
+    pub fn execute(
+        self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        transients: &mut TransientImages,
+        context: &mut C,
+    ) -> Result<(), RendererError> {
+        // 뒤에서부터 출력에 이어지는 패스만 남기고, 그 패스가 읽는 이미지도 필요한 것으로 표시
+        let mut live = vec![false; self.images.len()];
+        for output in &self.outputs {
+            live[output.0] = true;
+        }
+        let mut kept = vec![false; self.passes.len()];
+        for (index, pass) in self.passes.iter().enumerate().rev() {
+            if pass.writes.iter().any(|image| live[image.0]) {
+                kept[index] = true;
+                for image in &pass.reads {
+                    live[image.0] = true;
+                }
+            }
+        }
+        let passes: Vec<_> = self
+            .passes
+            .into_iter()
+            .zip(kept)
+            .filter_map(|(pass, kept)| kept.then_some(pass))
+            .collect();
+
+        // 임시 이미지마다 처음과 마지막으로 쓰는 패스 (출력이면 끝까지)
+        let mut lifetimes: Vec<Option<(usize, usize)>> = vec![None; self.images.len()];
+        for (index, pass) in passes.iter().enumerate() {
+            for image in &pass.reads {
+                if matches!(self.images[image.0], ImageSource::Transient(_))
+                    && lifetimes[image.0].is_none()
+                {
+                    return Err(RendererError::command(format!(
+                        "{} 패스가 아직 쓰지 않은 임시 이미지를 읽습니다",
+                        pass.name
+                    )));
+                }
+            }
+            for image in pass.reads.iter().chain(&pass.writes) {
+                let lifetime = lifetimes[image.0].get_or_insert((index, index));
+                lifetime.1 = index;
+            }
+        }
+        for output in &self.outputs {
+            if let Some(lifetime) = &mut lifetimes[output.0] {
+                lifetime.1 = passes.len();
+            }
+        }
+
+        transients.begin();
+        let mut views: Vec<Option<Arc<ImageView>>> = vec![None; self.images.len()];
+        for (image, source) in self.images.iter().enumerate() {
+            if let ImageSource::Imported(view) = source {
+                views[image] = Some(view.clone());
+            }
+        }
+        let mut resources = GraphResources { views };
+
+        for (index, pass) in passes.into_iter().enumerate() {
+            for (image, source) in self.images.iter().enumerate() {
+                if let (ImageSource::Transient(desc), Some((first, _))) = (source, lifetimes[image])
+                {
+                    if first == index {
+                        resources.views[image] = Some(transients.acquire(*desc)?);
+                    }
+                }
+            }
+
+            let _span = debug_span!("render_graph_pass", name = pass.name).entered();
+            (pass.record)(builder, &resources, context)?;
+
+            // 수명이 끝난 임시 이미지는 뒤 패스가 다시 씀
+            for (image, source) in self.images.iter().enumerate() {
+                if let (ImageSource::Transient(desc), Some((_, last))) = (source, lifetimes[image])
+                {
+                    if last == index {
+                        if let Some(view) = resources.views[image].take() {
+                            transients.release(*desc, view);
+                        }
+                    }
+                }
+            }
+        }
+        transients.finish();
+
+        Ok(())
+    }
+}
+
+/// 패스를 기록할 때 그래프 이미지의 실제 이미지 뷰
+pub(crate) struct GraphResources {
+    views: Vec<Option<Arc<ImageView>>>,
+}
+
+impl GraphResources {
+    pub fn view(&self, image: GraphImage) -> Result<&Arc<ImageView>, RendererError> {
+        self.views[image.0]
+            .as_ref()
+            .ok_or_else(|| RendererError::command("패스가 선언하지 않은 그래프 이미지입니다"))
+    }
+}
+
+/// 렌더 그래프의 임시 이미지 풀
+///
+/// 프레임마다 같은 설명의 이미지를 같은 순서로 돌려주므로, 크기가 그대로면
+/// 패스가 만든 디스크립터 셋을 다시 쓸 수 있습니다. 한 번의 `execute`에서 쓰지 않은 이미지는 버립니다.
+pub(crate) struct TransientImages {
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    images: HashMap<ImageDesc, Vec<Arc<ImageView>>>,
+    // 이번 execute에서 설명마다 꺼낸 수와 수명이 끝나 다시 쓸 수 있는 이미지
+    used: HashMap<ImageDesc, usize>,
+    free: HashMap<ImageDesc, Vec<Arc<ImageView>>>,
+}
+
+impl TransientImages {
+    pub fn new(memory_allocator: Arc<StandardMemoryAllocator>) -> Self {
+        Self {
+            memory_allocator,
+            images: HashMap::new(),
+            used: HashMap::new(),
+            free: HashMap::new(),
+        }
+    }
+
+    fn begin(&mut self) {
+        self.used.clear();
+        self.free.clear();
+    }
+
+    fn acquire(&mut self, desc: ImageDesc) -> Result<Arc<ImageView>, RendererError> {
+        if let Some(view) = self.free.get_mut(&desc).and_then(Vec::pop) {
+            return Ok(view);
+        }
+
+        let used = self.used.entry(desc).or_default();
+        let images = self.images.entry(desc).or_default();
+        if *used == images.len() {
+            let image = Image::new(
+                self.memory_allocator.clone(),
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format: desc.format,
+                    extent: [desc.extent[0], desc.extent[1], 1],
+                    usage: desc.usage,
+                    samples: desc.samples,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .map_err(RendererError::texture)?;
+            images.push(ImageView::new_default(image).map_err(RendererError::texture)?);
+        }
+        *used += 1;
+        Ok(images[*used - 1].clone())
+    }
+
+    fn release(&mut self, desc: ImageDesc, view: Arc<ImageView>) {
+        self.free.entry(desc).or_default().push(view);
+    }
+
+    // 이번에 쓰지 않은 설명과 남는 이미지는 버림 (대상 크기가 바뀐 뒤 등)
+    fn finish(&mut self) {
+        let used = &self.used;
+        self.images.retain(|desc, images| match used.get(desc) {
+            Some(&count) => {
+                images.truncate(count);
+                true
+            }
+            None => false,
+        });
+    }
+}
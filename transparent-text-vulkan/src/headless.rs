@@ -0,0 +1,416 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+use vulkano::{
+    command_buffer::{
+        allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+    },
+    device::{physical::PhysicalDevice, Device, DeviceExtensions, Features, Queue, QueueFlags},
+    format::Format,
+    image::{Image, ImageCreateInfo, ImageType, ImageUsage},
+    instance::{Instance, InstanceCreateFlags, InstanceCreateInfo},
+    memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    pipeline::graphics::viewport::Viewport,
+    sync::{self, GpuFuture},
+    VulkanLibrary,
+};
+use glam::{Mat4, Vec3};
+use tracing::{info, warn};
+use transparent_text_vulkan::{
+    Antialiasing, ImageCapture, LayoutOptions, RendererConfig, RendererError, Screenshot,
+    TextBackground, TextLayer, TextParams, TextRenderer,
+};
+use vulkan_context::{
+    choose_device, create_device, enumerate_devices, select_sample_count, GpuSelector,
+};
+
+use crate::{
+    app_config::{AppConfig, DEFAULT_CONFIG_PATH},
+    create_text_renderer, read_config,
+    render_path::{enable_dynamic_rendering, RenderPath},
+    styled_markup, wrap_options, Args,
+};
+
+/// `--out`을 생략했을 때 저장할 파일 (`--frames`면 `text-0000.png`부터 번호를 붙임)
+const DEFAULT_OUT: &str = "text.png";
+
+/// `--out -`: 프레임을 표준 출력에 raw RGBA로 씀 (ffmpeg 입력용)
+const STDOUT_OUT: &str = "-";
+
+// `--fps`가 없거나 0 이하일 때
+const DEFAULT_FPS: f32 = 30.0;
+
+// `--text`, `--text-file`, 설정 파일 모두 텍스트가 없을 때
+const DEFAULT_TEXT: &str = "투명 텍스트";
+
+/// `--size`가 없을 때 텍스트 크기에 더하는 여백 (픽셀, 외곽선, 그림자, 발광이 잘리지 않도록)
+pub const EXPORT_PADDING: f32 = 32.0;
+
+// 스크린샷과 같은 sRGB 출력 (블렌딩은 선형 공간에서)
+const EXPORT_FORMAT: Format = Format::R8G8B8A8_SRGB;
+
+/// `--size 640x200` 형식의 이미지 크기 (물리 픽셀)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageSize(pub [u32; 2]);
+
+impl FromStr for ImageSize {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let length = |text: &str| text.trim().parse::<u32>().ok().filter(|&n| n > 0);
+        value
+            .split_once(['x', 'X'])
+            .and_then(|(width, height)| Some(ImageSize([length(width)?, length(height)?])))
+            .ok_or_else(|| format!("크기는 640x200 형식이어야 합니다: {value}"))
+    }
+}
+
+/// `--headless`: 창과 스왑체인 없이 텍스트를 오프스크린 이미지에 그려 PNG로 저장
+///
+/// 설정 파일의 텍스트, 폰트, 색, 효과를 쓰고 `--text`/`--text-file`이 설정 파일의 텍스트보다 우선합니다.
+/// `--size`가 없으면 텍스트 크기에 여백을 더한 크기로 그리고 줄바꿈하지 않습니다.
+/// `--frames`를 주면 애니메이션을 고정 시간 간격으로 여러 장 그립니다 ([`export_frames`]).
+/// 표준 출력은 프레임 데이터에 쓸 수 있으므로 진행 메시지는 표준 오류로 출력합니다.
+pub fn export(args: &Args) -> Result<(), RendererError> {
+    let config = export_config(args);
+    let mut target = HeadlessTarget::new(args, &config)?;
+    let text = export_text(args, &config);
+    let size = target.set_text(&text, &config, args.size)?;
+    info!("이미지 크기: {}x{}", size[0], size[1]);
+
+    let params = config_params(&config, size);
+    let out = args
+        .out
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_OUT));
+    if let Some(frames) = args.frames {
+        let fps = args.fps.filter(|&fps| fps > 0.0).unwrap_or(DEFAULT_FPS);
+        return export_frames(&mut target, params, frames, fps, &out);
+    }
+
+    target.render(&params)?.save_png(&out)?;
+    info!("저장: {}", out.display());
+
+    Ok(())
+}
+
+/// `frames`장을 `1 / fps`초 간격의 애니메이션 시간으로 그려 번호 붙은 PNG로 저장
+///
+/// `out`이 `-`이면 PNG 대신 프레임마다 straight alpha RGBA8 픽셀을 이어서 표준 출력에 씁니다.
+///
+/// ```bash
+/// transparent-text-vulkan --headless --frames 90 --size 640x200 --out - \
+///     | ffmpeg -f rawvideo -pix_fmt rgba -s 640x200 -r 30 -i - text.webm
+/// ```
+pub fn export_frames(
+    target: &mut HeadlessTarget,
+    mut params: TextParams,
+    frames: u32,
+    fps: f32,
+    out: &Path,
+) -> Result<(), RendererError> {
+    let to_stdout = out == Path::new(STDOUT_OUT);
+    let stdout_error = |e: std::io::Error| RendererError::Capture {
+        path: STDOUT_OUT.to_owned(),
+        source: e.into(),
+    };
+    let mut stdout = std::io::stdout().lock();
+
+    for index in 0..frames {
+        params.time = index as f32 / fps;
+        let frame = target.render(&params)?;
+        if to_stdout {
+            stdout.write_all(&frame.pixels).map_err(stdout_error)?;
+        } else {
+            frame.save_png(numbered_path(out, index))?;
+        }
+    }
+    stdout.flush().map_err(stdout_error)?;
+
+    if to_stdout {
+        info!("표준 출력: {frames}프레임 ({fps} fps)");
+    } else {
+        info!(
+            "저장: {} ~ {} ({frames}프레임, {fps} fps)",
+            numbered_path(out, 0).display(),
+            numbered_path(out, frames.saturating_sub(1)).display()
+        );
+    }
+    Ok(())
+}
+
+/// 창 없이 텍스트를 그리는 장치와 오프스크린 대상
+pub struct HeadlessTarget {
+    device: Arc<Device>,
+    queue: Arc<Queue>,
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    command_buffer_allocator: StandardCommandBufferAllocator,
+    // 출력 이미지에 합성하는 render pass와 framebuffer (또는 dynamic rendering 이미지 뷰)
+    render_path: RenderPath,
+    layer: TextLayer,
+    text_renderer: TextRenderer,
+    // set_text가 텍스트 크기에 맞춰 만드는 출력 이미지의 viewport와 캡처
+    output: Option<(Viewport, ImageCapture)>,
+}
+
+impl HeadlessTarget {
+    pub fn new(args: &Args, config: &AppConfig) -> Result<Self, RendererError> {
+        // 표시할 surface가 없으므로 surface 확장 없이 시작
+        let library = VulkanLibrary::new()?;
+        let instance = Instance::new(
+            library,
+            InstanceCreateInfo {
+                flags: InstanceCreateFlags::ENUMERATE_PORTABILITY,
+                ..Default::default()
+            },
+        )
+        .map_err(RendererError::Instance)?;
+
+        let (physical_device, queue_family_index) =
+            select_headless_device(&instance, args.gpu.as_ref())?;
+        info!(
+            gpu = %physical_device.properties().device_name,
+            device_type = ?physical_device.properties().device_type,
+            "사용 중인 GPU"
+        );
+
+        let mut device_extensions = DeviceExtensions::empty();
+        let mut device_features = Features {
+            dual_src_blend: physical_device.supported_features().dual_src_blend,
+            ..Features::empty()
+        };
+        let dynamic_rendering = enable_dynamic_rendering(
+            &physical_device,
+            args.dynamic_rendering || config.dynamic_rendering.unwrap_or(false),
+            &mut device_extensions,
+            &mut device_features,
+        );
+        let (device, queues) = create_device(
+            physical_device,
+            queue_family_index,
+            device_extensions,
+            device_features,
+        )?;
+        let queue = queues.graphics.clone();
+
+        let renderer_config = RendererConfig {
+            antialiasing: if args.subpixel {
+                Antialiasing::Subpixel
+            } else {
+                Antialiasing::Grayscale
+            },
+            mipmaps: config.mipmaps.unwrap_or(true),
+            // 한 프레임씩 그리고 기다리므로 한 개면 충분
+            frames_in_flight: 1,
+            ..RendererConfig::default()
+        };
+        let samples = select_sample_count(
+            device.physical_device(),
+            args.msaa.or(config.msaa).unwrap_or(1),
+        );
+        let render_path = RenderPath::new(&device, EXPORT_FORMAT, samples, dynamic_rendering)?;
+        let layer = TextLayer::new(device.clone(), render_path.target())?;
+        let text_renderer = create_text_renderer(
+            &device,
+            &queues,
+            layer.render_target(),
+            args,
+            config,
+            renderer_config,
+        )?;
+
+        Ok(Self {
+            memory_allocator: Arc::new(StandardMemoryAllocator::new_default(device.clone())),
+            command_buffer_allocator: StandardCommandBufferAllocator::new(
+                device.clone(),
+                Default::default(),
+            ),
+            device,
+            queue,
+            render_path,
+            layer,
+            text_renderer,
+            output: None,
+        })
+    }
+
+    /// 텍스트를 바꾸고 출력 이미지를 `size`(없으면 텍스트 크기 + 여백)로 다시 만듦
+    pub fn set_text(
+        &mut self,
+        text: &str,
+        config: &AppConfig,
+        size: Option<ImageSize>,
+    ) -> Result<[u32; 2], RendererError> {
+        let options = match size {
+            Some(ImageSize(size)) => wrap_options(
+                size.map(|length| length as f32),
+                false,
+                LayoutOptions::default(),
+            ),
+            None => LayoutOptions::default(),
+        };
+        self.text_renderer.set_layout_options(options)?;
+        self.text_renderer
+            .set_styled_text(&styled_markup(text, config.color.map(|color| color.0)))?;
+
+        let size = size.map_or_else(
+            || {
+                self.text_renderer
+                    .text_extent()
+                    .map(|length| (length + EXPORT_PADDING * 2.0).ceil() as u32)
+            },
+            |ImageSize(size)| size,
+        );
+
+        let image = Image::new(
+            self.memory_allocator.clone(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: EXPORT_FORMAT,
+                extent: [size[0], size[1], 1],
+                usage: ImageUsage::COLOR_ATTACHMENT | ImageUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .map_err(|e| RendererError::Texture(e.into()))?;
+        let mut viewport = Viewport::default();
+        self.render_path.resize(
+            std::slice::from_ref(&image),
+            &self.memory_allocator,
+            &mut viewport,
+        )?;
+        let capture = ImageCapture::new(self.memory_allocator.clone(), image)?;
+        self.output = Some((viewport, capture));
+
+        Ok(size)
+    }
+
+    /// 투명한 배경에 텍스트를 그리고 끝날 때까지 기다린 뒤 픽셀을 읽음
+    pub fn render(&mut self, params: &TextParams) -> Result<Screenshot, RendererError> {
+        let Some((viewport, capture)) = &self.output else {
+            return Err(RendererError::Texture("출력 이미지가 없습니다".into()));
+        };
+        let [width, height] = viewport.extent.map(|length| length as u32);
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &self.command_buffer_allocator,
+            self.queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .map_err(|e| RendererError::Command(e.into()))?;
+
+        self.layer.draw(
+            &mut builder,
+            &mut self.text_renderer,
+            params,
+            [width, height],
+        )?;
+
+        self.render_path
+            .begin(&mut builder, 0, [0.0, 0.0, 0.0, 0.0])?;
+        builder
+            .set_viewport(0, [viewport.clone()].into_iter().collect())
+            .map_err(|e| RendererError::Command(e.into()))?;
+
+        self.layer.composite(&mut builder, params.opacity)?;
+
+        self.render_path.end(&mut builder)?;
+        capture.record(&mut builder)?;
+
+        let command_buffer = builder
+            .build()
+            .map_err(|e| RendererError::Command(e.into()))?;
+        // 새 글리프 업로드가 끝난 뒤 실행
+        self.text_renderer
+            .take_upload_future()
+            .unwrap_or_else(|| sync::now(self.device.clone()).boxed())
+            .then_execute(self.queue.clone(), command_buffer)
+            .map_err(|e| RendererError::Command(e.into()))?
+            .then_signal_fence_and_flush()
+            .map_err(|e| RendererError::Command(e.into()))?
+            .wait(None)
+            .map_err(|e| RendererError::Command(e.into()))?;
+
+        capture.read()
+    }
+}
+
+/// `--config`나 기본 설정 파일 (기본 파일이 없으면 조용히 기본 설정)
+pub fn export_config(args: &Args) -> AppConfig {
+    let config_path = Path::new(args.config.as_deref().unwrap_or(DEFAULT_CONFIG_PATH));
+    if args.config.is_none() && !config_path.exists() {
+        AppConfig::default()
+    } else {
+        read_config(config_path)
+    }
+}
+
+/// `--text`, `--text-file`, 설정 파일 순서 (텍스트 파일을 읽지 못하면 출력하고 다음 것을 씀)
+pub fn export_text(args: &Args, config: &AppConfig) -> String {
+    if let Some(text) = &args.text {
+        return text.clone();
+    }
+    if let Some(path) = &args.text_file {
+        match std::fs::read_to_string(path) {
+            Ok(text) => return text.trim_end_matches(['\r', '\n']).to_owned(),
+            Err(e) => warn!("텍스트 파일을 읽을 수 없습니다 ({path}): {e}"),
+        }
+    }
+    config
+        .text
+        .clone()
+        .unwrap_or_else(|| DEFAULT_TEXT.to_owned())
+}
+
+// out의 파일 이름 뒤에 프레임 번호를 붙임 (text.png → text-0000.png)
+fn numbered_path(out: &Path, index: u32) -> PathBuf {
+    let stem = out.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = out
+        .extension()
+        .map_or("png".into(), |e| e.to_string_lossy());
+    out.with_file_name(format!("{stem}-{index:04}.{extension}"))
+}
+
+/// 설정 파일의 투명도와 효과를 이미지 가운데에 그리는 파라미터로
+pub fn config_params(config: &AppConfig, size: [u32; 2]) -> TextParams {
+    let defaults = TextParams::default();
+    let [width, height] = size.map(|length| length as f32);
+    TextParams {
+        opacity: config
+            .opacity
+            .map_or(1.0, |opacity| opacity.clamp(0.0, 1.0)),
+        effects: config.effects.map_or(defaults.effects, |effects| effects.0),
+        outline_color: config.outline_color.map_or(defaults.outline_color, |c| c.0),
+        outline_width: config.outline_width.unwrap_or(defaults.outline_width),
+        glow_color: config.glow_color.map_or(defaults.glow_color, |c| c.0),
+        glow_radius: config.glow_radius.unwrap_or(defaults.glow_radius),
+        shadow_color: config.shadow_color.map_or(defaults.shadow_color, |c| c.0),
+        shadow_blur: config.shadow_blur.unwrap_or(defaults.shadow_blur),
+        projection: TextParams::pixel_projection([width, height]),
+        transform: Mat4::from_translation(Vec3::new(width / 2.0, height / 2.0, 0.0)),
+        background: (config.background == Some(true)).then(TextBackground::default),
+        ..defaults
+    }
+}
+
+// 그래픽스 큐가 있는 장치 (surface 지원은 따지지 않음)
+fn select_headless_device(
+    instance: &Arc<Instance>,
+    gpu: Option<&GpuSelector>,
+) -> Result<(Arc<PhysicalDevice>, u32), RendererError> {
+    let candidates = enumerate_devices(instance)?
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, p)| {
+            p.queue_family_properties()
+                .iter()
+                .position(|q| q.queue_flags.intersects(QueueFlags::GRAPHICS))
+                .map(|i| (index, p, i as u32))
+        })
+        .collect();
+    Ok(choose_device(candidates, gpu)?)
+}
@@ -0,0 +1,73 @@
+use std::collections::VecDeque;
+
+/// 설정 파일에 `history_depth`가 없을 때 되돌릴 수 있는 변경 수
+pub const DEFAULT_HISTORY_DEPTH: usize = 100;
+
+/// 되돌리기/다시 실행 기록
+///
+/// 변경할 때마다 명령을 남기는 대신 상태를 통째로 기록합니다.
+/// `record`에 현재 상태를 넘기면 마지막으로 기록한 상태와 다를 때만 이전 상태를 쌓으므로,
+/// 텍스트가 키 입력, 제어 소켓, 파일 중 어디서 바뀌었는지 신경 쓰지 않아도 됩니다.
+pub struct History<T> {
+    current: Option<T>,
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    depth: usize,
+}
+
+impl<T: Clone + PartialEq> History<T> {
+    /// `depth`는 되돌릴 수 있는 최대 변경 수 (0이면 기록하지 않음)
+    pub fn new(depth: usize) -> Self {
+        Self {
+            current: None,
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            depth,
+        }
+    }
+
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+        self.trim();
+    }
+
+    /// 상태가 바뀌었으면 이전 상태를 되돌리기 목록에 넣음 (새 변경이 생기면 다시 실행 목록은 비움)
+    ///
+    /// 처음 호출할 때는 기준 상태로만 기억합니다.
+    pub fn record(&mut self, state: T) {
+        if self.current.as_ref() == Some(&state) {
+            return;
+        }
+        if let Some(previous) = self.current.replace(state) {
+            self.undo.push_back(previous);
+            self.redo.clear();
+            self.trim();
+        }
+    }
+
+    /// 마지막 변경 이전 상태 (되돌릴 변경이 없으면 None)
+    pub fn undo(&mut self) -> Option<&T> {
+        let state = self.undo.pop_back()?;
+        if let Some(current) = self.current.replace(state) {
+            self.redo.push(current);
+        }
+        self.current.as_ref()
+    }
+
+    /// 마지막으로 되돌린 변경 이후 상태 (다시 실행할 변경이 없으면 None)
+    pub fn redo(&mut self) -> Option<&T> {
+        let state = self.redo.pop()?;
+        if let Some(current) = self.current.replace(state) {
+            self.undo.push_back(current);
+            self.trim();
+        }
+        self.current.as_ref()
+    }
+
+    // 오래된 변경부터 버림
+    fn trim(&mut self) {
+        while self.undo.len() > self.depth {
+            self.undo.pop_front();
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use vulkano::{device::Device, shader::ShaderModule};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{error::RendererError, glsl};
+
+// 셰이더 디렉터리를 감시하고 GLSL 파일을 런타임에 SPIR-V로 컴파일
+// (파일 이름은 crate의 shaders/ 디렉터리와 같음)
+pub(crate) struct ShaderWatcher {
+    dir: PathBuf,
+    events: Receiver<notify::Result<notify::Event>>,
+    // 드롭되면 감시가 끝나므로 보관만 함
+    _watcher: RecommendedWatcher,
+}
+
+impl ShaderWatcher {
+    pub(crate) fn new(dir: impl Into<PathBuf>) -> Result<Self, RendererError> {
+        let dir = dir.into();
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)
+            .map_err(|e| RendererError::shader("hot reload", e))?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|e| RendererError::shader("hot reload", e))?;
+
+        Ok(Self {
+            dir,
+            events,
+            _watcher: watcher,
+        })
+    }
+
+    // 마지막 확인 이후 셰이더 파일이 생성/수정되었는지 (쌓인 이벤트는 모두 비움)
+    // 편집기가 임시 파일을 rename해 저장하는 경우도 create 이벤트로 잡힘
+    pub(crate) fn poll(&self) -> bool {
+        self.events.try_iter().fold(false, |changed, event| {
+            let touched = event.is_ok_and(|event| {
+                (event.kind.is_modify() || event.kind.is_create())
+                    && event
+                        .paths
+                        .iter()
+                        .any(|path| glsl::shader_kind(path).is_some())
+            });
+            changed || touched
+        })
+    }
+
+    pub(crate) fn load(
+        &self,
+        device: &Arc<Device>,
+        file_name: &'static str,
+    ) -> Result<Arc<ShaderModule>, RendererError> {
+        let path = self.dir.join(file_name);
+        let kind = glsl::shader_kind(&path)
+            .ok_or_else(|| RendererError::shader(file_name, "알 수 없는 셰이더 확장자"))?;
+        let source = fs::read_to_string(&path).map_err(|e| RendererError::shader(file_name, e))?;
+
+        glsl::compile(device, &source, kind, file_name)
+    }
+}
@@ -0,0 +1,457 @@
+use std::sync::Arc;
+use vulkano::{
+    buffer::BufferContents,
+    command_buffer::{
+        AutoCommandBufferBuilder, RenderPassBeginInfo, RenderingAttachmentInfo,
+        RenderingAttachmentResolveInfo, RenderingInfo, SubpassBeginInfo, SubpassContents,
+        SubpassEndInfo,
+    },
+    descriptor_set::{
+        allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
+    },
+    device::Device,
+    format::{ClearValue, Format},
+    image::{
+        sampler::{Sampler, SamplerCreateInfo},
+        view::ImageView,
+        Image, ImageCreateInfo, ImageType, ImageUsage, SampleCount,
+    },
+    memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    pipeline::{
+        graphics::{vertex_input::VertexInputState, viewport::Viewport},
+        GraphicsPipeline, Pipeline, PipelineBindPoint,
+    },
+    render_pass::{
+        AttachmentLoadOp, AttachmentStoreOp, Framebuffer, FramebufferCreateInfo, RenderPass,
+    },
+};
+
+use crate::{
+    bloom::Bloom,
+    effect::TextParams,
+    error::RendererError,
+    graph::{RenderGraph, TransientImages},
+    renderer::{build_pipeline, premultiplied_blend, TextRenderer},
+    shaders,
+    target::RenderTarget,
+};
+
+// composite.frag의 push constant 블록과 같은 레이아웃
+#[derive(BufferContents, Clone, Copy)]
+#[repr(C)]
+struct CompositePushConstants {
+    opacity: f32,
+}
+
+// 현재 크기의 레이어 이미지(멀티샘플이면 그릴 이미지도), framebuffer, 합성용 디스크립터 셋
+struct LayerTarget {
+    extent: [u32; 2],
+    multisampled: Option<Arc<ImageView>>,
+    layer: Arc<ImageView>,
+    // render pass로 그릴 때만 (dynamic rendering은 이미지 뷰에 바로 그림)
+    framebuffer: Option<Arc<Framebuffer>>,
+    descriptor_set: Arc<PersistentDescriptorSet>,
+}
+
+/// 텍스트를 먼저 그려 두는 오프스크린 레이어
+///
+/// 장면을 대상과 같은 크기와 포맷의 이미지에 그린 뒤, 대상 render pass에서 이미지 한 장으로 합성합니다.
+/// 장면과 파라미터가 그대로면(`TextRenderer::is_unchanged`) 다시 그리지 않고 이전 이미지를 재사용하므로
+/// 정적인 텍스트는 효과가 많아도 프레임마다 사각형 하나만 그립니다.
+/// 투명도는 합성할 때 레이어 전체에 곱하므로 겹친 효과가 서로 비쳐 보이지 않고,
+/// 투명도만 바뀌는 페이드에서도 레이어를 다시 그리지 않습니다.
+/// 발광(`TextEffects::GLOW`)은 레이어를 그릴 때 블룸으로 만들어 두었다가 레이어 뒤에 더합니다.
+///
+/// ```ignore
+/// let mut layer = TextLayer::new(device.clone(), render_pass.clone())?;
+/// let mut text_renderer = TextRenderer::new(device, queue, layer.render_target())?;
+///
+/// // 대상 render pass 밖에서
+/// layer.draw(&mut builder, &mut text_renderer, &params, swapchain.image_extent())?;
+/// // 대상 render pass 안에서 (viewport는 호출하는 쪽에서 설정)
+/// layer.composite(&mut builder, params.opacity)?;
+/// ```
+pub struct TextLayer {
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    descriptor_set_allocator: StandardDescriptorSetAllocator,
+    // 텍스트를 그리는 오프스크린 대상 (합성 대상과 같은 포맷, 샘플 수, 그리기 방식)
+    render_target: RenderTarget,
+    // 대상에 레이어를 합성하는 파이프라인
+    pipeline: Arc<GraphicsPipeline>,
+    sampler: Arc<Sampler>,
+    // 발광 효과 (레이어와 같은 대상 크기)
+    bloom: Bloom,
+    // 텍스트, 발광 마스크, 블룸 패스를 잇는 렌더 그래프의 임시 이미지
+    transients: TransientImages,
+    target: Option<LayerTarget>,
+    // 레이어 이미지에 마지막 draw 결과가 남아 있는지
+    valid: bool,
+    // 다시 그릴 때마다 증가
+    revision: u64,
+}
+
+impl TextLayer {
+    /// `target`(render pass의 서브패스나 dynamic rendering 대상)에 합성하는 레이어를 만듭니다.
+    ///
+    /// 레이어 이미지는 대상의 첫 번째 색 첨부와 같은 포맷이고,
+    /// 대상이 멀티샘플이면 같은 샘플 수로 그린 뒤 레이어 이미지로 resolve합니다.
+    /// 대상이 dynamic rendering이면 레이어도 render pass와 framebuffer 없이 그립니다.
+    pub fn new(
+        device: Arc<Device>,
+        target: impl Into<RenderTarget>,
+    ) -> Result<Self, RendererError> {
+        let target = target.into();
+        let (format, samples) = target.color_attachment()?;
+
+        let vs = shaders::composite_vs::load(device.clone())
+            .map_err(|e| RendererError::shader("composite vertex", e))?
+            .entry_point("main")
+            .ok_or_else(|| RendererError::shader("composite vertex", "main 진입점 없음"))?;
+        let fs = shaders::composite_fs::load(device.clone())
+            .map_err(|e| RendererError::shader("composite fragment", e))?
+            .entry_point("main")
+            .ok_or_else(|| RendererError::shader("composite fragment", "main 진입점 없음"))?;
+        // 정점은 셰이더가 gl_VertexIndex로 만듦
+        let pipeline = build_pipeline(
+            device.clone(),
+            target.clone(),
+            [vs, fs],
+            VertexInputState::new(),
+            premultiplied_blend(),
+        )?;
+
+        let sampler = Sampler::new(device.clone(), SamplerCreateInfo::default())
+            .map_err(RendererError::texture)?;
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let bloom = Bloom::new(device.clone(), memory_allocator.clone(), target.clone())?;
+        let render_target = if target.is_dynamic() {
+            RenderTarget::Dynamic { format, samples }
+        } else {
+            create_layer_render_pass(&device, format, samples)?.into()
+        };
+
+        Ok(Self {
+            transients: TransientImages::new(memory_allocator.clone()),
+            memory_allocator,
+            descriptor_set_allocator: StandardDescriptorSetAllocator::new(
+                device.clone(),
+                Default::default(),
+            ),
+            render_target,
+            pipeline,
+            sampler,
+            bloom,
+            target: None,
+            valid: false,
+            revision: 0,
+        })
+    }
+
+    /// 텍스트를 그리는 대상 (`TextRenderer`를 만들 때 넘김)
+    pub fn render_target(&self) -> RenderTarget {
+        self.render_target.clone()
+    }
+
+    /// 레이어 이미지를 다시 그릴 때마다 바뀌는 번호
+    ///
+    /// `composite`로 기록한 명령은 번호가 같은 동안 그대로 다시 제출할 수 있습니다
+    /// (`CommandBufferUsage::SimultaneousUse`로 기록해 두고 장면이 바뀔 때만 다시 기록).
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// 다음 `draw`에서 장면이 그대로여도 다시 그리게 합니다 (제출에 실패한 뒤 등).
+    pub fn invalidate(&mut self) {
+        self.valid = false;
+    }
+
+    /// 장면을 레이어 이미지에 그립니다 (바뀐 것이 없으면 아무것도 기록하지 않음).
+    ///
+    /// 레이어 render pass(또는 dynamic rendering)를 직접 열고 닫으므로 대상 render pass 밖에서 호출하세요.
+    /// `extent`는 대상 이미지 크기(물리 픽셀)이고, 달라지면 레이어 이미지를 다시 만듭니다.
+    /// `params.opacity`는 쓰지 않고 `composite`에 넘깁니다.
+    pub fn draw<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        text_renderer: &mut TextRenderer,
+        params: &TextParams,
+        extent: [u32; 2],
+    ) -> Result<(), RendererError> {
+        let extent = extent.map(|size| size.max(1));
+        let params = TextParams {
+            opacity: 1.0,
+            ..*params
+        };
+
+        if self.target.as_ref().map(|target| target.extent) != Some(extent) {
+            self.target = Some(self.create_target(extent)?);
+            self.valid = false;
+        }
+        if self.valid && text_renderer.is_unchanged(&params) {
+            return Ok(());
+        }
+        let Some(target) = &self.target else {
+            return Ok(());
+        };
+
+        // 레이어 이미지에 텍스트를 그린 다음 같은 장면으로 발광 마스크와 블룸 체인
+        let mut graph = RenderGraph::<_, TextRenderer>::new();
+        let layer = graph.import(target.layer.clone());
+        graph.output(layer);
+        graph.add_pass("텍스트", &[], &[layer], |builder, _, text_renderer| {
+            begin_target(builder, target)?;
+            builder
+                .set_viewport(
+                    0,
+                    [Viewport {
+                        offset: [0.0, 0.0],
+                        extent: [extent[0] as f32, extent[1] as f32],
+                        depth_range: 0.0..=1.0,
+                    }]
+                    .into_iter()
+                    .collect(),
+                )
+                .map_err(RendererError::command)?;
+
+            text_renderer.draw_scene(builder, &params)?;
+
+            if target.framebuffer.is_some() {
+                builder.end_render_pass(SubpassEndInfo::default())
+            } else {
+                builder.end_rendering()
+            }
+            .map_err(RendererError::command)?;
+            Ok(())
+        });
+        self.bloom.add_passes(
+            &mut graph,
+            text_renderer,
+            &params,
+            extent,
+            &self.descriptor_set_allocator,
+        )?;
+        graph.execute(builder, &mut self.transients, text_renderer)?;
+
+        self.valid = true;
+        self.revision += 1;
+        Ok(())
+    }
+
+    /// 레이어 이미지를 `opacity`를 곱해 대상에 덮어 그립니다 (발광을 켰으면 블룸을 먼저 더함).
+    ///
+    /// 대상 render pass 안에서 `draw` 다음에 호출하세요 (viewport는 호출하는 쪽에서 설정).
+    pub fn composite<L>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        opacity: f32,
+    ) -> Result<(), RendererError> {
+        let Some(target) = self.target.as_ref().filter(|_| self.valid) else {
+            return Ok(());
+        };
+
+        self.bloom.composite(builder, opacity)?;
+
+        builder
+            .bind_pipeline_graphics(self.pipeline.clone())
+            .map_err(RendererError::command)?
+            .bind_descriptor_sets(
+                PipelineBindPoint::Graphics,
+                self.pipeline.layout().clone(),
+                0,
+                target.descriptor_set.clone(),
+            )
+            .map_err(RendererError::command)?
+            .push_constants(
+                self.pipeline.layout().clone(),
+                0,
+                CompositePushConstants { opacity },
+            )
+            .map_err(RendererError::command)?
+            .draw(3, 1, 0, 0)
+            .map_err(RendererError::command)?;
+
+        Ok(())
+    }
+
+    // 레이어 이미지 (멀티샘플이면 그릴 이미지도 함께), framebuffer, 디스크립터 셋
+    fn create_target(&self, extent: [u32; 2]) -> Result<LayerTarget, RendererError> {
+        let (format, samples) = self.render_target.color_attachment()?;
+        let create_view = |samples: SampleCount, usage: ImageUsage| {
+            let image = Image::new(
+                self.memory_allocator.clone(),
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format,
+                    extent: [extent[0], extent[1], 1],
+                    usage,
+                    samples,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .map_err(RendererError::texture)?;
+            ImageView::new_default(image).map_err(RendererError::texture)
+        };
+
+        // 합성할 때 샘플링하는 레이어 이미지 (멀티샘플이면 resolve 대상)
+        let multisampled = match samples {
+            SampleCount::Sample1 => None,
+            samples => Some(create_view(
+                samples,
+                ImageUsage::COLOR_ATTACHMENT | ImageUsage::TRANSIENT_ATTACHMENT,
+            )?),
+        };
+        let layer = create_view(
+            SampleCount::Sample1,
+            ImageUsage::COLOR_ATTACHMENT | ImageUsage::SAMPLED,
+        )?;
+
+        let framebuffer = match &self.render_target {
+            RenderTarget::Subpass(subpass) => Some(
+                Framebuffer::new(
+                    subpass.render_pass().clone(),
+                    FramebufferCreateInfo {
+                        attachments: multisampled
+                            .iter()
+                            .cloned()
+                            .chain([layer.clone()])
+                            .collect(),
+                        ..Default::default()
+                    },
+                )
+                .map_err(RendererError::texture)?,
+            ),
+            RenderTarget::Dynamic { .. } => None,
+        };
+
+        let descriptor_set = PersistentDescriptorSet::new(
+            &self.descriptor_set_allocator,
+            self.pipeline.layout().set_layouts()[0].clone(),
+            [WriteDescriptorSet::image_view_sampler(
+                0,
+                layer.clone(),
+                self.sampler.clone(),
+            )],
+            [],
+        )
+        .map_err(RendererError::texture)?;
+
+        Ok(LayerTarget {
+            extent,
+            multisampled,
+            layer,
+            framebuffer,
+            descriptor_set,
+        })
+    }
+}
+
+// 레이어 이미지를 투명하게 지우고 그리기 시작 (render pass 또는 dynamic rendering)
+fn begin_target<L>(
+    builder: &mut AutoCommandBufferBuilder<L>,
+    target: &LayerTarget,
+) -> Result<(), RendererError> {
+    if let Some(framebuffer) = &target.framebuffer {
+        builder
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: clear_values(framebuffer.render_pass()),
+                    ..RenderPassBeginInfo::framebuffer(framebuffer.clone())
+                },
+                SubpassBeginInfo {
+                    contents: SubpassContents::Inline,
+                    ..Default::default()
+                },
+            )
+            .map_err(RendererError::command)?;
+        return Ok(());
+    }
+
+    // 멀티샘플이면 멀티샘플 이미지에 그려 레이어 이미지로 resolve하고 멀티샘플 내용은 버림
+    let (view, store_op, resolve_info) = match &target.multisampled {
+        Some(multisampled) => (
+            multisampled.clone(),
+            AttachmentStoreOp::DontCare,
+            Some(RenderingAttachmentResolveInfo::image_view(
+                target.layer.clone(),
+            )),
+        ),
+        None => (target.layer.clone(), AttachmentStoreOp::Store, None),
+    };
+    builder
+        .begin_rendering(RenderingInfo {
+            color_attachments: vec![Some(RenderingAttachmentInfo {
+                load_op: AttachmentLoadOp::Clear,
+                store_op,
+                clear_value: Some([0.0; 4].into()),
+                resolve_info,
+                ..RenderingAttachmentInfo::image_view(view)
+            })],
+            contents: SubpassContents::Inline,
+            ..Default::default()
+        })
+        .map_err(RendererError::command)?;
+    Ok(())
+}
+
+// 멀티샘플이면 첫 번째 첨부에 그려 두 번째 첨부(레이어 이미지)로 resolve
+fn create_layer_render_pass(
+    device: &Arc<Device>,
+    format: Format,
+    samples: SampleCount,
+) -> Result<Arc<RenderPass>, RendererError> {
+    if samples == SampleCount::Sample1 {
+        return vulkano::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                layer: {
+                    format: format,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: Store,
+                },
+            },
+            pass: {
+                color: [layer],
+                depth_stencil: {},
+            },
+        )
+        .map_err(RendererError::pipeline);
+    }
+
+    vulkano::single_pass_renderpass!(
+        device.clone(),
+        attachments: {
+            multisampled: {
+                format: format,
+                samples: samples as u32,
+                load_op: Clear,
+                store_op: DontCare,
+            },
+            layer: {
+                format: format,
+                samples: 1,
+                load_op: DontCare,
+                store_op: Store,
+            },
+        },
+        pass: {
+            color: [multisampled],
+            color_resolve: [layer],
+            depth_stencil: {},
+        },
+    )
+    .map_err(RendererError::pipeline)
+}
+
+// 레이어는 투명하게 지우고 시작 (resolve 첨부는 지우지 않음)
+fn clear_values(render_pass: &RenderPass) -> Vec<Option<ClearValue>> {
+    render_pass
+        .attachments()
+        .iter()
+        .map(|attachment| {
+            (attachment.load_op == AttachmentLoadOp::Clear).then_some([0.0; 4].into())
+        })
+        .collect()
+}
@@ -0,0 +1,556 @@
+use std::{io::ErrorKind, sync::Arc, time::Instant};
+use vulkano::{
+    command_buffer::{
+        allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+    },
+    device::{Device, DeviceExtensions, Features, Queue},
+    instance::{Instance, InstanceCreateFlags, InstanceCreateInfo, InstanceExtensions},
+    memory::allocator::StandardMemoryAllocator,
+    pipeline::graphics::viewport::Viewport,
+    swapchain::{acquire_next_image, Surface, Swapchain, SwapchainPresentInfo},
+    sync::{self, GpuFuture},
+    Validated, VulkanError, VulkanLibrary,
+};
+use smithay_client_toolkit::{
+    compositor::{CompositorHandler, CompositorState, Region},
+    delegate_compositor, delegate_layer, delegate_output, delegate_registry,
+    output::{OutputHandler, OutputState},
+    registry::{ProvidesRegistryState, RegistryState},
+    registry_handlers,
+    shell::{
+        wlr_layer::{
+            Anchor, KeyboardInteractivity, Layer, LayerShell, LayerShellHandler, LayerSurface,
+            LayerSurfaceConfigure,
+        },
+        WaylandSurface,
+    },
+};
+use tracing::info;
+use transparent_text_vulkan::{
+    resize_swapchain, select_surface_format, Antialiasing, LayoutOptions, PresentModePreference,
+    RendererConfig, RendererError, TextLayer, TextParams, TextRenderer,
+};
+use vulkan_context::{create_device, create_swapchain, select_physical_device, select_sample_count};
+use wayland_client::{
+    backend::WaylandError,
+    globals::registry_queue_init,
+    protocol::{wl_output, wl_surface},
+    Connection, DispatchError, Proxy, QueueHandle,
+};
+
+use crate::{
+    app_config::AppConfig,
+    create_text_renderer,
+    headless::{config_params, export_config, export_text, EXPORT_PADDING},
+    placement::{self, DEFAULT_MARGIN},
+    render_path::{enable_dynamic_rendering, RenderPath},
+    styled_markup, wrap_options, Args,
+};
+
+// 레이어 surface의 네임스페이스 (합성기 규칙에서 이 이름으로 고를 수 있음)
+const NAMESPACE: &str = "transparent-text";
+
+/// `--layer <background|bottom|top|overlay>`: 일반 창 대신 wlr-layer-shell surface에 텍스트를 그림
+///
+/// 창 관리자를 거치지 않고 바탕화면 위(`background`, `bottom`)나 모든 창 위(`top`, `overlay`)에 고정합니다.
+/// `--anchor`/`--margin`(또는 설정 파일 `[window]`)으로 모니터 가장자리에 붙이고,
+/// `--exclusive-zone <픽셀>`을 주면 다른 창이 그만큼 비켜 가도록 자리를 차지합니다 (`-1`이면 다른 패널도 무시하고 겹침).
+/// 크기는 설정 파일의 `width`/`height`, 없으면 텍스트 크기에 여백을 더한 크기이고,
+/// 키보드 입력을 받지 않으므로 텍스트와 효과는 시작할 때의 설정 파일과 `--text`/`--text-file`을 따릅니다.
+pub fn run(args: &Args, layer: &str) -> Result<(), RendererError> {
+    let layer = parse_layer(layer)?;
+    let config = export_config(args);
+
+    let conn = Connection::connect_to_env().map_err(|e| RendererError::Window(e.into()))?;
+    let (globals, mut event_queue) =
+        registry_queue_init::<LayerState>(&conn).map_err(|e| RendererError::Window(e.into()))?;
+    let qh = event_queue.handle();
+    let compositor =
+        CompositorState::bind(&globals, &qh).map_err(|e| RendererError::Window(e.into()))?;
+    let layer_shell = LayerShell::bind(&globals, &qh).map_err(|e| {
+        RendererError::Window(format!("합성기가 wlr-layer-shell을 지원하지 않습니다: {e}").into())
+    })?;
+    let layer_surface = layer_shell.create_layer_surface(
+        &qh,
+        compositor.create_surface(&qh),
+        layer,
+        Some(NAMESPACE),
+        None,
+    );
+
+    let library = VulkanLibrary::new()?;
+    let instance = Instance::new(
+        library,
+        InstanceCreateInfo {
+            flags: InstanceCreateFlags::ENUMERATE_PORTABILITY,
+            enabled_extensions: InstanceExtensions {
+                khr_surface: true,
+                khr_wayland_surface: true,
+                ..InstanceExtensions::empty()
+            },
+            ..Default::default()
+        },
+    )
+    .map_err(RendererError::Instance)?;
+    // SAFETY: 연결과 wl_surface는 이 함수가 끝날 때까지 살아 있고,
+    // 나중에 선언한 Vulkan surface와 스왑체인이 먼저 해제됨
+    let surface = unsafe {
+        Surface::from_wayland(
+            instance.clone(),
+            conn.backend().display_ptr() as *mut _,
+            layer_surface.wl_surface().id().as_ptr() as *mut _,
+            None,
+        )
+    }
+    .map_err(|e| RendererError::Window(e.into()))?;
+
+    let mut target = LayerTarget::new(args, &config, &instance, surface)?;
+    let text = export_text(args, &config);
+    let size = target.set_text(&text, &config)?;
+
+    let anchor = args.anchor.or(config.window.anchor).unwrap_or_default();
+    let margin = args
+        .margin
+        .or(config.window.margin)
+        .unwrap_or(DEFAULT_MARGIN) as i32;
+    layer_surface.set_anchor(layer_anchor(anchor));
+    layer_surface.set_margin(margin, margin, margin, margin);
+    layer_surface.set_size(size[0], size[1]);
+    layer_surface.set_exclusive_zone(args.exclusive_zone.unwrap_or(0));
+    layer_surface.set_keyboard_interactivity(KeyboardInteractivity::None);
+    // 클릭 통과는 빈 입력 영역으로 (창 관리자가 없으므로 설정 파일 값을 그대로 씀)
+    if config.window.click_through == Some(true) {
+        let region = Region::new(&compositor).map_err(|e| RendererError::Window(e.into()))?;
+        layer_surface
+            .wl_surface()
+            .set_input_region(Some(region.wl_region()));
+    }
+    layer_surface.commit();
+    info!(
+        layer = ?layer,
+        anchor = anchor.name(),
+        width = size[0],
+        height = size[1],
+        "레이어 surface"
+    );
+
+    let mut state = LayerState {
+        registry_state: RegistryState::new(&globals),
+        output_state: OutputState::new(&globals, &qh),
+        requested_size: size,
+        configured: None,
+        closed: false,
+    };
+    let dispatch_error = |e: DispatchError| RendererError::Window(e.into());
+
+    // 첫 configure를 받기 전에는 버퍼를 붙일 수 없음
+    while state.configured.is_none() && !state.closed {
+        event_queue
+            .blocking_dispatch(&mut state)
+            .map_err(dispatch_error)?;
+    }
+
+    let started = Instant::now();
+    let mut params = config_params(&config, size);
+    while !state.closed {
+        if let Some(size) = state.configured.take() {
+            target.resize(size)?;
+            params = config_params(&config, size);
+        }
+
+        params.time = started.elapsed().as_secs_f32();
+        target.render(&params)?;
+
+        if target.text_renderer.is_animated(&params) {
+            // 애니메이션 중에는 기다리지 않고 읽은 이벤트만 처리 (프레임 속도는 FIFO 프레젠트가 맞춤)
+            event_queue
+                .flush()
+                .map_err(|e| RendererError::Window(e.into()))?;
+            if let Some(guard) = event_queue.prepare_read() {
+                match guard.read() {
+                    Ok(_) => {}
+                    Err(WaylandError::Io(e)) if e.kind() == ErrorKind::WouldBlock => {}
+                    Err(e) => return Err(RendererError::Window(e.into())),
+                }
+            }
+            event_queue
+                .dispatch_pending(&mut state)
+                .map_err(dispatch_error)?;
+        } else {
+            // 멈춘 텍스트는 크기가 바뀌거나 닫힐 때까지 다시 그리지 않음
+            event_queue
+                .blocking_dispatch(&mut state)
+                .map_err(dispatch_error)?;
+        }
+    }
+
+    info!("레이어 surface가 닫혔습니다");
+    Ok(())
+}
+
+// 창 위치 이름을 레이어가 붙을 모니터 가장자리로 (center는 어느 쪽에도 붙지 않아 가운데)
+fn layer_anchor(anchor: placement::Anchor) -> Anchor {
+    match anchor {
+        placement::Anchor::TopLeft => Anchor::TOP | Anchor::LEFT,
+        placement::Anchor::TopCenter => Anchor::TOP,
+        placement::Anchor::TopRight => Anchor::TOP | Anchor::RIGHT,
+        placement::Anchor::CenterLeft => Anchor::LEFT,
+        placement::Anchor::Center => Anchor::empty(),
+        placement::Anchor::CenterRight => Anchor::RIGHT,
+        placement::Anchor::BottomLeft => Anchor::BOTTOM | Anchor::LEFT,
+        placement::Anchor::BottomCenter => Anchor::BOTTOM,
+        placement::Anchor::BottomRight => Anchor::BOTTOM | Anchor::RIGHT,
+    }
+}
+
+fn parse_layer(name: &str) -> Result<Layer, RendererError> {
+    match name {
+        "background" => Ok(Layer::Background),
+        "bottom" => Ok(Layer::Bottom),
+        "top" => Ok(Layer::Top),
+        "overlay" => Ok(Layer::Overlay),
+        _ => Err(RendererError::Window(
+            format!("--layer는 background, bottom, top, overlay 중 하나여야 합니다: {name}").into(),
+        )),
+    }
+}
+
+// 레이어 surface의 스왑체인과 텍스트 렌더러 (한 프레임씩 그리고 기다림)
+struct LayerTarget {
+    device: Arc<Device>,
+    queue: Arc<Queue>,
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    command_buffer_allocator: StandardCommandBufferAllocator,
+    swapchain: Option<Arc<Swapchain>>,
+    surface: Arc<Surface>,
+    present_mode: PresentModePreference,
+    render_path: RenderPath,
+    viewport: Viewport,
+    layer: TextLayer,
+    text_renderer: TextRenderer,
+    // 합성기가 정한 크기 (스왑체인이 오래되면 같은 크기로 다시 만듦)
+    extent: [u32; 2],
+    recreate_swapchain: bool,
+}
+
+impl LayerTarget {
+    fn new(
+        args: &Args,
+        config: &AppConfig,
+        instance: &Arc<Instance>,
+        surface: Arc<Surface>,
+    ) -> Result<Self, RendererError> {
+        let mut device_extensions = DeviceExtensions {
+            khr_swapchain: true,
+            ..DeviceExtensions::empty()
+        };
+        let (physical_device, queue_family_index) =
+            select_physical_device(instance, &surface, &device_extensions, args.gpu.as_ref())?;
+        info!(
+            gpu = %physical_device.properties().device_name,
+            device_type = ?physical_device.properties().device_type,
+            "사용 중인 GPU"
+        );
+
+        let mut device_features = Features {
+            dual_src_blend: physical_device.supported_features().dual_src_blend,
+            ..Features::empty()
+        };
+        let dynamic_rendering = enable_dynamic_rendering(
+            &physical_device,
+            args.dynamic_rendering || config.dynamic_rendering.unwrap_or(false),
+            &mut device_extensions,
+            &mut device_features,
+        );
+        let (device, queues) = create_device(
+            physical_device,
+            queue_family_index,
+            device_extensions,
+            device_features,
+        )?;
+        let queue = queues.graphics.clone();
+
+        let renderer_config = RendererConfig {
+            antialiasing: if args.subpixel {
+                Antialiasing::Subpixel
+            } else {
+                Antialiasing::Grayscale
+            },
+            mipmaps: config.mipmaps.unwrap_or(true),
+            // 한 프레임씩 그리고 기다리므로 한 개면 충분
+            frames_in_flight: 1,
+            ..RendererConfig::default()
+        };
+        let present_mode = renderer_config.present_mode;
+        let (image_format, _) = select_surface_format(device.physical_device(), &surface)?;
+        let samples = select_sample_count(
+            device.physical_device(),
+            args.msaa.or(config.msaa).unwrap_or(1),
+        );
+        let render_path = RenderPath::new(&device, image_format, samples, dynamic_rendering)?;
+        let layer = TextLayer::new(device.clone(), render_path.target())?;
+        let text_renderer = create_text_renderer(
+            &device,
+            &queues,
+            layer.render_target(),
+            args,
+            config,
+            renderer_config,
+        )?;
+
+        Ok(Self {
+            memory_allocator: Arc::new(StandardMemoryAllocator::new_default(device.clone())),
+            command_buffer_allocator: StandardCommandBufferAllocator::new(
+                device.clone(),
+                Default::default(),
+            ),
+            device,
+            queue,
+            swapchain: None,
+            surface,
+            present_mode,
+            render_path,
+            viewport: Viewport::default(),
+            layer,
+            text_renderer,
+            extent: [0, 0],
+            recreate_swapchain: false,
+        })
+    }
+
+    // 텍스트를 바꾸고 요청할 크기를 돌려줌 (설정 파일 크기, 없으면 텍스트 크기 + 여백)
+    fn set_text(&mut self, text: &str, config: &AppConfig) -> Result<[u32; 2], RendererError> {
+        let size = config.window.size();
+        let options = match size {
+            Some(size) => wrap_options(
+                size.map(|length| length as f32),
+                false,
+                LayoutOptions::default(),
+            ),
+            None => LayoutOptions::default(),
+        };
+        self.text_renderer.set_layout_options(options)?;
+        self.text_renderer
+            .set_styled_text(&styled_markup(text, config.color.map(|color| color.0)))?;
+
+        Ok(size.unwrap_or_else(|| {
+            self.text_renderer
+                .text_extent()
+                .map(|length| (length + EXPORT_PADDING * 2.0).ceil() as u32)
+        }))
+    }
+
+    // configure로 받은 크기에 맞춰 스왑체인을 만들거나 다시 만듦
+    fn resize(&mut self, extent: [u32; 2]) -> Result<(), RendererError> {
+        self.extent = extent;
+        let (swapchain, images) = match &self.swapchain {
+            Some(swapchain) => {
+                // 컴포지터가 아직 0 크기를 주거나 surface가 새 크기를 받아들이지 않으면 다음 프레임에 다시 시도
+                match resize_swapchain(swapchain, extent, swapchain.present_mode())? {
+                    Some(recreated) => recreated,
+                    None => {
+                        self.recreate_swapchain = true;
+                        return Ok(());
+                    }
+                }
+            }
+            None => create_swapchain(
+                &self.device,
+                self.surface.clone(),
+                extent,
+                self.present_mode,
+                false,
+            )?,
+        };
+        self.render_path
+            .resize(&images, &self.memory_allocator, &mut self.viewport)?;
+        self.swapchain = Some(swapchain);
+        self.recreate_swapchain = false;
+        Ok(())
+    }
+
+    fn render(&mut self, params: &TextParams) -> Result<(), RendererError> {
+        if self.recreate_swapchain {
+            self.resize(self.extent)?;
+        }
+        let Some(swapchain) = self.swapchain.clone() else {
+            return Ok(());
+        };
+
+        let (image_index, suboptimal, acquire_future) =
+            match acquire_next_image(swapchain.clone(), None).map_err(Validated::unwrap) {
+                Ok(r) => r,
+                Err(VulkanError::OutOfDate) => {
+                    self.recreate_swapchain = true;
+                    return Ok(());
+                }
+                Err(e) => return Err(RendererError::Swapchain(Validated::Error(e))),
+            };
+        if suboptimal {
+            self.recreate_swapchain = true;
+        }
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &self.command_buffer_allocator,
+            self.queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .map_err(|e| RendererError::Command(e.into()))?;
+
+        self.layer.draw(
+            &mut builder,
+            &mut self.text_renderer,
+            params,
+            swapchain.image_extent(),
+        )?;
+
+        self.render_path
+            .begin(&mut builder, image_index as usize, [0.0, 0.0, 0.0, 0.0])?;
+        builder
+            .set_viewport(0, [self.viewport.clone()].into_iter().collect())
+            .map_err(|e| RendererError::Command(e.into()))?;
+
+        self.layer.composite(&mut builder, params.opacity)?;
+
+        self.render_path.end(&mut builder)?;
+        let command_buffer = builder
+            .build()
+            .map_err(|e| RendererError::Command(e.into()))?;
+
+        // 새 글리프 업로드가 끝난 뒤 실행
+        let future = self
+            .text_renderer
+            .take_upload_future()
+            .unwrap_or_else(|| sync::now(self.device.clone()).boxed())
+            .join(acquire_future)
+            .then_execute(self.queue.clone(), command_buffer)
+            .map_err(|e| RendererError::Command(e.into()))?
+            .then_swapchain_present(
+                self.queue.clone(),
+                SwapchainPresentInfo::swapchain_image_index(swapchain, image_index),
+            )
+            .then_signal_fence_and_flush();
+
+        match future.map_err(Validated::unwrap) {
+            Ok(future) => future
+                .wait(None)
+                .map_err(|e| RendererError::Command(e.into())),
+            Err(VulkanError::OutOfDate) => {
+                self.recreate_swapchain = true;
+                Ok(())
+            }
+            Err(e) => Err(RendererError::Command(e.into())),
+        }
+    }
+}
+
+// sctk 이벤트를 받는 상태 (configure로 받은 크기, 닫힘)
+struct LayerState {
+    registry_state: RegistryState,
+    output_state: OutputState,
+    requested_size: [u32; 2],
+    configured: Option<[u32; 2]>,
+    closed: bool,
+}
+
+impl CompositorHandler for LayerState {
+    fn scale_factor_changed(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _surface: &wl_surface::WlSurface,
+        _new_factor: i32,
+    ) {
+    }
+
+    fn transform_changed(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _surface: &wl_surface::WlSurface,
+        _new_transform: wl_output::Transform,
+    ) {
+    }
+
+    fn frame(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _surface: &wl_surface::WlSurface,
+        _time: u32,
+    ) {
+    }
+}
+
+impl OutputHandler for LayerState {
+    fn output_state(&mut self) -> &mut OutputState {
+        &mut self.output_state
+    }
+
+    fn new_output(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _output: wl_output::WlOutput,
+    ) {
+    }
+
+    fn update_output(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _output: wl_output::WlOutput,
+    ) {
+    }
+
+    fn output_destroyed(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _output: wl_output::WlOutput,
+    ) {
+    }
+}
+
+impl LayerShellHandler for LayerState {
+    fn closed(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _layer: &LayerSurface) {
+        self.closed = true;
+    }
+
+    fn configure(
+        &mut self,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+        _layer: &LayerSurface,
+        configure: LayerSurfaceConfigure,
+        _serial: u32,
+    ) {
+        // 0이면 크기를 마음대로 정해도 되므로 요청한 크기
+        let (width, height) = configure.new_size;
+        self.configured = Some([
+            if width == 0 {
+                self.requested_size[0]
+            } else {
+                width
+            },
+            if height == 0 {
+                self.requested_size[1]
+            } else {
+                height
+            },
+        ]);
+    }
+}
+
+impl ProvidesRegistryState for LayerState {
+    fn registry(&mut self) -> &mut RegistryState {
+        &mut self.registry_state
+    }
+
+    registry_handlers![OutputState];
+}
+
+delegate_compositor!(LayerState);
+delegate_output!(LayerState);
+delegate_layer!(LayerState);
+delegate_registry!(LayerState);
@@ -0,0 +1,938 @@
+use std::ops::Range;
+use fontdue::layout::GlyphRasterConfig;
+use unicode_bidi::{BidiInfo, Level};
+
+use crate::{font::FontFace, style::StyledText, variation::Variation};
+
+/// 문단의 기본 쓰기 방향
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextDirection {
+    /// 문단의 첫 번째 강한 방향 문자로 결정 (UAX #9 P2/P3)
+    #[default]
+    Auto,
+    LeftToRight,
+    RightToLeft,
+}
+
+/// 글자를 쓰는 방향
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WritingMode {
+    /// 가로쓰기 (글자는 왼쪽에서 오른쪽으로, 줄은 위에서 아래로)
+    #[default]
+    Horizontal,
+    /// 세로쓰기 (글자는 위에서 아래로, 줄은 오른쪽에서 왼쪽으로)
+    ///
+    /// 한글, 한자, 가나, 전각 문자와 이모지는 세로 메트릭(vmtx)으로 똑바로 세우고
+    /// 세로 대체 글리프(`vert`)를 쓰며, 라틴 문자와 숫자 등은 시계 방향으로 90° 눕힙니다.
+    VerticalRightToLeft,
+}
+
+/// 텍스트 레이아웃 설정
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutOptions {
+    /// 자동 줄바꿈 너비 (픽셀, 세로쓰기에서는 열의 높이). `None`이면 `\n`에서만 줄을 바꿉니다.
+    pub wrap_width: Option<f32>,
+    /// 폰트의 기본 줄 간격에 곱하는 배율 (줄마다 가장 큰 글자 기준)
+    pub line_spacing: f32,
+    /// 각 문단(`\n`으로 나뉜 줄)의 기본 방향. 오른쪽→왼쪽 문단은 오른쪽 정렬됩니다.
+    pub direction: TextDirection,
+    /// 커닝 적용 여부 (원본 폰트 데이터가 있으면 GPOS/kern, 없으면 kern 테이블)
+    pub kerning: bool,
+    /// 가로쓰기 또는 세로쓰기
+    pub writing_mode: WritingMode,
+    /// 탭 간격 (기본 폰트의 공백 너비 배수). `\t`는 다음 탭 위치까지 띄웁니다.
+    pub tab_width: f32,
+    /// 탭으로 나뉜 칸을 열로 맞춤 (탭이 있는 문단이 이어지는 동안 열마다 가장 넓은 칸 다음 탭 위치에서 시작)
+    pub align_tabs: bool,
+}
+
+impl Default for LayoutOptions {
+    fn default() -> Self {
+        Self {
+            wrap_width: None,
+            line_spacing: 1.0,
+            direction: TextDirection::Auto,
+            kerning: true,
+            writing_mode: WritingMode::Horizontal,
+            tab_width: 4.0,
+            align_tabs: false,
+        }
+    }
+}
+
+/// 레이아웃된 한 줄의 메트릭 (픽셀, 텍스트 블록 왼쪽 위 기준)
+///
+/// 세로쓰기에서는 가로와 세로를 바꿔 읽습니다. `x`는 블록 위쪽에서 열 시작까지, `baseline`은
+/// 블록 왼쪽에서 열의 중심선까지, `width`는 열의 길이, `height`는 다음 열까지의 간격입니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineMetrics {
+    /// 블록 왼쪽에서 줄 시작까지의 거리 (오른쪽 정렬된 줄에서 0보다 큼)
+    pub x: f32,
+    /// 블록 위쪽에서 이 줄의 베이스라인까지의 거리
+    pub baseline: f32,
+    /// 줄 끝 공백을 뺀 너비
+    pub width: f32,
+    /// 다음 줄까지의 간격 (line_spacing 적용)
+    pub height: f32,
+    /// 오른쪽→왼쪽 문단의 줄인지 여부
+    pub rtl: bool,
+}
+
+// 배치된 글리프 (origin은 펜 위치와 베이스라인, Y는 아래 방향)
+// 비트맵 위치는 아틀라스 항목의 offset으로 정해짐 (외곽선/컬러 글리프 공통)
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PositionedGlyph {
+    // 글리프를 가진 폰트 (fonts 슬라이스 인덱스)
+    pub font_index: usize,
+    pub key: GlyphRasterConfig,
+    pub origin: [f32; 2],
+    // 이 글리프를 만든 문자의 텍스트 내 바이트 위치 (셰이핑 클러스터)
+    pub cluster: usize,
+    // 가짜 굵게 (래스터라이즈 시 커버리지를 가로로 넓힘)
+    pub bold: bool,
+    // 세로쓰기에서 시계 방향으로 90° 눕힌 글리프 (래스터라이즈 시 비트맵을 돌림)
+    pub sideways: bool,
+    // 가변 폰트의 축 좌표 (가변 폰트가 아니면 무시)
+    pub variation: Variation,
+}
+
+pub(crate) struct TextLayout {
+    pub glyphs: Vec<PositionedGlyph>,
+    pub lines: Vec<LineMetrics>,
+    pub carets: CaretMap,
+    pub width: f32,
+    pub height: f32,
+}
+
+// 글자(셰이핑 클러스터) 하나가 줄에서 차지하는 가로 구간
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ClusterBox {
+    pub line: usize,
+    // 텍스트 내 바이트 위치
+    pub cluster: usize,
+    pub x: f32,
+    pub advance: f32,
+    pub rtl: bool,
+}
+
+// 바이트 위치와 커서 위치(줄, x)의 대응 (x는 텍스트 블록 왼쪽 기준)
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CaretMap {
+    // 줄마다 바이트 범위 (자동 줄바꿈된 줄의 끝은 다음 줄의 시작, 문단 끝은 `\n` 앞)
+    pub line_ranges: Vec<Range<usize>>,
+    pub clusters: Vec<ClusterBox>,
+}
+
+impl CaretMap {
+    // 바이트 위치가 속한 줄 (줄바꿈 경계에서는 다음 줄)
+    pub fn line_at(&self, offset: usize) -> usize {
+        self.line_ranges
+            .iter()
+            .rposition(|range| range.start <= offset)
+            .unwrap_or(0)
+    }
+
+    // line 줄에서 바이트 위치 offset 앞에 놓인 커서의 x
+    // (offset에서 시작하는 글자의 앞 가장자리, 없으면 앞 글자의 뒤 가장자리, 빈 줄이면 줄의 시작)
+    pub fn caret_x(&self, lines: &[LineMetrics], line: usize, offset: usize) -> f32 {
+        let boxes = || self.clusters.iter().filter(move |b| b.line == line);
+        if let Some(b) = boxes().find(|b| b.cluster == offset) {
+            return if b.rtl { b.x + b.advance } else { b.x };
+        }
+        if let Some(b) = boxes()
+            .filter(|b| b.cluster < offset)
+            .max_by_key(|b| b.cluster)
+        {
+            return if b.rtl { b.x } else { b.x + b.advance };
+        }
+        lines
+            .get(line)
+            .map_or(0.0, |m| if m.rtl { m.x + m.width } else { m.x })
+    }
+
+    // line 줄에서 x에 가장 가까운 커서의 바이트 위치
+    pub fn offset_at(&self, lines: &[LineMetrics], line: usize, x: f32) -> usize {
+        let Some(range) = self.line_ranges.get(line) else {
+            return 0;
+        };
+        let distance = |offset: usize| (self.caret_x(lines, line, offset) - x).abs();
+        self.clusters
+            .iter()
+            .filter(|b| b.line == line)
+            .map(|b| b.cluster)
+            .chain([range.start, range.end])
+            .min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+            .unwrap_or(range.start)
+    }
+
+    // 크기를 바꿈 (물리 픽셀 → 논리 픽셀)
+    pub fn scale(&mut self, factor: f32) {
+        for b in &mut self.clusters {
+            b.x *= factor;
+            b.advance *= factor;
+        }
+    }
+}
+
+// 굵게 표시할 때 글리프를 가로로 넓히는 픽셀 수
+pub(crate) fn bold_strength(px: f32) -> usize {
+    ((px / 24.0).round() as usize).max(1)
+}
+
+// 아틀라스에서 굵은 글리프와 눕힌 글리프를 일반 글리프와 구분하기 위해 font_hash에 섞는 값
+const BOLD_KEY: usize = 0x5bd1_e995;
+const SIDEWAYS_KEY: usize = 0x2f6b_3a1d;
+
+const KERN_TAG: rustybuzz::ttf_parser::Tag = rustybuzz::ttf_parser::Tag::from_bytes(b"kern");
+
+// 같은 방식으로 셰이핑할 수 있는 구간의 속성
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RunStyle {
+    font_index: usize,
+    // 양방향 임베딩 레벨 (홀수면 오른쪽→왼쪽)
+    level: u8,
+    px: f32,
+    bold: bool,
+    // 글자마다 advance에 더하는 간격 (픽셀)
+    letter_spacing: f32,
+    // 줄 간격 배율 (None이면 LayoutOptions::line_spacing)
+    line_height: Option<f32>,
+    kerning: bool,
+    orientation: Orientation,
+    variation: Variation,
+}
+
+// 글리프를 세우는 방향 (세로쓰기에서 글자마다 정함)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Orientation {
+    Horizontal,
+    // 세로 메트릭으로 위에서 아래로 셰이핑
+    Upright,
+    // 가로로 셰이핑한 뒤 시계 방향으로 90° 눕힘
+    Sideways,
+}
+
+// 셰이핑된 글리프 하나 (커닝/셰이핑 결과가 반영된 advance, 세로로 세운 글리프는 아래 방향 advance)
+struct Item {
+    run: RunStyle,
+    glyph_index: u16,
+    advance: f32,
+    // 셰이핑 오프셋 (Y는 아래 방향, 세로로 세운 글리프는 열 중심선 위의 펜에서 가로 원점까지)
+    offset: [f32; 2],
+    whitespace: bool,
+    // 탭 문자 (advance는 줄바꿈할 때 탭 위치에 맞춰 정함)
+    tab: bool,
+    cluster: usize,
+}
+
+/// `\n`으로 문단을 나누고, 각 문단을 셰이핑한 뒤 `wrap_width`에 맞춰 줄바꿈하고 글리프를 배치
+///
+/// 셰이핑과 줄바꿈은 논리 순서로 하고, 줄마다 양방향 레벨에 따라 시각 순서로 재배열합니다.
+///
+/// `fonts`는 우선순위 순서이며, 글자마다 그 글리프를 가진 첫 번째 폰트를 사용합니다.
+/// 글자 크기와 굵기는 구간 스타일을 따르고, 줄 높이는 그 줄에서 가장 큰 글자에 맞춥니다.
+///
+/// `scale`(HiDPI 배율)을 글자 크기와 줄바꿈 너비에 곱해 물리 픽셀 단위로 레이아웃합니다.
+/// `\t`는 줄 시작부터 잰 다음 탭 위치까지 띄우고, `align_tabs`이면 이어진 문단끼리 열을 맞춥니다.
+/// 세로쓰기는 줄을 열로 바꿔 같은 방식으로 나누고, 첫 열부터 오른쪽에서 왼쪽으로 놓습니다.
+pub(crate) fn layout_text(
+    fonts: &[FontFace],
+    font_size: f32,
+    scale: f32,
+    text: &StyledText,
+    options: &LayoutOptions,
+) -> TextLayout {
+    let font_size = font_size * scale;
+    let wrap_width = options.wrap_width.map(|width| width * scale);
+    let vertical = options.writing_mode == WritingMode::VerticalRightToLeft;
+    let mut glyphs = Vec::new();
+    let mut lines: Vec<LineMetrics> = Vec::new();
+    let mut carets = CaretMap::default();
+    // 줄마다 그 줄에 속한 글리프 범위 (오른쪽 정렬 시 이동용)
+    let mut line_glyphs = Vec::new();
+    let mut paragraph_start = 0;
+    let mut line_top = 0.0;
+    let mut last_descent = 0.0;
+
+    // 열을 맞추려면 다른 문단의 칸 너비가 필요하므로 모든 문단을 먼저 셰이핑
+    let mut paragraphs = Vec::new();
+    for paragraph in text.text.split('\n') {
+        let trimmed = paragraph.strip_suffix('\r').unwrap_or(paragraph);
+        let paragraph_range = paragraph_start..paragraph_start + trimmed.len();
+        let (items, rtl) = shape_paragraph(
+            fonts,
+            font_size,
+            scale,
+            text,
+            paragraph_range.clone(),
+            options,
+        );
+        paragraph_start += paragraph.len() + 1;
+        paragraphs.push((paragraph_range, items, rtl));
+    }
+
+    let space = fonts[0].font.metrics(' ', font_size).advance_width;
+    let tab_interval = tab_interval(space, options.tab_width);
+    let columns = if options.align_tabs {
+        let cells: Vec<&[Item]> = paragraphs.iter().map(|(_, items, _)| &items[..]).collect();
+        column_stops(&cells, tab_interval)
+    } else {
+        vec![Vec::new(); paragraphs.len()]
+    };
+
+    for ((paragraph_range, mut items, rtl), columns) in paragraphs.into_iter().zip(columns) {
+        let first_line = lines.len();
+        let tabs = TabStops {
+            interval: tab_interval,
+            columns: &columns,
+        };
+        for range in wrap_lines(&mut items, wrap_width, &tabs) {
+            // 자동 줄바꿈된 줄은 첫 글자부터 (앞 줄의 끝은 아래에서 이 줄의 시작으로 맞춤)
+            let line_start = match items.get(range.start) {
+                Some(item) if lines.len() > first_line => item.cluster,
+                _ => paragraph_range.start,
+            };
+            if lines.len() > first_line {
+                if let Some(previous) = carets.line_ranges.last_mut() {
+                    previous.end = line_start;
+                }
+            }
+            carets.line_ranges.push(line_start..paragraph_range.end);
+
+            let line_items = &items[range.clone()];
+            let (ascent, descent, line_height) =
+                line_metrics(fonts, font_size, options.line_spacing, line_items);
+            let baseline = line_top + ascent;
+            // 세로쓰기의 열 중심선 (블록 오른쪽 끝 기준, 열을 모두 놓은 뒤 블록 너비만큼 옮김)
+            let center = -(line_top + line_height / 2.0);
+            let mut pen_x = 0.0;
+            let mut width = 0.0;
+            let first_glyph = glyphs.len();
+
+            for index in visual_order(line_items) {
+                let item = &line_items[index];
+                carets.clusters.push(ClusterBox {
+                    line: lines.len(),
+                    cluster: item.cluster,
+                    x: pen_x,
+                    advance: item.advance,
+                    rtl: item.run.level % 2 == 1,
+                });
+                if !item.whitespace {
+                    let font = &fonts[item.run.font_index].font;
+                    let sideways = item.run.orientation == Orientation::Sideways;
+                    let mut font_hash = font.file_hash();
+                    if item.run.bold {
+                        font_hash ^= BOLD_KEY;
+                    }
+                    if sideways {
+                        font_hash ^= SIDEWAYS_KEY;
+                    }
+                    if fonts[item.run.font_index].is_variable() {
+                        font_hash ^= item.run.variation.key();
+                    }
+                    let origin = match item.run.orientation {
+                        Orientation::Horizontal => {
+                            [pen_x + item.offset[0], baseline + item.offset[1]]
+                        }
+                        Orientation::Upright => [center + item.offset[0], pen_x + item.offset[1]],
+                        // 눕힌 글자의 ascent와 descent 사이가 열 중심에 오도록
+                        Orientation::Sideways => {
+                            let (ascent, descent) = font
+                                .horizontal_line_metrics(item.run.px)
+                                .map_or((item.run.px, 0.0), |m| (m.ascent, m.descent));
+                            [
+                                center - (ascent + descent) / 2.0 - item.offset[1],
+                                pen_x + item.offset[0],
+                            ]
+                        }
+                    };
+                    glyphs.push(PositionedGlyph {
+                        font_index: item.run.font_index,
+                        key: GlyphRasterConfig {
+                            glyph_index: item.glyph_index,
+                            px: item.run.px,
+                            font_hash,
+                        },
+                        origin,
+                        cluster: item.cluster,
+                        bold: item.run.bold,
+                        sideways,
+                        variation: item.run.variation,
+                    });
+                    width = pen_x + item.advance;
+                }
+                pen_x += item.advance;
+            }
+
+            lines.push(LineMetrics {
+                x: 0.0,
+                baseline: if vertical { center } else { baseline },
+                width,
+                height: line_height,
+                rtl,
+            });
+            line_glyphs.push(first_glyph..glyphs.len());
+            line_top += line_height;
+            last_descent = descent;
+        }
+    }
+
+    if vertical {
+        // 열을 블록 오른쪽부터 놓음 (오른쪽→왼쪽 문단도 위에서부터 씀)
+        let width = line_top;
+        for glyph in &mut glyphs {
+            glyph.origin[0] += width;
+        }
+        for line in &mut lines {
+            line.baseline += width;
+        }
+        let height = lines.iter().fold(0.0f32, |h, line| h.max(line.width));
+        return TextLayout {
+            glyphs,
+            lines,
+            carets,
+            width,
+            height,
+        };
+    }
+
+    let width = lines.iter().fold(0.0f32, |w, line| w.max(line.width));
+    // 마지막 줄은 줄 간격 대신 글자 높이만큼만 차지
+    let height = lines
+        .last()
+        .map_or(0.0, |line| line.baseline - last_descent);
+
+    // 오른쪽→왼쪽 문단은 블록 오른쪽에 맞춤
+    for (line, range) in lines.iter_mut().zip(line_glyphs) {
+        if line.rtl {
+            line.x = width - line.width;
+            for glyph in &mut glyphs[range] {
+                glyph.origin[0] += line.x;
+            }
+        }
+    }
+    for b in &mut carets.clusters {
+        b.x += lines[b.line].x;
+    }
+
+    TextLayout {
+        glyphs,
+        lines,
+        carets,
+        width,
+        height,
+    }
+}
+
+// 줄에서 가장 큰 글자 기준의 (ascent, descent, 줄 간격), 빈 줄은 기본 크기 사용
+// 줄 간격은 글자마다 (폰트 줄 간격 × 구간 line_height 또는 line_spacing) 중 최댓값
+fn line_metrics(
+    fonts: &[FontFace],
+    font_size: f32,
+    line_spacing: f32,
+    items: &[Item],
+) -> (f32, f32, f32) {
+    let metrics_at = |px: f32, spacing: f32| match fonts[0].font.horizontal_line_metrics(px) {
+        Some(m) => (m.ascent, m.descent, m.new_line_size * spacing),
+        None => (px, 0.0, px * spacing),
+    };
+
+    if items.is_empty() {
+        return metrics_at(font_size, line_spacing);
+    }
+
+    items
+        .iter()
+        .fold((0.0, 0.0, 0.0), |(ascent, descent, size), item| {
+            let spacing = item.run.line_height.unwrap_or(line_spacing);
+            let (a, d, s) = metrics_at(item.run.px, spacing);
+            (f32::max(ascent, a), f32::min(descent, d), f32::max(size, s))
+        })
+}
+
+// 양방향 레벨을 구하고, 글자마다 폰트와 스타일을 정해 같은 구간(run)별로 셰이핑
+// range는 전체 텍스트에서 이 문단의 바이트 범위, 구간 글자 크기에는 scale을 곱함
+// 반환값의 bool은 문단 기본 방향이 오른쪽→왼쪽인지 여부
+fn shape_paragraph(
+    fonts: &[FontFace],
+    font_size: f32,
+    scale: f32,
+    styled: &StyledText,
+    range: Range<usize>,
+    options: &LayoutOptions,
+) -> (Vec<Item>, bool) {
+    let direction = options.direction;
+    let base = range.start;
+    let text = &styled.text[range];
+    let default_level = match direction {
+        TextDirection::Auto => None,
+        TextDirection::LeftToRight => Some(Level::ltr()),
+        TextDirection::RightToLeft => Some(Level::rtl()),
+    };
+    let bidi = BidiInfo::new(text, default_level);
+    let rtl = bidi
+        .paragraphs
+        .first()
+        .map(|paragraph| paragraph.level.is_rtl())
+        .unwrap_or(direction == TextDirection::RightToLeft);
+
+    let mut items = Vec::new();
+    let mut run: Option<(usize, RunStyle)> = None; // (시작 바이트, 구간 속성)
+    let mut orientation = Orientation::Horizontal;
+
+    for (i, c) in text.char_indices() {
+        // 결합 문자는 앞 글자와 같은 방향으로 (구간이 갈라지지 않도록)
+        if options.writing_mode == WritingMode::VerticalRightToLeft && !is_combining(c) {
+            orientation = if is_upright(c) {
+                Orientation::Upright
+            } else {
+                Orientation::Sideways
+            };
+        }
+        let style = styled.style_at(base + i);
+        let px = style.size.map_or(font_size, |size| size * scale);
+        let char_run = RunStyle {
+            font_index: find_font(fonts, c),
+            level: bidi.levels[i].number(),
+            px,
+            bold: style.bold.unwrap_or(false),
+            letter_spacing: style.letter_spacing.unwrap_or(0.0) * px,
+            line_height: style.line_height,
+            kerning: options.kerning,
+            orientation,
+            variation: Variation {
+                weight: style.weight,
+                width: style.width,
+                slant: style.slant,
+            },
+        };
+        match run {
+            Some((_, current)) if current == char_run => {}
+            Some((start, current)) => {
+                shape_run(fonts, current, &text[start..i], base + start, &mut items);
+                run = Some((i, char_run));
+            }
+            None => run = Some((i, char_run)),
+        }
+    }
+    if let Some((start, current)) = run {
+        shape_run(fonts, current, &text[start..], base + start, &mut items);
+    }
+
+    (items, rtl)
+}
+
+// 세로쓰기에서 똑바로 세우는 글자 (UAX #50의 U 방향을 블록 단위로 줄인 것)
+fn is_upright(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}' // 한글 자모
+        | '\u{2E80}'..='\u{2FDF}' // 한자 부수
+        | '\u{3000}'..='\u{A4CF}' // 한중일 기호, 가나, 호환 자모, 한자, 이 문자
+        | '\u{A960}'..='\u{A97F}' // 한글 자모 확장 A
+        | '\u{AC00}'..='\u{D7FF}' // 한글 음절, 자모 확장 B
+        | '\u{F900}'..='\u{FAFF}' // 한자 호환
+        | '\u{FE10}'..='\u{FE1F}' // 세로 형태
+        | '\u{FE30}'..='\u{FE4F}' // 한중일 호환 형태
+        | '\u{FF00}'..='\u{FFEF}' // 전각, 반각 형태
+        | '\u{1F000}'..='\u{1FAFF}' // 이모지와 기호
+        | '\u{20000}'..='\u{3FFFF}' // 한자 확장
+    )
+}
+
+// 앞 글자에 붙는 결합 문자와 이음표, 이체자 선택자
+fn is_combining(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{E0100}'..='\u{E01EF}'
+    )
+}
+
+// 줄 안의 글리프를 시각 순서로 (UAX #9 L2: 높은 레벨부터 연속 구간을 뒤집음)
+fn visual_order(items: &[Item]) -> Vec<usize> {
+    let level = |index: usize| items[index].run.level;
+    let mut order: Vec<usize> = (0..items.len()).collect();
+    let Some(lowest_odd) = order.iter().map(|&i| level(i)).filter(|l| l % 2 == 1).min() else {
+        return order;
+    };
+    let highest = order.iter().map(|&i| level(i)).max().unwrap_or(0);
+
+    for current in (lowest_odd..=highest).rev() {
+        let mut i = 0;
+        while i < order.len() {
+            if level(order[i]) < current {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < order.len() && level(order[i]) >= current {
+                i += 1;
+            }
+            order[start..i].reverse();
+        }
+    }
+
+    order
+}
+
+// 원본 폰트 데이터가 있으면 rustybuzz로 셰이핑하고, 없으면 fontdue 메트릭과 커닝만 사용
+// 결과는 항상 논리 순서로 items에 추가 (시각 순서 재배열은 줄바꿈 후에)
+// 세로로 세우는 구간은 위에서 아래로 셰이핑 (세로 advance와 원점, vert 대체 글리프)
+fn shape_run(fonts: &[FontFace], run: RunStyle, text: &str, base: usize, items: &mut Vec<Item>) {
+    let face = &fonts[run.font_index];
+    let rtl = run.level % 2 == 1;
+    let upright = run.orientation == Orientation::Upright;
+    let run_start = items.len();
+    let is_whitespace = |cluster: usize| {
+        text[cluster..]
+            .chars()
+            .next()
+            .is_some_and(char::is_whitespace)
+    };
+    // 굵은 글리프는 넓어진 만큼 advance도 늘리고, 자간을 더함
+    let extra_advance = run.letter_spacing
+        + if run.bold {
+            bold_strength(run.px) as f32
+        } else {
+            0.0
+        };
+
+    if let Some(shaper) = face.shaper(&run.variation) {
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(text);
+        buffer.guess_segment_properties();
+        buffer.set_direction(if upright {
+            rustybuzz::Direction::TopToBottom
+        } else if rtl {
+            rustybuzz::Direction::RightToLeft
+        } else {
+            rustybuzz::Direction::LeftToRight
+        });
+        // kern 기능을 끄면 GPOS 페어 조정과 kern 테이블 모두 적용되지 않음
+        let features = if run.kerning {
+            Vec::new()
+        } else {
+            vec![rustybuzz::Feature::new(KERN_TAG, 0, ..)]
+        };
+        let shaped = rustybuzz::shape(&shaper, &features, buffer);
+        let scale = run.px / shaper.units_per_em() as f32;
+
+        for (info, position) in shaped.glyph_infos().iter().zip(shaped.glyph_positions()) {
+            let cluster = info.cluster as usize;
+            // 세로 advance는 위쪽이 양수인 폰트 단위라 부호를 바꿈
+            let advance = if upright {
+                -position.y_advance
+            } else {
+                position.x_advance
+            };
+            // 결합 문자처럼 advance가 없는 글리프에는 간격을 더하지 않음
+            let extra = if advance != 0 { extra_advance } else { 0.0 };
+            items.push(Item {
+                run,
+                glyph_index: info.glyph_id as u16,
+                advance: advance as f32 * scale + extra,
+                offset: [
+                    position.x_offset as f32 * scale,
+                    -position.y_offset as f32 * scale,
+                ],
+                whitespace: is_whitespace(cluster),
+                tab: text[cluster..].starts_with('\t'),
+                cluster: base + cluster,
+            });
+        }
+
+        // 오른쪽→왼쪽 구간은 시각 순서로 나오므로 논리 순서로 되돌림
+        if rtl && !upright {
+            items[run_start..].reverse();
+        }
+        return;
+    }
+
+    let font = &face.font;
+    // 세로로 세운 글자는 가운데를 열 중심선에, 베이스라인을 글자 칸 위쪽에서 ascent만큼 아래에 맞춤
+    let ascent = font
+        .horizontal_line_metrics(run.px)
+        .map_or(run.px, |m| m.ascent);
+    for (cluster, c) in text.char_indices() {
+        let glyph_index = font.lookup_glyph_index(c);
+        let metrics = font.metrics_indexed(glyph_index, run.px);
+        let (advance, offset) = if upright {
+            // vmtx가 없는 폰트는 글자 칸을 정사각형으로
+            let height = if metrics.advance_height > 0.0 {
+                metrics.advance_height
+            } else {
+                run.px
+            };
+            (height, [-metrics.advance_width / 2.0, ascent])
+        } else {
+            (metrics.advance_width, [0.0, 0.0])
+        };
+
+        // 커닝은 앞 글리프의 advance에 더함 (가로 커닝이라 세로로 세운 구간에는 쓰지 않음)
+        if let Some(previous) = items[run_start..]
+            .last_mut()
+            .filter(|_| run.kerning && !upright)
+        {
+            previous.advance += font
+                .horizontal_kern_indexed(previous.glyph_index, glyph_index, run.px)
+                .unwrap_or(0.0);
+        }
+
+        items.push(Item {
+            run,
+            glyph_index,
+            advance: advance + extra_advance,
+            offset,
+            whitespace: c.is_whitespace(),
+            tab: c == '\t',
+            cluster: base + cluster,
+        });
+    }
+}
+
+// 글자를 가진 첫 번째 폰트를 찾고, 어느 폰트에도 없으면 기본 폰트 사용 (.notdef)
+fn find_font(fonts: &[FontFace], c: char) -> usize {
+    fonts
+        .iter()
+        .position(|face| face.font.lookup_glyph_index(c) != 0)
+        .unwrap_or(0)
+}
+
+// 한 문단의 탭 위치 (픽셀)
+struct TabStops<'a> {
+    // 일정한 탭 간격
+    interval: f32,
+    // 열을 맞출 때 n번째 탭이 가는 위치 (없으면 일정한 간격만 사용)
+    columns: &'a [f32],
+}
+
+impl TabStops<'_> {
+    // 줄에서 x에 있는 n번째 탭의 advance (지난 열 위치는 건너뛰고 다음 탭 위치로)
+    fn advance(&self, x: f32, n: usize) -> f32 {
+        let stop = self
+            .columns
+            .get(n)
+            .copied()
+            .filter(|&stop| stop > x)
+            .unwrap_or_else(|| next_tab_stop(x, self.interval));
+        stop - x
+    }
+}
+
+// 공백 너비의 tab_width배인 탭 간격 (0, 음수, NaN이면 탭이 앞으로 가지 않으므로 1픽셀 이상)
+fn tab_interval(space: f32, tab_width: f32) -> f32 {
+    (space * tab_width).max(1.0)
+}
+
+// x 뒤의 첫 번째 탭 위치 (x가 탭 위치에 있으면 한 간격 뒤)
+fn next_tab_stop(x: f32, interval: f32) -> f32 {
+    ((x / interval).floor() + 1.0) * interval
+}
+
+// 탭이 있는 문단이 이어지는 구간마다 열 위치를 정함 (탭이 없는 문단은 구간을 끊음)
+// n번째 열은 그 앞 칸들 중 가장 넓은 칸의 끝 다음 탭 위치에서 시작
+fn column_stops(paragraphs: &[&[Item]], interval: f32) -> Vec<Vec<f32>> {
+    // 문단마다 탭으로 나뉜 칸의 너비 (마지막 칸은 뒤에 탭이 없음)
+    let cells: Vec<Vec<f32>> = paragraphs
+        .iter()
+        .map(|items| {
+            let mut cells = vec![0.0];
+            for item in items.iter() {
+                if item.tab {
+                    cells.push(0.0);
+                } else if let Some(width) = cells.last_mut() {
+                    *width += item.advance;
+                }
+            }
+            cells
+        })
+        .collect();
+
+    let mut stops = vec![Vec::new(); paragraphs.len()];
+    let mut start = 0;
+    while start < cells.len() {
+        let end = start
+            + cells[start..]
+                .iter()
+                .position(|cells| cells.len() < 2)
+                .unwrap_or(cells.len() - start);
+        let block = &cells[start..end];
+        let mut columns = Vec::new();
+        let mut column_start = 0.0;
+        for n in 0.. {
+            let widest = block
+                .iter()
+                .filter(|cells| cells.len() > n + 1)
+                .map(|cells| cells[n])
+                .reduce(f32::max);
+            let Some(widest) = widest else {
+                break;
+            };
+            column_start = next_tab_stop(column_start + widest, interval);
+            columns.push(column_start);
+        }
+        stops[start..end].fill(columns);
+        start = end + 1;
+    }
+    stops
+}
+
+// 공백 뒤에서 줄을 바꾸고, 한 단어가 너비를 넘으면 글자 단위로 자름
+// 탭의 advance는 줄 시작부터 잰 위치로 정하므로, 줄을 바꾸면 새 줄 처음부터 다시 잼
+fn wrap_lines(items: &mut [Item], wrap_width: Option<f32>, tabs: &TabStops) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut x = 0.0;
+    // 문단에서 지금까지 나온 탭 수 (열 위치 번호)
+    let mut tab_count = 0;
+    let mut last_break = None;
+    let mut i = 0;
+
+    while i < items.len() {
+        if items[i].tab {
+            items[i].advance = tabs.advance(x, tab_count);
+            tab_count += 1;
+        }
+        let item = &items[i];
+        let overflow = wrap_width.is_some_and(|max_width| x + item.advance > max_width);
+        if !item.whitespace && i > start && overflow {
+            let next = last_break.filter(|&b| b > start).unwrap_or(i);
+            lines.push(start..next);
+            start = next;
+            x = 0.0;
+            tab_count = items[..start].iter().filter(|item| item.tab).count();
+            last_break = None;
+            i = start;
+            continue;
+        }
+
+        x += item.advance;
+        if item.whitespace {
+            last_break = Some(i + 1);
+        }
+        i += 1;
+    }
+
+    lines.push(start..items.len());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 글자마다 같은 너비의 항목 (탭은 advance를 줄바꿈할 때 정함)
+    fn items(text: &str, width: f32) -> Vec<Item> {
+        let run = RunStyle {
+            font_index: 0,
+            level: 0,
+            px: 16.0,
+            bold: false,
+            letter_spacing: 0.0,
+            line_height: None,
+            kerning: false,
+            orientation: Orientation::Horizontal,
+            variation: Variation::default(),
+        };
+        text.char_indices()
+            .map(|(cluster, c)| Item {
+                run,
+                glyph_index: 0,
+                advance: if c == '\t' { 0.0 } else { width },
+                offset: [0.0, 0.0],
+                whitespace: c.is_whitespace(),
+                tab: c == '\t',
+                cluster,
+            })
+            .collect()
+    }
+
+    fn stops(paragraphs: &[&str], interval: f32) -> Vec<Vec<f32>> {
+        let items: Vec<Vec<Item>> = paragraphs.iter().map(|text| items(text, 10.0)).collect();
+        let cells: Vec<&[Item]> = items.iter().map(|items| &items[..]).collect();
+        column_stops(&cells, interval)
+    }
+
+    fn wrap(text: &str, wrap_width: Option<f32>, tabs: &TabStops) -> (Vec<Range<usize>>, Vec<f32>) {
+        let mut items = items(text, 10.0);
+        let lines = wrap_lines(&mut items, wrap_width, tabs);
+        (lines, items.iter().map(|item| item.advance).collect())
+    }
+
+    #[test]
+    fn columns_start_after_the_widest_cell() {
+        // 칸 너비가 탭 위치에 딱 맞으면 다음 탭 위치로
+        assert_eq!(stops(&["ab\tc", "abcd\te"], 40.0), [[80.0], [80.0]]);
+        assert_eq!(stops(&["ab\tc", "a\te"], 40.0), [[40.0], [40.0]]);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        assert_eq!(
+            stops(&["a\tb\tc", "aaaaa\tb", "x", "a\tb", ""], 40.0),
+            [
+                vec![80.0, 120.0],
+                vec![80.0, 120.0],
+                vec![],
+                vec![40.0],
+                vec![]
+            ]
+        );
+    }
+
+    #[test]
+    fn tabs_advance_to_the_next_stop() {
+        let tabs = TabStops {
+            interval: 40.0,
+            columns: &[],
+        };
+        let (lines, advances) = wrap("a\tb\t\tc", None, &tabs);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0], 0..6);
+        assert_eq!(advances, [10.0, 30.0, 10.0, 30.0, 40.0, 10.0]);
+
+        // 열 위치가 이미 지났으면 일정한 간격의 다음 탭 위치로
+        let tabs = TabStops {
+            interval: 40.0,
+            columns: &[80.0, 20.0],
+        };
+        let (_, advances) = wrap("a\tbcd\te", None, &tabs);
+        assert_eq!(advances, [10.0, 70.0, 10.0, 10.0, 10.0, 10.0, 10.0]);
+    }
+
+    #[test]
+    fn tabs_are_measured_from_the_wrapped_line_start() {
+        let tabs = TabStops {
+            interval: 40.0,
+            columns: &[],
+        };
+        let (lines, advances) = wrap("abcd ef\tg", Some(50.0), &tabs);
+        assert_eq!(lines, [0..5, 5..9]);
+        assert_eq!(advances[7], 20.0);
+
+        // 탭 뒤에서 줄을 바꾸면 탭은 앞 줄에 남음
+        let (lines, advances) = wrap("ab\tcd", Some(50.0), &tabs);
+        assert_eq!(lines, [0..3, 3..5]);
+        assert_eq!(advances[2], 20.0);
+    }
+
+    #[test]
+    fn zero_or_negative_tab_width() {
+        for tab_width in [0.0, -4.0, f32::NAN] {
+            assert_eq!(tab_interval(10.0, tab_width), 1.0);
+        }
+        assert_eq!(tab_interval(10.0, 4.0), 40.0);
+
+        // 가장 작은 간격에서도 빈 칸의 탭은 앞으로 감
+        assert_eq!(stops(&["\t\t", "\t"], 1.0), [[1.0, 2.0], [1.0, 2.0]]);
+        let tabs = TabStops {
+            interval: 1.0,
+            columns: &[],
+        };
+        let (_, advances) = wrap("\t\ta", None, &tabs);
+        assert_eq!(advances, [1.0, 1.0, 10.0]);
+    }
+}
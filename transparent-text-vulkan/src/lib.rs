@@ -0,0 +1,76 @@
+//! 투명 텍스트 렌더러 라이브러리
+//!
+//! vulkano 애플리케이션에 GPU 텍스트 렌더링을 붙일 수 있도록
+//! 폰트 로드, 글리프 아틀라스, 파이프라인, 디스크립터 셋, 드로우 기록을 하나로 묶어 제공합니다.
+//!
+//! ```ignore
+//! let mut text_renderer = TextRenderer::new(device, queue, render_pass)?;
+//!
+//! // render pass 안에서 (viewport는 호출하는 쪽에서 설정)
+//! text_renderer.draw(&mut builder, "안녕하세요", &TextParams::default())?;
+//!
+//! // 새 글리프 업로드를 기다린 뒤 실행
+//! let mut future = previous_future.join(acquire_future).boxed();
+//! if let Some(upload) = text_renderer.take_upload_future() {
+//!     future = future.join(upload).boxed();
+//! }
+//! ```
+
+mod animation;
+mod atlas;
+mod bloom;
+mod builder;
+mod capture;
+mod color;
+mod config;
+mod custom_shader;
+mod effect;
+mod error;
+mod font;
+mod frame;
+#[cfg(feature = "glsl")]
+mod glsl;
+mod graph;
+#[cfg(feature = "hot-reload")]
+mod hot_reload;
+mod layer;
+mod layout;
+mod markup;
+mod outline;
+mod profiler;
+mod renderer;
+mod scene;
+mod shaders;
+mod shadow;
+mod staging;
+mod style;
+mod target;
+mod variation;
+
+pub use animation::{Clock, Easing, Karaoke, KaraokeSyllable, Tween, Typewriter};
+pub use atlas::GlyphCacheStats;
+pub use builder::TextRendererBuilder;
+pub use capture::{ImageCapture, Screenshot};
+pub use config::{Antialiasing, RendererConfig};
+pub use custom_shader::CustomShader;
+pub use effect::{
+    GradientMode, TextBackground, TextEffect, TextEffects, TextGradient, TextHighlight, TextParams,
+};
+pub use error::RendererError;
+pub use font::{load_font_file, FontData};
+#[cfg(feature = "system-fonts")]
+pub use font::load_system_font;
+pub use frame::{FrameFence, FramesInFlight};
+pub use layer::TextLayer;
+pub use layout::{LayoutOptions, LineMetrics, TextDirection, WritingMode};
+pub use markup::{format_color, format_effects, parse_color, parse_effects, parse_markup};
+// 스왑체인 관련 함수는 vulkan-context 크레이트에 있고 기존 경로로도 쓸 수 있도록 다시 내보냄
+pub use vulkan_context::{
+    resize_swapchain, select_hdr_surface_format, select_present_mode, select_surface_format,
+    swapchain_extent, PresentModePreference, HDR_SURFACE_FORMAT, PREFERRED_SURFACE_FORMATS,
+};
+pub use profiler::{FrameStats, GpuProfiler, PassTiming};
+pub use renderer::TextRenderer;
+pub use scene::{TextObject, TextObjectId};
+pub use style::{SpanStyle, StyleSpan, StyledText};
+pub use target::RenderTarget;
@@ -1,790 +1,3533 @@
-use std::sync::Arc;
+mod action;
+mod app_config;
+mod backdrop;
+mod control;
+mod file_watch;
+mod fullscreen;
+#[cfg(feature = "global-hotkey")]
+mod global_keys;
+mod headless;
+mod history;
+#[cfg(all(feature = "layer-shell", target_os = "linux"))]
+mod layer_shell;
+mod marquee;
+mod mirror;
+#[cfg(feature = "egui-panel")]
+mod panel;
+mod placement;
+mod preset;
+mod render_path;
+mod saved_state;
+#[cfg(feature = "scripting")]
+mod script;
+mod stats;
+mod stdin_input;
+mod subtitle;
+#[cfg(feature = "system-stats")]
+mod system_stats;
+mod text_source;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    ops::Range,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 use vulkano::{
-    buffer::{Buffer, BufferContents, BufferCreateInfo, BufferUsage},
     command_buffer::{
         allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
-        CopyBufferToImageInfo, RenderPassBeginInfo, SubpassBeginInfo, SubpassContents,
-    },
-    descriptor_set::{
-        allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
-    },
-    device::{
-        physical::PhysicalDeviceType, Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo,
-        QueueFlags,
-    },
-    format::Format,
-    image::{
-        sampler::{Sampler, SamplerCreateInfo, Filter, SamplerAddressMode},
-        view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage,
+        PrimaryAutoCommandBuffer,
     },
-    instance::{Instance, InstanceCreateFlags, InstanceCreateInfo},
-    memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
-    pipeline::{
-        graphics::{
-            color_blend::{ColorBlendAttachmentState, ColorBlendState},
-            input_assembly::InputAssemblyState,
-            multisample::MultisampleState,
-            rasterization::RasterizationState,
-            vertex_input::{Vertex, VertexDefinition},
-            viewport::{Viewport, ViewportState},
-            GraphicsPipelineCreateInfo,
-        },
-        layout::PipelineDescriptorSetLayoutCreateInfo,
-        DynamicState, GraphicsPipeline, Pipeline, PipelineBindPoint, PipelineLayout,
-        PipelineShaderStageCreateInfo,
-    },
-    render_pass::{Framebuffer, FramebufferCreateInfo, Subpass},
-    swapchain::{
-        acquire_next_image, Surface, Swapchain, SwapchainCreateInfo, SwapchainPresentInfo,
-        CompositeAlpha,
-    },
-    sync::{self, GpuFuture},
-    Validated, VulkanError, VulkanLibrary,
+    device::{Device, Queue},
+    format::{Format, NumericFormat},
+    image::ImageUsage,
+    memory::allocator::StandardMemoryAllocator,
+    pipeline::graphics::viewport::Viewport,
+    swapchain::{acquire_next_image, CompositeAlpha, Swapchain, SwapchainPresentInfo},
+    sync::GpuFuture,
+    Validated, VulkanError,
 };
+use arboard::Clipboard;
+use glam::{Mat4, Quat, Vec3};
+use tracing::{debug_span, error, info, warn};
+use tracing_subscriber::EnvFilter;
 use winit::{
-    event::{Event, WindowEvent, KeyEvent},
-    event_loop::{ControlFlow, EventLoop},
-    window::{WindowBuilder, Window},
-    keyboard::{KeyCode, PhysicalKey},
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
+    event::{ElementState, Event, Ime, KeyEvent, MouseButton, WindowEvent},
+    event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
+    keyboard::{KeyCode, ModifiersState, PhysicalKey},
+    window::{Window, WindowBuilder, WindowId, WindowLevel},
 };
-use fontdue::{Font, FontSettings};
-use glam::{Mat4, Vec3};
-
-// 정점 구조체
-#[derive(BufferContents, Vertex, Clone, Copy)]
-#[repr(C)]
-struct TextVertex {
-    #[format(R32G32_SFLOAT)]
-    position: [f32; 2],
-    #[format(R32G32_SFLOAT)]
-    tex_coords: [f32; 2],
-}
-
-// Push Constants (투명도와 효과 설정)
-#[derive(BufferContents, Clone, Copy)]
-#[repr(C)]
-struct PushConstants {
-    opacity: f32,
-    effect_type: i32, // 0: normal, 1: outline, 2: shadow, 3: glow
-    outline_width: f32,
-    shadow_offset: [f32; 2],
-}
+use transparent_text_vulkan::{
+    parse_markup, resize_swapchain, select_present_mode, Antialiasing, Clock, CustomShader,
+    FontData, FramesInFlight, GpuProfiler, GradientMode, ImageCapture, Karaoke, LayoutOptions,
+    PresentModePreference, RenderTarget, RendererConfig, RendererError, SpanStyle, StyleSpan,
+    StyledText, TextBackground, TextEffects, TextGradient, TextHighlight, TextLayer, TextObject,
+    TextObjectId, TextParams, TextRenderer, Tween, Typewriter, WritingMode,
+};
+use unicode_segmentation::UnicodeSegmentation;
+use vulkan_context::{select_sample_count, ContextOptions, GpuSelector, Queues, VulkanContext, GPU_ENV};
+
+use crate::{
+    action::Action,
+    app_config::{AppConfig, Color, ConfigWatcher, Effects, WindowConfig, DEFAULT_CONFIG_PATH},
+    backdrop::Backdrop,
+    control::{ControlMessage, ControlServer, StyleUpdate},
+    file_watch::FileWatcher,
+    fullscreen::{enable_exclusive_fullscreen, exclusive_instance_extensions, FullscreenToggle},
+    headless::ImageSize,
+    history::{History, DEFAULT_HISTORY_DEPTH},
+    marquee::{Marquee, DEFAULT_MARQUEE_GAP},
+    mirror::{MirrorMonitors, MirrorWindow},
+    placement::{Anchor, MonitorWatcher, Placement, DEFAULT_MARGIN},
+    preset::{Preset, PresetError, PresetStore},
+    render_path::{enable_dynamic_rendering, RenderPath},
+    saved_state::{SavedState, SavedWindow, StateFile},
+    stats::FrameCounter,
+    stdin_input::{StdinLines, StdinMode},
+    subtitle::{SubtitleQueue, DEFAULT_SUBTITLE_FADE},
+    text_source::{ClockSource, SourceLines, TextSource, TimerSource, MAX_TIMER_DECIMALS},
+};
+#[cfg(feature = "global-hotkey")]
+use crate::global_keys::GlobalKeys;
+#[cfg(feature = "egui-panel")]
+use crate::panel::{ControlPanel, PanelValues};
+#[cfg(feature = "scripting")]
+use crate::script::{OverlayScript, DEFAULT_SCRIPT_PATH};
+#[cfg(feature = "system-stats")]
+use crate::system_stats::{SystemStatsSource, DEFAULT_STATS_INTERVAL};
+
+// 텍스트 줄바꿈 시 창 좌우에 남겨 둘 여백 (픽셀)
+const TEXT_MARGIN: f32 = 40.0;
+
+// 상태 줄의 글자 크기와 창 아래쪽 가장자리에서 상태 줄 중심까지의 거리 (픽셀)
+const STATUS_FONT_SIZE: f32 = 20.0;
+const STATUS_MARGIN: f32 = 24.0;
+
+// 통계 표시의 글자 크기와 창 왼쪽 위 모서리에서 떨어진 거리 (픽셀)
+const STATS_FONT_SIZE: f32 = 16.0;
+const STATS_MARGIN: f32 = 12.0;
+
+// 합성기가 창의 알파를 지원하지 않을 때 검은 화면 대신 채우는 기본 배경 (설정 파일 window.opaque_background)
+const OPAQUE_BACKGROUND: [u8; 4] = [32, 32, 36, 255];
+
+// 방향키로 텍스트를 한 번에 옮기는 거리 (픽셀), R 키로 한 번에 돌리는 각도 (도)
+const MOVE_STEP: f32 = 10.0;
+const ROTATE_STEP: f32 = 15.0;
+
+// [ / ] 키와 설정으로 바꿀 수 있는 최대 발광 반지름 (논리 픽셀, 블룸이 번지는 범위)
+const MAX_GLOW_RADIUS: f32 = 32.0;
+
+// 내장 폰트 (텍스트 렌더러와 컨트롤 패널의 한글)
+const BUNDLED_FONT: &[u8] = include_bytes!("../NotoSansKR-Regular.ttf");
+
+// 게으른 다시 그리기에서 이벤트가 없어도 텍스트 파일, 표준 입력, 제어 소켓을 확인하는 간격
+const LAZY_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// 창이 보이지 않아 그리기를 멈춘 동안 같은 입력과 창 상태를 확인하는 간격
+const PAUSED_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// --max-fps로 제한할 수 있는 범위 (아주 작은 값은 프레임 간격이 Duration을 넘음)
+const MIN_MAX_FPS: f32 = 1.0;
+const MAX_MAX_FPS: f32 = 1000.0;
+
+// 시작할 때 출력하는 주요 키 (전체 목록은 README)
+const CONTROLS_HELP: &str = "\
+1-9: 투명도 조절 (10% - 90%)
+0: 투명도 100%
+E: 텍스트 효과 전환
+Enter: 텍스트 입력 모드 (한글 입력기 사용 가능)
+F1-F8: 프리셋 불러오기 (Shift+F1-F8: 현재 스타일을 저장)
+C: 클릭 통과 켜기/끄기
+H: 창 숨기기/보이기 (global-hotkey 기능: Ctrl+Alt+H)
+V: 프레젠트 모드 전환 (수직 동기화/Mailbox/Immediate)
+Ctrl+C/Ctrl+V: 텍스트 복사/붙여넣기
+Ctrl+Z/Ctrl+Shift+Z: 되돌리기/다시 실행
+텍스트 끌기: 창 옮기기
+ESC: 종료";
+
+// 텍스트를 눌러 창을 끌 때 텍스트 블록 바깥으로 넓혀 주는 판정 여백 (픽셀)
+const DRAG_MARGIN: f32 = 8.0;
+
+// 텍스트 입력 모드의 커서 (너비는 픽셀, 깜박임 주기는 초)와 선택 영역 색
+const CARET_WIDTH: f32 = 2.0;
+const CARET_BLINK_PERIOD: f32 = 1.0;
+const CARET_COLOR: [u8; 4] = [255, 220, 0, 255];
+const SELECTION_COLOR: [u8; 4] = [80, 140, 255, 110];
+
+// 투명도와 효과 파라미터가 새 값으로 바뀌는 시간 (초, 설정 파일의 fade_duration)
+const FADE_DURATION: f32 = 0.3;
+
+// 창이 나타나거나 사라지는 시간 (초, H 키, show/hide 메시지, 종료)
+const VISIBILITY_FADE_DURATION: f32 = 0.4;
+
+// D 키로 텍스트가 타며 사라지거나 나타나는 데 걸리는 시간 (초)
+const DISSOLVE_DURATION: f32 = 1.5;
+
+// 설정 파일의 karaoke_color가 없을 때 노래방 효과로 칠하는 색
+const KARAOKE_COLOR: [u8; 4] = [255, 204, 0, 255];
+
+// O 키로 돌아가며 고르는 외곽선 색
+const OUTLINE_COLORS: [(&str, [u8; 4]); 4] = [
+    ("노랑", [255, 255, 0, 204]),
+    ("검정", [0, 0, 0, 230]),
+    ("빨강", [255, 60, 60, 230]),
+    ("파랑", [60, 140, 255, 230]),
+];
+
+// E 키로 돌아가며 고르는 효과 조합
+const EFFECT_PRESETS: [TextEffects; 12] = [
+    TextEffects::NONE,
+    TextEffects::OUTLINE,
+    TextEffects::SHADOW,
+    TextEffects::GLOW,
+    TextEffects::GRADIENT,
+    TextEffects::GRADIENT.union(TextEffects::OUTLINE),
+    TextEffects::WAVE,
+    TextEffects::WAVE.union(TextEffects::GRADIENT),
+    TextEffects::GLITCH,
+    TextEffects::OUTLINE.union(TextEffects::SHADOW),
+    TextEffects::GLOW.union(TextEffects::SHADOW),
+    TextEffects::OUTLINE
+        .union(TextEffects::SHADOW)
+        .union(TextEffects::GLOW),
+];
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum TextEffect {
-    Normal,
-    Outline,
-    Shadow,
-    Glow,
+fn main() {
+    if let Err(e) = run() {
+        error!("{e}");
+        std::process::exit(1);
+    }
 }
 
-impl TextEffect {
-    fn to_i32(&self) -> i32 {
-        match self {
-            TextEffect::Normal => 0,
-            TextEffect::Outline => 1,
-            TextEffect::Shadow => 2,
-            TextEffect::Glow => 3,
-        }
+fn run() -> Result<(), RendererError> {
+    // 인자를 해석하며 내는 경고도 남도록 로그를 먼저 설정
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    init_logging(args.iter().any(|arg| arg == "--log-json"));
+    let args = Args::parse(args.into_iter());
+    if args.headless {
+        return headless::export(&args);
     }
-
-    fn next(&self) -> Self {
-        match self {
-            TextEffect::Normal => TextEffect::Outline,
-            TextEffect::Outline => TextEffect::Shadow,
-            TextEffect::Shadow => TextEffect::Glow,
-            TextEffect::Glow => TextEffect::Normal,
-        }
+    if let Some(layer) = &args.layer {
+        #[cfg(all(feature = "layer-shell", target_os = "linux"))]
+        return layer_shell::run(&args, layer);
+        #[cfg(not(all(feature = "layer-shell", target_os = "linux")))]
+        warn!("--layer {layer}: layer-shell 기능 없이 빌드되어 일반 창으로 실행합니다");
     }
 
-    fn name(&self) -> &str {
-        match self {
-            TextEffect::Normal => "일반",
-            TextEffect::Outline => "외곽선",
-            TextEffect::Shadow => "그림자",
-            TextEffect::Glow => "발광",
-        }
-    }
+    let event_loop = EventLoop::new().map_err(|e| RendererError::Window(e.into()))?;
+    let mut app = App::new(&event_loop, args)?;
+
+    info!("=== 컨트롤 ===\n{CONTROLS_HELP}");
+
+    event_loop
+        .run(move |event, elwt| {
+            // 미러 창의 이벤트는 주 창의 입력으로 처리하지 않음
+            if let Event::WindowEvent { window_id, event } = &event {
+                if *window_id != app.window.id() {
+                    app.mirror_event(*window_id, event);
+                    return;
+                }
+            }
+            // 입력, 크기 변경 같은 창 이벤트는 게으른 모드에서도 다음 프레임을 그리게 함
+            if let Event::WindowEvent { event, .. } = &event {
+                if !matches!(event, WindowEvent::RedrawRequested) {
+                    app.needs_redraw = true;
+                }
+                // 컨트롤 패널이 가져간 입력(패널 위의 클릭, 텍스트 상자 입력)은 앱에서 처리하지 않음
+                #[cfg(feature = "egui-panel")]
+                if app.panel_event(event) {
+                    return;
+                }
+            }
+
+            match event {
+                Event::WindowEvent {
+                    event: WindowEvent::CloseRequested,
+                    ..
+                } => app.quit(elwt),
+                Event::WindowEvent {
+                    event: WindowEvent::KeyboardInput { event, .. },
+                    ..
+                } => app.handle_key(&event, elwt),
+                Event::WindowEvent {
+                    event: WindowEvent::ModifiersChanged(modifiers),
+                    ..
+                } => app.modifiers = modifiers.state(),
+                Event::WindowEvent {
+                    event: WindowEvent::Ime(ime),
+                    ..
+                } => app.handle_ime(ime),
+                Event::WindowEvent {
+                    event: WindowEvent::Resized(_),
+                    ..
+                } => app.resized(),
+                Event::WindowEvent {
+                    event: WindowEvent::ScaleFactorChanged { scale_factor, .. },
+                    ..
+                } => app.set_scale_factor(scale_factor),
+                // 다른 창에 완전히 가려지거나 화면이 잠기면 (플랫폼이 알려 줄 때) 그리지 않음
+                Event::WindowEvent {
+                    event: WindowEvent::Occluded(occluded),
+                    ..
+                } => app.occluded = occluded,
+                Event::Suspended => app.suspended = true,
+                Event::Resumed => app.suspended = false,
+                Event::WindowEvent {
+                    event: WindowEvent::CursorMoved { position, .. },
+                    ..
+                } => {
+                    let position = position.to_logical::<f32>(app.window.scale_factor());
+                    app.cursor = Some([position.x, position.y]);
+                }
+                Event::WindowEvent {
+                    event: WindowEvent::CursorLeft { .. },
+                    ..
+                } => app.cursor = None,
+                Event::WindowEvent {
+                    event:
+                        WindowEvent::MouseInput {
+                            state: ElementState::Pressed,
+                            button: MouseButton::Left,
+                            ..
+                        },
+                    ..
+                } => app.start_drag(),
+                Event::WindowEvent {
+                    event: WindowEvent::RedrawRequested,
+                    ..
+                } => {
+                    // 스왑체인 관련 오류는 다음 프레임에 재생성으로 복구를 시도
+                    if let Err(e) = app.render() {
+                        error!(error = %e, "렌더링 실패");
+                        app.recreate_swapchain = true;
+                    }
+                }
+                Event::LoopExiting => app.save_state(),
+                Event::AboutToWait => {
+                    let mut changed = app.poll_config();
+                    changed |= app.poll_text_file();
+                    changed |= app.poll_stdin();
+                    changed |= app.poll_text_source();
+                    changed |= app.poll_subtitles();
+                    changed |= app.poll_control();
+                    app.poll_monitors();
+                    #[cfg(feature = "global-hotkey")]
+                    {
+                        changed |= app.poll_global_keys(elwt);
+                    }
+                    #[cfg(feature = "scripting")]
+                    {
+                        changed |= app.poll_script();
+                    }
+                    app.record_history();
+                    app.finish_fade_out(elwt);
+                    app.schedule_redraw(elwt, changed);
+                }
+                _ => (),
+            }
+        })
+        .map_err(|e| RendererError::Window(e.into()))
 }
 
-fn main() {
-    // Vulkan 초기화
-    let library = VulkanLibrary::new().expect("Vulkan 라이브러리 로드 실패");
-    let instance = Instance::new(
-        library,
-        InstanceCreateInfo {
-            flags: InstanceCreateFlags::ENUMERATE_PORTABILITY,
-            ..Default::default()
-        },
-    )
-    .expect("Instance 생성 실패");
-
-    // 투명한 윈도우 생성
-    let event_loop = EventLoop::new();
-    let window = Arc::new(
-        WindowBuilder::new()
-            .with_title("투명 텍스트 렌더러 (Vulkan)")
-            .with_transparent(true) // 투명 윈도우 설정
-            .with_decorations(true)
-            .build(&event_loop)
-            .unwrap(),
-    );
+// 미리 기록한 합성 command buffer를 다시 쓸 수 있는지 판단하는 (레이어 리비전, 투명도, 배경 색)
+type StaticFrameKey = (u64, f32, [f32; 4]);
 
-    let surface = Surface::from_window(instance.clone(), window.clone()).unwrap();
+// Ctrl+Z로 되돌릴 수 있는 상태 (투명도는 트윈의 목표값)
+#[derive(Debug, Clone, PartialEq)]
+struct EditState {
+    text: String,
+    opacity: f32,
+    effects: TextEffects,
+    text_position: [f32; 2],
+    text_rotation: f32,
+    text_scale: f32,
+}
 
-    // Device 설정
-    let device_extensions = DeviceExtensions {
-        khr_swapchain: true,
-        ..DeviceExtensions::empty()
-    };
+struct App {
+    window: Arc<Window>,
+    device: Arc<Device>,
+    queue: Arc<Queue>,
+    // 그래픽 큐(`queue`)와 전송/비동기 컴퓨트 큐 (텍스트 렌더러를 다시 만들 때 전달)
+    queues: Queues,
+    swapchain: Arc<Swapchain>,
+    // 스왑체인 이미지에 합성하는 render pass와 framebuffer (또는 dynamic rendering 이미지 뷰)
+    render_path: RenderPath,
+    // MSAA 멀티샘플 이미지용 (텍스트 렌더러는 자체 할당자 사용)
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    viewport: Viewport,
+    frames: FramesInFlight,
+    // 장면이 그대로일 때 다시 제출하는 스왑체인 이미지별 합성 command buffer (스왑체인을 다시 만들면 비움)
+    static_frames: Vec<Option<(StaticFrameKey, Arc<PrimaryAutoCommandBuffer>)>>,
+    static_allocator: StandardCommandBufferAllocator,
+    // 구간별 GPU 시간 (큐가 타임스탬프를 지원하지 않으면 None)
+    profiler: Option<GpuProfiler>,
+    text_renderer: TextRenderer,
+    // 텍스트를 먼저 그려 두었다가 스왑체인 이미지에 합성하는 오프스크린 레이어
+    layer: TextLayer,
+    recreate_swapchain: bool,
+    present_mode: PresentModePreference,
+    // F11 전체 화면 (독점 모드를 쓸 수 있으면 스왑체인도 다시 만듦)
+    fullscreen: FullscreenToggle,
+    renderer_config: RendererConfig,
+    args: Args,
+    // 설정 파일 (config.toml)과 그 감시자, 설정이 반영된 키 배치
+    config: AppConfig,
+    config_watcher: Option<ConfigWatcher>,
+    keys: HashMap<KeyCode, Action>,
+    // --text-file로 지정한 텍스트 파일 감시 (설정 파일의 text보다 우선)
+    text_file: Option<FileWatcher>,
+    // --stdin/--stdin-append로 표준 입력의 줄을 표시 (텍스트 파일과 같이 설정 파일보다 우선)
+    stdin: Option<StdinLines>,
+    // --clock 같은 동적 텍스트 (설정 파일보다 우선)와 다음에 갱신할 시각
+    text_source: Option<Box<dyn TextSource>>,
+    text_source_due: Instant,
+    // --subtitles나 제어 메시지로 받은 시간 맞춤 자막 (설정 파일의 text보다 우선)
+    subtitles: Option<SubtitleQueue>,
+    // --control/--listen으로 연 제어 서버 (외부 프로그램이 JSON 메시지로 상태를 바꿈)
+    control: Option<ControlServer>,
+    // --monitor/--anchor나 설정 파일로 정한 창 위치 (모니터 구성이나 창 크기가 바뀌면 다시 계산)
+    placement: Option<Placement>,
+    monitors: MonitorWatcher,
+    // --mirrors로 다른 모니터에 띄운 창 (장치, 할당자, 텍스트 렌더러는 공유하고 스왑체인만 따로)
+    mirrors: Vec<MirrorWindow>,
+    // 창에 포커스가 없어도 동작하는 단축키 (등록에 실패하면 None)
+    #[cfg(feature = "global-hotkey")]
+    global_keys: Option<GlobalKeys>,
+    // F10이나 --panel로 띄우는 egui 컨트롤 패널 (만들지 못했으면 None)
+    #[cfg(feature = "egui-panel")]
+    panel: Option<ControlPanel>,
+    // --script나 overlay.rhai (설정과 키로 정한 값보다 우선, 파일을 저장하면 다시 컴파일)
+    #[cfg(feature = "scripting")]
+    script: Option<OverlayScript>,
 
-    let (physical_device, queue_family_index) = instance
-        .enumerate_physical_devices()
-        .expect("Physical device 열거 실패")
-        .filter(|p| p.supported_extensions().contains(&device_extensions))
-        .filter_map(|p| {
-            p.queue_family_properties()
-                .iter()
-                .enumerate()
-                .position(|(i, q)| {
-                    q.queue_flags.intersects(QueueFlags::GRAPHICS)
-                        && p.surface_support(i as u32, &surface).unwrap_or(false)
-                })
-                .map(|i| (p, i as u32))
-        })
-        .min_by_key(|(p, _)| match p.properties().device_type {
-            PhysicalDeviceType::DiscreteGpu => 0,
-            PhysicalDeviceType::IntegratedGpu => 1,
-            PhysicalDeviceType::VirtualGpu => 2,
-            PhysicalDeviceType::Cpu => 3,
-            PhysicalDeviceType::Other => 4,
-            _ => 5,
-        })
-        .expect("사용 가능한 device 없음");
+    // 상태 변수
+    text: String,
+    text_color: Option<[u8; 4]>,
+    // 투명도, 외곽선 두께, 발광 반지름/세기는 fade_duration초 동안 부드럽게 바뀜 (실제 시간 기준)
+    opacity: Tween,
+    effects: TextEffects,
+    // E/O 키로 돌아가며 고르는 프리셋 위치
+    current_effect: usize,
+    current_outline_color: usize,
+    outline_color: [u8; 4],
+    outline_width: Tween,
+    outline_softness: f32,
+    glow_radius: Tween,
+    glow_intensity: Tween,
+    glow_color: [u8; 4],
+    shadow_color: [u8; 4],
+    shadow_blur: f32,
+    // 컨트롤 패널에서 바꾸는 그림자 오프셋 (논리 픽셀)
+    shadow_offset: [f32; 2],
+    gradient: TextGradient,
+    // 방향키/R/숫자패드 +, -로 바꾸는 기본 텍스트의 위치 (픽셀, 창 중앙 기준), 회전 (도), 크기 배율
+    text_position: [f32; 2],
+    text_rotation: f32,
+    text_scale: f32,
+    fade_duration: f32,
+    fade_clock: Clock,
+    // 애니메이션 시계 (P: 일시 정지, Z/X: 배속)
+    clock: Clock,
+    // 소멸 방향 (true면 사라지는 중)과 전환한 시각 (clock 기준 초)
+    dissolve_out: bool,
+    dissolve_changed: f32,
+    text_entry: bool,
+    // 입력기(IME)가 조합 중인 글자 (텍스트 입력 모드에서 커서 앞에 표시, 확정되면 텍스트에 더함)
+    ime_preedit: String,
+    // 입력 모드의 커서 (텍스트의 바이트 위치)와 Shift로 선택을 시작한 위치
+    // 커서를 옮기면 깜박임을 다시 켜진 상태부터 시작
+    caret: usize,
+    selection_anchor: Option<usize>,
+    caret_moved: Instant,
+    modifiers: ModifiersState,
+    // Ctrl+C/Ctrl+V (Linux에서는 복사한 내용이 이 객체가 살아 있는 동안만 남으므로 계속 보관)
+    clipboard: Option<Clipboard>,
+    // 입력과 상관없이 이벤트 루프마다 상태를 비교해 바뀌었으면 기록
+    history: History<EditState>,
+    // F1-F8로 불러오는 스타일 프리셋 (사용자 설정 디렉터리를 찾지 못하면 None)
+    presets: Option<PresetStore>,
+    // 종료할 때 창 위치/크기와 표시 상태를 저장하는 파일 (--no-state면 None)
+    state_file: Option<StateFile>,
+    background: bool,
+    // I 키로 켜는 상태 줄 (기본 텍스트와 별도인 텍스트 객체, 렌더러를 다시 만들면 None)
+    show_status: bool,
+    status_line: Option<TextObjectId>,
+    // 왼쪽 위의 FPS와 프레임 시간 표시
+    show_stats: bool,
+    stats: FrameCounter,
+    stats_overlay: Option<TextObjectId>,
+    // 창보다 넓은 텍스트를 가로로 흘리는 전광판 모드와 뒤따르는 복사본 (렌더러를 다시 만들면 None)
+    marquee: Option<Marquee>,
+    marquee_copy: Option<TextObjectId>,
+    // --vertical, --tab-width, --align-tabs나 설정 파일로 정하는 레이아웃 (줄바꿈 너비는 창 크기로 정함)
+    text_layout: LayoutOptions,
+    // C 키로 켜는 클릭 통과 (마우스 입력이 창 아래의 프로그램으로 감)
+    click_through: bool,
+    // 창 전체의 투명도 배율 (나타날 때 0 → 1, H 키로 숨기거나 종료할 때 1 → 0)
+    // 사라지는 애니메이션이 끝나면 창을 숨기거나 quitting이면 종료
+    visibility: Tween,
+    quitting: bool,
+    // 게으른 다시 그리기: 창 이벤트나 외부 입력으로 바뀐 것이 있는지, 마지막 장면에 시간 효과가 있었는지
+    needs_redraw: bool,
+    scene_animated: bool,
+    // FPS 제한이 있을 때 다음 프레임을 그릴 시각
+    next_frame: Option<Instant>,
+    // 창이 가려졌는지, 앱이 일시 중단되었는지, 그래서 (최소화 포함) 그리기를 멈췄는지
+    occluded: bool,
+    suspended: bool,
+    render_paused: bool,
+    // 창 안의 마우스 위치 (논리 픽셀, 창 밖이면 None)
+    cursor: Option<[f32; 2]>,
+    // F12나 제어 메시지로 요청한 스크린샷 경로 (다음 프레임을 그린 뒤 저장)
+    screenshot: Option<PathBuf>,
+}
 
-    println!(
-        "사용 중인 GPU: {} ({:?})",
-        physical_device.properties().device_name,
-        physical_device.properties().device_type
-    );
+impl App {
+    fn new(event_loop: &EventLoop<()>, args: Args) -> Result<Self, RendererError> {
+        // --font <패밀리 이름>으로 시스템 폰트를 고를 수 있고, 없으면 내장 폰트 사용
+        // --subpixel은 서브픽셀 안티앨리어싱 (불투명한 배경용)
+        // --shaders <디렉터리>는 그 디렉터리의 GLSL 셰이더를 감시해 저장할 때마다 다시 불러옴
+        // --effect-shader <파일>은 텍스트 프래그먼트 셰이더를 사용자 셰이더(.spv 또는 GLSL)로 교체
+        // --config <파일>은 설정 파일 (없으면 현재 디렉터리의 config.toml이 있을 때만 사용)
+        // --text-file <파일>은 파일 내용을 표시하고 파일이 바뀔 때마다 갱신
+        // --stdin은 표준 입력의 줄마다 텍스트를 바꾸고, --stdin-append는 줄을 덧붙임
+        // --control <소켓 경로>는 JSON 제어 메시지를 받는 로컬 소켓 (Unix 외에는 127.0.0.1:포트)
+        // --listen ws://0.0.0.0:<포트>는 같은 메시지를 받는 WebSocket 서버 (websocket 기능 필요)
+        // --overlay는 항상 위, 테두리 없음, 작업 표시줄에서 숨김, 클릭 통과인 HUD용 창
+        // --monitor <번호> --anchor <위치> --margin <간격>은 모니터 안의 위치에 창을 놓음
+        // global-hotkey 기능으로 빌드하면 Ctrl+Alt 전역 단축키를 등록 (창에 포커스가 없어도 동작)
+        // --msaa <1|2|4|8>은 멀티샘플 안티앨리어싱 샘플 수 (장치가 지원하는 가장 큰 값 이하로 낮춤)
+        // --no-state는 지난 실행의 창 위치와 설정을 복원하지 않고, 종료할 때 저장하지도 않음
+        // --headless --out <파일>은 창 없이 그려 PNG로 저장하고 종료 (headless.rs)
+        // --hdr은 지원하면 scRGB 스왑체인으로 발광을 SDR 흰색보다 밝게 그림
+        // --mirrors <all|번호,...>는 다른 모니터에도 같은 텍스트를 띄움 (mirror.rs)
+        // --exclusive-fullscreen은 F11 전체 화면을 독점 모드로 (Windows, fullscreen.rs)
+        // --panel은 시작할 때 egui 컨트롤 패널을 띄움 (egui-panel 기능, F10으로 켜고 끔, panel.rs)
+        // --script <파일>은 프레임마다 실행해 텍스트, 투명도, 색, 변환을 정하는 rhai 스크립트 (scripting 기능, script.rs)
+        // --clock <형식>은 strftime 형식의 현재 시각을 매초 표시 (text_source.rs)
+        // --subtitles <파일.srt>는 자막을 시간에 맞춰 서서히 띄우고 지움 (--subtitle-fade <초>, subtitle.rs)
+        // --marquee <속도>는 창보다 넓은 텍스트를 초당 <속도> 픽셀로 흘림 (--marquee-gap <픽셀>은 복사본 사이 간격, marquee.rs)
+        // --vertical은 세로쓰기 (한글과 한자는 세우고 라틴 문자는 눕혀 위에서 아래로, 줄은 오른쪽에서 왼쪽으로)
+        // --tab-width <공백 수>는 탭 간격, --align-tabs는 탭으로 나뉜 칸을 열로 맞춤
+        // --countdown <시간>과 --stopwatch는 --timer-decimals <0-3>자리까지 흐르는 타이머 (Space: 시작/일시 정지, Backspace: 리셋)
+        // --system-stats <템플릿>은 {cpu}, {mem_used} 같은 시스템 통계를 --stats-interval <초>마다 표시 (system-stats 기능, system_stats.rs)
+        let (config, config_watcher) = load_config(args.config.as_deref());
+        let (state_file, saved_state) = load_saved_state(args.no_state);
+
+        // 투명한 윈도우 생성
+        let mut window_builder = WindowBuilder::new()
+            .with_title("투명 텍스트 렌더러 (Vulkan)")
+            .with_transparent(true) // 투명 윈도우 설정
+            .with_decorations(!args.overlay);
+        if args.overlay {
+            window_builder = overlay_window(window_builder);
+        }
+        if let Some([width, height]) = config.window.size() {
+            window_builder = window_builder.with_inner_size(LogicalSize::new(width, height));
+        }
+        if let Some([x, y]) = config.window.position() {
+            window_builder = window_builder.with_position(LogicalPosition::new(x, y));
+        }
+        // 지난 실행의 창 위치와 크기가 설정 파일보다 우선 (모니터 안의 위치를 지정했으면 아래에서 다시 놓음)
+        if let Some(saved) = saved_state.as_ref().and_then(|state| state.window) {
+            window_builder = window_builder
+                .with_inner_size(PhysicalSize::new(saved.width, saved.height))
+                .with_position(PhysicalPosition::new(saved.x, saved.y));
+        }
+        let window = Arc::new(
+            window_builder
+                .build(event_loop)
+                .map_err(|e| RendererError::Window(e.into()))?,
+        );
+        placement::print_monitors(&window);
+        if args.backdrop.is_some() || config.window.backdrop.is_some() {
+            apply_backdrop(&window, &args, &config.window);
+        }
+        let window_placement = window_placement(&args, &config.window);
+        if let Some(window_placement) = window_placement {
+            window_placement.apply(&window);
+        }
+        let monitors = MonitorWatcher::new(&window);
+
+        // Vulkan 초기화 (instance, GPU 선택, 큐, 스왑체인은 vulkan-context에서)
+        // HDR 색 공간과 독점 전체 화면(--exclusive-fullscreen)은 instance 확장이 필요함
+        let hdr = args.hdr || config.hdr.unwrap_or(false);
+        let exclusive_fullscreen =
+            args.exclusive_fullscreen || config.exclusive_fullscreen.unwrap_or(false);
+        let dynamic_rendering = args.dynamic_rendering || config.dynamic_rendering.unwrap_or(false);
+        let renderer_config = RendererConfig {
+            antialiasing: if args.subpixel {
+                Antialiasing::Subpixel
+            } else {
+                Antialiasing::Grayscale
+            },
+            mipmaps: config.mipmaps.unwrap_or(true),
+            ..RendererConfig::default()
+        };
+        let present_mode = renderer_config.present_mode;
+        let context = VulkanContext::with_options(
+            window.clone(),
+            ContextOptions {
+                instance_extensions: exclusive_instance_extensions(exclusive_fullscreen),
+                gpu: args.gpu.clone(),
+                present_mode,
+                hdr,
+                ..ContextOptions::default()
+            },
+            |physical_device, extensions, features| {
+                // 서브픽셀 안티앨리어싱용 dual-source blending (지원하는 장치에서만)
+                features.dual_src_blend = physical_device.supported_features().dual_src_blend;
+                // --dynamic-rendering은 지원하지 않는 장치면 render pass로 그림
+                enable_dynamic_rendering(physical_device, dynamic_rendering, extensions, features);
+                enable_exclusive_fullscreen(physical_device, exclusive_fullscreen, extensions);
+            },
+        )?;
+        let VulkanContext {
+            device,
+            queues,
+            swapchain,
+            images,
+            ..
+        } = context;
+        let dynamic_rendering = device.enabled_features().dynamic_rendering;
+        let exclusive_fullscreen = device.enabled_extensions().ext_full_screen_exclusive;
+        if swapchain.composite_alpha() == CompositeAlpha::Opaque {
+            warn!(
+                "이 surface는 불투명 합성만 지원해 창이 투명하게 보이지 않습니다. \
+                 window.opaque_background 색의 배경 위에 그립니다 \
+                 (Linux/X11이면 컴포지터를 실행하거나 Wayland 세션을 사용하세요)"
+            );
+        }
+        let queue = queues.graphics.clone();
 
-    let (device, mut queues) = Device::new(
-        physical_device.clone(),
-        DeviceCreateInfo {
-            queue_create_infos: vec![QueueCreateInfo {
-                queue_family_index,
-                ..Default::default()
-            }],
-            enabled_extensions: device_extensions,
-            ..Default::default()
-        },
-    )
-    .expect("Device 생성 실패");
-
-    let queue = queues.next().unwrap();
-
-    // Swapchain 생성 (투명도 지원)
-    let (mut swapchain, images) = {
-        let surface_capabilities = device
-            .physical_device()
-            .surface_capabilities(&surface, Default::default())
-            .expect("Surface capabilities 가져오기 실패");
-
-        let image_format = device
-            .physical_device()
-            .surface_formats(&surface, Default::default())
-            .unwrap()[0]
-            .0;
-
-        // 투명도를 위한 CompositeAlpha 설정
-        let composite_alpha = surface_capabilities
-            .supported_composite_alpha
-            .into_iter()
-            .find(|&alpha| alpha == CompositeAlpha::PreMultiplied || alpha == CompositeAlpha::PostMultiplied)
-            .or_else(|| surface_capabilities.supported_composite_alpha.into_iter().next())
-            .unwrap();
-
-        println!("Composite Alpha: {:?}", composite_alpha);
-
-        Swapchain::new(
+        // Render Pass (MSAA면 멀티샘플 이미지에 그린 뒤 스왑체인 이미지로 resolve)
+        let samples = select_sample_count(
+            device.physical_device(),
+            args.msaa.or(config.msaa).unwrap_or(1),
+        );
+        info!(samples = samples as u32, "MSAA");
+        let mut render_path = RenderPath::new(
+            &device,
+            swapchain.image_format(),
+            samples,
+            dynamic_rendering,
+        )?;
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+
+        // 텍스트는 오프스크린 레이어에 그린 뒤 스왑체인 이미지에 합성
+        let layer = TextLayer::new(device.clone(), render_path.target())?;
+
+        // 텍스트 렌더러 (폰트, 텍스처, 파이프라인 관리)
+        let mut text_renderer = create_text_renderer(
+            &device,
+            &queues,
+            layer.render_target(),
+            &args,
+            &config,
+            renderer_config,
+        )?;
+
+        let mut viewport = Viewport {
+            offset: [0.0, 0.0],
+            extent: window.inner_size().into(),
+            depth_range: 0.0..=1.0,
+        };
+
+        render_path.resize(&images, &memory_allocator, &mut viewport)?;
+        let scale_factor = window.scale_factor() as f32;
+        text_renderer.set_scale_factor(scale_factor)?;
+        let marquee = create_marquee(&args, &config);
+        let text_layout = text_layout(&args, &config);
+        text_renderer.set_layout_options(wrap_options(
+            logical_extent(&viewport, scale_factor),
+            marquee.is_some(),
+            text_layout,
+        ))?;
+        text_renderer.start_typewriter(Typewriter::default());
+
+        // 프레임별 펜스와 command buffer 할당자
+        let frames = FramesInFlight::new(device.clone(), &renderer_config);
+        let static_frames = vec![None; render_path.len()];
+        let static_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let profiler = GpuProfiler::new(&queue, frames.frames_in_flight())?;
+
+        let stdin = args.stdin.map(StdinLines::spawn);
+        let control = open_control_server(&args);
+        let mirrors = match &args.mirrors {
+            Some(monitors) => create_mirrors(
+                event_loop,
+                &window,
+                monitors,
+                window_placement,
+                (&queue, &render_path, &memory_allocator),
+                &renderer_config,
+            ),
+            None => Vec::new(),
+        };
+
+        // 컨트롤 패널은 합성한 레이어 위에 스왑체인 render pass에서 그림 (만들 수 없으면 경고만)
+        let show_panel = args.panel || config.panel.unwrap_or(false);
+        #[cfg(feature = "egui-panel")]
+        let panel = ControlPanel::new(
+            &window,
             device.clone(),
-            surface,
-            SwapchainCreateInfo {
-                min_image_count: surface_capabilities.min_image_count.max(2),
-                image_format,
-                image_extent: window.inner_size().into(),
-                image_usage: ImageUsage::COLOR_ATTACHMENT,
-                composite_alpha,
-                ..Default::default()
-            },
+            memory_allocator.clone(),
+            render_path.target(),
+            show_panel,
         )
-        .unwrap()
-    };
-
-    let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        .map_err(|e| warn!("컨트롤 패널을 사용할 수 없습니다: {e}"))
+        .ok();
+        #[cfg(not(feature = "egui-panel"))]
+        if show_panel {
+            warn!("--panel: egui-panel 기능 없이 빌드되어 컨트롤 패널을 띄울 수 없습니다");
+        }
 
-    // 폰트 로드 및 텍스트 렌더링
-    let font_data = include_bytes!("../NotoSansKR-Regular.ttf");
-    let font = Font::from_bytes(font_data as &[u8], FontSettings::default())
-        .expect("폰트 로드 실패");
+        let text_source = create_text_source(&args, &config);
+        let subtitles = create_subtitles(&args, &config);
+
+        // --script나 설정 파일의 script, 둘 다 없으면 현재 디렉터리의 overlay.rhai가 있을 때만
+        let script_path = args.script.clone().or_else(|| config.script.clone());
+        #[cfg(feature = "scripting")]
+        let script = script_path
+            .map(PathBuf::from)
+            .or_else(|| Some(PathBuf::from(DEFAULT_SCRIPT_PATH)).filter(|path| path.exists()))
+            .map(OverlayScript::load);
+        #[cfg(not(feature = "scripting"))]
+        if let Some(path) = script_path {
+            warn!("--script {path}: scripting 기능 없이 빌드되어 스크립트를 실행할 수 없습니다");
+        }
 
-    let text = "GPU 가속 투명 텍스트\n투명도: 100%\n효과: 일반";
-    let font_size = 48.0;
+        let mut app = Self {
+            window,
+            device,
+            queue,
+            queues,
+            swapchain,
+            render_path,
+            memory_allocator,
+            viewport,
+            frames,
+            static_frames,
+            static_allocator,
+            profiler,
+            text_renderer,
+            layer,
+            recreate_swapchain: false,
+            present_mode,
+            fullscreen: FullscreenToggle::new(exclusive_fullscreen),
+            renderer_config,
+            args,
+            config: config.clone(),
+            config_watcher,
+            keys: action::default_bindings(),
+            text_file: None,
+            stdin,
+            text_source,
+            text_source_due: Instant::now(),
+            subtitles,
+            control,
+            placement: window_placement,
+            monitors,
+            mirrors,
+            #[cfg(feature = "global-hotkey")]
+            global_keys: create_global_keys(&config),
+            #[cfg(feature = "egui-panel")]
+            panel,
+            #[cfg(feature = "scripting")]
+            script,
+            text: String::from(
+                "<size=56><b>GPU 가속</b> 투명 텍스트</size>\n\
+                 투명도: <color=#ffcc00>100%</color>\n효과: 일반",
+            ),
+            text_color: None,
+            opacity: Tween::new(1.0),
+            effects: EFFECT_PRESETS[0],
+            current_effect: 0,
+            current_outline_color: 0,
+            outline_color: OUTLINE_COLORS[0].1,
+            outline_width: Tween::new(TextParams::default().outline_width),
+            outline_softness: TextParams::default().outline_softness,
+            glow_radius: Tween::new(TextParams::default().glow_radius),
+            glow_intensity: Tween::new(TextParams::default().glow_intensity),
+            glow_color: TextParams::default().glow_color,
+            shadow_color: TextParams::default().shadow_color,
+            shadow_blur: TextParams::default().shadow_blur,
+            shadow_offset: TextParams::default().shadow_offset,
+            gradient: TextGradient::default(),
+            text_position: [0.0, 0.0],
+            text_rotation: 0.0,
+            text_scale: 1.0,
+            fade_duration: FADE_DURATION,
+            fade_clock: Clock::new(),
+            clock: Clock::new(),
+            dissolve_out: false,
+            dissolve_changed: f32::NEG_INFINITY,
+            text_entry: false,
+            ime_preedit: String::new(),
+            caret: 0,
+            selection_anchor: None,
+            caret_moved: Instant::now(),
+            modifiers: ModifiersState::empty(),
+            clipboard: Clipboard::new()
+                .map_err(|e| warn!("클립보드를 사용할 수 없습니다: {e}"))
+                .ok(),
+            history: History::new(DEFAULT_HISTORY_DEPTH),
+            presets: PresetStore::in_config_dir()
+                .map_err(|e| warn!("프리셋을 사용할 수 없습니다: {e}"))
+                .ok(),
+            state_file,
+            background: false,
+            show_status: false,
+            status_line: None,
+            show_stats: false,
+            stats: FrameCounter::new(),
+            stats_overlay: None,
+            marquee,
+            marquee_copy: None,
+            text_layout,
+            click_through: false,
+            visibility: Tween::new(0.0),
+            quitting: false,
+            needs_redraw: true,
+            scene_animated: false,
+            next_frame: None,
+            occluded: false,
+            suspended: false,
+            render_paused: false,
+            cursor: None,
+            screenshot: None,
+        };
+        // 창 위치/크기와 폰트는 이미 반영했으므로 나머지 값만 적용됨
+        app.apply_config(config)?;
+        if let Some(state) = saved_state {
+            app.restore_state(state);
+        }
+        if let Some(path) = app.args.text_file.clone() {
+            app.watch_text_file(path);
+        }
+        if app.stdin.is_some() {
+            info!("표준 입력에서 텍스트를 읽습니다");
+            app.text.clear();
+        }
+        if app.args.overlay {
+            app.set_click_through(true);
+        }
+        app.show();
 
-    // 텍스트를 이미지로 렌더링
-    let (texture_image, texture_width, texture_height) = create_text_texture(
-        &font,
-        text,
-        font_size,
-        device.clone(),
-        memory_allocator.clone(),
-        queue.clone(),
-    );
+        Ok(app)
+    }
 
-    let texture_image_view = ImageView::new_default(texture_image.clone()).unwrap();
-
-    // Sampler 생성
-    let sampler = Sampler::new(
-        device.clone(),
-        SamplerCreateInfo {
-            mag_filter: Filter::Linear,
-            min_filter: Filter::Linear,
-            address_mode: [SamplerAddressMode::ClampToEdge; 3],
-            ..Default::default()
-        },
-    )
-    .unwrap();
-
-    // Vertex Buffer 생성 (화면 중앙에 텍스트 배치)
-    let aspect_ratio = window.inner_size().width as f32 / window.inner_size().height as f32;
-    let text_scale = 0.5;
-    let vertices = [
-        TextVertex {
-            position: [-text_scale * aspect_ratio, -text_scale],
-            tex_coords: [0.0, 0.0],
-        },
-        TextVertex {
-            position: [text_scale * aspect_ratio, -text_scale],
-            tex_coords: [1.0, 0.0],
-        },
-        TextVertex {
-            position: [-text_scale * aspect_ratio, text_scale],
-            tex_coords: [0.0, 1.0],
-        },
-        TextVertex {
-            position: [text_scale * aspect_ratio, text_scale],
-            tex_coords: [1.0, 1.0],
-        },
-    ];
-
-    let vertex_buffer = Buffer::from_iter(
-        memory_allocator.clone(),
-        BufferCreateInfo {
-            usage: BufferUsage::VERTEX_BUFFER,
-            ..Default::default()
-        },
-        AllocationCreateInfo {
-            memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
-                | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
-            ..Default::default()
-        },
-        vertices,
-    )
-    .unwrap();
-
-    // 셰이더 정의
-    mod vs {
-        vulkano_shaders::shader! {
-            ty: "vertex",
-            src: r"
-                #version 460
-
-                layout(location = 0) in vec2 position;
-                layout(location = 1) in vec2 tex_coords;
-
-                layout(location = 0) out vec2 fragTexCoords;
-
-                void main() {
-                    gl_Position = vec4(position, 0.0, 1.0);
-                    fragTexCoords = tex_coords;
-                }
-            ",
-        }
-    }
-
-    mod fs {
-        vulkano_shaders::shader! {
-            ty: "fragment",
-            src: r"
-                #version 460
-
-                layout(location = 0) in vec2 fragTexCoords;
-                layout(location = 0) out vec4 outColor;
-
-                layout(set = 0, binding = 0) uniform sampler2D texSampler;
-
-                layout(push_constant) uniform PushConstants {
-                    float opacity;
-                    int effect_type;
-                    float outline_width;
-                    vec2 shadow_offset;
-                } pc;
-
-                void main() {
-                    vec4 texColor = texture(texSampler, fragTexCoords);
-
-                    if (pc.effect_type == 0) {
-                        // 일반
-                        outColor = vec4(texColor.rgb, texColor.a * pc.opacity);
-                    } else if (pc.effect_type == 1) {
-                        // 외곽선
-                        float alpha = texColor.a;
-                        vec2 texelSize = 1.0 / textureSize(texSampler, 0);
-                        float outline = 0.0;
-                        for (int x = -2; x <= 2; x++) {
-                            for (int y = -2; y <= 2; y++) {
-                                outline = max(outline, texture(texSampler, fragTexCoords + vec2(x, y) * texelSize * pc.outline_width).a);
-                            }
-                        }
-                        vec3 color = mix(vec3(1.0, 1.0, 0.0), texColor.rgb, alpha);
-                        outColor = vec4(color, max(alpha, outline * 0.8) * pc.opacity);
-                    } else if (pc.effect_type == 2) {
-                        // 그림자
-                        vec4 shadow = texture(texSampler, fragTexCoords + pc.shadow_offset);
-                        vec3 color = mix(shadow.rgb * 0.3, texColor.rgb, texColor.a);
-                        float alpha = max(texColor.a, shadow.a * 0.6);
-                        outColor = vec4(color, alpha * pc.opacity);
-                    } else if (pc.effect_type == 3) {
-                        // 발광
-                        float glow = 0.0;
-                        vec2 texelSize = 1.0 / textureSize(texSampler, 0);
-                        for (int x = -3; x <= 3; x++) {
-                            for (int y = -3; y <= 3; y++) {
-                                float dist = length(vec2(x, y));
-                                glow += texture(texSampler, fragTexCoords + vec2(x, y) * texelSize * 2.0).a / (1.0 + dist);
-                            }
-                        }
-                        vec3 glowColor = vec3(0.2, 0.8, 1.0);
-                        vec3 color = mix(glowColor * glow * 0.5, texColor.rgb, texColor.a);
-                        float alpha = max(texColor.a, glow * 0.3);
-                        outColor = vec4(color, alpha * pc.opacity);
-                    }
-                }
-            ",
+    // 설정 파일의 값을 적용 (생략된 항목은 현재 값 유지)
+    // 폰트나 창 설정은 이전 설정과 달라졌을 때만 다시 적용
+    fn apply_config(&mut self, config: AppConfig) -> Result<(), RendererError> {
+        if config.font_changed(&self.config) {
+            self.reload_font(&config)?;
+        }
+        if config.window != self.config.window {
+            if let Some([width, height]) = config.window.size() {
+                let _ = self
+                    .window
+                    .request_inner_size(LogicalSize::new(width, height));
+            }
+            if let Some([x, y]) = config.window.position() {
+                self.window.set_outer_position(LogicalPosition::new(x, y));
+            }
+            self.placement = window_placement(&self.args, &config.window);
+            if let Some(placement) = self.placement {
+                placement.apply(&self.window);
+            }
+            let previous = &self.config.window;
+            if (config.window.backdrop, config.window.backdrop_tint)
+                != (previous.backdrop, previous.backdrop_tint)
+            {
+                apply_backdrop(&self.window, &self.args, &config.window);
+            }
+        }
+        if let Some(click_through) = config.window.click_through {
+            self.set_click_through(click_through);
         }
-    }
 
-    let vs = vs::load(device.clone()).unwrap().entry_point("main").unwrap();
-    let fs = fs::load(device.clone()).unwrap().entry_point("main").unwrap();
+        self.marquee = create_marquee(&self.args, &config);
+        self.text_layout = text_layout(&self.args, &config);
+        self.text_renderer
+            .set_layout_options(self.layout_options())?;
+
+        let previous = &self.config;
+        if config.clock != previous.clock
+            || config.countdown != previous.countdown
+            || config.stopwatch != previous.stopwatch
+            || config.timer_decimals != previous.timer_decimals
+            || config.system_stats != previous.system_stats
+            || config.system_stats_interval != previous.system_stats_interval
+        {
+            self.text_source = create_text_source(&self.args, &config);
+            self.text_source_due = Instant::now();
+        }
+        let subtitles_changed = config.subtitles != previous.subtitles
+            || config.subtitle_fade != previous.subtitle_fade;
+        if subtitles_changed && self.args.subtitles.is_none() {
+            self.subtitles = create_subtitles(&self.args, &config);
+        }
+        let external_text = self.text_file.is_some()
+            || self.stdin.is_some()
+            || self.text_source.is_some()
+            || self.subtitles.is_some();
+        if let Some(text) = config.text.as_ref().filter(|_| !external_text) {
+            self.text = text.clone();
+        }
+        self.text_color = config.color.map(|color| color.0);
+        if let Some(duration) = config.fade_duration {
+            self.fade_duration = duration.max(0.0);
+        }
+        if let Some(depth) = config.history_depth {
+            self.history.set_depth(depth);
+        }
+        if let Some(bias) = config.lod_bias {
+            self.text_renderer.set_lod_bias(bias)?;
+        }
+        if let Some(opacity) = config.opacity {
+            self.opacity
+                .animate_to(opacity.clamp(0.0, 1.0), self.fade_duration);
+        }
+        if let Some(effects) = config.effects {
+            self.effects = effects.0;
+        }
+        if let Some(color) = config.outline_color {
+            self.outline_color = color.0;
+        }
+        if let Some(width) = config.outline_width {
+            self.set_outline_width(width);
+        }
+        if let Some(color) = config.glow_color {
+            self.glow_color = color.0;
+        }
+        if let Some(radius) = config.glow_radius {
+            self.set_glow_radius(radius);
+        }
+        if let Some(color) = config.shadow_color {
+            self.shadow_color = color.0;
+        }
+        if let Some(sigma) = config.shadow_blur {
+            self.set_shadow_blur(sigma);
+        }
+        if let Some(background) = config.background {
+            self.background = background;
+        }
+        self.keys = action::bindings(&config.keys);
+        #[cfg(feature = "global-hotkey")]
+        if config.global_keys != self.config.global_keys {
+            if let Some(global_keys) = &mut self.global_keys {
+                global_keys.set_bindings(&config.global_keys);
+            }
+        }
+        self.config = config;
 
-    // Render Pass
-    let render_pass = vulkano::single_pass_renderpass!(
-        device.clone(),
-        attachments: {
-            color: {
-                format: swapchain.image_format(),
-                samples: 1,
-                load_op: Clear,
-                store_op: Store,
-            },
-        },
-        pass: {
-            color: [color],
-            depth_stencil: {},
-        },
-    )
-    .unwrap();
-
-    // Graphics Pipeline
-    let pipeline = {
-        let vertex_input_state = TextVertex::per_vertex()
-            .definition(&vs.info().input_interface)
-            .unwrap();
-
-        let stages = [
-            PipelineShaderStageCreateInfo::new(vs),
-            PipelineShaderStageCreateInfo::new(fs),
-        ];
-
-        let layout = PipelineLayout::new(
-            device.clone(),
-            PipelineDescriptorSetLayoutCreateInfo::from_stages(&stages)
-                .into_pipeline_layout_create_info(device.clone())
-                .unwrap(),
-        )
-        .unwrap();
+        Ok(())
+    }
 
-        let subpass = Subpass::from(render_pass.clone(), 0).unwrap();
+    // 파일 내용을 표시하고 감시 시작 (파일이 아직 없어도 생기면 표시)
+    fn watch_text_file(&mut self, path: String) {
+        match FileWatcher::new(&path) {
+            Ok(watcher) => {
+                info!("텍스트 파일: {path}");
+                self.text_file = Some(watcher);
+                self.reload_text_file();
+            }
+            Err(e) => warn!("텍스트 파일 감시 실패 ({path}): {e}"),
+        }
+    }
 
-        // 블렌딩 활성화 (투명도 지원)
-        let mut color_blend_state = ColorBlendState::with_attachment_states(
-            subpass.num_color_attachments(),
-            ColorBlendAttachmentState::default(),
-        );
-        color_blend_state.attachments[0].blend = Some(vulkano::pipeline::graphics::color_blend::AttachmentBlend::alpha());
+    // 텍스트 파일이 바뀌었으면 다시 읽어 표시
+    fn poll_text_file(&mut self) -> bool {
+        let changed = self.text_file.as_ref().is_some_and(FileWatcher::changed);
+        if changed {
+            self.reload_text_file();
+        }
+        changed
+    }
 
-        GraphicsPipeline::new(
-            device.clone(),
-            None,
-            GraphicsPipelineCreateInfo {
-                stages: stages.into_iter().collect(),
-                vertex_input_state: Some(vertex_input_state),
-                input_assembly_state: Some(InputAssemblyState::default()),
-                viewport_state: Some(ViewportState::default()),
-                rasterization_state: Some(RasterizationState::default()),
-                multisample_state: Some(MultisampleState::default()),
-                color_blend_state: Some(color_blend_state),
-                dynamic_state: [DynamicState::Viewport].into_iter().collect(),
-                subpass: Some(subpass.into()),
-                ..GraphicsPipelineCreateInfo::layout(layout)
-            },
-        )
-        .unwrap()
-    };
+    // 파일 내용은 마크업으로 해석하고 끝의 줄바꿈은 무시
+    // (쓰는 중이라 읽지 못하면 이전 텍스트 유지, 다음 변경 때 다시 읽음)
+    fn reload_text_file(&mut self) {
+        let Some(watcher) = &self.text_file else {
+            return;
+        };
+        match std::fs::read_to_string(watcher.path()) {
+            Ok(text) => self.text = text.trim_end_matches(['\r', '\n']).to_owned(),
+            Err(e) => warn!(
+                "텍스트 파일을 읽을 수 없습니다 ({}): {e}",
+                watcher.path().display()
+            ),
+        }
+    }
 
-    let mut viewport = Viewport {
-        offset: [0.0, 0.0],
-        extent: window.inner_size().into(),
-        depth_range: 0.0..=1.0,
-    };
+    // 보여 줄 자막이 바뀌었으면 그 텍스트로 (자막 사이에는 빈 텍스트)
+    // `{\k}` 음절 시간이 있는 자막은 노래방 효과로 칠함
+    fn poll_subtitles(&mut self) -> bool {
+        let color = self.karaoke_color();
+        let Some(subtitles) = &mut self.subtitles else {
+            return false;
+        };
+        if !subtitles.poll() {
+            return false;
+        }
+        let cue = subtitles.current();
+        self.text = cue.map_or_else(String::new, |cue| cue.text.clone());
+        match cue.filter(|cue| !cue.karaoke.is_empty()) {
+            Some(cue) => self.text_renderer.start_karaoke(Karaoke {
+                color,
+                syllables: cue.karaoke.clone(),
+            }),
+            None => self.text_renderer.stop_karaoke(),
+        }
+        self.clamp_caret();
+        true
+    }
 
-    let mut framebuffers = window_size_dependent_setup(&images, render_pass.clone(), &mut viewport);
+    fn karaoke_color(&self) -> [u8; 4] {
+        self.config
+            .karaoke_color
+            .map_or(KARAOKE_COLOR, |color| color.0)
+    }
 
-    let descriptor_set_allocator = StandardDescriptorSetAllocator::new(device.clone(), Default::default());
-    let command_buffer_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+    // 자막이 서서히 나타나거나 사라지는 동안은 프레임마다 다시 그림
+    fn subtitles_fading(&self) -> bool {
+        self.subtitles
+            .as_ref()
+            .is_some_and(SubtitleQueue::is_fading)
+    }
 
-    let descriptor_set = PersistentDescriptorSet::new(
-        &descriptor_set_allocator,
-        pipeline.layout().set_layouts().get(0).unwrap().clone(),
-        [WriteDescriptorSet::image_view_sampler(
-            0,
-            texture_image_view.clone(),
-            sampler.clone(),
-        )],
-        [],
-    )
-    .unwrap();
+    // 타이머를 바꿨으면 바로 다시 만들도록 갱신 시각을 당김
+    fn update_timer(&mut self, name: &str, update: impl FnOnce(&mut dyn TextSource) -> bool) {
+        if self
+            .text_source
+            .as_mut()
+            .is_some_and(|source| update(source.as_mut()))
+        {
+            self.text_source_due = Instant::now();
+            info!("타이머 {name}");
+        } else {
+            info!("타이머가 없습니다 (--countdown 또는 --stopwatch)");
+        }
+    }
 
-    let mut recreate_swapchain = false;
-    let mut previous_frame_end = Some(sync::now(device.clone()).boxed());
+    // 동적 텍스트가 바뀔 시각이 지났으면 새 텍스트로 (같으면 다시 그리지 않음)
+    fn poll_text_source(&mut self) -> bool {
+        let Some(source) = &mut self.text_source else {
+            return false;
+        };
+        let now = Instant::now();
+        if now < self.text_source_due {
+            return false;
+        }
+        let text = source.text();
+        let wall_clock = SystemTime::now();
+        let delay = source
+            .next_update(wall_clock)
+            .duration_since(wall_clock)
+            .unwrap_or_default();
+        self.text_source_due = now + delay;
+        if text == self.text {
+            return false;
+        }
+        self.text = text;
+        self.clamp_caret();
+        true
+    }
 
-    // 상태 변수
-    let mut opacity = 1.0f32;
-    let mut current_effect = TextEffect::Normal;
-
-    println!("\n=== 컨트롤 ===");
-    println!("1-9: 투명도 조절 (10% - 90%)");
-    println!("0: 투명도 100%");
-    println!("E: 텍스트 효과 전환");
-    println!("ESC: 종료\n");
-
-    event_loop.run(move |event, _, control_flow| match event {
-        Event::WindowEvent {
-            event: WindowEvent::CloseRequested,
-            ..
-        } => {
-            *control_flow = ControlFlow::Exit;
+    fn poll_stdin(&mut self) -> bool {
+        match &self.stdin {
+            Some(stdin) => stdin.apply(&mut self.text),
+            None => false,
         }
-        Event::WindowEvent {
-            event: WindowEvent::KeyboardInput {
-                event: KeyEvent {
-                    physical_key: PhysicalKey::Code(key_code),
-                    ..
-                },
-                ..
-            },
-            ..
-        } => {
-            match key_code {
-                KeyCode::Escape => *control_flow = ControlFlow::Exit,
-                KeyCode::Digit1 => {
-                    opacity = 0.1;
-                    println!("투명도: 10%");
-                }
-                KeyCode::Digit2 => {
-                    opacity = 0.2;
-                    println!("투명도: 20%");
-                }
-                KeyCode::Digit3 => {
-                    opacity = 0.3;
-                    println!("투명도: 30%");
-                }
-                KeyCode::Digit4 => {
-                    opacity = 0.4;
-                    println!("투명도: 40%");
-                }
-                KeyCode::Digit5 => {
-                    opacity = 0.5;
-                    println!("투명도: 50%");
+    }
+
+    // 제어 소켓으로 받은 메시지 적용 (받은 메시지가 있었는지 돌려줌)
+    fn poll_control(&mut self) -> bool {
+        let Some(control) = &self.control else {
+            return false;
+        };
+        let messages: Vec<_> = control.poll().collect();
+        let received = !messages.is_empty();
+        for message in messages {
+            match message {
+                ControlMessage::SetText { text } => self.text = text,
+                ControlMessage::Subtitle {
+                    text,
+                    delay,
+                    duration,
+                } => {
+                    let fade = self
+                        .args
+                        .subtitle_fade
+                        .or(self.config.subtitle_fade)
+                        .unwrap_or(DEFAULT_SUBTITLE_FADE);
+                    self.subtitles
+                        .get_or_insert_with(|| SubtitleQueue::new(fade))
+                        .push(text, delay, duration);
                 }
-                KeyCode::Digit6 => {
-                    opacity = 0.6;
-                    println!("투명도: 60%");
+                ControlMessage::SetOpacity { opacity } => self.set_opacity(opacity.clamp(0.0, 1.0)),
+                ControlMessage::SetEffect { effect } => {
+                    self.effects = effect.0;
+                    info!("효과: {}", self.effects.name());
                 }
-                KeyCode::Digit7 => {
-                    opacity = 0.7;
-                    println!("투명도: 70%");
+                ControlMessage::SetStyle(style) => self.apply_style(style),
+                ControlMessage::SetPosition { x, y } => {
+                    self.window.set_outer_position(LogicalPosition::new(x, y));
                 }
-                KeyCode::Digit8 => {
-                    opacity = 0.8;
-                    println!("투명도: 80%");
+                ControlMessage::Typewriter { duration } => {
+                    let default = Typewriter::default();
+                    self.text_renderer.start_typewriter(Typewriter {
+                        duration: duration.map_or(default.duration, |d| d.max(0.0)),
+                        ..default
+                    });
                 }
-                KeyCode::Digit9 => {
-                    opacity = 0.9;
-                    println!("투명도: 90%");
+                ControlMessage::Karaoke { duration, color } => {
+                    let color = color.map_or_else(|| self.karaoke_color(), |color| color.0);
+                    self.text_renderer
+                        .start_karaoke(Karaoke::uniform(color, duration.max(0.0)));
                 }
-                KeyCode::Digit0 => {
-                    opacity = 1.0;
-                    println!("투명도: 100%");
+                ControlMessage::Dissolve { out } => {
+                    if out != self.dissolve_out {
+                        self.toggle_dissolve();
+                    }
                 }
-                KeyCode::KeyE => {
-                    current_effect = current_effect.next();
-                    println!("효과: {}", current_effect.name());
+                ControlMessage::Show => self.show(),
+                ControlMessage::Hide => self.hide(),
+                ControlMessage::SavePreset { name } => self.save_preset_named(name),
+                ControlMessage::LoadPreset { name } => {
+                    let preset = self.presets.as_ref().map(|presets| presets.named(&name));
+                    if let Some(preset) = preset {
+                        self.load_preset(preset);
+                    }
                 }
-                _ => {}
+                ControlMessage::Screenshot => self.screenshot = Some(screenshot_path()),
             }
         }
-        Event::WindowEvent {
-            event: WindowEvent::Resized(_),
-            ..
-        } => {
-            recreate_swapchain = true;
+        received
+    }
+
+    // 폰트나 글자 크기가 바뀌면 렌더러를 새로 만듦
+    fn reload_font(&mut self, config: &AppConfig) -> Result<(), RendererError> {
+        // 이전 렌더러의 버퍼를 GPU가 아직 쓰고 있을 수 있음
+        self.frames.wait_all();
+        self.text_renderer = create_text_renderer(
+            &self.device,
+            &self.queues,
+            self.layer.render_target(),
+            &self.args,
+            config,
+            self.renderer_config,
+        )?;
+        self.text_renderer
+            .set_scale_factor(self.window.scale_factor() as f32)?;
+        self.text_renderer
+            .set_layout_options(self.layout_options())?;
+        self.status_line = None;
+        self.stats_overlay = None;
+        self.marquee_copy = None;
+        Ok(())
+    }
+
+    // 현재 폰트, 색, 효과, 투명도, 위치 (투명도는 트윈의 목표값)
+    fn current_preset(&self, name: String) -> Preset {
+        Preset {
+            name,
+            slot: None,
+            font: self
+                .args
+                .family
+                .clone()
+                .or_else(|| self.config.font.clone()),
+            font_size: self.config.font_size,
+            color: self.text_color.map(Color),
+            effects: Some(Effects(self.effects)),
+            opacity: Some(self.opacity.target()),
+            position: Some(self.text_position),
+        }
+    }
+
+    // Shift+F1-F8 (이름은 파일에 있던 이름을 유지)
+    fn save_preset_slot(&mut self, slot: usize) {
+        let Some(presets) = &self.presets else {
+            return;
+        };
+        match presets.save_slot(slot, self.current_preset(String::new())) {
+            Ok(preset) => info!(
+                "프리셋 저장: F{slot} {} ({})",
+                preset.name,
+                presets.path().display()
+            ),
+            Err(e) => warn!("프리셋 저장 실패: {e}"),
         }
-        Event::RedrawEventsCleared => {
-            let image_extent: [u32; 2] = window.inner_size().into();
-            if image_extent.contains(&0) {
+    }
+
+    fn save_preset_named(&mut self, name: String) {
+        let Some(presets) = &self.presets else {
+            return;
+        };
+        match presets.save_named(self.current_preset(name)) {
+            Ok(preset) => info!(
+                "프리셋 저장: {} ({})",
+                preset.name,
+                presets.path().display()
+            ),
+            Err(e) => warn!("프리셋 저장 실패: {e}"),
+        }
+    }
+
+    // 프리셋에 없는 항목은 현재 값 유지
+    // 폰트와 글자 크기는 설정 파일 값처럼 다루므로, 설정 파일을 다시 저장하면 그 값으로 돌아감
+    fn load_preset(&mut self, preset: Result<Preset, PresetError>) {
+        let preset = match preset {
+            Ok(preset) => preset,
+            Err(e) => {
+                warn!("{e}");
+                return;
+            }
+        };
+        let mut config = self.config.clone();
+        config.font = preset.font.or(config.font);
+        config.font_size = preset.font_size.or(config.font_size);
+        if config.font_changed(&self.config) {
+            if let Err(e) = self.reload_font(&config) {
+                warn!("프리셋 폰트 적용 실패: {e}");
                 return;
             }
+            self.config = config;
+        }
+        if let Some(color) = preset.color {
+            self.text_color = Some(color.0);
+        }
+        if let Some(effects) = preset.effects {
+            self.effects = effects.0;
+        }
+        if let Some(opacity) = preset.opacity {
+            self.opacity
+                .animate_to(opacity.clamp(0.0, 1.0), self.fade_duration);
+        }
+        if let Some(position) = preset.position {
+            self.text_position = position;
+        }
+        info!("프리셋: {}", preset.name);
+    }
+
+    // 지난 실행에서 저장한 값 적용 (트윈은 애니메이션 없이 바로 바꿈)
+    fn restore_state(&mut self, state: SavedState) {
+        let external_text = self.args.text_file.is_some()
+            || self.stdin.is_some()
+            || self.text_source.is_some()
+            || self.subtitles.is_some();
+        if let Some(text) = state.text.filter(|_| !external_text) {
+            self.text = text;
+        }
+        if let Some(color) = state.color {
+            self.text_color = Some(color.0);
+        }
+        if let Some(opacity) = state.opacity {
+            self.opacity.set(opacity.clamp(0.0, 1.0));
+        }
+        if let Some(effects) = state.effects {
+            self.effects = effects.0;
+        }
+        if let Some(color) = state.outline_color {
+            self.outline_color = color.0;
+        }
+        if let Some(width) = state.outline_width {
+            self.outline_width.set(width.clamp(0.5, 8.0));
+        }
+        if let Some(softness) = state.outline_softness {
+            self.outline_softness = softness.clamp(0.0, 4.0);
+        }
+        if let Some(color) = state.glow_color {
+            self.glow_color = color.0;
+        }
+        if let Some(radius) = state.glow_radius {
+            self.glow_radius.set(radius.clamp(1.0, MAX_GLOW_RADIUS));
+        }
+        if let Some(intensity) = state.glow_intensity {
+            self.glow_intensity.set(intensity.clamp(0.5, 8.0));
+        }
+        if let Some(color) = state.shadow_color {
+            self.shadow_color = color.0;
+        }
+        if let Some(sigma) = state.shadow_blur {
+            self.shadow_blur = sigma.clamp(0.0, 2.5);
+        }
+        if let Some(background) = state.background {
+            self.background = background;
+        }
+        if let Some(click_through) = state.click_through {
+            self.set_click_through(click_through);
+        }
+        if let Some(position) = state.text_position {
+            self.text_position = position;
+        }
+        if let Some(rotation) = state.text_rotation {
+            self.text_rotation = rotation % 360.0;
+        }
+        if let Some(scale) = state.text_scale {
+            self.text_scale = scale.clamp(0.25, 4.0);
+        }
+    }
+
+    // 투명도와 효과 파라미터는 트윈의 목표값을 저장
+    // (Wayland처럼 창 위치를 알 수 없거나 전체 화면이면 창 항목은 저장하지 않음)
+    fn save_state(&self) {
+        let Some(state_file) = &self.state_file else {
+            return;
+        };
+        let external_text = self.text_file.is_some()
+            || self.stdin.is_some()
+            || self.text_source.is_some()
+            || self.subtitles.is_some();
+        let position = self
+            .window
+            .outer_position()
+            .ok()
+            .filter(|_| !self.fullscreen.is_active());
+        let size = self.window.inner_size();
+        let state = SavedState {
+            text: (!external_text).then(|| self.text.clone()),
+            color: self.text_color.map(Color),
+            opacity: Some(self.opacity.target()),
+            effects: Some(Effects(self.effects)),
+            outline_color: Some(Color(self.outline_color)),
+            outline_width: Some(self.outline_width.target()),
+            outline_softness: Some(self.outline_softness),
+            glow_color: Some(Color(self.glow_color)),
+            glow_radius: Some(self.glow_radius.target()),
+            glow_intensity: Some(self.glow_intensity.target()),
+            shadow_color: Some(Color(self.shadow_color)),
+            shadow_blur: Some(self.shadow_blur),
+            background: Some(self.background),
+            click_through: Some(self.click_through),
+            text_position: Some(self.text_position),
+            text_rotation: Some(self.text_rotation),
+            text_scale: Some(self.text_scale),
+            window: position.map(|position| SavedWindow {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+            }),
+        };
+        match state_file.save(&state) {
+            Ok(()) => info!("상태 저장: {}", state_file.path().display()),
+            Err(e) => warn!("{e}"),
+        }
+    }
+
+    // 제어 메시지의 스타일 적용 (생략된 항목은 현재 값 유지)
+    fn apply_style(&mut self, style: StyleUpdate) {
+        if let Some(color) = style.color {
+            self.text_color = Some(color.0);
+        }
+        if let Some(color) = style.outline_color {
+            self.outline_color = color.0;
+        }
+        if let Some(width) = style.outline_width {
+            self.set_outline_width(width);
+        }
+        if let Some(color) = style.glow_color {
+            self.glow_color = color.0;
+        }
+        if let Some(radius) = style.glow_radius {
+            self.set_glow_radius(radius);
+        }
+        if let Some(color) = style.shadow_color {
+            self.shadow_color = color.0;
+        }
+        if let Some(sigma) = style.shadow_blur {
+            self.set_shadow_blur(sigma);
+        }
+        if let Some(background) = style.background {
+            self.background = background;
+        }
+    }
+
+    // 전역 단축키로 받은 동작 실행 (창의 키 입력과 같지만 텍스트 입력 모드와 무관)
+    #[cfg(feature = "global-hotkey")]
+    fn poll_global_keys(&mut self, elwt: &EventLoopWindowTarget<()>) -> bool {
+        let Some(global_keys) = &self.global_keys else {
+            return false;
+        };
+        let actions: Vec<_> = global_keys.poll().collect();
+        let received = !actions.is_empty();
+        for action in actions {
+            self.perform(action, elwt);
+        }
+        received
+    }
 
-            previous_frame_end.as_mut().unwrap().cleanup_finished();
+    fn show(&mut self) {
+        self.window.set_visible(true);
+        for mirror in &self.mirrors {
+            mirror.set_visible(true);
+        }
+        self.visibility.animate_to(1.0, VISIBILITY_FADE_DURATION);
+    }
+
+    // 사라지는 애니메이션이 끝나면 finish_fade_out에서 창을 숨김
+    fn hide(&mut self) {
+        self.visibility.animate_to(0.0, VISIBILITY_FADE_DURATION);
+    }
 
-            if recreate_swapchain {
-                let (new_swapchain, new_images) = swapchain
-                    .recreate(SwapchainCreateInfo {
-                        image_extent,
-                        ..swapchain.create_info()
-                    })
-                    .expect("Swapchain 재생성 실패");
+    // 서서히 사라진 뒤 종료 (이미 숨겨져 있으면 바로 종료)
+    fn quit(&mut self, elwt: &EventLoopWindowTarget<()>) {
+        if self.quitting || self.visibility.value() <= 0.0 {
+            elwt.exit();
+            return;
+        }
+        self.quitting = true;
+        self.hide();
+    }
 
-                swapchain = new_swapchain;
-                framebuffers = window_size_dependent_setup(&new_images, render_pass.clone(), &mut viewport);
-                recreate_swapchain = false;
+    // 사라지는 애니메이션이 끝났으면 창을 숨기거나 종료
+    fn finish_fade_out(&mut self, elwt: &EventLoopWindowTarget<()>) {
+        if self.visibility.target() > 0.0 || !self.visibility.is_finished() {
+            return;
+        }
+        if self.quitting {
+            elwt.exit();
+        } else if self.window.is_visible() != Some(false) {
+            self.window.set_visible(false);
+            for mirror in &self.mirrors {
+                mirror.set_visible(false);
             }
+        }
+    }
 
-            let (image_index, suboptimal, acquire_future) =
-                match acquire_next_image(swapchain.clone(), None).map_err(Validated::unwrap) {
-                    Ok(r) => r,
-                    Err(VulkanError::OutOfDate) => {
-                        recreate_swapchain = true;
-                        return;
-                    }
-                    Err(e) => panic!("이미지 획득 실패: {e}"),
-                };
+    // 창 크기가 바뀌면 오른쪽/아래에 붙인 창이 어긋나므로 다시 놓음 (전체 화면이면 그대로)
+    fn resized(&mut self) {
+        self.recreate_swapchain = true;
+        if let Some(placement) = self.placement.filter(|_| !self.fullscreen.is_active()) {
+            placement.apply(&self.window);
+        }
+        let size = self.window.inner_size();
+        for mirror in &mut self.mirrors {
+            mirror.resize(size);
+        }
+    }
 
-            if suboptimal {
-                recreate_swapchain = true;
+    // 미러 창의 이벤트 (입력은 받지 않으므로 닫기와 크기 변경만 처리)
+    fn mirror_event(&mut self, window_id: WindowId, event: &WindowEvent) {
+        let Some(index) = self
+            .mirrors
+            .iter()
+            .position(|mirror| mirror.id() == window_id)
+        else {
+            return;
+        };
+        match event {
+            WindowEvent::CloseRequested => {
+                self.mirrors.remove(index);
             }
+            WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. } => {
+                self.mirrors[index].resized();
+                self.needs_redraw = true;
+            }
+            _ => {}
+        }
+    }
 
-            let mut builder = AutoCommandBufferBuilder::primary(
-                &command_buffer_allocator,
-                queue.queue_family_index(),
-                CommandBufferUsage::OneTimeSubmit,
-            )
-            .unwrap();
+    // 모니터가 연결/해제되거나 해상도가 바뀌면 창을 다시 놓음
+    fn poll_monitors(&mut self) {
+        if !self.monitors.changed(&self.window) {
+            return;
+        }
+        info!("모니터 구성이 바뀌었습니다");
+        placement::print_monitors(&self.window);
+        if let Some(placement) = self.placement {
+            placement.apply(&self.window);
+        }
+        for mirror in &self.mirrors {
+            mirror.place();
+        }
+    }
 
-            let push_constants = PushConstants {
-                opacity,
-                effect_type: current_effect.to_i32(),
-                outline_width: 2.0,
-                shadow_offset: [0.005, 0.005],
-            };
+    // 설정 파일이 바뀌었으면 다시 읽어 적용 (오류는 출력만 하고 이전 설정 유지)
+    fn poll_config(&mut self) -> bool {
+        let Some(result) = self.config_watcher.as_ref().and_then(ConfigWatcher::poll) else {
+            return false;
+        };
+        match result {
+            Ok(config) => match self.apply_config(config) {
+                Ok(()) => info!("설정 파일을 다시 불러왔습니다"),
+                Err(e) => warn!("설정 적용 실패: {e}"),
+            },
+            Err(e) => warn!("{e}"),
+        }
+        true
+    }
 
-            builder
-                .begin_render_pass(
-                    RenderPassBeginInfo {
-                        clear_values: vec![Some([0.0, 0.0, 0.0, 0.0].into())], // 투명 배경
-                        ..RenderPassBeginInfo::framebuffer(framebuffers[image_index as usize].clone())
-                    },
-                    SubpassBeginInfo {
-                        contents: SubpassContents::Inline,
-                        ..Default::default()
-                    },
-                )
-                .unwrap()
-                .set_viewport(0, [viewport.clone()].into_iter().collect())
-                .unwrap()
-                .bind_pipeline_graphics(pipeline.clone())
-                .unwrap()
-                .bind_descriptor_sets(
-                    PipelineBindPoint::Graphics,
-                    pipeline.layout().clone(),
-                    0,
-                    descriptor_set.clone(),
-                )
-                .unwrap()
-                .push_constants(pipeline.layout().clone(), 0, push_constants)
-                .unwrap()
-                .bind_vertex_buffers(0, vertex_buffer.clone())
-                .unwrap()
-                .draw(vertex_buffer.len() as u32, 1, 0, 0)
-                .unwrap()
-                .end_render_pass(Default::default())
-                .unwrap();
-
-            let command_buffer = builder.build().unwrap();
-
-            let future = previous_frame_end
-                .take()
-                .unwrap()
-                .join(acquire_future)
-                .then_execute(queue.clone(), command_buffer)
-                .unwrap()
-                .then_swapchain_present(
-                    queue.clone(),
-                    SwapchainPresentInfo::swapchain_image_index(swapchain.clone(), image_index),
-                )
-                .then_signal_fence_and_flush();
-
-            match future.map_err(Validated::unwrap) {
-                Ok(future) => {
-                    previous_frame_end = Some(future.boxed());
-                }
-                Err(VulkanError::OutOfDate) => {
-                    recreate_swapchain = true;
-                    previous_frame_end = Some(sync::now(device.clone()).boxed());
-                }
-                Err(e) => {
-                    println!("렌더링 실패: {e}");
-                    previous_frame_end = Some(sync::now(device.clone()).boxed());
-                }
+    // 다음 프레임을 언제 그릴지 정함 (FPS 제한, 게으른 모드에서는 바뀐 것이 있을 때만)
+    fn schedule_redraw(&mut self, elwt: &EventLoopWindowTarget<()>, changed: bool) {
+        self.needs_redraw |= changed;
+        let now = Instant::now();
+        if self.update_render_paused() {
+            // 프레임은 제출하지 않고 파일, 표준 입력, 제어 소켓과 창 상태만 가끔 확인
+            elwt.set_control_flow(ControlFlow::WaitUntil(now + PAUSED_POLL_INTERVAL));
+            return;
+        }
+        if self.lazy_redraw() && !self.needs_redraw && !self.is_animating() {
+            // 파일, 표준 입력, 제어 소켓은 이벤트 루프를 깨우지 않으므로 가끔 깨어나 확인
+            // (동적 텍스트와 자막은 타이머의 밀리초처럼 더 자주 바뀔 수 있으므로 바뀌는 시각에 맞춰)
+            let mut wake = now + LAZY_POLL_INTERVAL;
+            if self.text_source.is_some() {
+                wake = wake.min(self.text_source_due);
+            }
+            if let Some(change) = self.subtitles.as_ref().and_then(SubtitleQueue::next_change) {
+                wake = wake.min(change);
             }
+            elwt.set_control_flow(ControlFlow::WaitUntil(wake));
+            return;
+        }
+        if let Some(next_frame) = self.next_frame.filter(|&next_frame| next_frame > now) {
+            elwt.set_control_flow(ControlFlow::WaitUntil(next_frame));
+            return;
         }
-        _ => (),
-    });
-}
 
-fn create_text_texture(
-    font: &Font,
-    text: &str,
-    font_size: f32,
-    device: Arc<Device>,
-    memory_allocator: Arc<StandardMemoryAllocator>,
-    queue: Arc<vulkano::device::Queue>,
-) -> (Arc<Image>, u32, u32) {
-    use fontdue::layout::{CoordinateSystem, Layout, LayoutSettings, TextStyle};
-
-    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
-    layout.reset(&LayoutSettings {
-        max_width: Some(800.0),
-        max_height: Some(600.0),
-        ..LayoutSettings::default()
-    });
-    layout.append(&[font], &TextStyle::new(text, font_size, 0));
-
-    let width = 512;
-    let height = 256;
-    let mut buffer = vec![0u8; width * height];
-
-    for glyph in layout.glyphs() {
-        let (metrics, bitmap) = font.rasterize_config(glyph.key);
-        let x_pos = glyph.x as i32;
-        let y_pos = glyph.y as i32;
-
-        for y in 0..metrics.height {
-            for x in 0..metrics.width {
-                let px = x_pos + x as i32;
-                let py = y_pos + y as i32;
-
-                if px >= 0 && px < width as i32 && py >= 0 && py < height as i32 {
-                    let idx = (py * width as i32 + px) as usize;
-                    let glyph_idx = y * metrics.width + x;
-                    buffer[idx] = bitmap[glyph_idx];
-                }
-            }
+        elwt.set_control_flow(ControlFlow::Poll);
+        self.needs_redraw = false;
+        // 늦게 깨어나도 프레임 간격이 밀리지 않도록 지난 예정 시각에서 이어 감 (한 간격 넘게 늦으면 지금부터)
+        self.next_frame = self.frame_interval().map(|interval| match self.next_frame {
+            Some(next_frame) if now < next_frame + interval => next_frame + interval,
+            _ => now + interval,
+        });
+        self.window.request_redraw();
+    }
+
+    // 최소화되었거나 가려졌거나 일시 중단되었으면 그리기를 멈추고 true
+    // 멈출 때는 제출한 프레임이 끝날 때까지 기다리고, 다시 시작할 때는 스왑체인을 다시 만들어 바로 그림
+    fn update_render_paused(&mut self) -> bool {
+        let size = self.window.inner_size();
+        let minimized =
+            self.window.is_minimized() == Some(true) || size.width == 0 || size.height == 0;
+        let paused = self.suspended || self.occluded || minimized;
+        if paused == self.render_paused {
+            return paused;
         }
+        self.render_paused = paused;
+        if paused {
+            info!("창이 보이지 않아 그리기를 멈춥니다");
+            self.frames.wait_all();
+        } else {
+            info!("그리기를 다시 시작합니다");
+            self.recreate_swapchain = true;
+            self.needs_redraw = true;
+            self.next_frame = None;
+            // 멈춘 동안의 간격이 프레임 시간 통계에 섞이지 않도록
+            self.stats.reset();
+        }
+        paused
     }
 
-    // RGBA 변환
-    let rgba_buffer: Vec<u8> = buffer
-        .iter()
-        .flat_map(|&a| [255u8, 255u8, 255u8, a])
-        .collect();
-
-    let upload_buffer = Buffer::from_iter(
-        memory_allocator.clone(),
-        BufferCreateInfo {
-            usage: BufferUsage::TRANSFER_SRC,
-            ..Default::default()
-        },
-        AllocationCreateInfo {
-            memory_type_filter: MemoryTypeFilter::PREFER_HOST
-                | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
-            ..Default::default()
-        },
-        rgba_buffer,
-    )
-    .unwrap();
-
-    let image = Image::new(
-        memory_allocator.clone(),
-        ImageCreateInfo {
-            image_type: ImageType::Dim2d,
-            format: Format::R8G8B8A8_UNORM,
-            extent: [width as u32, height as u32, 1],
-            usage: ImageUsage::TRANSFER_DST | ImageUsage::SAMPLED,
-            ..Default::default()
-        },
-        AllocationCreateInfo::default(),
-    )
-    .unwrap();
-
-    let command_buffer_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
-    let mut builder = AutoCommandBufferBuilder::primary(
-        &command_buffer_allocator,
-        queue.queue_family_index(),
-        CommandBufferUsage::OneTimeSubmit,
-    )
-    .unwrap();
-
-    builder
-        .copy_buffer_to_image(CopyBufferToImageInfo::buffer_image(
-            upload_buffer,
-            image.clone(),
-        ))
-        .unwrap();
-
-    let command_buffer = builder.build().unwrap();
-    let future = sync::now(device.clone())
-        .then_execute(queue.clone(), command_buffer)
-        .unwrap()
-        .then_signal_fence_and_flush()
-        .unwrap();
-
-    future.wait(None).unwrap();
-
-    (image, width as u32, height as u32)
-}
-
-fn window_size_dependent_setup(
-    images: &[Arc<Image>],
-    render_pass: Arc<vulkano::render_pass::RenderPass>,
-    viewport: &mut Viewport,
-) -> Vec<Arc<Framebuffer>> {
-    let extent = images[0].extent();
-    viewport.extent = [extent[0] as f32, extent[1] as f32];
-
-    images
-        .iter()
-        .map(|image| {
-            let view = ImageView::new_default(image.clone()).unwrap();
-            Framebuffer::new(
-                render_pass.clone(),
-                FramebufferCreateInfo {
-                    attachments: vec![view],
-                    ..Default::default()
-                },
-            )
-            .unwrap()
+    // 명령줄 --max-fps가 설정 파일보다 우선 (0 이하면 제한 없음, 1-1000으로 제한)
+    fn frame_interval(&self) -> Option<Duration> {
+        let max_fps = self.args.max_fps.or(self.config.max_fps)?;
+        (max_fps > 0.0)
+            .then(|| Duration::from_secs_f32(1.0 / max_fps.clamp(MIN_MAX_FPS, MAX_MAX_FPS)))
+    }
+
+    fn lazy_redraw(&self) -> bool {
+        self.args.lazy || self.config.lazy_redraw == Some(true)
+    }
+
+    // 가만히 있어도 다음 프레임이 달라지는지 (트윈, 소멸, 시간 효과, 입력 커서 깜박임, 통계 표시, 패널, 스크립트)
+    fn is_animating(&self) -> bool {
+        let tweening = [
+            &self.opacity,
+            &self.outline_width,
+            &self.glow_radius,
+            &self.glow_intensity,
+            &self.visibility,
+        ]
+        .iter()
+        .any(|tween| !tween.is_finished());
+        let progress = self.dissolve_progress(self.clock.elapsed());
+        let dissolving = if self.dissolve_out {
+            progress < 1.0
+        } else {
+            progress > 0.0
+        };
+        let scrolling = self.marquee_copy.is_some();
+        let timed = !self.clock.is_paused() && (dissolving || self.scene_animated || scrolling);
+        tweening
+            || timed
+            || self.text_entry
+            || self.show_stats
+            || self.panel_animating()
+            || self.script_running()
+            || self.subtitles_fading()
+    }
+
+    // 스크립트는 시간에 따라 값을 바꿀 수 있으므로 프레임마다 실행
+    #[cfg(feature = "scripting")]
+    fn script_running(&self) -> bool {
+        self.script.is_some()
+    }
+
+    #[cfg(not(feature = "scripting"))]
+    fn script_running(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "scripting")]
+    fn poll_script(&mut self) -> bool {
+        self.script.as_mut().is_some_and(OverlayScript::poll)
+    }
+
+    // 스크립트가 정한 값만 적용 (투명도는 트윈 없이 바로, 크기 배율은 키와 같은 범위로 제한)
+    #[cfg(feature = "scripting")]
+    fn run_script(&mut self) {
+        let time = self.clock.elapsed();
+        let extent = self.logical_extent();
+        let Some(output) = self
+            .script
+            .as_mut()
+            .and_then(|script| script.run(time, extent))
+        else {
+            return;
+        };
+        if let Some(text) = output.text.filter(|text| *text != self.text) {
+            self.text = text;
+            self.clamp_caret();
+        }
+        if let Some(opacity) = output.opacity {
+            self.opacity.set(opacity);
+        }
+        if let Some(color) = output.color {
+            self.text_color = Some(color);
+        }
+        if let Some(position) = output.position {
+            self.text_position = position;
+        }
+        if let Some(rotation) = output.rotation {
+            self.text_rotation = rotation;
+        }
+        if let Some(scale) = output.scale {
+            self.text_scale = scale.clamp(0.25, 4.0);
+        }
+    }
+
+    #[cfg(feature = "egui-panel")]
+    fn panel_animating(&self) -> bool {
+        self.panel.as_ref().is_some_and(ControlPanel::is_animating)
+    }
+
+    #[cfg(not(feature = "egui-panel"))]
+    fn panel_animating(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "egui-panel")]
+    fn panel_visible(&self) -> bool {
+        self.panel.as_ref().is_some_and(ControlPanel::is_visible)
+    }
+
+    #[cfg(not(feature = "egui-panel"))]
+    fn panel_visible(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "egui-panel")]
+    fn panel_event(&mut self, event: &WindowEvent) -> bool {
+        match &mut self.panel {
+            Some(panel) => panel.on_window_event(&self.window, event),
+            None => false,
+        }
+    }
+
+    // 패널을 한 프레임 실행하고 바뀐 값만 반영 (텍스처 업로드는 render pass 전에 기록)
+    #[cfg(feature = "egui-panel")]
+    fn update_panel<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+    ) -> Result<(), RendererError> {
+        let Some(panel) = &mut self.panel else {
+            return Ok(());
+        };
+        let before = PanelValues {
+            text: self.text.clone(),
+            opacity: self.opacity.target(),
+            outline_width: self.outline_width.target(),
+            shadow_offset: self.shadow_offset,
+            glow_radius: self.glow_radius.target(),
+            font_size: self.text_renderer.font_size(),
+        };
+        let mut values = before.clone();
+        panel.update(
+            &self.window,
+            &mut values,
+            builder,
+            self.swapchain.image_extent(),
+        )?;
+        if values == before {
+            return Ok(());
+        }
+
+        if values.text != before.text {
+            // 입력 모드의 커서는 바뀐 텍스트의 끝으로
+            self.text = values.text;
+            self.caret = self.text.len();
+            self.selection_anchor = None;
+        }
+        if values.opacity != before.opacity {
+            self.set_opacity(values.opacity);
+        }
+        if values.outline_width != before.outline_width {
+            self.set_outline_width(values.outline_width);
+        }
+        if values.glow_radius != before.glow_radius {
+            self.set_glow_radius(values.glow_radius);
+        }
+        self.shadow_offset = values.shadow_offset;
+        if values.font_size != before.font_size {
+            info!("글자 크기: {}px", values.font_size);
+            self.config.font_size = Some(values.font_size);
+            let config = self.config.clone();
+            self.reload_font(&config)?;
+        }
+        Ok(())
+    }
+
+    fn handle_key(&mut self, event: &KeyEvent, elwt: &EventLoopWindowTarget<()>) {
+        if event.state != ElementState::Pressed {
+            return;
+        }
+        let PhysicalKey::Code(key_code) = event.physical_key else {
+            return;
+        };
+
+        if self.modifiers.control_key() {
+            match key_code {
+                KeyCode::KeyC => return self.copy_text(),
+                KeyCode::KeyV => return self.paste_text(),
+                KeyCode::KeyZ if self.modifiers.shift_key() => return self.redo(),
+                KeyCode::KeyZ => return self.undo(),
+                KeyCode::KeyA if self.text_entry => {
+                    self.selection_anchor = Some(0);
+                    self.caret = self.text.len();
+                    return self.caret_changed();
+                }
+                _ => {}
+            }
+        }
+
+        let action = self.keys.get(&key_code).copied();
+
+        // 텍스트 입력 모드에서는 키 입력을 텍스트로 처리
+        // (입력기가 조합 중인 키는 입력기가 처리하므로 무시)
+        if self.text_entry {
+            if !self.ime_preedit.is_empty() {
+                return;
+            }
+            self.edit_text(key_code, action, event);
+            return;
+        }
+
+        if let Some(action) = action {
+            self.perform(action, elwt);
+        }
+    }
+
+    fn edit_state(&self) -> EditState {
+        EditState {
+            text: self.text.clone(),
+            opacity: self.opacity.target(),
+            effects: self.effects,
+            text_position: self.text_position,
+            text_rotation: self.text_rotation,
+            text_scale: self.text_scale,
+        }
+    }
+
+    fn record_history(&mut self) {
+        // 스크립트가 프레임마다 바꾸는 값으로 기록이 넘치지 않도록
+        if self.script_running() {
+            return;
+        }
+        let state = self.edit_state();
+        self.history.record(state);
+    }
+
+    // 되돌리기 전에 아직 기록하지 않은 변경(같은 루프에서 입력한 키 등)을 먼저 기록
+    fn undo(&mut self) {
+        self.record_history();
+        match self.history.undo().cloned() {
+            Some(state) => {
+                self.restore(state);
+                info!("되돌리기");
+            }
+            None => info!("되돌릴 변경이 없습니다"),
+        }
+    }
+
+    fn redo(&mut self) {
+        self.record_history();
+        match self.history.redo().cloned() {
+            Some(state) => {
+                self.restore(state);
+                info!("다시 실행");
+            }
+            None => info!("다시 실행할 변경이 없습니다"),
+        }
+    }
+
+    // 투명도는 다른 키 입력과 같이 부드럽게 바뀜
+    fn restore(&mut self, state: EditState) {
+        self.text = state.text;
+        self.opacity.animate_to(state.opacity, self.fade_duration);
+        self.effects = state.effects;
+        self.text_position = state.text_position;
+        self.text_rotation = state.text_rotation;
+        self.text_scale = state.text_scale;
+        if self.text_entry {
+            self.clamp_caret();
+            self.caret_changed();
+        }
+    }
+
+    // 입력 모드의 키 처리 (Shift와 함께 커서를 옮기면 선택 영역을 넓힘)
+    fn edit_text(&mut self, key_code: KeyCode, action: Option<Action>, event: &KeyEvent) {
+        self.clamp_caret();
+        let shift = self.modifiers.shift_key();
+        let enter = matches!(key_code, KeyCode::Enter | KeyCode::NumpadEnter);
+        match key_code {
+            _ if enter && shift => self.insert_text("\n"),
+            _ if key_code == KeyCode::Escape || action == Some(Action::TextEntry) || enter => {
+                return self.set_text_entry(false);
+            }
+            KeyCode::ArrowLeft => self.move_caret(prev_grapheme(&self.text, self.caret), shift),
+            KeyCode::ArrowRight => self.move_caret(next_grapheme(&self.text, self.caret), shift),
+            KeyCode::Home => {
+                self.move_caret(self.text_renderer.line_range(self.caret).start, shift)
+            }
+            KeyCode::End => self.move_caret(self.text_renderer.line_range(self.caret).end, shift),
+            KeyCode::ArrowUp => {
+                self.move_caret(self.text_renderer.offset_below(self.caret, -1), shift)
+            }
+            KeyCode::ArrowDown => {
+                self.move_caret(self.text_renderer.offset_below(self.caret, 1), shift)
+            }
+            KeyCode::Backspace => {
+                if !self.delete_selection() {
+                    let start = prev_grapheme(&self.text, self.caret);
+                    self.text.replace_range(start..self.caret, "");
+                    self.caret = start;
+                }
+            }
+            KeyCode::Delete => {
+                if !self.delete_selection() {
+                    let end = next_grapheme(&self.text, self.caret);
+                    self.text.replace_range(self.caret..end, "");
+                }
+            }
+            _ => {
+                let Some(key_text) = &event.text else {
+                    return;
+                };
+                let key_text: String = key_text.chars().filter(|c| !c.is_control()).collect();
+                if key_text.is_empty() {
+                    return;
+                }
+                self.insert_text(&key_text);
+            }
+        }
+        self.caret_changed();
+    }
+
+    fn move_caret(&mut self, offset: usize, select: bool) {
+        if select {
+            self.selection_anchor.get_or_insert(self.caret);
+        } else {
+            self.selection_anchor = None;
+        }
+        self.caret = offset;
+    }
+
+    // 선택한 범위 (비어 있으면 None)
+    fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.selection_anchor?;
+        (anchor != self.caret).then(|| anchor.min(self.caret)..anchor.max(self.caret))
+    }
+
+    // 선택한 범위를 지우고 커서를 그 자리로 옮김 (선택이 없었으면 false)
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.selection_anchor = None;
+        let Some(range) = selection else {
+            return false;
+        };
+        self.caret = range.start;
+        self.text.replace_range(range, "");
+        true
+    }
+
+    // 선택한 범위를 바꾸거나 커서 위치에 끼워 넣음
+    fn insert_text(&mut self, text: &str) {
+        self.delete_selection();
+        self.text.insert_str(self.caret, text);
+        self.caret += text.len();
+    }
+
+    // 파일, 표준 입력, 제어 소켓 등으로 텍스트가 바뀌어 커서가 범위를 벗어났으면 글자 경계로 당김
+    fn clamp_caret(&mut self) {
+        self.caret = floor_char(&self.text, self.caret);
+        self.selection_anchor = self
+            .selection_anchor
+            .map(|anchor| floor_char(&self.text, anchor));
+    }
+
+    // 텍스트나 커서가 바뀐 뒤 다시 레이아웃하고 입력기 후보 창을 커서로 옮김
+    fn caret_changed(&mut self) {
+        self.caret_moved = Instant::now();
+        if let Err(e) = self.text_renderer.set_styled_text(&self.styled_text()) {
+            warn!("텍스트 갱신 실패: {e}");
+        }
+        self.update_ime_cursor_area();
+    }
+
+    fn perform(&mut self, action: Action, elwt: &EventLoopWindowTarget<()>) {
+        if let Some(opacity) = action.opacity() {
+            self.set_opacity(opacity);
+            return;
+        }
+        // Shift와 함께 누르면 저장
+        if let Some(slot) = action.preset_slot() {
+            if self.modifiers.shift_key() {
+                self.save_preset_slot(slot);
+            } else if let Some(preset) = self.presets.as_ref().map(|presets| presets.slot(slot)) {
+                self.load_preset(preset);
+            }
+            return;
+        }
+
+        match action {
+            Action::Quit => self.quit(elwt),
+            Action::TextEntry => self.set_text_entry(true),
+            Action::NextEffect => {
+                self.current_effect = (self.current_effect + 1) % EFFECT_PRESETS.len();
+                self.effects = EFFECT_PRESETS[self.current_effect];
+                info!("효과: {}", self.effects.name());
+            }
+            Action::NextOutlineColor => {
+                self.current_outline_color =
+                    (self.current_outline_color + 1) % OUTLINE_COLORS.len();
+                let (name, color) = OUTLINE_COLORS[self.current_outline_color];
+                self.outline_color = color;
+                info!("외곽선 색: {name}");
+            }
+            Action::OutlineThinner => self.set_outline_width(self.outline_width.target() - 0.5),
+            Action::OutlineThicker => self.set_outline_width(self.outline_width.target() + 0.5),
+            Action::OutlineSharper => self.set_outline_softness(self.outline_softness - 0.5),
+            Action::OutlineSofter => self.set_outline_softness(self.outline_softness + 0.5),
+            Action::GlowSmaller => self.set_glow_radius(self.glow_radius.target() - 1.0),
+            Action::GlowLarger => self.set_glow_radius(self.glow_radius.target() + 1.0),
+            Action::GlowDimmer => self.set_glow_intensity(self.glow_intensity.target() - 0.5),
+            Action::GlowBrighter => self.set_glow_intensity(self.glow_intensity.target() + 0.5),
+            Action::ShadowSharper => self.set_shadow_blur(self.shadow_blur - 0.5),
+            Action::ShadowBlurrier => self.set_shadow_blur(self.shadow_blur + 0.5),
+            Action::Dissolve => self.toggle_dissolve(),
+            Action::Pause => {
+                self.clock.set_paused(!self.clock.is_paused());
+                info!("애니메이션 일시 정지: {}", self.clock.is_paused());
+            }
+            Action::SlowDown => self.set_clock_speed(self.clock.speed() / 2.0),
+            Action::SpeedUp => self.set_clock_speed(self.clock.speed() * 2.0),
+            Action::Typewriter => {
+                self.text_renderer.start_typewriter(Typewriter::default());
+                info!("타자기 애니메이션 다시 시작");
+            }
+            Action::Gradient => {
+                self.gradient = match self.gradient.mode {
+                    GradientMode::Linear => TextGradient::rainbow(),
+                    GradientMode::Rainbow => TextGradient::default(),
+                };
+                info!("그라데이션: {:?}", self.gradient.mode);
+            }
+            Action::Background => {
+                self.background = !self.background;
+                info!("배경 상자: {}", if self.background { "켜짐" } else { "꺼짐" });
+            }
+            Action::StatusLine => {
+                self.show_status = !self.show_status;
+                info!("상태 줄: {}", if self.show_status { "켜짐" } else { "꺼짐" });
+            }
+            Action::Stats => {
+                self.show_stats = !self.show_stats;
+                self.stats.reset();
+                info!("통계 표시: {}", if self.show_stats { "켜짐" } else { "꺼짐" });
+            }
+            Action::MoveLeft => self.move_text(-MOVE_STEP, 0.0),
+            Action::MoveRight => self.move_text(MOVE_STEP, 0.0),
+            Action::MoveUp => self.move_text(0.0, -MOVE_STEP),
+            Action::MoveDown => self.move_text(0.0, MOVE_STEP),
+            Action::Rotate => {
+                self.text_rotation = (self.text_rotation + ROTATE_STEP) % 360.0;
+                info!("회전: {}°", self.text_rotation);
+            }
+            Action::ScaleUp => self.set_text_scale(self.text_scale * 1.25),
+            Action::ScaleDown => self.set_text_scale(self.text_scale / 1.25),
+            Action::ClickThrough => self.set_click_through(!self.click_through),
+            Action::Visibility => {
+                if self.visibility.target() > 0.0 {
+                    self.hide();
+                } else {
+                    self.show();
+                }
+            }
+            Action::PresentMode => {
+                // 프레젠트 모드는 스왑체인 재생성 시 적용
+                self.present_mode = self.present_mode.next();
+                self.recreate_swapchain = true;
+                info!("프레젠트 모드 요청: {}", self.present_mode.name());
+            }
+            Action::Fullscreen => {
+                // 창 크기가 바뀌면 Resized로도 다시 만들지만 독점 모드 전환은 크기와 상관없이 필요
+                self.fullscreen.toggle(&self.window);
+                self.recreate_swapchain = true;
+            }
+            Action::Screenshot => self.screenshot = Some(screenshot_path()),
+            Action::Panel => self.toggle_panel(),
+            Action::TimerToggle => {
+                self.update_timer("시작/일시 정지", |source| source.toggle_timer())
+            }
+            Action::TimerReset => self.update_timer("리셋", |source| source.reset_timer()),
+            // 투명도 동작은 위에서 처리
+            _ => {}
+        }
+    }
+
+    // 입력한 텍스트는 마크업으로 해석 (예: <color=#ff0000>빨강</color>)
+    // 설정 파일의 기본 색은 맨 앞 구간으로 넣어 마크업 색이 우선하도록 함
+    // 입력 모드에서는 커서 위치가 맞도록 마크업을 해석하지 않고 그대로 보여 주며,
+    // 조합 중인 글자는 커서 위치에 끼워 넣음
+    fn styled_text(&self) -> StyledText {
+        if self.text_entry {
+            let caret = floor_char(&self.text, self.caret);
+            let style = self
+                .text_color
+                .map_or_else(SpanStyle::default, SpanStyle::color);
+            let mut styled = StyledText::default();
+            styled
+                .push(&self.text[..caret], style)
+                .push(&self.ime_preedit, SpanStyle::color([120, 200, 255, 255]))
+                .push(&self.text[caret..], style);
+            return styled;
+        }
+        styled_markup(&self.text, self.text_color)
+    }
+
+    // 입력 모드에서 선택 영역과 깜박이는 커서 (조합 중에는 선택 영역을 표시하지 않음)
+    fn edit_highlights(&self) -> Vec<TextHighlight> {
+        if !self.text_entry {
+            return Vec::new();
+        }
+        let mut highlights = Vec::new();
+        if let Some(range) = self.selection().filter(|_| self.ime_preedit.is_empty()) {
+            highlights.extend(
+                self.text_renderer
+                    .selection_rects(range)
+                    .into_iter()
+                    .map(|rect| TextHighlight {
+                        rect,
+                        color: SELECTION_COLOR,
+                    }),
+            );
+        }
+        let blink = self.caret_moved.elapsed().as_secs_f32() % CARET_BLINK_PERIOD;
+        if blink < CARET_BLINK_PERIOD / 2.0 {
+            let offset = floor_char(&self.text, self.caret) + self.ime_preedit.len();
+            highlights.push(TextHighlight {
+                rect: self.text_renderer.caret_rect(offset, CARET_WIDTH),
+                color: CARET_COLOR,
+            });
+        }
+        highlights
+    }
+
+    // 표시 중인 텍스트를 마크업 그대로 복사 (입력 모드에서 선택한 범위가 있으면 그 부분만)
+    fn copy_text(&mut self) {
+        let text = match self.selection().filter(|_| self.text_entry) {
+            Some(range) => self.text[range].to_owned(),
+            None => self.text.clone(),
+        };
+        let Some(clipboard) = &mut self.clipboard else {
+            return;
+        };
+        match clipboard.set_text(text) {
+            Ok(()) => info!("텍스트를 복사했습니다"),
+            Err(e) => warn!("복사 실패: {e}"),
+        }
+    }
+
+    // 클립보드의 텍스트로 바꿈 (입력 모드에서는 커서 위치에 넣음)
+    fn paste_text(&mut self) {
+        let Some(clipboard) = &mut self.clipboard else {
+            return;
+        };
+        let text = match clipboard.get_text() {
+            Ok(text) => text,
+            Err(e) => {
+                warn!("붙여넣기 실패: {e}");
+                return;
+            }
+        };
+        let text = text.trim_end_matches(['\r', '\n']).replace("\r\n", "\n");
+        if self.text_entry {
+            self.clamp_caret();
+            self.insert_text(&text);
+            self.caret_changed();
+        } else {
+            self.text = text;
+        }
+    }
+
+    // 입력 모드에서만 입력기를 켜 한글 등을 조합해 입력할 수 있게 함
+    fn set_text_entry(&mut self, text_entry: bool) {
+        self.text_entry = text_entry;
+        self.ime_preedit.clear();
+        self.caret = self.text.len();
+        self.selection_anchor = None;
+        self.window.set_ime_allowed(text_entry);
+        if text_entry {
+            self.caret_changed();
+            info!(
+                "텍스트 입력 모드 (Enter: 확정, Shift+Enter: 줄바꿈, 방향키/Home/End: 커서 이동, \
+                 Shift: 선택, Backspace/Delete: 삭제, ESC: 종료)"
+            );
+        } else {
+            info!("텍스트 입력 모드 종료");
+        }
+    }
+
+    fn handle_ime(&mut self, ime: Ime) {
+        if !self.text_entry {
+            return;
+        }
+        match ime {
+            Ime::Preedit(text, _) => self.ime_preedit = text,
+            Ime::Commit(text) => {
+                self.ime_preedit.clear();
+                self.clamp_caret();
+                self.insert_text(&text);
+                self.caret_changed();
+            }
+            Ime::Enabled => {}
+            Ime::Disabled => self.ime_preedit.clear(),
+        }
+    }
+
+    // 입력기 후보 창을 커서 바로 아래에 띄움
+    fn update_ime_cursor_area(&self) {
+        let [x, y, _, height] = self.text_renderer.caret_rect(self.caret, CARET_WIDTH);
+        let corner = self
+            .text_transform()
+            .transform_point3(Vec3::new(x, y + height, 0.0));
+        self.window.set_ime_cursor_area(
+            LogicalPosition::new(corner.x, corner.y),
+            LogicalSize::new(1.0, 1.0),
+        );
+    }
+
+    // 창 아래쪽에 현재 효과와 투명도를 작게 표시 (기본 텍스트의 효과와 타자기 애니메이션은 받지 않음)
+    fn update_status_line(&mut self) -> Result<(), RendererError> {
+        if !self.show_status {
+            if let Some(id) = self.status_line.take() {
+                self.text_renderer.remove_object(id);
+            }
+            return Ok(());
+        }
+
+        let [width, height] = self.logical_extent();
+        let mut text = StyledText::default();
+        text.push(
+            &format!(
+                "효과: {} · 투명도: {}%",
+                self.effects.name(),
+                (self.opacity.target() * 100.0).round()
+            ),
+            SpanStyle {
+                size: Some(STATUS_FONT_SIZE),
+                ..SpanStyle::default()
+            },
+        );
+        let object = TextObject {
+            opacity: 0.8,
+            effects: Some(TextEffects::NONE),
+            ..TextObject::new(text).at([width / 2.0, height - STATUS_MARGIN])
+        };
+
+        let updated = match self.status_line {
+            Some(id) => self.text_renderer.update_object(id, object.clone())?,
+            None => false,
+        };
+        if !updated {
+            self.status_line = Some(self.text_renderer.add_object(object)?);
+        }
+        Ok(())
+    }
+
+    // 창 왼쪽 위에 FPS, 프레임 시간, 글리프 캐시 통계를 작게 표시 (요약은 가끔 바뀌므로 그 사이에는 레이어를 다시 그리지 않음)
+    fn update_stats_overlay(&mut self) -> Result<(), RendererError> {
+        if !self.show_stats {
+            if let Some(id) = self.stats_overlay.take() {
+                self.text_renderer.remove_object(id);
+            }
+            return Ok(());
+        }
+
+        let summary = self
+            .stats
+            .summary()
+            .map_or_else(|| "FPS -".to_owned(), |summary| summary.text());
+        let mut text = StyledText::default();
+        text.push(
+            &summary,
+            SpanStyle {
+                size: Some(STATS_FONT_SIZE),
+                ..SpanStyle::default()
+            },
+        );
+        // 객체는 중심 기준으로 놓이므로 지난번 크기로 모서리에 맞춤 (처음 한 번은 한 프레임 어긋남)
+        let [width, height] = self
+            .stats_overlay
+            .and_then(|id| self.text_renderer.object_extent(id))
+            .unwrap_or_default();
+        let object = TextObject {
+            opacity: 0.8,
+            effects: Some(TextEffects::NONE),
+            ..TextObject::new(text).at([STATS_MARGIN + width / 2.0, STATS_MARGIN + height / 2.0])
+        };
+
+        let updated = match self.stats_overlay {
+            Some(id) => self.text_renderer.update_object(id, object.clone())?,
+            None => false,
+        };
+        if !updated {
+            self.stats_overlay = Some(self.text_renderer.add_object(object)?);
+        }
+        Ok(())
+    }
+
+    // 전광판 모드에서 텍스트가 창보다 넓으면 가로로 흘리고, 한 간격 뒤에 복사본을 텍스트 객체로 그려 이어 붙임
+    // 글리프는 그대로 두고 변환만 바꾸므로 프레임마다 다시 래스터라이즈하지 않음 (입력 모드에서는 멈춤)
+    fn update_marquee(&mut self, text: StyledText, time: f32) -> Result<Mat4, RendererError> {
+        let transform = self.text_transform();
+        let [width, _] = self.logical_extent();
+        let text_width = self.text_renderer.text_extent()[0] * self.text_scale;
+        let offsets = self
+            .marquee
+            .filter(|_| !self.text_entry)
+            .and_then(|marquee| marquee.offsets(time, text_width, width));
+        let Some((left, period)) = offsets else {
+            if let Some(id) = self.marquee_copy.take() {
+                self.text_renderer.remove_object(id);
+            }
+            return Ok(transform);
+        };
+
+        // 텍스트 블록의 왼쪽 끝이 창 왼쪽에서 left만큼 떨어지도록 (방향키로 옮긴 위치는 그대로 더함)
+        let dx = left + (text_width - width) / 2.0;
+        let shift = |dx: f32| Mat4::from_translation(Vec3::new(dx, 0.0, 0.0)) * transform;
+        let object = TextObject::new(text).with_transform(shift(dx + period));
+        let updated = match self.marquee_copy {
+            Some(id) => self.text_renderer.update_object(id, object.clone())?,
+            None => false,
+        };
+        if !updated {
+            self.marquee_copy = Some(self.text_renderer.add_object(object)?);
+        }
+        Ok(shift(dx))
+    }
+
+    fn move_text(&mut self, dx: f32, dy: f32) {
+        self.text_position[0] += dx;
+        self.text_position[1] += dy;
+        let [x, y] = self.text_position;
+        info!("위치: ({x}, {y})");
+    }
+
+    fn set_text_scale(&mut self, scale: f32) {
+        self.text_scale = scale.clamp(0.25, 4.0);
+        info!("크기: {:.2}x", self.text_scale);
+    }
+
+    // 기본 텍스트의 모델 행렬 (텍스트 중심 기준으로 크기 조절, 회전 후 창 중앙에서 이동)
+    fn text_transform(&self) -> Mat4 {
+        let [x, y] = self.text_position;
+        let [width, height] = self.logical_extent();
+        Mat4::from_scale_rotation_translation(
+            Vec3::new(self.text_scale, self.text_scale, 1.0),
+            Quat::from_rotation_z(self.text_rotation.to_radians()),
+            Vec3::new(width / 2.0 + x, height / 2.0 + y, 0.0),
+        )
+    }
+
+    // 텍스트 위를 누르면 창 끌기 시작 (테두리 없는 오버레이 창을 옮기는 방법)
+    // 직접 옮긴 창은 모니터 구성이 바뀌어도 다시 놓지 않음
+    fn start_drag(&mut self) {
+        if !self.cursor.is_some_and(|cursor| self.text_contains(cursor)) {
+            return;
+        }
+        match self.window.drag_window() {
+            Ok(()) => self.placement = None,
+            Err(e) => warn!("창을 끌 수 없습니다: {e}"),
+        }
+    }
+
+    // 창 좌표(논리 픽셀)가 기본 텍스트 블록 안인지 (회전과 크기 조절을 되돌려 텍스트 블록 좌표에서 비교)
+    fn text_contains(&self, [x, y]: [f32; 2]) -> bool {
+        let [width, height] = self.text_renderer.text_extent();
+        let local = self
+            .text_transform()
+            .inverse()
+            .transform_point3(Vec3::new(x, y, 0.0));
+        local.x.abs() <= width / 2.0 + DRAG_MARGIN && local.y.abs() <= height / 2.0 + DRAG_MARGIN
+    }
+
+    // 모니터 배율이 바뀌면 글리프를 새 배율로 다시 래스터라이즈 (창 크기 변경은 Resized로 따로 옴)
+    fn set_scale_factor(&mut self, scale_factor: f64) {
+        info!("배율: {scale_factor}");
+        if let Err(e) = self.text_renderer.set_scale_factor(scale_factor as f32) {
+            warn!("배율 적용 실패: {e}");
+        }
+        self.recreate_swapchain = true;
+    }
+
+    // 스왑체인 크기를 논리 픽셀로 (텍스트 위치와 투영은 논리 픽셀 기준)
+    fn logical_extent(&self) -> [f32; 2] {
+        logical_extent(&self.viewport, self.window.scale_factor() as f32)
+    }
+
+    fn layout_options(&self) -> LayoutOptions {
+        wrap_options(
+            self.logical_extent(),
+            self.marquee.is_some(),
+            self.text_layout,
+        )
+    }
+
+    #[cfg(feature = "egui-panel")]
+    fn toggle_panel(&mut self) {
+        match &mut self.panel {
+            Some(panel) => {
+                panel.toggle();
+                let state = if panel.is_visible() {
+                    "켜짐"
+                } else {
+                    "꺼짐"
+                };
+                info!("컨트롤 패널: {state}");
+            }
+            None => warn!("컨트롤 패널을 만들지 못해 띄울 수 없습니다"),
+        }
+    }
+
+    #[cfg(not(feature = "egui-panel"))]
+    fn toggle_panel(&mut self) {
+        warn!("egui-panel 기능 없이 빌드되어 컨트롤 패널을 띄울 수 없습니다");
+    }
+
+    // 히트 테스트를 끄면 클릭이 창 아래로 통과함 (지원하지 않는 플랫폼이면 출력만 하고 유지)
+    fn set_click_through(&mut self, click_through: bool) {
+        if click_through == self.click_through {
+            return;
+        }
+        match self.window.set_cursor_hittest(!click_through) {
+            Ok(()) => {
+                self.click_through = click_through;
+                info!("클릭 통과: {}", if click_through { "켜짐" } else { "꺼짐" });
+            }
+            Err(e) => warn!("클릭 통과를 설정할 수 없습니다: {e}"),
+        }
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.opacity.animate_to(opacity, self.fade_duration);
+        info!("투명도: {}%", (opacity * 100.0).round());
+    }
+
+    // 셰이더가 글리프 여백(8픽셀) 안에서만 샘플링하므로 그 범위로 제한
+    fn set_outline_width(&mut self, width: f32) {
+        let width = width.clamp(0.5, 8.0);
+        self.outline_width.animate_to(width, self.fade_duration);
+        info!("외곽선 두께: {width}px");
+    }
+
+    fn set_outline_softness(&mut self, softness: f32) {
+        self.outline_softness = softness.clamp(0.0, 4.0);
+        info!("외곽선 흐림: {}px", self.outline_softness);
+    }
+
+    fn set_glow_radius(&mut self, radius: f32) {
+        let radius = radius.clamp(1.0, MAX_GLOW_RADIUS);
+        self.glow_radius.animate_to(radius, self.fade_duration);
+        info!("발광 반지름: {radius}px");
+    }
+
+    fn set_glow_intensity(&mut self, intensity: f32) {
+        let intensity = intensity.clamp(0.5, 8.0);
+        self.glow_intensity
+            .animate_to(intensity, self.fade_duration);
+        info!("발광 세기: {intensity}");
+    }
+
+    fn set_clock_speed(&mut self, speed: f32) {
+        self.clock.set_speed(speed.clamp(0.125, 8.0));
+        info!("애니메이션 배속: {}x", self.clock.speed());
+    }
+
+    fn set_shadow_blur(&mut self, sigma: f32) {
+        self.shadow_blur = sigma.clamp(0.0, 2.5);
+        info!("그림자 블러: σ = {}px", self.shadow_blur);
+    }
+
+    // 진행 중에 방향을 바꾸면 현재 진행도에서 이어지도록 전환 시각을 조정
+    fn toggle_dissolve(&mut self) {
+        let now = self.clock.elapsed();
+        let progress = self.dissolve_progress(now);
+        self.dissolve_out = !self.dissolve_out;
+
+        let elapsed = if self.dissolve_out {
+            info!("소멸: 사라짐");
+            progress
+        } else {
+            info!("소멸: 나타남");
+            1.0 - progress
+        };
+        self.dissolve_changed = now - elapsed * DISSOLVE_DURATION;
+    }
+
+    // 시각 now(clock 기준 초)의 소멸 진행도
+    fn dissolve_progress(&self, now: f32) -> f32 {
+        let t = ((now - self.dissolve_changed) / DISSOLVE_DURATION).clamp(0.0, 1.0);
+        if self.dissolve_out {
+            t
+        } else {
+            1.0 - t
+        }
+    }
+
+    fn render(&mut self) -> Result<(), RendererError> {
+        let _frame = debug_span!("frame").entered();
+        let window_extent: [u32; 2] = self.window.inner_size().into();
+        // 그리기를 멈춘 동안 운영체제가 다시 그리기를 요청해도 제출하지 않음
+        if window_extent.contains(&0) || self.render_paused {
+            return Ok(());
+        }
+
+        // 최소화 중이거나 surface가 아직 새 크기를 받아들이지 않으면 다음 프레임에 다시 시도
+        if self.recreate_swapchain && !self.recreate_swapchain(window_extent)? {
+            return Ok(());
+        }
+
+        // 셰이더 파일이 바뀌었으면 파이프라인 교체 (오류는 출력만 하고 기존 셰이더 유지)
+        #[cfg(feature = "hot-reload")]
+        match self.text_renderer.reload_changed_shaders() {
+            Ok(true) => info!("셰이더를 다시 불러왔습니다"),
+            Ok(false) => {}
+            Err(e) => warn!("{e}"),
+        }
+
+        let (image_index, suboptimal, acquire_future) =
+            match acquire_next_image(self.swapchain.clone(), None).map_err(Validated::unwrap) {
+                Ok(r) => r,
+                // 독점 전체 화면을 잃었으면(다른 창으로 전환 등) 다시 만들며 다시 얻음
+                Err(VulkanError::OutOfDate | VulkanError::FullScreenExclusiveModeLost) => {
+                    self.recreate_swapchain = true;
+                    return Ok(());
+                }
+                Err(e) => return Err(RendererError::Swapchain(Validated::Error(e))),
+            };
+
+        if suboptimal {
+            self.recreate_swapchain = true;
+        }
+
+        // N 프레임 전에 같은 슬롯을 쓴 프레임이 끝났는지 확인
+        self.frames.wait_current();
+        // CPU 프레임 시간은 여기서부터 제출까지 (펜스와 스왑체인 대기 제외)
+        let cpu_started = Instant::now();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            self.frames.command_buffer_allocator(),
+            self.queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .map_err(|e| RendererError::Command(e.into()))?;
+        let frame_index = self.frames.frame_index();
+        if let Some(profiler) = self.gpu_profiler() {
+            profiler.begin_frame(&mut builder, frame_index)?;
+        }
+
+        // 스크립트와 패널에서 바꾼 텍스트와 값은 이번 프레임부터 반영
+        #[cfg(feature = "scripting")]
+        self.run_script();
+        #[cfg(feature = "egui-panel")]
+        self.update_panel(&mut builder)?;
+
+        // 상태 줄과 통계는 새 글리프를 업로드할 수 있으므로 render pass 전에 갱신
+        self.update_status_line()?;
+        self.update_stats_overlay()?;
+
+        // 커서와 선택 영역은 새 레이아웃 기준이므로 텍스트를 먼저 바꾼 뒤 계산
+        let styled_text = self.styled_text();
+        self.text_renderer.set_styled_text(&styled_text)?;
+        let highlights = self.edit_highlights();
+        self.text_renderer.set_highlights(&highlights);
+
+        // 글리프를 창 중앙에 그림 (투영은 현재 스왑체인 크기의 논리 픽셀 좌표)
+        self.clock.tick();
+        let time = self.clock.elapsed();
+        // 트윈은 애니메이션 일시 정지나 배속과 무관하게 실제 시간으로 진행
+        let delta = self.fade_clock.tick();
+        let opacity = self.opacity.advance(delta);
+        let outline_width = self.outline_width.advance(delta);
+        let glow_radius = self.glow_radius.advance(delta);
+        let glow_intensity = self.glow_intensity.advance(delta);
+        let visibility = self.visibility.advance(delta);
+        let dissolve_progress = self.dissolve_progress(time);
+        let transform = self.update_marquee(styled_text, time)?;
+        let mut effects = self.effects;
+        if dissolve_progress > 0.0 {
+            effects |= TextEffects::DISSOLVE;
+        }
+        let subtitle_alpha = self.subtitles.as_ref().map_or(1.0, SubtitleQueue::alpha);
+        let params = TextParams {
+            opacity: opacity * visibility * subtitle_alpha,
+            effects,
+            outline_color: self.outline_color,
+            outline_width,
+            outline_softness: self.outline_softness,
+            glow_radius,
+            glow_intensity,
+            glow_color: self.glow_color,
+            shadow_color: self.shadow_color,
+            shadow_blur: self.shadow_blur,
+            shadow_offset: self.shadow_offset,
+            gradient: self.gradient,
+            dissolve_progress,
+            time,
+            projection: TextParams::pixel_projection(self.logical_extent()),
+            transform,
+            background: self.background.then(TextBackground::default),
+            ..TextParams::default()
+        };
+        self.scene_animated = self.text_renderer.is_animated(&params);
+
+        // 장면이 바뀌었을 때만 레이어에 다시 그림 (투명도는 합성할 때 곱함)
+        let layer_revision = self.layer.revision();
+        self.begin_gpu_pass(&mut builder, "레이어")?;
+        self.layer.draw(
+            &mut builder,
+            &mut self.text_renderer,
+            &params,
+            self.swapchain.image_extent(),
+        )?;
+        self.end_gpu_pass(&mut builder)?;
+        let layer_drawn = self.layer.revision() != layer_revision;
+
+        // 타임스탬프나 스크린샷을 기록하지 않고 패널도 없으면 합성은 미리 기록해 둔 command buffer를 다시 제출
+        let static_frame = if self.gpu_profiler().is_none()
+            && self.screenshot.is_none()
+            && !self.panel_visible()
+        {
+            Some(self.static_frame(image_index as usize, params.opacity)?)
+        } else {
+            self.record_composite(&mut builder, image_index as usize, params.opacity)?;
+            None
+        };
+
+        // 스크린샷은 합성이 끝난 스왑체인 이미지를 복사
+        let capture = match self.screenshot.take() {
+            Some(path) => match self.capture_swapchain_image(image_index as usize) {
+                Ok(capture) => {
+                    capture.record(&mut builder)?;
+                    Some((path, capture))
+                }
+                Err(e) => {
+                    warn!("{e}");
+                    None
+                }
+            },
+            None => None,
+        };
+
+        // 레이어도 그대로고 합성도 다시 쓰면 이번 프레임에 새로 기록한 명령이 없음
+        let mut future = self.frames.previous_future().join(acquire_future).boxed();
+        // 새 글리프 업로드가 끝난 뒤 그림
+        if let Some(upload) = self.text_renderer.take_upload_future() {
+            future = future.join(upload).boxed();
+        }
+        if layer_drawn || static_frame.is_none() {
+            let command_buffer = builder
+                .build()
+                .map_err(|e| RendererError::Command(e.into()))?;
+            future = future
+                .then_execute(self.queue.clone(), command_buffer)
+                .map_err(|e| RendererError::Command(e.into()))?
+                .boxed();
+        }
+        if let Some(static_frame) = static_frame {
+            future = future
+                .then_execute(self.queue.clone(), static_frame)
+                .map_err(|e| RendererError::Command(e.into()))?
+                .boxed();
+        }
+        let future = future
+            .then_swapchain_present(
+                self.queue.clone(),
+                SwapchainPresentInfo::swapchain_image_index(self.swapchain.clone(), image_index),
+            )
+            .boxed()
+            .then_signal_fence_and_flush();
+
+        match future.map_err(Validated::unwrap) {
+            Ok(future) => {
+                self.frames.finish_frame(Some(future));
+                if let Some(fence) = self.frames.last_fence() {
+                    self.text_renderer.frame_submitted(fence);
+                }
+                self.finish_gpu_frame(true);
+                let gpu = self.profiler.as_ref().and_then(GpuProfiler::stats);
+                let glyph_cache = self.text_renderer.glyph_cache_stats();
+                self.stats.record(cpu_started.elapsed(), gpu, glyph_cache);
+                if let Some((path, capture)) = capture {
+                    // 복사가 끝나야 버퍼를 읽을 수 있으므로 이번 프레임까지 대기
+                    self.frames.wait_all();
+                    let saved = capture
+                        .read()
+                        .and_then(|screenshot| screenshot.save_png(&path));
+                    match saved {
+                        Ok(()) => info!("스크린샷 저장: {}", path.display()),
+                        Err(e) => warn!("{e}"),
+                    }
+                }
+            }
+            Err(VulkanError::OutOfDate | VulkanError::FullScreenExclusiveModeLost) => {
+                self.recreate_swapchain = true;
+                self.frames.finish_frame(None);
+                self.finish_gpu_frame(false);
+                self.layer.invalidate();
+            }
+            Err(e) => {
+                self.frames.finish_frame(None);
+                self.finish_gpu_frame(false);
+                self.layer.invalidate();
+                return Err(RendererError::Swapchain(Validated::Error(e)));
+            }
+        }
+
+        // 미러 창은 주 창과 같은 장면 (주 창의 배율로 래스터라이즈한 글리프를 그대로 사용)
+        let scale_factor = self.window.scale_factor() as f32;
+        let main_extent = self.logical_extent();
+        for mirror in &mut self.mirrors {
+            if let Err(e) =
+                mirror.render(&mut self.text_renderer, &params, scale_factor, main_extent)
+            {
+                warn!(error = %e, "미러 창 렌더링 실패");
+                mirror.resized();
+            }
+        }
+        // 다음 프레임의 주 창 draw가 미러 창의 장면과 비교하지 않도록
+        if !self.mirrors.is_empty() {
+            self.layer.invalidate();
+        }
+
+        Ok(())
+    }
+
+    // 스왑체인 이미지를 지우고 레이어를 합성
+    fn record_composite<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        image_index: usize,
+        opacity: f32,
+    ) -> Result<(), RendererError> {
+        self.render_path
+            .begin(builder, image_index, self.background_color())?;
+        builder
+            .set_viewport(0, [self.viewport.clone()].into_iter().collect())
+            .map_err(|e| RendererError::Command(e.into()))?;
+
+        self.begin_gpu_pass(builder, "합성")?;
+        self.layer.composite(builder, opacity)?;
+        self.end_gpu_pass(builder)?;
+
+        #[cfg(feature = "egui-panel")]
+        if let Some(panel) = &self.panel {
+            panel.draw(builder)?;
+        }
+
+        self.render_path.end(builder)
+    }
+
+    // 레이어 리비전, 투명도, 배경 색이 기록할 때와 같으면 그 합성 command buffer를 다시 씀
+    // (여러 프레임이 동시에 제출할 수 있도록 SimultaneousUse로 기록)
+    fn static_frame(
+        &mut self,
+        image_index: usize,
+        opacity: f32,
+    ) -> Result<Arc<PrimaryAutoCommandBuffer>, RendererError> {
+        let key: StaticFrameKey = (self.layer.revision(), opacity, self.background_color());
+        if let Some((recorded, command_buffer)) = &self.static_frames[image_index] {
+            if *recorded == key {
+                return Ok(command_buffer.clone());
+            }
+        }
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &self.static_allocator,
+            self.queue.queue_family_index(),
+            CommandBufferUsage::SimultaneousUse,
+        )
+        .map_err(|e| RendererError::Command(e.into()))?;
+        self.record_composite(&mut builder, image_index, opacity)?;
+        let command_buffer = builder
+            .build()
+            .map_err(|e| RendererError::Command(e.into()))?;
+        self.static_frames[image_index] = Some((key, command_buffer.clone()));
+        Ok(command_buffer)
+    }
+
+    // 구간별 GPU 시간은 통계를 표시할 때만 잼 (재지 않는 프레임은 합성 command buffer를 다시 쓸 수 있음)
+    fn gpu_profiler(&mut self) -> Option<&mut GpuProfiler> {
+        let show_stats = self.show_stats;
+        self.profiler.as_mut().filter(|_| show_stats)
+    }
+
+    // 타임스탬프를 지원하지 않거나 통계를 표시하지 않으면 아무것도 하지 않음
+    fn begin_gpu_pass<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        name: &'static str,
+    ) -> Result<(), RendererError> {
+        match self.gpu_profiler() {
+            Some(profiler) => profiler.begin_pass(builder, name),
+            None => Ok(()),
+        }
+    }
+
+    fn end_gpu_pass<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+    ) -> Result<(), RendererError> {
+        match self.gpu_profiler() {
+            Some(profiler) => profiler.end_pass(builder),
+            None => Ok(()),
+        }
+    }
+
+    fn finish_gpu_frame(&mut self, submitted: bool) {
+        if let Some(profiler) = &mut self.profiler {
+            profiler.finish_frame(submitted);
+        }
+    }
+
+    // 스왑체인 이미지(MSAA면 resolve 대상)를 복사할 준비
+    fn capture_swapchain_image(&self, image_index: usize) -> Result<ImageCapture, RendererError> {
+        let usage = self.swapchain.image_usage();
+        if !usage.intersects(ImageUsage::TRANSFER_SRC) {
+            return Err(RendererError::Texture(
+                "스왑체인 이미지를 복사할 수 없어 스크린샷을 저장할 수 없습니다".into(),
+            ));
+        }
+        let image = self
+            .render_path
+            .image(image_index)
+            .ok_or_else(|| RendererError::Texture("스왑체인 이미지가 없습니다".into()))?;
+        ImageCapture::new(self.memory_allocator.clone(), image)
+    }
+
+    // 창을 지우는 색 (불투명 합성만 되면 투명 대신 배경 색으로 채움)
+    fn background_color(&self) -> [f32; 4] {
+        if self.swapchain.composite_alpha() != CompositeAlpha::Opaque {
+            return [0.0, 0.0, 0.0, 0.0];
+        }
+        let background = self
+            .config
+            .window
+            .opaque_background
+            .map_or(OPAQUE_BACKGROUND, |color| color.0);
+        opaque_clear_color(background, self.swapchain.image_format())
+    }
+
+    // 다시 만들었으면 true (그릴 수 없는 크기면 false로 두고 다음 프레임에 다시 시도)
+    fn recreate_swapchain(&mut self, window_extent: [u32; 2]) -> Result<bool, RendererError> {
+        // 이전 프레임이 아직 옛 스왑체인 이미지를 쓰고 있을 수 있으므로 모두 끝날 때까지 대기
+        self.frames.wait_all();
+
+        let selected_present_mode = select_present_mode(
+            self.device.physical_device(),
+            self.swapchain.surface(),
+            self.present_mode,
+        );
+        if selected_present_mode != self.swapchain.present_mode() {
+            info!(present_mode = ?selected_present_mode, "프레젠트 모드 변경");
+        }
+
+        // 독점 전체 화면을 켜거나 껐으면 먼저 그 설정으로 바꾸고 크기는 아래에서 맞춤
+        if let Some(swapchain) = self
+            .fullscreen
+            .switch_exclusive(&self.window, &self.swapchain)?
+        {
+            self.swapchain = swapchain;
+        }
+        let Some((new_swapchain, new_images)) =
+            resize_swapchain(&self.swapchain, window_extent, selected_present_mode)?
+        else {
+            return Ok(false);
+        };
+
+        self.swapchain = new_swapchain;
+        self.fullscreen.acquire(&self.swapchain);
+        self.render_path
+            .resize(&new_images, &self.memory_allocator, &mut self.viewport)?;
+        self.static_frames = vec![None; self.render_path.len()];
+        self.recreate_swapchain = false;
+
+        // 창 너비가 바뀌었으면 줄바꿈도 다시 계산
+        self.text_renderer
+            .set_layout_options(self.layout_options())?;
+
+        Ok(true)
+    }
+}
+
+// 명령줄 폰트 인자: `--font <패밀리>` 뒤에 대체 폰트(파일 경로 또는 패밀리 이름)를 나열
+struct Args {
+    family: Option<String>,
+    fallbacks: Vec<String>,
+    subpixel: bool,
+    shader_dir: Option<String>,
+    effect_shader: Option<String>,
+    config: Option<String>,
+    text_file: Option<String>,
+    stdin: Option<StdinMode>,
+    control: Option<String>,
+    listen: Option<String>,
+    overlay: bool,
+    monitor: Option<usize>,
+    anchor: Option<Anchor>,
+    margin: Option<f64>,
+    msaa: Option<u32>,
+    // 투명한 창 뒤의 Windows 배경 (blur, acrylic, mica)
+    backdrop: Option<Backdrop>,
+    // --layer로 일반 창 대신 그릴 wlr-layer-shell 레이어와 차지할 영역 (layer-shell 기능 필요)
+    layer: Option<String>,
+    exclusive_zone: Option<i32>,
+    // 사용할 GPU (없으면 VK_DEVICE 환경 변수)
+    gpu: Option<GpuSelector>,
+    hdr: bool,
+    // 지원하면 render pass와 framebuffer 대신 dynamic rendering으로 그림
+    dynamic_rendering: bool,
+    // F11 전체 화면을 독점 모드로 (Windows, VK_EXT_full_screen_exclusive)
+    exclusive_fullscreen: bool,
+    // 같은 텍스트를 띄울 다른 모니터 (all 또는 번호 목록)
+    mirrors: Option<MirrorMonitors>,
+    // 시작할 때 컨트롤 패널을 띄움 (egui-panel 기능 필요)
+    panel: bool,
+    // 프레임마다 실행할 rhai 스크립트 (scripting 기능 필요)
+    script: Option<String>,
+    // 현재 시각을 표시하는 strftime 형식
+    clock: Option<String>,
+    // 시간에 맞춰 보여 줄 SRT 자막 파일과 나타나고 사라지는 시간 (초)
+    subtitles: Option<String>,
+    subtitle_fade: Option<f32>,
+    // 전광판 모드의 속도 (초당 논리 픽셀)와 복사본 사이 간격
+    marquee: Option<f32>,
+    marquee_gap: Option<f32>,
+    // 세로쓰기 (글자는 위에서 아래로, 줄은 오른쪽에서 왼쪽으로)
+    vertical: bool,
+    // 탭 간격 (공백 수)과 탭으로 나뉜 칸을 열로 맞출지
+    tab_width: Option<f32>,
+    align_tabs: bool,
+    // 카운트다운 시간 (10:00 등), 스톱워치, 타이머의 소수점 아래 자릿수
+    countdown: Option<String>,
+    stopwatch: bool,
+    timer_decimals: Option<u32>,
+    // 시스템 통계 템플릿과 갱신 간격 (초, system-stats 기능 필요)
+    system_stats: Option<String>,
+    stats_interval: Option<f32>,
+    no_state: bool,
+    // 초당 최대 프레임 수, 바뀐 것이 있을 때만 다시 그리기
+    max_fps: Option<f32>,
+    lazy: bool,
+    // --headless로 창 없이 그릴 때의 출력 파일, 텍스트, 이미지 크기 (물리 픽셀), 애니메이션 프레임 수와 fps
+    headless: bool,
+    out: Option<PathBuf>,
+    text: Option<String>,
+    size: Option<ImageSize>,
+    frames: Option<u32>,
+    fps: Option<f32>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Self {
+        let mut parsed = Args {
+            family: None,
+            fallbacks: Vec::new(),
+            subpixel: false,
+            shader_dir: None,
+            effect_shader: None,
+            config: None,
+            text_file: None,
+            stdin: None,
+            control: None,
+            listen: None,
+            overlay: false,
+            monitor: None,
+            anchor: None,
+            margin: None,
+            msaa: None,
+            backdrop: None,
+            layer: None,
+            exclusive_zone: None,
+            gpu: None,
+            hdr: false,
+            dynamic_rendering: false,
+            exclusive_fullscreen: false,
+            mirrors: None,
+            panel: false,
+            script: None,
+            clock: None,
+            subtitles: None,
+            subtitle_fade: None,
+            marquee: None,
+            marquee_gap: None,
+            vertical: false,
+            tab_width: None,
+            align_tabs: false,
+            countdown: None,
+            stopwatch: false,
+            timer_decimals: None,
+            system_stats: None,
+            stats_interval: None,
+            no_state: false,
+            max_fps: None,
+            lazy: false,
+            headless: false,
+            out: None,
+            text: None,
+            size: None,
+            frames: None,
+            fps: None,
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--font" => parsed.family = args.next(),
+                "--subpixel" => parsed.subpixel = true,
+                "--shaders" => parsed.shader_dir = args.next(),
+                "--effect-shader" => parsed.effect_shader = args.next(),
+                "--config" => parsed.config = args.next(),
+                "--text-file" => parsed.text_file = args.next(),
+                "--stdin" => parsed.stdin = Some(StdinMode::Replace),
+                "--stdin-append" => parsed.stdin = Some(StdinMode::Append),
+                "--control" => parsed.control = args.next(),
+                "--listen" => parsed.listen = args.next(),
+                "--overlay" => parsed.overlay = true,
+                "--monitor" => parsed.monitor = parse_value(&arg, args.next()),
+                "--anchor" => parsed.anchor = parse_value(&arg, args.next()),
+                "--margin" => parsed.margin = parse_value(&arg, args.next()),
+                "--msaa" => parsed.msaa = parse_value(&arg, args.next()),
+                "--gpu" => parsed.gpu = parse_value(&arg, args.next()),
+                "--mirrors" => parsed.mirrors = parse_value(&arg, args.next()),
+                "--hdr" => parsed.hdr = true,
+                "--dynamic-rendering" => parsed.dynamic_rendering = true,
+                "--exclusive-fullscreen" => parsed.exclusive_fullscreen = true,
+                "--panel" => parsed.panel = true,
+                "--script" => parsed.script = args.next(),
+                "--clock" => parsed.clock = args.next(),
+                "--subtitles" => parsed.subtitles = args.next(),
+                "--subtitle-fade" => parsed.subtitle_fade = parse_value(&arg, args.next()),
+                "--marquee" => parsed.marquee = parse_value(&arg, args.next()),
+                "--marquee-gap" => parsed.marquee_gap = parse_value(&arg, args.next()),
+                "--vertical" => parsed.vertical = true,
+                "--tab-width" => parsed.tab_width = parse_value(&arg, args.next()),
+                "--align-tabs" => parsed.align_tabs = true,
+                "--countdown" => parsed.countdown = args.next(),
+                "--stopwatch" => parsed.stopwatch = true,
+                "--timer-decimals" => parsed.timer_decimals = parse_value(&arg, args.next()),
+                "--system-stats" => parsed.system_stats = args.next(),
+                "--stats-interval" => parsed.stats_interval = parse_value(&arg, args.next()),
+                "--backdrop" => parsed.backdrop = parse_value(&arg, args.next()),
+                "--layer" => parsed.layer = args.next(),
+                "--exclusive-zone" => parsed.exclusive_zone = parse_value(&arg, args.next()),
+                "--no-state" => parsed.no_state = true,
+                // run에서 로그를 설정할 때 이미 처리함
+                "--log-json" => {}
+                "--max-fps" => parsed.max_fps = parse_value(&arg, args.next()),
+                "--lazy" => parsed.lazy = true,
+                "--headless" => parsed.headless = true,
+                "--out" => parsed.out = args.next().map(PathBuf::from),
+                "--text" => parsed.text = args.next(),
+                "--size" => parsed.size = parse_value(&arg, args.next()),
+                "--frames" => parsed.frames = parse_value(&arg, args.next()),
+                "--fps" => parsed.fps = parse_value(&arg, args.next()),
+                _ => parsed.fallbacks.push(arg),
+            }
+        }
+        if parsed.gpu.is_none() {
+            parsed.gpu = parse_value(GPU_ENV, std::env::var(GPU_ENV).ok());
+        }
+        parsed
+    }
+}
+
+// RUST_LOG로 출력 수준을 정하고 (기본 info), json이면 이벤트마다 JSON 한 줄로 출력
+// 헤드리스 모드는 표준 출력에 프레임을 쓰므로 로그는 표준 오류로
+fn init_logging(json: bool) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+    if json {
+        builder.json().init();
+    } else {
+        builder.init();
+    }
+}
+
+#[cfg(feature = "global-hotkey")]
+fn create_global_keys(config: &AppConfig) -> Option<GlobalKeys> {
+    match GlobalKeys::new() {
+        Ok(mut global_keys) => {
+            global_keys.set_bindings(&config.global_keys);
+            Some(global_keys)
+        }
+        Err(e) => {
+            warn!("전역 단축키를 사용할 수 없습니다: {e}");
+            None
+        }
+    }
+}
+
+// 형식이 틀린 값은 출력하고 무시
+fn parse_value<T: FromStr>(name: &str, value: Option<String>) -> Option<T>
+where
+    T::Err: Display,
+{
+    let value = value?;
+    value
+        .parse()
+        .map_err(|e| info!("{name} 값이 올바르지 않습니다 ({value}): {e}"))
+        .ok()
+}
+
+// 명령줄 인자가 설정 파일보다 우선 (모니터나 위치 중 하나라도 있어야 적용)
+fn window_placement(args: &Args, window: &WindowConfig) -> Option<Placement> {
+    let monitor = args.monitor.or(window.monitor);
+    let anchor = args.anchor.or(window.anchor);
+    if monitor.is_none() && anchor.is_none() {
+        return None;
+    }
+    Some(Placement {
+        monitor,
+        anchor: anchor.unwrap_or_default(),
+        margin: args.margin.or(window.margin).unwrap_or(DEFAULT_MARGIN),
+    })
+}
+
+// 명령줄 인자가 설정 파일보다 우선 (--font, 대체 폰트는 설정 파일의 목록 앞에 추가)
+fn create_text_renderer(
+    device: &Arc<Device>,
+    queues: &Queues,
+    target: RenderTarget,
+    args: &Args,
+    config: &AppConfig,
+    renderer_config: RendererConfig,
+) -> Result<TextRenderer, RendererError> {
+    let family = args.family.as_deref().or(config.font.as_deref());
+    let font = match family.map(load_font_data) {
+        Some(Ok(font)) => font,
+        Some(Err(e)) => {
+            warn!("{e} - 내장 폰트를 사용합니다");
+            bundled_font()
+        }
+        None => bundled_font(),
+    };
+    // 빌더는 지원하지 않는 옵션을 오류로 돌려주므로 서브픽셀은 미리 확인해 대체
+    let mut renderer_config = renderer_config;
+    if renderer_config.antialiasing == Antialiasing::Subpixel
+        && !device.enabled_features().dual_src_blend
+    {
+        warn!("dual-source blending을 지원하지 않아 일반 안티앨리어싱을 사용합니다");
+        renderer_config.antialiasing = Antialiasing::Grayscale;
+    }
+    let (_, samples) = target.color_attachment()?;
+    let mut builder = TextRenderer::builder()
+        .font(font)
+        .config(renderer_config)
+        .msaa(samples as u32);
+    if let Some(font_size) = config.font_size {
+        builder = builder.font_size(font_size);
+    }
+    let mut text_renderer = builder.build(device.clone(), queues.graphics.clone(), target)?;
+    text_renderer.set_transfer_queue(queues.transfer.clone())?;
+    text_renderer.set_compute_queue(queues.compute.clone())?;
+    if let Some(bias) = config.lod_bias {
+        text_renderer.set_lod_bias(bias)?;
+    }
+    if let Some(dir) = &args.shader_dir {
+        watch_shaders(&mut text_renderer, dir);
+    }
+    if let Some(path) = &args.effect_shader {
+        let applied = CustomShader::from_file(path)
+            .and_then(|shader| text_renderer.set_custom_shader(Some(&shader)));
+        match applied {
+            Ok(()) => info!("사용자 효과 셰이더: {path}"),
+            Err(e) => warn!("{e} - 내장 셰이더를 사용합니다"),
+        }
+    }
+
+    // 나머지 인자는 대체 폰트 (기본 폰트에 없는 글자용)
+    for name in args.fallbacks.iter().chain(&config.fallback_fonts) {
+        let added =
+            load_font_data(name).and_then(|data| text_renderer.add_fallback_font_data(data));
+        match added {
+            Ok(()) => info!("대체 폰트 추가: {name}"),
+            Err(e) => warn!("{e}"),
+        }
+    }
+
+    Ok(text_renderer)
+}
+
+// 사용자 설정 디렉터리의 상태 파일을 읽음 (처음 실행이거나 읽지 못하면 저장한 상태 없음)
+fn load_saved_state(no_state: bool) -> (Option<StateFile>, Option<SavedState>) {
+    if no_state {
+        return (None, None);
+    }
+    let state_file = match StateFile::in_config_dir() {
+        Ok(state_file) => state_file,
+        Err(e) => {
+            warn!("{e} - 상태를 저장하지 않습니다");
+            return (None, None);
+        }
+    };
+    let state = match state_file.load() {
+        Ok(state) => state,
+        Err(e) => {
+            warn!("{e} - 저장한 상태를 무시합니다");
+            None
+        }
+    };
+    if state.is_some() {
+        info!("저장한 상태: {}", state_file.path().display());
+    }
+    (Some(state_file), state)
+}
+
+// 설정 파일을 읽고 감시 시작 (파일을 지정하지 않았고 config.toml도 없으면 기본 설정)
+fn load_config(path: Option<&str>) -> (AppConfig, Option<ConfigWatcher>) {
+    let path = PathBuf::from(path.unwrap_or(DEFAULT_CONFIG_PATH));
+    if !path.exists() && path.as_os_str() == DEFAULT_CONFIG_PATH {
+        return (AppConfig::default(), None);
+    }
+
+    let config = read_config(&path);
+    let watcher = match ConfigWatcher::new(path) {
+        Ok(watcher) => Some(watcher),
+        Err(e) => {
+            warn!("{e}");
+            None
+        }
+    };
+    (config, watcher)
+}
+
+// 명령줄이 설정 파일보다 우선 (읽지 못하면 출력하고 None, 제어 메시지로 자막을 받으면 그때 만듦)
+fn create_subtitles(args: &Args, config: &AppConfig) -> Option<SubtitleQueue> {
+    let path = args.subtitles.as_deref().or(config.subtitles.as_deref())?;
+    let fade = args
+        .subtitle_fade
+        .or(config.subtitle_fade)
+        .unwrap_or(DEFAULT_SUBTITLE_FADE);
+    SubtitleQueue::load(path, fade)
+        .map_err(|e| warn!("{e}"))
+        .ok()
+}
+
+// 명령줄이 설정 파일보다 우선 (속도가 없으면 전광판 모드를 쓰지 않음)
+fn create_marquee(args: &Args, config: &AppConfig) -> Option<Marquee> {
+    let speed = args.marquee.or(config.marquee)?;
+    Some(Marquee {
+        speed,
+        gap: args
+            .marquee_gap
+            .or(config.marquee_gap)
+            .unwrap_or(DEFAULT_MARQUEE_GAP),
+    })
+}
+
+// 명령줄이 설정 파일보다 우선 (여럿을 함께 주면 시계, 타이머, 통계 순서로 한 줄씩, 틀린 것은 출력하고 건너뜀)
+fn create_text_source(args: &Args, config: &AppConfig) -> Option<Box<dyn TextSource>> {
+    let mut sources: Vec<Box<dyn TextSource>> = Vec::new();
+    if let Some(format) = args.clock.as_deref().or(config.clock.as_deref()) {
+        match ClockSource::new(format) {
+            Ok(clock) => {
+                info!("시계: {format}");
+                sources.push(Box::new(clock));
+            }
+            Err(e) => warn!("{e}"),
+        }
+    }
+    let decimals = args
+        .timer_decimals
+        .or(config.timer_decimals)
+        .unwrap_or(MAX_TIMER_DECIMALS);
+    if let Some(duration) = args.countdown.as_deref().or(config.countdown.as_deref()) {
+        match TimerSource::countdown(duration, decimals) {
+            Ok(countdown) => {
+                info!("카운트다운: {duration}");
+                sources.push(Box::new(countdown));
+            }
+            Err(e) => warn!("{e}"),
+        }
+    } else if args.stopwatch || config.stopwatch.unwrap_or(false) {
+        info!("스톱워치");
+        sources.push(Box::new(TimerSource::stopwatch(decimals)));
+    }
+    if let Some(template) = args
+        .system_stats
+        .as_deref()
+        .or(config.system_stats.as_deref())
+    {
+        let interval = args.stats_interval.or(config.system_stats_interval);
+        sources.extend(create_stats_source(template, interval));
+    }
+    match sources.len() {
+        0 => None,
+        1 => sources.pop(),
+        _ => Some(Box::new(SourceLines::new(sources))),
+    }
+}
+
+#[cfg(feature = "system-stats")]
+fn create_stats_source(template: &str, interval: Option<f32>) -> Option<Box<dyn TextSource>> {
+    let interval = interval.unwrap_or(DEFAULT_STATS_INTERVAL);
+    match SystemStatsSource::new(template, interval) {
+        Ok(stats) => {
+            info!("시스템 통계: {template} ({interval}초마다)");
+            Some(Box::new(stats))
+        }
+        Err(e) => {
+            warn!("{e}");
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "system-stats"))]
+fn create_stats_source(template: &str, _interval: Option<f32>) -> Option<Box<dyn TextSource>> {
+    warn!("--system-stats {template}: system-stats 기능 없이 빌드되어 통계를 표시할 수 없습니다");
+    None
+}
+
+// 형식이 틀리거나 읽지 못하면 출력하고 기본 설정
+fn read_config(path: &Path) -> AppConfig {
+    match AppConfig::load(path) {
+        Ok(config) => {
+            info!("설정 파일: {}", path.display());
+            config
+        }
+        Err(e) => {
+            warn!("{e} - 기본 설정을 사용합니다");
+            AppConfig::default()
+        }
+    }
+}
+
+// --control과 --listen은 같은 메시지 채널로 받음 (둘 다 없거나 모두 실패하면 None)
+fn open_control_server(args: &Args) -> Option<ControlServer> {
+    let server = ControlServer::new();
+    let mut listening = false;
+    if let Some(address) = &args.control {
+        match server.listen_local(address) {
+            Ok(()) => {
+                info!("제어 소켓: {address}");
+                listening = true;
+            }
+            Err(e) => warn!("제어 소켓을 열 수 없습니다 ({address}): {e}"),
+        }
+    }
+    if let Some(url) = &args.listen {
+        listening |= listen_websocket(&server, url);
+    }
+    listening.then_some(server)
+}
+
+#[cfg(feature = "websocket")]
+fn listen_websocket(server: &ControlServer, url: &str) -> bool {
+    match server.listen_websocket(url) {
+        Ok(()) => {
+            info!("WebSocket 서버: {url}");
+            true
+        }
+        Err(e) => {
+            warn!("WebSocket 서버를 열 수 없습니다 ({url}): {e}");
+            false
+        }
+    }
+}
+
+#[cfg(not(feature = "websocket"))]
+fn listen_websocket(_server: &ControlServer, url: &str) -> bool {
+    warn!("--listen {url}: websocket 기능 없이 빌드되어 무시합니다");
+    false
+}
+
+// 명령줄이 설정 파일보다 우선 (둘 다 없으면 배경 없음)
+// 주 창과 같은 크기로 모니터마다 하나씩 (만들지 못한 모니터는 출력하고 건너뜀)
+fn create_mirrors(
+    elwt: &EventLoopWindowTarget<()>,
+    window: &Window,
+    monitors: &MirrorMonitors,
+    placement: Option<Placement>,
+    (queue, render_path, memory_allocator): (
+        &Arc<Queue>,
+        &RenderPath,
+        &Arc<StandardMemoryAllocator>,
+    ),
+    renderer_config: &RendererConfig,
+) -> Vec<MirrorWindow> {
+    monitors
+        .indices(window)
+        .into_iter()
+        .filter_map(|monitor| {
+            let placement = Placement {
+                monitor: Some(monitor),
+                anchor: placement.map_or_else(Anchor::default, |placement| placement.anchor),
+                margin: placement.map_or(DEFAULT_MARGIN, |placement| placement.margin),
+            };
+            MirrorWindow::new(
+                elwt,
+                queue,
+                render_path,
+                memory_allocator,
+                renderer_config,
+                placement,
+                window.inner_size(),
+            )
+            .map_err(|e| warn!("모니터 {monitor}에 미러 창을 만들 수 없습니다: {e}"))
+            .ok()
         })
-        .collect::<Vec<_>>()
+        .collect()
+}
+
+fn apply_backdrop(window: &Window, args: &Args, config: &WindowConfig) {
+    let backdrop = args.backdrop.or(config.backdrop).unwrap_or_default();
+    backdrop::apply(window, backdrop, config.backdrop_tint.map(|color| color.0));
+}
+
+// 다른 창 위에 떠 있는 HUD (작업 표시줄에서 숨기기는 Windows만 지원)
+fn overlay_window(window_builder: WindowBuilder) -> WindowBuilder {
+    let window_builder = window_builder.with_window_level(WindowLevel::AlwaysOnTop);
+    #[cfg(windows)]
+    let window_builder = {
+        use winit::platform::windows::WindowBuilderExtWindows;
+        window_builder.with_skip_taskbar(true)
+    };
+    window_builder
+}
+
+fn bundled_font() -> FontData {
+    FontData::new(BUNDLED_FONT.to_vec())
+}
+
+// 존재하는 파일이면 파일에서, 아니면 시스템 폰트에서 패밀리 이름으로 로드
+fn load_font_data(name: &str) -> Result<FontData, RendererError> {
+    if Path::new(name).is_file() {
+        FontData::from_file(name)
+    } else {
+        load_family(name)
+    }
+}
+
+#[cfg(feature = "system-fonts")]
+fn load_family(family: &str) -> Result<FontData, RendererError> {
+    FontData::from_system(family)
+}
+
+#[cfg(not(feature = "system-fonts"))]
+fn load_family(family: &str) -> Result<FontData, RendererError> {
+    Err(RendererError::FontNotFound(family.to_owned()))
+}
+
+#[cfg(feature = "hot-reload")]
+fn watch_shaders(text_renderer: &mut TextRenderer, dir: &str) {
+    match text_renderer.watch_shaders(dir) {
+        Ok(()) => info!("셰이더 감시: {dir}"),
+        Err(e) => warn!("{e} - 내장 셰이더를 사용합니다"),
+    }
+}
+
+#[cfg(not(feature = "hot-reload"))]
+fn watch_shaders(_text_renderer: &mut TextRenderer, _dir: &str) {
+    warn!("hot-reload 기능 없이 빌드되어 --shaders를 무시합니다");
+}
+
+fn logical_extent(viewport: &Viewport, scale_factor: f32) -> [f32; 2] {
+    viewport.extent.map(|length| length / scale_factor)
+}
+
+// extent는 논리 픽셀 크기 (전광판 모드에서는 창보다 넓은 텍스트를 흘리므로 줄바꿈하지 않음)
+// 세로쓰기는 창 높이에 맞춰 열을 바꿈
+fn wrap_options(extent: [f32; 2], marquee: bool, layout: LayoutOptions) -> LayoutOptions {
+    let length = match layout.writing_mode {
+        WritingMode::Horizontal => extent[0],
+        WritingMode::VerticalRightToLeft => extent[1],
+    };
+    LayoutOptions {
+        wrap_width: (!marquee).then(|| (length - TEXT_MARGIN * 2.0).max(TEXT_MARGIN)),
+        ..layout
+    }
+}
+
+// 명령줄 인자가 설정 파일보다 우선
+fn text_layout(args: &Args, config: &AppConfig) -> LayoutOptions {
+    let writing_mode = if args.vertical || config.vertical == Some(true) {
+        WritingMode::VerticalRightToLeft
+    } else {
+        WritingMode::Horizontal
+    };
+    let defaults = LayoutOptions::default();
+    LayoutOptions {
+        writing_mode,
+        tab_width: args
+            .tab_width
+            .or(config.tab_width)
+            .unwrap_or(defaults.tab_width)
+            .max(1.0),
+        align_tabs: args.align_tabs || config.align_tabs == Some(true),
+        ..defaults
+    }
+}
+
+// 설정 파일의 기본 색은 맨 앞 구간으로 넣어 마크업 색이 우선하도록 함
+fn styled_markup(text: &str, color: Option<[u8; 4]>) -> StyledText {
+    let mut styled = parse_markup(text);
+    if let Some(color) = color {
+        styled.spans.insert(
+            0,
+            StyleSpan {
+                range: 0..styled.text.len(),
+                style: SpanStyle::color(color),
+            },
+        );
+    }
+    styled
+}
+
+// 현재 디렉터리의 screenshot-<유닉스 시각 밀리초>.png
+fn screenshot_path() -> PathBuf {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+    PathBuf::from(format!("screenshot-{millis}.png"))
+}
+
+// 바이트 위치 앞/뒤 글자의 시작 (입력 모드에서 한 글자씩 옮기거나 지울 때)
+// 글자는 확장 grapheme cluster라 결합 문자나 이모지 시퀀스를 가르지 않음
+fn prev_grapheme(text: &str, offset: usize) -> usize {
+    text[..offset]
+        .grapheme_indices(true)
+        .next_back()
+        .map_or(0, |(index, _)| index)
+}
+
+fn next_grapheme(text: &str, offset: usize) -> usize {
+    text[offset..]
+        .graphemes(true)
+        .next()
+        .map_or(offset, |grapheme| offset + grapheme.len())
+}
+
+// 텍스트 길이를 넘지 않는 가장 가까운 앞쪽 글자 경계
+fn floor_char(text: &str, offset: usize) -> usize {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+// 불투명 합성용 배경 색 (sRGB 포맷이면 clear 값은 선형 공간이므로 변환)
+fn opaque_clear_color([r, g, b, _]: [u8; 4], format: Format) -> [f32; 4] {
+    // sRGB와 HDR(scRGB) 대상은 선형 값으로 지움
+    let linear = matches!(
+        format.numeric_format_color(),
+        Some(NumericFormat::SRGB | NumericFormat::SFLOAT)
+    );
+    let channel = |c: u8| {
+        let c = c as f32 / 255.0;
+        if !linear {
+            c
+        } else if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    [channel(r), channel(g), channel(b), 1.0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 텍스트 앞에서 끝까지 next_grapheme으로 옮긴 위치와 끝에서 prev_grapheme으로 되돌아온 위치
+    fn caret_stops(text: &str) -> (Vec<usize>, Vec<usize>) {
+        let mut forward = vec![0];
+        while let Some(&offset) = forward.last().filter(|&&offset| offset < text.len()) {
+            forward.push(next_grapheme(text, offset));
+        }
+        let mut backward = vec![text.len()];
+        while let Some(&offset) = backward.last().filter(|&&offset| offset > 0) {
+            backward.push(prev_grapheme(text, offset));
+        }
+        backward.reverse();
+        (forward, backward)
+    }
+
+    #[test]
+    fn caret_skips_combining_marks() {
+        // e + U+0301 (2바이트)
+        let (forward, backward) = caret_stops("e\u{301}x");
+        assert_eq!(forward, [0, 3, 4]);
+        assert_eq!(backward, forward);
+    }
+
+    #[test]
+    fn caret_skips_zwj_emoji() {
+        // 👨 ZWJ 👩 ZWJ 👧 (18바이트)
+        let (forward, backward) = caret_stops("a\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}b");
+        assert_eq!(forward, [0, 1, 19, 20]);
+        assert_eq!(backward, forward);
+    }
+
+    #[test]
+    fn caret_skips_hangul_jamo() {
+        // 첫가끝 자모 ᄀ ᅡ ᆨ은 한 글자, 완성형 '한'도 한 글자
+        let (forward, backward) = caret_stops("\u{1100}\u{1161}\u{11A8}한");
+        assert_eq!(forward, [0, 9, 12]);
+        assert_eq!(backward, forward);
+    }
+
+    #[test]
+    fn caret_stays_at_the_ends() {
+        assert_eq!(prev_grapheme("가", 0), 0);
+        assert_eq!(next_grapheme("가", 3), 3);
+        assert_eq!(next_grapheme("", 0), 0);
+    }
 }
@@ -0,0 +1,324 @@
+use crate::{
+    effect::{TextEffect, TextEffects},
+    style::{SpanStyle, StyleSpan, StyledText},
+};
+
+/// 간단한 인라인 마크업을 구간 스타일로 변환
+///
+/// 지원하는 태그 (닫는 태그는 `</color>`처럼 이름만):
+///
+/// - `<color=#rrggbb>`, `<color=#rrggbbaa>`, `<color=#rgb>`
+/// - `<alpha=0.5>` (구간 투명도)
+/// - `<size=32>` (글자 크기, 픽셀)
+/// - `<b>` (굵게)
+/// - `<spacing=0.1>` (자간, 글자 크기에 대한 배율)
+/// - `<line-height=1.5>` (줄 간격 배율)
+/// - `<wght=650>`, `<wdth=85>`, `<slnt=-10>` (가변 폰트의 굵기, 너비, 기울기 축)
+/// - `<effect=outline>` (`normal`, `outline`, `shadow`, `glow`, `gradient`, `wave`, `dissolve`, `glitch`, `+`로 조합: `outline+shadow`)
+///
+/// 알 수 없거나 형식이 틀린 태그는 글자 그대로 남고, 닫히지 않은 태그는 텍스트 끝까지 적용됩니다.
+///
+/// ```ignore
+/// let text = parse_markup("<size=64><b>제목</b></size>\n<color=#ffcc00>강조</color> 일반");
+/// text_renderer.set_styled_text(&text)?;
+/// ```
+pub fn parse_markup(input: &str) -> StyledText {
+    let mut styled = StyledText::default();
+    // 열린 태그 이름과 그 구간의 spans 인덱스
+    let mut open: Vec<(&str, usize)> = Vec::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find('<') {
+        styled.text.push_str(&rest[..start]);
+        let after = &rest[start..];
+        let Some(end) = after.find('>') else {
+            rest = after;
+            break;
+        };
+        let tag = &after[1..end];
+
+        let handled = if let Some(name) = tag.strip_prefix('/') {
+            match open.iter().rposition(|&(open_name, _)| open_name == name) {
+                Some(position) => {
+                    let (_, index) = open.remove(position);
+                    styled.spans[index].range.end = styled.text.len();
+                    true
+                }
+                None => false,
+            }
+        } else if let Some((name, style)) = parse_tag(tag) {
+            // 여는 시점에 구간을 추가해 안쪽 태그가 바깥 태그보다 우선하도록 함
+            let position = styled.text.len();
+            open.push((name, styled.spans.len()));
+            styled.spans.push(StyleSpan {
+                range: position..position,
+                style,
+            });
+            true
+        } else {
+            false
+        };
+
+        if !handled {
+            styled.text.push_str(&after[..=end]);
+        }
+        rest = &after[end + 1..];
+    }
+    styled.text.push_str(rest);
+
+    for (_, index) in open {
+        styled.spans[index].range.end = styled.text.len();
+    }
+    styled.spans.retain(|span| !span.range.is_empty());
+
+    styled
+}
+
+// 여는 태그 하나를 (이름, 스타일)로
+fn parse_tag(tag: &str) -> Option<(&str, SpanStyle)> {
+    let (name, value) = match tag.split_once('=') {
+        Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+        None => (tag.trim(), None),
+    };
+
+    let mut style = SpanStyle::default();
+    match (name, value) {
+        ("b", None) => style.bold = Some(true),
+        ("color", Some(value)) => style.color = Some(parse_color(value)?),
+        ("alpha", Some(value)) => {
+            style.opacity = Some(value.parse::<f32>().ok()?.clamp(0.0, 1.0));
+        }
+        ("size", Some(value)) => {
+            style.size = Some(value.parse::<f32>().ok().filter(|size| *size > 0.0)?);
+        }
+        ("effect", Some(value)) => style.effects = Some(parse_effects(value)?),
+        ("spacing", Some(value)) => style.letter_spacing = Some(value.parse().ok()?),
+        ("line-height", Some(value)) => {
+            style.line_height = Some(value.parse::<f32>().ok().filter(|h| *h > 0.0)?);
+        }
+        ("wght", Some(value)) => style.weight = Some(value.parse().ok()?),
+        ("wdth", Some(value)) => style.width = Some(value.parse().ok()?),
+        ("slnt", Some(value)) => style.slant = Some(value.parse().ok()?),
+        _ => return None,
+    }
+
+    Some((name, style))
+}
+
+/// `#rgb`, `#rrggbb`, `#rrggbbaa` 형식의 색 (마크업의 `<color>`와 같은 형식)
+pub fn parse_color(value: &str) -> Option<[u8; 4]> {
+    let hex = value.strip_prefix('#')?;
+    if !hex.is_ascii() {
+        return None;
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+
+    match hex.len() {
+        3 => {
+            let mut color = [255; 4];
+            for (out, i) in color.iter_mut().zip(0..3) {
+                *out = channel(&hex[i..i + 1])? * 17;
+            }
+            Some(color)
+        }
+        6 | 8 => {
+            let mut color = [255; 4];
+            for (out, i) in color.iter_mut().zip((0..hex.len()).step_by(2)) {
+                *out = channel(&hex[i..i + 2])?;
+            }
+            Some(color)
+        }
+        _ => None,
+    }
+}
+
+/// 색을 `parse_color`가 읽는 `#rrggbb` 형식으로 (불투명하지 않으면 `#rrggbbaa`)
+pub fn format_color(color: [u8; 4]) -> String {
+    let [r, g, b, a] = color;
+    if a == 255 {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    } else {
+        format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+}
+
+// 마크업과 설정 파일의 효과 이름
+const EFFECT_NAMES: [(&str, TextEffect); 8] = [
+    ("normal", TextEffect::Normal),
+    ("outline", TextEffect::Outline),
+    ("shadow", TextEffect::Shadow),
+    ("glow", TextEffect::Glow),
+    ("gradient", TextEffect::Gradient),
+    ("wave", TextEffect::Wave),
+    ("dissolve", TextEffect::Dissolve),
+    ("glitch", TextEffect::Glitch),
+];
+
+/// `outline+shadow`처럼 `+`로 이은 효과 이름 (마크업의 `<effect>`와 같은 형식)
+pub fn parse_effects(value: &str) -> Option<TextEffects> {
+    value
+        .split('+')
+        .try_fold(TextEffects::NONE, |effects, name| {
+            let (_, effect) = EFFECT_NAMES
+                .into_iter()
+                .find(|(effect_name, _)| *effect_name == name.trim())?;
+            Some(effects | effect.into())
+        })
+}
+
+/// 효과 조합을 `parse_effects`가 읽는 형식으로 (효과가 없으면 `normal`)
+pub fn format_effects(effects: TextEffects) -> String {
+    if effects.is_empty() {
+        return "normal".to_string();
+    }
+    EFFECT_NAMES
+        .into_iter()
+        .filter(|(_, effect)| *effect != TextEffect::Normal && effects.contains((*effect).into()))
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (바이트 범위, 스타일) 목록으로
+    fn spans(styled: &StyledText) -> Vec<(std::ops::Range<usize>, SpanStyle)> {
+        styled
+            .spans
+            .iter()
+            .map(|span| (span.range.clone(), span.style))
+            .collect()
+    }
+
+    fn bold() -> SpanStyle {
+        SpanStyle {
+            bold: Some(true),
+            ..SpanStyle::default()
+        }
+    }
+
+    #[test]
+    fn nested_tags() {
+        let styled = parse_markup("<b>a<color=#f00>b</color>c</b>");
+        assert_eq!(styled.text, "abc");
+        assert_eq!(
+            spans(&styled),
+            [(0..3, bold()), (1..2, SpanStyle::color([255, 0, 0, 255]))]
+        );
+    }
+
+    #[test]
+    fn overlapping_tags_close_by_name() {
+        let styled = parse_markup("<b>a<color=#f00>b</b>c</color>");
+        assert_eq!(styled.text, "abc");
+        assert_eq!(
+            spans(&styled),
+            [(0..2, bold()), (1..3, SpanStyle::color([255, 0, 0, 255]))]
+        );
+    }
+
+    #[test]
+    fn unclosed_tag_runs_to_the_end() {
+        let styled = parse_markup("가<size=32>나다");
+        assert_eq!(styled.text, "가나다");
+        let size = SpanStyle {
+            size: Some(32.0),
+            ..SpanStyle::default()
+        };
+        assert_eq!(spans(&styled), [(3..9, size)]);
+    }
+
+    #[test]
+    fn unknown_and_malformed_tags_stay_in_the_text() {
+        for input in [
+            "<foo>x</foo>",
+            "<b=1>x",
+            "<size=-1>x",
+            "<color=red>x",
+            "<effect=sparkle>x",
+            "x</b>",
+            "a < b",
+            "a<b",
+        ] {
+            let styled = parse_markup(input);
+            assert_eq!(styled.text, input);
+            assert!(styled.spans.is_empty(), "{input}");
+        }
+    }
+
+    #[test]
+    fn empty_spans_are_dropped() {
+        let styled = parse_markup("<b></b>x<alpha=0.5>");
+        assert_eq!(styled.text, "x");
+        assert!(styled.spans.is_empty());
+    }
+
+    #[test]
+    fn tag_values() {
+        let styled =
+            parse_markup("<alpha=2>a</alpha><effect=\"outline+glow\">b</effect><wght=650>c</wght>");
+        assert_eq!(styled.text, "abc");
+        let [alpha, effect, weight] = [0, 1, 2].map(|i| styled.spans[i].style);
+        assert_eq!(alpha.opacity, Some(1.0));
+        assert_eq!(
+            effect.effects,
+            Some(TextEffects::OUTLINE | TextEffects::GLOW)
+        );
+        assert_eq!(weight.weight, Some(650.0));
+    }
+
+    #[test]
+    fn color_formats() {
+        assert_eq!(parse_color("#f0a"), Some([255, 0, 170, 255]));
+        assert_eq!(parse_color("#ff8000"), Some([255, 128, 0, 255]));
+        assert_eq!(parse_color("#FF800080"), Some([255, 128, 0, 128]));
+        for value in [
+            "ff8000", "#", "#ff80", "#ff8000f", "#ff800g", "#가나", "#ff８0",
+        ] {
+            assert_eq!(parse_color(value), None, "{value}");
+        }
+    }
+
+    #[test]
+    fn color_round_trip() {
+        for color in [[255, 128, 0, 255], [1, 2, 3, 4], [0, 0, 0, 0]] {
+            assert_eq!(parse_color(&format_color(color)), Some(color));
+        }
+        assert_eq!(format_color([255, 128, 0, 255]), "#ff8000");
+    }
+
+    #[test]
+    fn effect_names() {
+        assert_eq!(
+            parse_effects(" outline + shadow "),
+            Some(TextEffects::OUTLINE | TextEffects::SHADOW)
+        );
+        assert_eq!(parse_effects("normal"), Some(TextEffects::NONE));
+        assert_eq!(parse_effects("outline+sparkle"), None);
+        assert_eq!(parse_effects(""), None);
+        assert_eq!(format_effects(TextEffects::NONE), "normal");
+        assert_eq!(
+            format_effects(TextEffects::SHADOW | TextEffects::OUTLINE),
+            "outline+shadow"
+        );
+    }
+
+    #[test]
+    fn effects_round_trip() {
+        let effects = &EFFECT_NAMES[1..];
+        for mask in 0..1u32 << effects.len() {
+            let combination = (0..)
+                .zip(effects)
+                .filter(|(bit, _)| mask & 1 << bit != 0)
+                .fold(TextEffects::NONE, |combination, (_, &(_, effect))| {
+                    combination | effect.into()
+                });
+            assert_eq!(
+                parse_effects(&format_effects(combination)),
+                Some(combination)
+            );
+        }
+    }
+}
@@ -0,0 +1,28 @@
+/// `--marquee`에 간격을 주지 않았을 때 텍스트 끝과 다음 복사본 사이의 간격 (논리 픽셀)
+pub const DEFAULT_MARQUEE_GAP: f32 = 80.0;
+
+/// 창보다 넓은 텍스트를 가로로 흘리는 전광판 모드 (`--marquee <속도>`)
+///
+/// 텍스트 뒤에 한 간격 띄워 복사본을 하나 더 그려 끊김 없이 이어지며, 한 주기(텍스트 너비 + 간격)가
+/// 지나면 처음 위치로 돌아갑니다. 창에 들어가는 텍스트는 흘리지 않습니다.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Marquee {
+    /// 초당 움직이는 거리 (논리 픽셀, 양수면 왼쪽으로, 음수면 오른쪽으로)
+    pub speed: f32,
+    /// 텍스트 끝과 다음 복사본 사이의 간격 (논리 픽셀)
+    pub gap: f32,
+}
+
+impl Marquee {
+    /// 시각 `time`(초)에 첫 번째 텍스트의 왼쪽 끝 위치와 다음 복사본까지의 거리
+    ///
+    /// 텍스트가 창에 들어가면 None. 위치는 0 이하라 두 복사본이 항상 창 전체를 덮습니다.
+    pub fn offsets(&self, time: f32, text_width: f32, window_width: f32) -> Option<(f32, f32)> {
+        if text_width <= window_width {
+            return None;
+        }
+        let period = text_width + self.gap.max(0.0);
+        let offset = (time * self.speed).rem_euclid(period);
+        Some((-offset, period))
+    }
+}
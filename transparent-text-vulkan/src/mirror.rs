@@ -0,0 +1,313 @@
+use std::{str::FromStr, sync::Arc};
+use vulkano::{
+    command_buffer::{AutoCommandBufferBuilder, CommandBufferUsage},
+    device::Queue,
+    format::NumericFormat,
+    memory::allocator::StandardMemoryAllocator,
+    pipeline::graphics::viewport::Viewport,
+    swapchain::{acquire_next_image, Surface, Swapchain, SwapchainPresentInfo},
+    sync::GpuFuture,
+    Validated, VulkanError,
+};
+use glam::{Mat4, Vec3};
+use tracing::warn;
+use winit::{
+    dpi::PhysicalSize,
+    event_loop::EventLoopWindowTarget,
+    window::{Window, WindowBuilder, WindowId},
+};
+use transparent_text_vulkan::{
+    resize_swapchain, FramesInFlight, PresentModePreference, RendererConfig, RendererError,
+    TextLayer, TextParams, TextRenderer,
+};
+use vulkan_context::create_swapchain;
+
+use crate::{logical_extent, overlay_window, placement::Placement, render_path::RenderPath};
+
+/// `--mirrors all` 또는 `--mirrors 1,2`: 미러 창을 띄울 모니터
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MirrorMonitors {
+    /// 주 창이 있는 모니터를 뺀 모든 모니터
+    All,
+    /// `available_monitors()` 순서의 번호 (시작할 때 목록 출력)
+    List(Vec<usize>),
+}
+
+impl MirrorMonitors {
+    /// 미러 창을 놓을 모니터 번호 (범위를 벗어난 번호는 뺌)
+    pub fn indices(&self, window: &Window) -> Vec<usize> {
+        let current = window.current_monitor();
+        let monitors: Vec<_> = window.available_monitors().collect();
+        match self {
+            MirrorMonitors::All => (0..monitors.len())
+                .filter(|&index| current.as_ref() != Some(&monitors[index]))
+                .collect(),
+            MirrorMonitors::List(indices) => indices
+                .iter()
+                .copied()
+                .filter(|&index| index < monitors.len())
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for MirrorMonitors {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "all" {
+            return Ok(MirrorMonitors::All);
+        }
+        value
+            .split(',')
+            .map(|index| index.trim().parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map(MirrorMonitors::List)
+            .map_err(|_| format!("all 또는 쉼표로 구분한 모니터 번호여야 합니다: {value}"))
+    }
+}
+
+/// 다른 모니터에 주 창과 같은 텍스트를 띄우는 창
+///
+/// 창마다 surface, 스왑체인, 레이어 이미지, 프레임 동기화만 따로 두고
+/// 장치, 메모리 할당자, render pass(또는 dynamic rendering 포맷), 글리프 아틀라스와 파이프라인(`TextRenderer`)은 주 창과 공유합니다.
+/// 입력은 받지 않고 클릭은 아래로 통과하며, 주 창이 그린 다음 같은 장면으로 그립니다.
+pub struct MirrorWindow {
+    window: Arc<Window>,
+    queue: Arc<Queue>,
+    swapchain: Arc<Swapchain>,
+    render_path: RenderPath,
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    viewport: Viewport,
+    layer: TextLayer,
+    frames: FramesInFlight,
+    placement: Placement,
+    recreate_swapchain: bool,
+}
+
+impl MirrorWindow {
+    /// `size`(물리 픽셀)의 창을 `placement` 위치에 만듦 (주 창과 스왑체인 포맷이 다르면 실패)
+    pub fn new(
+        elwt: &EventLoopWindowTarget<()>,
+        queue: &Arc<Queue>,
+        render_path: &RenderPath,
+        memory_allocator: &Arc<StandardMemoryAllocator>,
+        renderer_config: &RendererConfig,
+        placement: Placement,
+        size: PhysicalSize<u32>,
+    ) -> Result<Self, RendererError> {
+        let device = queue.device().clone();
+        let window_builder = WindowBuilder::new()
+            .with_title("투명 텍스트 렌더러 (Vulkan) - 미러")
+            .with_transparent(true)
+            .with_decorations(false)
+            .with_inner_size(size);
+        let window = Arc::new(
+            overlay_window(window_builder)
+                .build(elwt)
+                .map_err(|e| RendererError::Window(e.into()))?,
+        );
+        if let Err(e) = window.set_cursor_hittest(false) {
+            warn!("미러 창의 클릭 통과를 켤 수 없습니다: {e}");
+        }
+        placement.apply(&window);
+
+        let surface = Surface::from_window(device.instance().clone(), window.clone())
+            .map_err(|e| RendererError::Window(e.into()))?;
+        // 주 창의 큐로 이 surface에 표시할 수 있어야 함 (다른 GPU에 연결된 모니터 등)
+        let supported = device
+            .physical_device()
+            .surface_support(queue.queue_family_index(), &surface)
+            .unwrap_or(false);
+        if !supported {
+            return Err(RendererError::Window(
+                "주 창의 GPU로 이 모니터에 표시할 수 없습니다".into(),
+            ));
+        }
+
+        // 주 창과 같은 루프에서 이어서 표시하므로 수직 동기화로 주 창의 프레임을 붙잡지 않도록 mailbox를 우선
+        // 주 창이 HDR이면 미러 창도 HDR 포맷이어야 render pass를 공유할 수 있음
+        let format = render_path.format();
+        let hdr = format.numeric_format_color() == Some(NumericFormat::SFLOAT);
+        let (swapchain, images) = create_swapchain(
+            &device,
+            surface,
+            window.inner_size().into(),
+            PresentModePreference::Mailbox,
+            hdr,
+        )?;
+        if swapchain.image_format() != format {
+            return Err(RendererError::Window(
+                format!(
+                    "미러 창의 스왑체인 포맷({:?})이 주 창과 다릅니다",
+                    swapchain.image_format()
+                )
+                .into(),
+            ));
+        }
+
+        let mut viewport = Viewport::default();
+        let mut render_path = render_path.share();
+        render_path.resize(&images, memory_allocator, &mut viewport)?;
+
+        Ok(Self {
+            layer: TextLayer::new(device.clone(), render_path.target())?,
+            frames: FramesInFlight::new(device, renderer_config),
+            window,
+            queue: queue.clone(),
+            swapchain,
+            render_path,
+            memory_allocator: memory_allocator.clone(),
+            viewport,
+            placement,
+            recreate_swapchain: false,
+        })
+    }
+
+    pub fn id(&self) -> WindowId {
+        self.window.id()
+    }
+
+    /// 창 크기나 배율이 바뀌었으면 다음 프레임에 스왑체인을 다시 만들고 같은 위치로 놓음
+    pub fn resized(&mut self) {
+        self.recreate_swapchain = true;
+        self.placement.apply(&self.window);
+    }
+
+    /// 모니터 구성이 바뀌었을 때 다시 놓음
+    pub fn place(&self) {
+        self.placement.apply(&self.window);
+    }
+
+    /// 주 창의 크기가 바뀌면 같은 크기로 맞춤 (물리 픽셀)
+    pub fn resize(&mut self, size: PhysicalSize<u32>) {
+        let _ = self.window.request_inner_size(size);
+        self.resized();
+    }
+
+    /// 주 창을 숨기거나 다시 보일 때 함께
+    pub fn set_visible(&self, visible: bool) {
+        self.window.set_visible(visible);
+    }
+
+    /// 주 창과 같은 장면을 그림
+    ///
+    /// 글리프는 주 창의 배율로 래스터라이즈되어 있으므로 `scale_factor`도 주 창의 배율이고,
+    /// 텍스트는 주 창의 가운데 기준 위치를 이 창의 가운데로 옮겨 그립니다 (`main_extent`는 주 창의 논리 크기).
+    pub fn render(
+        &mut self,
+        text_renderer: &mut TextRenderer,
+        params: &TextParams,
+        scale_factor: f32,
+        main_extent: [f32; 2],
+    ) -> Result<(), RendererError> {
+        let window_extent: [u32; 2] = self.window.inner_size().into();
+        if window_extent.contains(&0) {
+            return Ok(());
+        }
+        if self.recreate_swapchain && !self.recreate_swapchain(window_extent)? {
+            return Ok(());
+        }
+
+        let (image_index, suboptimal, acquire_future) =
+            match acquire_next_image(self.swapchain.clone(), None).map_err(Validated::unwrap) {
+                Ok(r) => r,
+                Err(VulkanError::OutOfDate) => {
+                    self.recreate_swapchain = true;
+                    return Ok(());
+                }
+                Err(e) => return Err(RendererError::Swapchain(Validated::Error(e))),
+            };
+        if suboptimal {
+            self.recreate_swapchain = true;
+        }
+
+        self.frames.wait_current();
+        let mut builder = AutoCommandBufferBuilder::primary(
+            self.frames.command_buffer_allocator(),
+            self.queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .map_err(|e| RendererError::Command(e.into()))?;
+
+        let extent = logical_extent(&self.viewport, scale_factor);
+        let offset = Vec3::new(
+            (extent[0] - main_extent[0]) / 2.0,
+            (extent[1] - main_extent[1]) / 2.0,
+            0.0,
+        );
+        let params = TextParams {
+            projection: TextParams::pixel_projection(extent),
+            transform: Mat4::from_translation(offset) * params.transform,
+            ..*params
+        };
+
+        // 텍스트 렌더러가 기억하는 마지막 장면은 주 창이 그린 것이라 바뀌지 않았다는 판단을 믿을 수 없음
+        self.layer.invalidate();
+        self.layer.draw(
+            &mut builder,
+            text_renderer,
+            &params,
+            self.swapchain.image_extent(),
+        )?;
+
+        self.render_path
+            .begin(&mut builder, image_index as usize, [0.0, 0.0, 0.0, 0.0])?;
+        builder
+            .set_viewport(0, [self.viewport.clone()].into_iter().collect())
+            .map_err(|e| RendererError::Command(e.into()))?;
+        self.layer.composite(&mut builder, params.opacity)?;
+        self.render_path.end(&mut builder)?;
+
+        let command_buffer = builder
+            .build()
+            .map_err(|e| RendererError::Command(e.into()))?;
+        let mut future = self.frames.previous_future().join(acquire_future).boxed();
+        if let Some(upload) = text_renderer.take_upload_future() {
+            future = future.join(upload).boxed();
+        }
+        let future = future
+            .then_execute(self.queue.clone(), command_buffer)
+            .map_err(|e| RendererError::Command(e.into()))?
+            .then_swapchain_present(
+                self.queue.clone(),
+                SwapchainPresentInfo::swapchain_image_index(self.swapchain.clone(), image_index),
+            )
+            .boxed()
+            .then_signal_fence_and_flush();
+
+        match future.map_err(Validated::unwrap) {
+            Ok(future) => {
+                self.frames.finish_frame(Some(future));
+                if let Some(fence) = self.frames.last_fence() {
+                    text_renderer.frame_submitted(fence);
+                }
+            }
+            Err(VulkanError::OutOfDate) => {
+                self.recreate_swapchain = true;
+                self.frames.finish_frame(None);
+            }
+            Err(e) => {
+                self.frames.finish_frame(None);
+                return Err(RendererError::Swapchain(Validated::Error(e)));
+            }
+        }
+        Ok(())
+    }
+
+    // 다시 만들었으면 true (그릴 수 없는 크기면 다음 프레임에 다시 시도)
+    fn recreate_swapchain(&mut self, window_extent: [u32; 2]) -> Result<bool, RendererError> {
+        self.frames.wait_all();
+        let present_mode = self.swapchain.present_mode();
+        let Some((swapchain, images)) =
+            resize_swapchain(&self.swapchain, window_extent, present_mode)?
+        else {
+            return Ok(false);
+        };
+        self.swapchain = swapchain;
+        self.render_path
+            .resize(&images, &self.memory_allocator, &mut self.viewport)?;
+        self.recreate_swapchain = false;
+        Ok(true)
+    }
+}
@@ -0,0 +1,215 @@
+use std::sync::Arc;
+use vulkano::{
+    buffer::BufferContents,
+    command_buffer::{
+        allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+    },
+    descriptor_set::{
+        allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
+    },
+    device::{Device, Queue},
+    image::{sampler::Sampler, view::ImageView},
+    memory::allocator::StandardMemoryAllocator,
+    pipeline::{
+        compute::ComputePipelineCreateInfo, layout::PipelineDescriptorSetLayoutCreateInfo,
+        ComputePipeline, Pipeline, PipelineBindPoint, PipelineLayout,
+        PipelineShaderStageCreateInfo,
+    },
+    sync::{self, GpuFuture},
+};
+use tracing::debug_span;
+
+use crate::{
+    atlas::GlyphAtlas,
+    error::RendererError,
+    shaders,
+    shadow::{create_fetch_sampler, create_storage_image},
+};
+
+const WORKGROUP_SIZE: u32 = 16;
+
+// dilate_cs의 push constant 블록과 같은 레이아웃
+#[derive(BufferContents, Clone, Copy)]
+#[repr(C)]
+struct DilateParams {
+    radius: f32,
+    softness: f32,
+}
+
+// 팽창 결과와 그 시점의 입력 (다시 팽창할 필요가 있는지 판단용)
+struct DilateTarget {
+    size: u32,
+    layers: u32,
+    output: Arc<ImageView>,
+    descriptor_set: Arc<PersistentDescriptorSet>,
+    // 마지막으로 팽창한 (아틀라스 세대, 아틀라스 리비전, 반지름, 흐림 폭)
+    dilated: Option<(u64, u64, f32, f32)>,
+}
+
+/// 글리프 아틀라스를 외곽선 두께만큼 팽창한 외곽선 아틀라스
+///
+/// 프래그먼트마다 반지름 안을 최대 17x17번 샘플링하던 외곽선을 아틀라스 텍셀마다 한 번만 계산해 두고,
+/// 텍스트 셰이더는 같은 좌표에서 한 번 샘플링합니다.
+/// 아틀라스 내용이나 두께, 흐림 폭이 바뀔 때만 컴퓨트 셰이더로 다시 팽창합니다.
+pub(crate) struct OutlineDilate {
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    pipeline: Arc<ComputePipeline>,
+    sampler: Arc<Sampler>,
+    target: Option<DilateTarget>,
+    atlas_generation: u64,
+}
+
+impl OutlineDilate {
+    pub fn new(
+        device: Arc<Device>,
+        memory_allocator: Arc<StandardMemoryAllocator>,
+    ) -> Result<Self, RendererError> {
+        let cs = shaders::dilate_cs::load(device.clone())
+            .map_err(|e| RendererError::shader("dilate compute", e))?
+            .entry_point("main")
+            .ok_or_else(|| RendererError::shader("dilate compute", "main 진입점 없음"))?;
+        let sampler = create_fetch_sampler(&device)?;
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
+                .into_pipeline_layout_create_info(device.clone())
+                .map_err(RendererError::pipeline)?,
+        )
+        .map_err(RendererError::pipeline)?;
+        let pipeline = ComputePipeline::new(
+            device,
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .map_err(RendererError::pipeline)?;
+
+        Ok(Self {
+            memory_allocator,
+            pipeline,
+            sampler,
+            target: None,
+            atlas_generation: 0,
+        })
+    }
+
+    /// 외곽선 아틀라스 (아틀라스가 커졌으면 같은 크기로 다시 만듦)
+    pub fn view(
+        &mut self,
+        atlas: &GlyphAtlas,
+        descriptor_set_allocator: &StandardDescriptorSetAllocator,
+    ) -> Result<Arc<ImageView>, RendererError> {
+        Ok(self.target(atlas, descriptor_set_allocator)?.output.clone())
+    }
+
+    /// 아틀라스 내용이나 두께, 흐림 폭이 바뀌었으면 다시 팽창 (기다리지 않고 제출)
+    ///
+    /// `queue`는 비동기 컴퓨트 큐이거나 그래픽 큐이며, 완료는 아틀라스의 `take_pending`으로 기다립니다.
+    ///
+    /// `radius`와 `softness`는 아틀라스 텍셀 단위입니다.
+    pub fn update(
+        &mut self,
+        atlas: &mut GlyphAtlas,
+        radius: f32,
+        softness: f32,
+        descriptor_set_allocator: &StandardDescriptorSetAllocator,
+        command_buffer_allocator: &StandardCommandBufferAllocator,
+        queue: Arc<Queue>,
+    ) -> Result<(), RendererError> {
+        let pipeline = self.pipeline.clone();
+        let target = self.target(atlas, descriptor_set_allocator)?;
+        let state = (atlas.generation(), atlas.revision(), radius, softness);
+        if target.dilated == Some(state) {
+            return Ok(());
+        }
+        let _span = debug_span!(
+            "outline_dilate",
+            size = target.size,
+            layers = target.layers,
+            radius,
+            softness
+        )
+        .entered();
+
+        let groups = target.size.div_ceil(WORKGROUP_SIZE);
+        let mut builder = AutoCommandBufferBuilder::primary(
+            command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .map_err(RendererError::command)?;
+
+        builder
+            .bind_pipeline_compute(pipeline.clone())
+            .map_err(RendererError::command)?
+            .bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                pipeline.layout().clone(),
+                0,
+                target.descriptor_set.clone(),
+            )
+            .map_err(RendererError::command)?
+            .push_constants(
+                pipeline.layout().clone(),
+                0,
+                DilateParams { radius, softness },
+            )
+            .map_err(RendererError::command)?
+            .dispatch([groups, groups, target.layers])
+            .map_err(RendererError::command)?;
+
+        let command_buffer = builder.build().map_err(RendererError::command)?;
+        // 아직 기다리지 않은 아틀라스 업로드가 있으면 그 뒤에 실행하고, 그리기는 세마포어로 이것을 기다림
+        let future = atlas
+            .take_pending()
+            .unwrap_or_else(|| sync::now(queue.device().clone()).boxed())
+            .then_execute(queue, command_buffer)
+            .map_err(RendererError::command)?
+            .then_signal_semaphore_and_flush()
+            .map_err(RendererError::command)?;
+        atlas.set_pending(future.boxed());
+
+        target.dilated = Some(state);
+        Ok(())
+    }
+
+    // 아틀라스 이미지가 다시 만들어졌으면 결과 이미지와 디스크립터 셋도 다시 만듦
+    fn target(
+        &mut self,
+        atlas: &GlyphAtlas,
+        descriptor_set_allocator: &StandardDescriptorSetAllocator,
+    ) -> Result<&mut DilateTarget, RendererError> {
+        if self.target.is_none() || self.atlas_generation != atlas.generation() {
+            let (size, layers) = (atlas.size(), atlas.layers());
+            let output =
+                create_storage_image(&self.memory_allocator, size, layers, atlas.queue_families())?;
+            let descriptor_set = PersistentDescriptorSet::new(
+                descriptor_set_allocator,
+                self.pipeline.layout().set_layouts()[0].clone(),
+                [
+                    WriteDescriptorSet::image_view_sampler(
+                        0,
+                        atlas.base_view(),
+                        self.sampler.clone(),
+                    ),
+                    WriteDescriptorSet::image_view(1, output.clone()),
+                ],
+                [],
+            )
+            .map_err(RendererError::texture)?;
+
+            self.target = Some(DilateTarget {
+                size,
+                layers,
+                output,
+                descriptor_set,
+                dilated: None,
+            });
+            self.atlas_generation = atlas.generation();
+        }
+
+        self.target
+            .as_mut()
+            .ok_or_else(|| RendererError::texture("외곽선 아틀라스 생성 실패"))
+    }
+}
@@ -0,0 +1,615 @@
+use std::{collections::HashMap, sync::Arc};
+use vulkano::{
+    buffer::{Buffer, BufferContents, BufferCreateInfo, BufferUsage, Subbuffer},
+    command_buffer::{AutoCommandBufferBuilder, BufferImageCopy, CopyBufferToImageInfo},
+    descriptor_set::{
+        allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
+    },
+    device::{Device, DeviceOwned},
+    format::{Format, NumericFormat},
+    image::{
+        sampler::{Filter, Sampler, SamplerCreateInfo},
+        view::ImageView,
+        Image, ImageCreateInfo, ImageType, ImageUsage,
+    },
+    memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+    pipeline::{
+        graphics::{
+            color_blend::{
+                AttachmentBlend, BlendFactor, BlendOp, ColorBlendAttachmentState, ColorBlendState,
+            },
+            input_assembly::InputAssemblyState,
+            multisample::MultisampleState,
+            rasterization::RasterizationState,
+            vertex_input::{Vertex, VertexDefinition},
+            viewport::{Scissor, ViewportState},
+            GraphicsPipelineCreateInfo,
+        },
+        layout::PipelineDescriptorSetLayoutCreateInfo,
+        DynamicState, GraphicsPipeline, Pipeline, PipelineBindPoint, PipelineLayout,
+        PipelineShaderStageCreateInfo,
+    },
+    shader::SpecializationConstant,
+};
+use egui::{
+    epaint::{ImageDelta, Primitive},
+    ClippedPrimitive, Context, FontData, FontDefinitions, FontFamily, ImageData, Slider,
+    TextureFilter, TextureId, TexturesDelta, ViewportId,
+};
+use winit::{event::WindowEvent, window::Window};
+use transparent_text_vulkan::{RenderTarget, RendererError};
+
+use crate::{BUNDLED_FONT, MAX_GLOW_RADIUS};
+
+mod vs {
+    vulkano_shaders::shader! {
+        ty: "vertex",
+        path: "shaders/panel.vert",
+    }
+}
+
+mod fs {
+    vulkano_shaders::shader! {
+        ty: "fragment",
+        path: "shaders/panel.frag",
+    }
+}
+
+// panel.vert의 정점 입력 (egui::epaint::Vertex와 같은 배치)
+#[derive(BufferContents, Vertex, Clone, Copy)]
+#[repr(C)]
+struct PanelVertex {
+    #[format(R32G32_SFLOAT)]
+    position: [f32; 2],
+    #[format(R32G32_SFLOAT)]
+    uv: [f32; 2],
+    #[format(R8G8B8A8_UNORM)]
+    color: [u8; 4],
+}
+
+// panel.vert의 push constant 블록과 같은 레이아웃
+#[derive(BufferContents, Clone, Copy)]
+#[repr(C)]
+struct PanelPushConstants {
+    screen_size: [f32; 2],
+}
+
+/// 패널에서 고치는 값
+///
+/// 프레임마다 App의 현재 상태로 채워 `ControlPanel::update`에 넘기고, 바뀐 값만 App에 반영합니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanelValues {
+    pub text: String,
+    pub opacity: f32,
+    pub outline_width: f32,
+    pub shadow_offset: [f32; 2],
+    pub glow_radius: f32,
+    pub font_size: f32,
+}
+
+// egui 텍스처 하나 (이미지와 그것을 샘플링하는 디스크립터 셋)
+struct PanelTexture {
+    image: Arc<Image>,
+    descriptor_set: Arc<PersistentDescriptorSet>,
+}
+
+// 메시 하나를 그리는 인덱스 범위와 가위 사각형 (물리 픽셀)
+struct PanelDraw {
+    texture_id: TextureId,
+    first_index: u32,
+    index_count: u32,
+    vertex_offset: i32,
+    scissor: Scissor,
+}
+
+// 이번 프레임에 그릴 메시를 모은 버퍼
+struct PanelFrame {
+    vertices: Subbuffer<[PanelVertex]>,
+    indices: Subbuffer<[u32]>,
+    draws: Vec<PanelDraw>,
+    screen_size: [f32; 2],
+}
+
+/// 창 안에 띄우는 egui 컨트롤 패널 (egui-panel 기능)
+///
+/// 투명도, 외곽선 두께, 그림자 오프셋, 발광 반지름, 글자 크기 슬라이더와 텍스트 상자가 있습니다.
+/// egui 메시는 스왑체인 render pass에서 레이어를 합성한 뒤 그 위에 그리고,
+/// 텍스처 업로드는 render pass 전에 같은 command buffer에 기록합니다.
+pub struct ControlPanel {
+    context: Context,
+    state: egui_winit::State,
+    visible: bool,
+    // egui가 곧바로 다시 그려 달라고 했는지 (애니메이션, 커서 깜박임)
+    repaint: bool,
+    // 끌고 있는 글자 크기 (렌더러를 다시 만들어야 하므로 놓을 때 한 번만 반영)
+    font_size_edit: Option<f32>,
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    descriptor_set_allocator: StandardDescriptorSetAllocator,
+    pipeline: Arc<GraphicsPipeline>,
+    textures: HashMap<TextureId, PanelTexture>,
+    // egui가 이번 프레임을 그린 뒤 지우라고 한 텍스처 (다음 update에서 지움)
+    pending_free: Vec<TextureId>,
+    frame: Option<PanelFrame>,
+}
+
+impl ControlPanel {
+    pub fn new(
+        window: &Window,
+        device: Arc<Device>,
+        memory_allocator: Arc<StandardMemoryAllocator>,
+        target: RenderTarget,
+        visible: bool,
+    ) -> Result<Self, RendererError> {
+        let context = Context::default();
+        // 기본 egui 폰트에는 한글이 없으므로 내장 폰트를 대체 폰트로 추가
+        let mut fonts = FontDefinitions::default();
+        fonts
+            .font_data
+            .insert("bundled".to_owned(), FontData::from_static(BUNDLED_FONT));
+        for family in [FontFamily::Proportional, FontFamily::Monospace] {
+            fonts
+                .families
+                .entry(family)
+                .or_default()
+                .push("bundled".to_owned());
+        }
+        context.set_fonts(fonts);
+
+        let state = egui_winit::State::new(
+            context.clone(),
+            ViewportId::ROOT,
+            window,
+            Some(window.scale_factor() as f32),
+            None,
+        );
+
+        Ok(Self {
+            context,
+            state,
+            visible,
+            repaint: false,
+            font_size_edit: None,
+            memory_allocator,
+            descriptor_set_allocator: StandardDescriptorSetAllocator::new(
+                device.clone(),
+                Default::default(),
+            ),
+            pipeline: create_pipeline(device, target)?,
+            textures: HashMap::new(),
+            pending_free: Vec::new(),
+            frame: None,
+        })
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        self.font_size_edit = None;
+    }
+
+    /// 보이는 동안 다음 프레임도 그려야 하는지
+    pub fn is_animating(&self) -> bool {
+        self.visible && self.repaint
+    }
+
+    /// 창 이벤트를 egui에 넘기고, egui가 입력을 가져갔으면(텍스트 상자 입력, 패널 위의 클릭) true
+    pub fn on_window_event(&mut self, window: &Window, event: &WindowEvent) -> bool {
+        if !self.visible {
+            return false;
+        }
+        self.state.on_window_event(window, event).consumed
+    }
+
+    /// 패널을 한 프레임 실행해 `values`를 고치고, 텍스처 업로드와 그릴 메시를 준비합니다.
+    ///
+    /// `builder`는 render pass 밖이어야 합니다. `extent`는 스왑체인 크기 (물리 픽셀)입니다.
+    pub fn update<L>(
+        &mut self,
+        window: &Window,
+        values: &mut PanelValues,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        extent: [u32; 2],
+    ) -> Result<(), RendererError> {
+        self.frame = None;
+        // 지난 프레임의 command buffer가 이미지를 붙잡고 있으므로 여기서 지워도 안전
+        for id in self.pending_free.drain(..) {
+            self.textures.remove(&id);
+        }
+        if !self.visible {
+            return Ok(());
+        }
+
+        let input = self.state.take_egui_input(window);
+        let font_size_edit = &mut self.font_size_edit;
+        let output = self.context.run(input, |context| {
+            show_panel(context, values, font_size_edit);
+        });
+        self.state
+            .handle_platform_output(window, output.platform_output);
+        self.repaint = output
+            .viewport_output
+            .get(&ViewportId::ROOT)
+            .is_some_and(|viewport| viewport.repaint_delay.is_zero());
+
+        self.upload_textures(builder, output.textures_delta)?;
+        let primitives = self
+            .context
+            .tessellate(output.shapes, output.pixels_per_point);
+        self.frame = self.prepare_frame(&primitives, output.pixels_per_point, extent)?;
+        Ok(())
+    }
+
+    /// `update`에서 준비한 메시를 그립니다 (대상 render pass 안에서, viewport는 호출하는 쪽에서 설정).
+    pub fn draw<L>(&self, builder: &mut AutoCommandBufferBuilder<L>) -> Result<(), RendererError> {
+        let Some(frame) = &self.frame else {
+            return Ok(());
+        };
+
+        builder
+            .bind_pipeline_graphics(self.pipeline.clone())
+            .and_then(|builder| {
+                builder.push_constants(
+                    self.pipeline.layout().clone(),
+                    0,
+                    PanelPushConstants {
+                        screen_size: frame.screen_size,
+                    },
+                )
+            })
+            .and_then(|builder| builder.bind_vertex_buffers(0, frame.vertices.clone()))
+            .and_then(|builder| builder.bind_index_buffer(frame.indices.clone()))
+            .map_err(|e| RendererError::Command(e.into()))?;
+
+        for draw in &frame.draws {
+            let Some(texture) = self.textures.get(&draw.texture_id) else {
+                continue;
+            };
+            builder
+                .set_scissor(0, [draw.scissor].into_iter().collect())
+                .and_then(|builder| {
+                    builder.bind_descriptor_sets(
+                        PipelineBindPoint::Graphics,
+                        self.pipeline.layout().clone(),
+                        0,
+                        texture.descriptor_set.clone(),
+                    )
+                })
+                .and_then(|builder| {
+                    builder.draw_indexed(
+                        draw.index_count,
+                        1,
+                        draw.first_index,
+                        draw.vertex_offset,
+                        0,
+                    )
+                })
+                .map_err(|e| RendererError::Command(e.into()))?;
+        }
+        Ok(())
+    }
+
+    // 새 텍스처는 만들어 전체를, 기존 텍스처는 바뀐 영역만 복사
+    fn upload_textures<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        delta: TexturesDelta,
+    ) -> Result<(), RendererError> {
+        for (id, image_delta) in delta.set {
+            let [width, height] = image_delta.image.size();
+            let image = match image_delta.pos {
+                Some(_) => match self.textures.get(&id) {
+                    Some(texture) => texture.image.clone(),
+                    None => continue,
+                },
+                None => {
+                    let texture = self.create_texture(&image_delta, [width, height])?;
+                    let image = texture.image.clone();
+                    self.textures.insert(id, texture);
+                    image
+                }
+            };
+            let [x, y] = image_delta.pos.unwrap_or([0, 0]);
+
+            let upload_buffer = Buffer::from_iter(
+                self.memory_allocator.clone(),
+                BufferCreateInfo {
+                    usage: BufferUsage::TRANSFER_SRC,
+                    ..Default::default()
+                },
+                AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                        | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                    ..Default::default()
+                },
+                image_pixels(&image_delta.image),
+            )
+            .map_err(|e| RendererError::Buffer(e.into()))?;
+            builder
+                .copy_buffer_to_image(CopyBufferToImageInfo {
+                    regions: [BufferImageCopy {
+                        image_subresource: image.subresource_layers(),
+                        image_offset: [x as u32, y as u32, 0],
+                        image_extent: [width as u32, height as u32, 1],
+                        ..Default::default()
+                    }]
+                    .into(),
+                    ..CopyBufferToImageInfo::buffer_image(upload_buffer, image)
+                })
+                .map_err(|e| RendererError::Texture(e.into()))?;
+        }
+        self.pending_free = delta.free;
+        Ok(())
+    }
+
+    fn create_texture(
+        &self,
+        image_delta: &ImageDelta,
+        [width, height]: [usize; 2],
+    ) -> Result<PanelTexture, RendererError> {
+        let image = Image::new(
+            self.memory_allocator.clone(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [width as u32, height as u32, 1],
+                usage: ImageUsage::SAMPLED | ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .map_err(|e| RendererError::Texture(e.into()))?;
+        let view =
+            ImageView::new_default(image.clone()).map_err(|e| RendererError::Texture(e.into()))?;
+        let sampler = Sampler::new(
+            self.memory_allocator.device().clone(),
+            SamplerCreateInfo {
+                mag_filter: filter(image_delta.options.magnification),
+                min_filter: filter(image_delta.options.minification),
+                ..Default::default()
+            },
+        )
+        .map_err(|e| RendererError::Texture(e.into()))?;
+        let descriptor_set = PersistentDescriptorSet::new(
+            &self.descriptor_set_allocator,
+            self.pipeline.layout().set_layouts()[0].clone(),
+            [WriteDescriptorSet::image_view_sampler(0, view, sampler)],
+            [],
+        )
+        .map_err(|e| RendererError::Texture(e.into()))?;
+        Ok(PanelTexture {
+            image,
+            descriptor_set,
+        })
+    }
+
+    // 모든 메시를 정점/인덱스 버퍼 하나씩에 모으고, 잘라낼 사각형을 물리 픽셀로 바꿈
+    fn prepare_frame(
+        &self,
+        primitives: &[ClippedPrimitive],
+        pixels_per_point: f32,
+        extent: [u32; 2],
+    ) -> Result<Option<PanelFrame>, RendererError> {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        let mut draws = Vec::new();
+        for ClippedPrimitive {
+            clip_rect,
+            primitive,
+        } in primitives
+        {
+            // 사용자 콜백은 쓰지 않음
+            let Primitive::Mesh(mesh) = primitive else {
+                continue;
+            };
+            if mesh.indices.is_empty() {
+                continue;
+            }
+
+            let min_x = (clip_rect.min.x * pixels_per_point).round().max(0.0) as u32;
+            let min_y = (clip_rect.min.y * pixels_per_point).round().max(0.0) as u32;
+            let max_x =
+                ((clip_rect.max.x * pixels_per_point).round().max(0.0) as u32).min(extent[0]);
+            let max_y =
+                ((clip_rect.max.y * pixels_per_point).round().max(0.0) as u32).min(extent[1]);
+            if min_x >= max_x || min_y >= max_y {
+                continue;
+            }
+
+            draws.push(PanelDraw {
+                texture_id: mesh.texture_id,
+                first_index: indices.len() as u32,
+                index_count: mesh.indices.len() as u32,
+                vertex_offset: vertices.len() as i32,
+                scissor: Scissor {
+                    offset: [min_x, min_y],
+                    extent: [max_x - min_x, max_y - min_y],
+                },
+            });
+            indices.extend_from_slice(&mesh.indices);
+            vertices.extend(mesh.vertices.iter().map(|vertex| PanelVertex {
+                position: [vertex.pos.x, vertex.pos.y],
+                uv: [vertex.uv.x, vertex.uv.y],
+                color: vertex.color.to_array(),
+            }));
+        }
+        if draws.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(PanelFrame {
+            vertices: self.create_buffer(BufferUsage::VERTEX_BUFFER, vertices)?,
+            indices: self.create_buffer(BufferUsage::INDEX_BUFFER, indices)?,
+            draws,
+            screen_size: [
+                extent[0] as f32 / pixels_per_point,
+                extent[1] as f32 / pixels_per_point,
+            ],
+        }))
+    }
+
+    // 프레임마다 새로 만드는 작은 버퍼 (이전 프레임의 버퍼는 그 command buffer가 끝날 때까지 남음)
+    fn create_buffer<T: BufferContents>(
+        &self,
+        usage: BufferUsage,
+        data: Vec<T>,
+    ) -> Result<Subbuffer<[T]>, RendererError> {
+        Buffer::from_iter(
+            self.memory_allocator.clone(),
+            BufferCreateInfo {
+                usage,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            data,
+        )
+        .map_err(|e| RendererError::Buffer(e.into()))
+    }
+}
+
+// 슬라이더와 텍스트 상자 (글자 크기는 슬라이더를 놓을 때 values에 반영)
+fn show_panel(context: &Context, values: &mut PanelValues, font_size_edit: &mut Option<f32>) {
+    egui::Window::new("컨트롤 패널")
+        .default_pos([16.0, 16.0])
+        .resizable(false)
+        .show(context, |ui| {
+            ui.add(Slider::new(&mut values.opacity, 0.0..=1.0).text("투명도"));
+            ui.add(Slider::new(&mut values.outline_width, 0.5..=8.0).text("외곽선 두께"));
+            ui.add(Slider::new(&mut values.shadow_offset[0], -20.0..=20.0).text("그림자 X"));
+            ui.add(Slider::new(&mut values.shadow_offset[1], -20.0..=20.0).text("그림자 Y"));
+            ui.add(Slider::new(&mut values.glow_radius, 1.0..=MAX_GLOW_RADIUS).text("발광 반지름"));
+
+            let mut font_size = font_size_edit.unwrap_or(values.font_size);
+            let response = ui.add(Slider::new(&mut font_size, 8.0..=200.0).text("글자 크기"));
+            if response.changed() {
+                *font_size_edit = Some(font_size);
+            }
+            if !response.dragged() {
+                if let Some(font_size) = font_size_edit.take() {
+                    values.font_size = font_size;
+                }
+            }
+
+            ui.label("텍스트");
+            ui.text_edit_multiline(&mut values.text);
+        });
+}
+
+// egui 이미지를 RGBA8 바이트로 (폰트 이미지는 커버리지를 premultiplied 흰색으로)
+fn image_pixels(image: &ImageData) -> Vec<u8> {
+    match image {
+        ImageData::Color(image) => image
+            .pixels
+            .iter()
+            .flat_map(|color| color.to_array())
+            .collect(),
+        ImageData::Font(image) => image
+            .srgba_pixels(None)
+            .flat_map(|color| color.to_array())
+            .collect(),
+    }
+}
+
+fn filter(filter: TextureFilter) -> Filter {
+    match filter {
+        TextureFilter::Nearest => Filter::Nearest,
+        TextureFilter::Linear => Filter::Linear,
+    }
+}
+
+// egui 색은 sRGB premultiplied이므로 대상이 sRGB나 부동소수점 포맷이면 셰이더에서 선형으로 바꿈
+fn create_pipeline(
+    device: Arc<Device>,
+    target: RenderTarget,
+) -> Result<Arc<GraphicsPipeline>, RendererError> {
+    let numeric_format = target.color_attachment()?.0.numeric_format_color();
+    let linear_output = matches!(
+        numeric_format,
+        Some(NumericFormat::SRGB | NumericFormat::SFLOAT)
+    );
+
+    let vs = vs::load(device.clone())
+        .map_err(|e| RendererError::Shader {
+            name: "panel vertex",
+            source: e.into(),
+        })?
+        .entry_point("main")
+        .ok_or_else(|| RendererError::Shader {
+            name: "panel vertex",
+            source: "main 진입점 없음".into(),
+        })?;
+    let fs = fs::load(device.clone())
+        .map_err(|e| RendererError::Shader {
+            name: "panel fragment",
+            source: e.into(),
+        })?
+        .specialize(
+            [(0, SpecializationConstant::Bool(linear_output))]
+                .into_iter()
+                .collect(),
+        )
+        .map_err(|e| RendererError::Shader {
+            name: "panel fragment",
+            source: e.into(),
+        })?
+        .entry_point("main")
+        .ok_or_else(|| RendererError::Shader {
+            name: "panel fragment",
+            source: "main 진입점 없음".into(),
+        })?;
+
+    let vertex_input_state = PanelVertex::per_vertex()
+        .definition(&vs.info().input_interface)
+        .map_err(|e| RendererError::Pipeline(e.into()))?;
+    let stages = [
+        PipelineShaderStageCreateInfo::new(vs),
+        PipelineShaderStageCreateInfo::new(fs),
+    ];
+    let layout = PipelineLayout::new(
+        device.clone(),
+        PipelineDescriptorSetLayoutCreateInfo::from_stages(&stages)
+            .into_pipeline_layout_create_info(device.clone())
+            .map_err(|e| RendererError::Pipeline(e.into()))?,
+    )
+    .map_err(|e| RendererError::Pipeline(e.into()))?;
+
+    let mut color_blend_state =
+        ColorBlendState::with_attachment_states(1, ColorBlendAttachmentState::default());
+    color_blend_state.attachments[0].blend = Some(AttachmentBlend {
+        src_color_blend_factor: BlendFactor::One,
+        dst_color_blend_factor: BlendFactor::OneMinusSrcAlpha,
+        color_blend_op: BlendOp::Add,
+        src_alpha_blend_factor: BlendFactor::One,
+        dst_alpha_blend_factor: BlendFactor::OneMinusSrcAlpha,
+        alpha_blend_op: BlendOp::Add,
+    });
+    GraphicsPipeline::new(
+        device,
+        None,
+        GraphicsPipelineCreateInfo {
+            stages: stages.into_iter().collect(),
+            vertex_input_state: Some(vertex_input_state),
+            input_assembly_state: Some(InputAssemblyState::default()),
+            viewport_state: Some(ViewportState::default()),
+            rasterization_state: Some(RasterizationState::default()),
+            multisample_state: Some(MultisampleState {
+                rasterization_samples: target.samples(),
+                ..MultisampleState::default()
+            }),
+            color_blend_state: Some(color_blend_state),
+            // 메시마다 잘라낼 사각형이 달라 가위 사각형도 동적 상태
+            dynamic_state: [DynamicState::Viewport, DynamicState::Scissor]
+                .into_iter()
+                .collect(),
+            subpass: Some(target.pipeline_subpass()),
+            ..GraphicsPipelineCreateInfo::layout(layout)
+        },
+    )
+    .map_err(|e| RendererError::Pipeline(e.into()))
+}
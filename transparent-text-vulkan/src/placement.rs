@@ -0,0 +1,199 @@
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+use winit::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    monitor::MonitorHandle,
+    window::Window,
+};
+use tracing::{info, warn};
+
+/// `--anchor`를 줬는데 `--margin`이 없을 때 모니터 가장자리와의 간격 (논리 픽셀)
+pub const DEFAULT_MARGIN: f64 = 40.0;
+
+/// 모니터 목록을 다시 확인하는 간격 (winit에 모니터 연결 이벤트가 없어 주기적으로 비교)
+const MONITOR_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// 모니터 구성 비교용 (이름, 위치, 해상도)
+type MonitorInfo = (Option<String>, PhysicalPosition<i32>, PhysicalSize<u32>);
+
+/// 창을 붙일 모니터의 위치 (`bottom-center`처럼 세로-가로 순서)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Anchor {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    #[default]
+    Center,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl Anchor {
+    pub const ALL: [Anchor; 9] = [
+        Anchor::TopLeft,
+        Anchor::TopCenter,
+        Anchor::TopRight,
+        Anchor::CenterLeft,
+        Anchor::Center,
+        Anchor::CenterRight,
+        Anchor::BottomLeft,
+        Anchor::BottomCenter,
+        Anchor::BottomRight,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Anchor::TopLeft => "top-left",
+            Anchor::TopCenter => "top-center",
+            Anchor::TopRight => "top-right",
+            Anchor::CenterLeft => "center-left",
+            Anchor::Center => "center",
+            Anchor::CenterRight => "center-right",
+            Anchor::BottomLeft => "bottom-left",
+            Anchor::BottomCenter => "bottom-center",
+            Anchor::BottomRight => "bottom-right",
+        }
+    }
+
+    // 가로, 세로 방향 비율 (0: 왼쪽/위, 0.5: 가운데, 1: 오른쪽/아래)
+    fn factors(self) -> (f64, f64) {
+        let index = Self::ALL
+            .iter()
+            .position(|&anchor| anchor == self)
+            .unwrap_or(4);
+        ((index % 3) as f64 / 2.0, (index / 3) as f64 / 2.0)
+    }
+}
+
+impl FromStr for Anchor {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|anchor| anchor.name() == value)
+            .ok_or_else(|| {
+                format!("알 수 없는 위치: {value} (예: top-left, center, bottom-center)")
+            })
+    }
+}
+
+impl TryFrom<String> for Anchor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// 창을 놓을 모니터와 모니터 안의 위치
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+    /// `available_monitors()` 순서의 번호 (없거나 범위를 벗어나면 주 모니터)
+    pub monitor: Option<usize>,
+    pub anchor: Anchor,
+    /// 모니터 가장자리와의 간격 (논리 픽셀, 가운데 정렬 방향에는 쓰지 않음)
+    pub margin: f64,
+}
+
+impl Placement {
+    /// 창의 현재 크기로 위치를 계산해 옮김 (모니터를 찾지 못하면 그대로 둠)
+    pub fn apply(&self, window: &Window) {
+        let Some(monitor) = self.monitor(window) else {
+            warn!("창을 놓을 모니터를 찾을 수 없습니다");
+            return;
+        };
+        let position = self.position(&monitor, window.outer_size());
+        window.set_outer_position(position);
+    }
+
+    fn monitor(&self, window: &Window) -> Option<MonitorHandle> {
+        let selected = self.monitor.and_then(|index| {
+            let monitor = window.available_monitors().nth(index);
+            if monitor.is_none() {
+                warn!("모니터 {index}이(가) 없어 주 모니터를 사용합니다");
+            }
+            monitor
+        });
+        selected
+            .or_else(|| window.primary_monitor())
+            .or_else(|| window.current_monitor())
+    }
+
+    fn position(&self, monitor: &MonitorHandle, size: PhysicalSize<u32>) -> PhysicalPosition<i32> {
+        let origin = monitor.position();
+        let area = monitor.size();
+        let margin = self.margin * monitor.scale_factor();
+        let (fx, fy) = self.anchor.factors();
+        // 양 끝이면 안쪽으로 간격만큼 (가운데 정렬이면 간격 없음)
+        let offset = |factor: f64, area: u32, size: u32| {
+            let free = area as f64 - size as f64;
+            (free * factor + margin * (1.0 - 2.0 * factor)).round() as i32
+        };
+        PhysicalPosition::new(
+            origin.x + offset(fx, area.width, size.width),
+            origin.y + offset(fy, area.height, size.height),
+        )
+    }
+}
+
+/// 모니터 연결/해제나 해상도 변경 감지
+pub struct MonitorWatcher {
+    monitors: Vec<MonitorInfo>,
+    checked: Instant,
+}
+
+impl MonitorWatcher {
+    pub fn new(window: &Window) -> Self {
+        Self {
+            monitors: Self::snapshot(window),
+            checked: Instant::now(),
+        }
+    }
+
+    /// 마지막 확인 이후 모니터 구성이 바뀌었는지 (확인은 일정 간격으로만 함)
+    pub fn changed(&mut self, window: &Window) -> bool {
+        if self.checked.elapsed() < MONITOR_POLL_INTERVAL {
+            return false;
+        }
+        self.checked = Instant::now();
+        let monitors = Self::snapshot(window);
+        if monitors == self.monitors {
+            return false;
+        }
+        self.monitors = monitors;
+        true
+    }
+
+    fn snapshot(window: &Window) -> Vec<MonitorInfo> {
+        window
+            .available_monitors()
+            .map(|monitor| (monitor.name(), monitor.position(), monitor.size()))
+            .collect()
+    }
+}
+
+/// 시작할 때 모니터 번호를 고를 수 있도록 목록 출력
+pub fn print_monitors(window: &Window) {
+    for (index, monitor) in window.available_monitors().enumerate() {
+        let size = monitor.size();
+        let position = monitor.position();
+        info!(
+            "모니터 {index}: {} ({}x{}, 위치 {}, {}, 배율 {})",
+            monitor.name().unwrap_or_default(),
+            size.width,
+            size.height,
+            position.x,
+            position.y,
+            monitor.scale_factor()
+        );
+    }
+}
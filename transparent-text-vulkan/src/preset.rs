@@ -0,0 +1,176 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::{user_config_dir, Color, Effects};
+
+/// 프리셋 파일 오류
+#[derive(Debug, thiserror::Error)]
+pub enum PresetError {
+    #[error("사용자 설정 디렉터리를 찾을 수 없습니다")]
+    NoConfigDir,
+
+    #[error("프리셋 파일을 읽을 수 없습니다 ({path}): {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("프리셋 파일을 저장할 수 없습니다 ({path}): {source}")]
+    Write {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("프리셋 파일 형식 오류: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("프리셋을 TOML로 바꿀 수 없습니다: {0}")]
+    Serialize(#[from] toml::ser::Error),
+
+    #[error("프리셋이 없습니다: {0}")]
+    NotFound(String),
+}
+
+/// 이름을 붙여 저장한 스타일 조합
+///
+/// 생략한 항목은 불러올 때 현재 값을 유지합니다.
+///
+/// ```toml
+/// [[preset]]
+/// name = "자막"
+/// slot = 1
+/// font = "Noto Sans KR"
+/// font_size = 40.0
+/// color = "#ffffff"
+/// effects = "outline+shadow"
+/// opacity = 0.9
+/// position = [0.0, 120.0]
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Preset {
+    pub name: String,
+    /// 불러오는 F 키 번호 (1-8, 없으면 이름으로만 불러옴)
+    pub slot: Option<usize>,
+    /// 파일 경로 또는 시스템 폰트 패밀리 이름
+    pub font: Option<String>,
+    pub font_size: Option<f32>,
+    /// 텍스트 전체의 기본 색
+    pub color: Option<Color>,
+    pub effects: Option<Effects>,
+    pub opacity: Option<f32>,
+    /// 텍스트 위치 (창 중앙 기준 논리 픽셀)
+    pub position: Option<[f32; 2]>,
+}
+
+// presets.toml 전체
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PresetFile {
+    preset: Vec<Preset>,
+}
+
+/// 사용자 설정 디렉터리의 프리셋 파일
+///
+/// 파일을 직접 고쳐도 바로 반영되도록 불러오거나 저장할 때마다 다시 읽습니다.
+/// 형식이 틀린 파일은 덮어쓰지 않습니다.
+pub struct PresetStore {
+    path: PathBuf,
+}
+
+impl PresetStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// `user_config_dir()`의 presets.toml
+    pub fn in_config_dir() -> Result<Self, PresetError> {
+        let dir = user_config_dir().ok_or(PresetError::NoConfigDir)?;
+        Ok(Self::new(dir.join("presets.toml")))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// F 키 번호(1-8)의 프리셋
+    pub fn slot(&self, slot: usize) -> Result<Preset, PresetError> {
+        self.load()?
+            .into_iter()
+            .find(|preset| preset.slot == Some(slot))
+            .ok_or_else(|| PresetError::NotFound(format!("F{slot}")))
+    }
+
+    pub fn named(&self, name: &str) -> Result<Preset, PresetError> {
+        self.load()?
+            .into_iter()
+            .find(|preset| preset.name == name)
+            .ok_or_else(|| PresetError::NotFound(name.to_string()))
+    }
+
+    /// 같은 F 키 번호의 프리셋을 바꿈 (없으면 추가, 이름을 비워 두면 기존 이름이나 `F1` 같은 이름을 씀)
+    pub fn save_slot(&self, slot: usize, mut preset: Preset) -> Result<Preset, PresetError> {
+        let mut presets = self.load()?;
+        preset.slot = Some(slot);
+        match presets.iter_mut().find(|saved| saved.slot == Some(slot)) {
+            Some(saved) => {
+                if preset.name.is_empty() {
+                    preset.name = saved.name.clone();
+                }
+                *saved = preset.clone();
+            }
+            None => {
+                if preset.name.is_empty() {
+                    preset.name = format!("F{slot}");
+                }
+                presets.push(preset.clone());
+            }
+        }
+        self.store(presets)?;
+        Ok(preset)
+    }
+
+    /// 같은 이름의 프리셋을 바꿈 (없으면 추가, F 키 번호는 기존 프리셋의 것을 유지)
+    pub fn save_named(&self, mut preset: Preset) -> Result<Preset, PresetError> {
+        let mut presets = self.load()?;
+        match presets.iter_mut().find(|saved| saved.name == preset.name) {
+            Some(saved) => {
+                preset.slot = saved.slot;
+                *saved = preset.clone();
+            }
+            None => presets.push(preset.clone()),
+        }
+        self.store(presets)?;
+        Ok(preset)
+    }
+
+    // 파일이 아직 없으면 빈 목록
+    fn load(&self) -> Result<Vec<Preset>, PresetError> {
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(source) => {
+                return Err(PresetError::Read {
+                    path: self.path.display().to_string(),
+                    source,
+                })
+            }
+        };
+        Ok(toml::from_str::<PresetFile>(&text)?.preset)
+    }
+
+    fn store(&self, preset: Vec<Preset>) -> Result<(), PresetError> {
+        let text = toml::to_string_pretty(&PresetFile { preset })?;
+        let write = |source| PresetError::Write {
+            path: self.path.display().to_string(),
+            source,
+        };
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(write)?;
+        }
+        std::fs::write(&self.path, text).map_err(write)
+    }
+}
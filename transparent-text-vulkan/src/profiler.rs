@@ -0,0 +1,226 @@
+use std::{sync::Arc, time::Duration};
+use vulkano::{
+    command_buffer::AutoCommandBufferBuilder,
+    device::Queue,
+    query::{QueryPool, QueryPoolCreateInfo, QueryResultFlags, QueryType},
+    sync::PipelineStage,
+};
+use tracing::warn;
+
+use crate::error::RendererError;
+
+// 프레임마다 잴 수 있는 최대 구간 수 (구간마다 시작/끝 타임스탬프 두 개)
+const MAX_PASSES: usize = 8;
+const QUERIES_PER_FRAME: u32 = MAX_PASSES as u32 * 2;
+
+/// 한 구간의 GPU 시간
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PassTiming {
+    pub name: &'static str,
+    pub gpu_time: Duration,
+}
+
+/// 한 프레임의 구간별 GPU 시간 (`begin_pass`를 호출한 순서)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameStats {
+    pub passes: Vec<PassTiming>,
+}
+
+impl FrameStats {
+    /// 모든 구간의 GPU 시간 합
+    pub fn gpu_time(&self) -> Duration {
+        self.passes.iter().map(|pass| pass.gpu_time).sum()
+    }
+}
+
+/// 타임스탬프 쿼리로 프레임의 구간별 GPU 시간을 잼
+///
+/// 프레임 슬롯마다 쿼리를 따로 두고, 같은 슬롯을 다시 쓸 때(`FramesInFlight::wait_current` 다음)
+/// N 프레임 전의 결과를 기다리지 않고 읽습니다. 그래서 `stats`는 몇 프레임 늦은 값입니다.
+///
+/// ```ignore
+/// frames.wait_current();
+/// profiler.begin_frame(&mut builder, frames.frame_index())?;
+/// profiler.begin_pass(&mut builder, "layer")?;
+/// layer.draw(&mut builder, &mut text_renderer, &params, extent)?;
+/// profiler.end_pass(&mut builder)?;
+/// // 제출한 뒤
+/// profiler.finish_frame(submitted);
+/// tracing::debug!("{:?}", profiler.stats());
+/// ```
+pub struct GpuProfiler {
+    query_pool: Arc<QueryPool>,
+    // 타임스탬프 한 눈금의 나노초
+    timestamp_period: f32,
+    // 큐 패밀리가 지원하는 타임스탬프 비트 (넘침 처리용)
+    valid_mask: u64,
+    // 슬롯마다 제출한 프레임의 구간 이름 (결과를 읽으면 비움)
+    submitted: Vec<Vec<&'static str>>,
+    // 지금 기록 중인 프레임의 슬롯과 구간 이름
+    current: usize,
+    recording: Vec<&'static str>,
+    pass_open: bool,
+    stats: Option<FrameStats>,
+}
+
+impl GpuProfiler {
+    /// 큐 패밀리가 타임스탬프를 지원하지 않으면 `None`
+    pub fn new(queue: &Arc<Queue>, frames_in_flight: usize) -> Result<Option<Self>, RendererError> {
+        let device = queue.device();
+        let family = &device.physical_device().queue_family_properties()
+            [queue.queue_family_index() as usize];
+        let Some(valid_bits) = family.timestamp_valid_bits else {
+            return Ok(None);
+        };
+
+        let frames_in_flight = frames_in_flight.max(1);
+        let query_pool = QueryPool::new(
+            device.clone(),
+            QueryPoolCreateInfo {
+                query_count: QUERIES_PER_FRAME * frames_in_flight as u32,
+                ..QueryPoolCreateInfo::query_type(QueryType::Timestamp)
+            },
+        )
+        .map_err(RendererError::command)?;
+
+        Ok(Some(Self {
+            query_pool,
+            timestamp_period: device.physical_device().properties().timestamp_period,
+            valid_mask: u64::MAX >> (64 - valid_bits.clamp(1, 64)),
+            submitted: vec![Vec::new(); frames_in_flight],
+            current: 0,
+            recording: Vec::new(),
+            pass_open: false,
+            stats: None,
+        }))
+    }
+
+    /// 프레임 기록 시작 (render pass 밖에서)
+    ///
+    /// `frame_index` 슬롯의 지난 결과가 준비됐으면 읽고 쿼리를 초기화합니다.
+    pub fn begin_frame<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        frame_index: usize,
+    ) -> Result<(), RendererError> {
+        self.current = frame_index % self.submitted.len();
+        self.read_results();
+        self.recording.clear();
+        self.pass_open = false;
+
+        let first = self.first_query();
+        // SAFETY: 이 슬롯의 쿼리를 쓴 프레임은 wait_current로 끝났고, 이번 프레임에서만 다시 씀
+        unsafe {
+            builder
+                .reset_query_pool(self.query_pool.clone(), first..first + QUERIES_PER_FRAME)
+                .map_err(RendererError::command)?;
+        }
+        Ok(())
+    }
+
+    /// 구간 시작 (구간이 `MAX_PASSES`개를 넘으면 무시)
+    pub fn begin_pass<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        name: &'static str,
+    ) -> Result<(), RendererError> {
+        if self.pass_open || self.recording.len() >= MAX_PASSES {
+            return Ok(());
+        }
+        let query = self.first_query() + self.recording.len() as u32 * 2;
+        self.write_timestamp(builder, query)?;
+        self.recording.push(name);
+        self.pass_open = true;
+        Ok(())
+    }
+
+    /// 마지막으로 시작한 구간의 끝
+    pub fn end_pass<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+    ) -> Result<(), RendererError> {
+        if !self.pass_open {
+            return Ok(());
+        }
+        let query = self.first_query() + self.recording.len() as u32 * 2 - 1;
+        self.write_timestamp(builder, query)?;
+        self.pass_open = false;
+        Ok(())
+    }
+
+    /// 프레임을 제출했는지 기록 (실패했으면 초기화도 실행되지 않았으므로 결과를 읽지 않음)
+    pub fn finish_frame(&mut self, submitted: bool) {
+        if self.pass_open {
+            // 끝을 기록하지 않은 구간은 버림
+            self.recording.pop();
+            self.pass_open = false;
+        }
+        self.submitted[self.current] = if submitted {
+            std::mem::take(&mut self.recording)
+        } else {
+            Vec::new()
+        };
+    }
+
+    /// 마지막으로 결과를 읽은 프레임의 구간별 GPU 시간
+    pub fn stats(&self) -> Option<&FrameStats> {
+        self.stats.as_ref()
+    }
+
+    fn first_query(&self) -> u32 {
+        self.current as u32 * QUERIES_PER_FRAME
+    }
+
+    fn write_timestamp<L>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        query: u32,
+    ) -> Result<(), RendererError> {
+        // SAFETY: begin_frame에서 초기화한 이번 프레임의 쿼리에 한 번만 씀
+        unsafe {
+            builder
+                .write_timestamp(self.query_pool.clone(), query, PipelineStage::BottomOfPipe)
+                .map_err(RendererError::command)?;
+        }
+        Ok(())
+    }
+
+    // 기다리지 않고 읽음 (아직 준비되지 않았으면 이전 통계 유지)
+    fn read_results(&mut self) {
+        let names = std::mem::take(&mut self.submitted[self.current]);
+        if names.is_empty() {
+            return;
+        }
+
+        let first = self.first_query();
+        let mut timestamps = vec![0u64; names.len() * 2];
+        let ready = self.query_pool.get_results(
+            first..first + timestamps.len() as u32,
+            &mut timestamps,
+            QueryResultFlags::empty(),
+        );
+        match ready {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                warn!("타임스탬프 쿼리 읽기 실패: {e}");
+                return;
+            }
+        }
+
+        let passes = names
+            .iter()
+            .zip(timestamps.chunks_exact(2))
+            .map(|(&name, pair)| {
+                let ticks = pair[1].wrapping_sub(pair[0]) & self.valid_mask;
+                PassTiming {
+                    name,
+                    gpu_time: Duration::from_nanos(
+                        (ticks as f64 * self.timestamp_period as f64) as u64,
+                    ),
+                }
+            })
+            .collect();
+        self.stats = Some(FrameStats { passes });
+    }
+}
@@ -0,0 +1,248 @@
+use std::sync::Arc;
+use vulkano::{
+    command_buffer::{
+        AutoCommandBufferBuilder, RenderPassBeginInfo, RenderingAttachmentInfo,
+        RenderingAttachmentResolveInfo, RenderingInfo, SubpassBeginInfo, SubpassContents,
+    },
+    device::{physical::PhysicalDevice, Device, DeviceExtensions, Features},
+    format::Format,
+    image::{view::ImageView, Image, SampleCount},
+    memory::allocator::StandardMemoryAllocator,
+    pipeline::graphics::viewport::Viewport,
+    render_pass::{AttachmentLoadOp, AttachmentStoreOp, Framebuffer, RenderPass},
+    Version,
+};
+use tracing::warn;
+use transparent_text_vulkan::{RenderTarget, RendererError};
+use vulkan_context::{clear_values, create_framebuffers, create_render_pass, multisampled_image};
+
+/// 스왑체인(또는 출력) 이미지에 레이어를 합성하는 방식
+///
+/// render pass를 쓰면 이미지마다 framebuffer를 만들고, dynamic rendering(`--dynamic-rendering`)이면
+/// render pass와 framebuffer 없이 이미지 뷰에 바로 그리므로 크기가 바뀔 때 다시 만드는 것이
+/// 이미지 뷰(와 MSAA 멀티샘플 이미지)뿐입니다.
+pub enum RenderPath {
+    RenderPass {
+        render_pass: Arc<RenderPass>,
+        framebuffers: Vec<Arc<Framebuffer>>,
+    },
+    Dynamic {
+        format: Format,
+        samples: SampleCount,
+        views: Vec<Arc<ImageView>>,
+        // MSAA 멀티샘플 이미지 (모든 이미지가 공유, resolve 후에는 내용이 필요 없음)
+        multisampled: Option<Arc<ImageView>>,
+    },
+}
+
+impl RenderPath {
+    /// `format` 이미지에 `samples`로 그리는 경로 (이미지는 `resize`로 붙임)
+    pub fn new(
+        device: &Arc<Device>,
+        format: Format,
+        samples: SampleCount,
+        dynamic_rendering: bool,
+    ) -> Result<Self, RendererError> {
+        if dynamic_rendering {
+            return Ok(RenderPath::Dynamic {
+                format,
+                samples,
+                views: Vec::new(),
+                multisampled: None,
+            });
+        }
+        Ok(RenderPath::RenderPass {
+            render_pass: create_render_pass(device, format, samples)?,
+            framebuffers: Vec::new(),
+        })
+    }
+
+    /// 같은 render pass(또는 포맷과 샘플 수)로 다른 스왑체인에 그리는 경로 (미러 창)
+    pub fn share(&self) -> Self {
+        match self {
+            RenderPath::RenderPass { render_pass, .. } => RenderPath::RenderPass {
+                render_pass: render_pass.clone(),
+                framebuffers: Vec::new(),
+            },
+            RenderPath::Dynamic {
+                format, samples, ..
+            } => RenderPath::Dynamic {
+                format: *format,
+                samples: *samples,
+                views: Vec::new(),
+                multisampled: None,
+            },
+        }
+    }
+
+    /// 레이어와 텍스트 렌더러의 파이프라인을 맞출 대상
+    pub fn target(&self) -> RenderTarget {
+        match self {
+            RenderPath::RenderPass { render_pass, .. } => render_pass.clone().into(),
+            RenderPath::Dynamic {
+                format, samples, ..
+            } => RenderTarget::Dynamic {
+                format: *format,
+                samples: *samples,
+            },
+        }
+    }
+
+    /// 그리는 이미지의 포맷
+    pub fn format(&self) -> Format {
+        match self {
+            RenderPath::RenderPass { render_pass, .. } => render_pass
+                .attachments()
+                .last()
+                .map_or(Format::UNDEFINED, |a| a.format),
+            RenderPath::Dynamic { format, .. } => *format,
+        }
+    }
+
+    /// 이미지(스왑체인 이미지 등)가 바뀌었을 때 다시 만들고 viewport를 이미지 크기에 맞춤
+    pub fn resize(
+        &mut self,
+        images: &[Arc<Image>],
+        memory_allocator: &Arc<StandardMemoryAllocator>,
+        viewport: &mut Viewport,
+    ) -> Result<(), RendererError> {
+        let extent = images[0].extent();
+        viewport.extent = [extent[0] as f32, extent[1] as f32];
+
+        match self {
+            RenderPath::RenderPass {
+                render_pass,
+                framebuffers,
+            } => {
+                *framebuffers = create_framebuffers(render_pass, images, memory_allocator)?;
+            }
+            RenderPath::Dynamic {
+                format,
+                samples,
+                views,
+                multisampled,
+            } => {
+                *multisampled = multisampled_image(memory_allocator, *format, *samples, extent)?;
+                *views = images
+                    .iter()
+                    .map(|image| {
+                        ImageView::new_default(image.clone()).map_err(RendererError::Swapchain)
+                    })
+                    .collect::<Result<_, _>>()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 붙인 이미지 수
+    pub fn len(&self) -> usize {
+        match self {
+            RenderPath::RenderPass { framebuffers, .. } => framebuffers.len(),
+            RenderPath::Dynamic { views, .. } => views.len(),
+        }
+    }
+
+    /// `index`번 이미지 (MSAA면 resolve 대상)
+    pub fn image(&self, index: usize) -> Option<Arc<Image>> {
+        let view = match self {
+            RenderPath::RenderPass { framebuffers, .. } => {
+                framebuffers.get(index)?.attachments().last()?.clone()
+            }
+            RenderPath::Dynamic { views, .. } => views.get(index)?.clone(),
+        };
+        Some(view.image().clone())
+    }
+
+    /// `index`번 이미지를 `color`로 지우고 그리기 시작 (MSAA면 멀티샘플 이미지에 그려 resolve)
+    pub fn begin<L>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        index: usize,
+        color: [f32; 4],
+    ) -> Result<(), RendererError> {
+        match self {
+            RenderPath::RenderPass {
+                render_pass,
+                framebuffers,
+            } => builder
+                .begin_render_pass(
+                    RenderPassBeginInfo {
+                        clear_values: clear_values(render_pass, color),
+                        ..RenderPassBeginInfo::framebuffer(framebuffers[index].clone())
+                    },
+                    SubpassBeginInfo {
+                        contents: SubpassContents::Inline,
+                        ..Default::default()
+                    },
+                )
+                .map_err(|e| RendererError::Command(e.into()))?,
+            RenderPath::Dynamic {
+                views,
+                multisampled,
+                ..
+            } => {
+                let (view, store_op, resolve_info) = match multisampled {
+                    Some(multisampled) => (
+                        multisampled.clone(),
+                        AttachmentStoreOp::DontCare,
+                        Some(RenderingAttachmentResolveInfo::image_view(
+                            views[index].clone(),
+                        )),
+                    ),
+                    None => (views[index].clone(), AttachmentStoreOp::Store, None),
+                };
+                builder
+                    .begin_rendering(RenderingInfo {
+                        color_attachments: vec![Some(RenderingAttachmentInfo {
+                            load_op: AttachmentLoadOp::Clear,
+                            store_op,
+                            clear_value: Some(color.into()),
+                            resolve_info,
+                            ..RenderingAttachmentInfo::image_view(view)
+                        })],
+                        contents: SubpassContents::Inline,
+                        ..Default::default()
+                    })
+                    .map_err(|e| RendererError::Command(e.into()))?
+            }
+        };
+        Ok(())
+    }
+
+    /// `begin`으로 시작한 그리기를 끝냄
+    pub fn end<L>(&self, builder: &mut AutoCommandBufferBuilder<L>) -> Result<(), RendererError> {
+        match self {
+            RenderPath::RenderPass { .. } => builder.end_render_pass(Default::default()),
+            RenderPath::Dynamic { .. } => builder.end_rendering(),
+        }
+        .map_err(|e| RendererError::Command(e.into()))?;
+        Ok(())
+    }
+}
+
+/// `requested`이고 장치가 지원하면 dynamic rendering에 필요한 장치 확장과 기능을 켜고 true
+///
+/// Vulkan 1.3 장치는 기능만, 그 전 장치는 `VK_KHR_dynamic_rendering` 확장도 켭니다.
+/// 지원하지 않으면 경고하고 render pass로 그립니다.
+pub fn enable_dynamic_rendering(
+    device: &PhysicalDevice,
+    requested: bool,
+    extensions: &mut DeviceExtensions,
+    features: &mut Features,
+) -> bool {
+    if !requested {
+        return false;
+    }
+    let core = device.api_version() >= Version::V1_3;
+    if !device.supported_features().dynamic_rendering
+        || !(core || device.supported_extensions().khr_dynamic_rendering)
+    {
+        warn!("이 장치는 dynamic rendering을 지원하지 않아 render pass로 그립니다");
+        return false;
+    }
+    if !core {
+        extensions.khr_dynamic_rendering = true;
+    }
+    features.dynamic_rendering = true;
+    true
+}
@@ -0,0 +1,1838 @@
+use std::{ops::Range, sync::Arc};
+use vulkano::{
+    buffer::{Buffer, BufferContents, BufferCreateInfo, BufferUsage, Subbuffer},
+    command_buffer::{allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder},
+    descriptor_set::{
+        allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
+    },
+    device::{Device, Queue},
+    format::NumericFormat,
+    image::sampler::{Filter, Sampler, SamplerAddressMode, SamplerCreateInfo, SamplerMipmapMode},
+    memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+    pipeline::{
+        graphics::{
+            color_blend::{
+                AttachmentBlend, BlendFactor, BlendOp, ColorBlendAttachmentState, ColorBlendState,
+            },
+            input_assembly::InputAssemblyState,
+            multisample::MultisampleState,
+            rasterization::RasterizationState,
+            subpass::PipelineSubpassType,
+            vertex_input::{Vertex, VertexDefinition, VertexInputState},
+            viewport::ViewportState,
+            GraphicsPipelineCreateInfo,
+        },
+        layout::PipelineDescriptorSetLayoutCreateInfo,
+        DynamicState, GraphicsPipeline, Pipeline, PipelineBindPoint, PipelineLayout,
+        PipelineShaderStageCreateInfo,
+    },
+    render_pass::Subpass,
+    shader::{EntryPoint, ShaderModule, SpecializationConstant},
+    sync::GpuFuture,
+};
+use fontdue::{layout::GlyphRasterConfig, Font};
+use glam::Mat4;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    animation::{self, Karaoke, Typewriter},
+    atlas::{AtlasEntry, GlyphAtlas, GlyphCacheStats, GLYPH_PADDING},
+    config::{Antialiasing, RendererConfig},
+    custom_shader::CustomShader,
+    effect::{EffectParams, TextEffects, TextHighlight, TextParams},
+    error::RendererError,
+    font::{FontData, FontFace},
+    frame::FrameFence,
+    layout::{
+        self, CaretMap, LayoutOptions, LineMetrics, PositionedGlyph, TextLayout, WritingMode,
+    },
+    outline::OutlineDilate,
+    scene::{TextObject, TextObjectId},
+    shaders,
+    shadow::ShadowBlur,
+    style::StyledText,
+    target::RenderTarget,
+};
+#[cfg(feature = "hot-reload")]
+use std::path::PathBuf;
+#[cfg(feature = "hot-reload")]
+use crate::hot_reload::ShaderWatcher;
+
+// 기본 내장 폰트와 크기, 스타일에 색이 없는 글자의 색
+pub(crate) const DEFAULT_FONT: &[u8] = include_bytes!("../NotoSansKR-Regular.ttf");
+pub(crate) const DEFAULT_FONT_SIZE: f32 = 48.0;
+pub(crate) const DEFAULT_COLOR: [u8; 4] = [255, 255, 255, 255];
+
+// 모든 글리프가 공유하는 단위 사각형의 꼭짓점 (0..1)
+#[derive(BufferContents, Vertex, Clone, Copy)]
+#[repr(C)]
+struct QuadVertex {
+    #[format(R32G32_SFLOAT)]
+    corner: [f32; 2],
+}
+
+const QUAD_VERTICES: [QuadVertex; 4] = [
+    QuadVertex { corner: [0.0, 0.0] },
+    QuadVertex { corner: [1.0, 0.0] },
+    QuadVertex { corner: [0.0, 1.0] },
+    QuadVertex { corner: [1.0, 1.0] },
+];
+const QUAD_INDICES: [u16; 6] = [0, 1, 2, 2, 1, 3];
+
+// 글리프 하나 = 인스턴스 하나
+// (position: 텍스트 중심 기준 왼쪽 위 픽셀, uv_rect: 아틀라스 텍셀 [x, y, 너비, 높이])
+#[derive(BufferContents, Vertex, Clone, Copy)]
+#[repr(C)]
+struct GlyphInstance {
+    #[format(R32G32_SFLOAT)]
+    position: [f32; 2],
+    #[format(R32G32_SFLOAT)]
+    size: [f32; 2],
+    #[format(R32G32B32A32_SFLOAT)]
+    uv_rect: [f32; 4],
+    #[format(R8G8B8A8_UNORM)]
+    color: [u8; 4],
+    // 구간 효과 비트마스크 (-1이면 push constant의 효과 사용)
+    #[format(R32_SINT)]
+    effect: i32,
+    // 1이면 컬러 글리프 (서브픽셀 모드에서 RGB를 커버리지로 해석하지 않음)
+    #[format(R32_UINT)]
+    colored: u32,
+    // 텍스트 블록 중심 기준 좌표를 화면 픽셀로 옮기는 모델 행렬 (열 우선, 4개 location)
+    #[format(R32G32B32A32_SFLOAT)]
+    transform: [[f32; 4]; 4],
+    // 글리프가 속한 텍스트 블록의 크기 (그라데이션을 블록 너비에 맞춤)
+    #[format(R32G32_SFLOAT)]
+    extent: [f32; 2],
+    // 블록 안에서 글리프가 속한 글자 번호 (결합 문자와 이모지 시퀀스는 앞 글자와 같은 번호, 물결과 무지개 위상)
+    #[format(R32_UINT)]
+    character: u32,
+    // 글리프가 든 아틀라스 배열 이미지의 레이어
+    #[format(R32_UINT)]
+    layer: u32,
+}
+
+// 장면의 텍스트 객체와 마지막으로 레이아웃한 결과
+struct SceneObject {
+    id: TextObjectId,
+    object: TextObject,
+    // 객체의 위치, 투명도, 효과를 적용하기 전의 인스턴스와 그 글리프 (아틀라스에서 내보내지 않도록)
+    instances: Vec<GlyphInstance>,
+    keys: Vec<GlyphRasterConfig>,
+    extent: [f32; 2],
+}
+
+/// vulkano 렌더 패스 안에 텍스트를 그리는 렌더러
+///
+/// 글리프는 동적 아틀라스에 한 번만 래스터라이즈되고, 텍스트 전체는 글리프당 인스턴스 하나로
+/// 단위 사각형을 인스턴싱해 드로우 콜 한 번에 그려집니다.
+/// `add_object`로 추가한 텍스트 객체도 같은 인스턴스 버퍼에 모여 함께 그려집니다.
+/// 텍스트가 바뀌면 레이아웃만 다시 하고, 새 글리프와 정점 데이터만 업로드합니다.
+pub struct TextRenderer {
+    queue: Arc<Queue>,
+    // 그림자/외곽선 컴퓨트 패스를 실행하는 비동기 컴퓨트 큐 (없으면 그래픽 큐)
+    compute_queue: Option<Arc<Queue>>,
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    command_buffer_allocator: StandardCommandBufferAllocator,
+    descriptor_set_allocator: StandardDescriptorSetAllocator,
+    // 기본 폰트와 그 뒤의 대체 폰트들 (우선순위 순서)
+    fonts: Vec<FontFace>,
+    font_size: f32,
+    // HiDPI 배율 (글리프는 font_size × scale_factor 물리 픽셀로 래스터라이즈)
+    scale_factor: f32,
+    layout_options: LayoutOptions,
+    antialiasing: Antialiasing,
+    // 생성할 때의 설정 (antialiasing은 실제로 사용 중인 방식)
+    config: RendererConfig,
+    // 스타일에 색이 없는 글자의 색과 `params`가 켜는 효과 (TextRendererBuilder)
+    color: [u8; 4],
+    effects: TextEffects,
+    // 파이프라인을 맞춘 그리기 대상 (셰이더를 바꿔 다시 만들 때 사용)
+    target: RenderTarget,
+    pipeline: Arc<GraphicsPipeline>,
+    background_pipeline: Arc<GraphicsPipeline>,
+    // TextLayer의 블룸 원본에 발광 글리프만 그리는 파이프라인 (텍스트 파이프라인과 같은 레이아웃)
+    glow_pipeline: Option<Arc<GraphicsPipeline>>,
+    sampler: Arc<Sampler>,
+    // 밉맵 단계 선택에 더하는 값 (set_lod_bias)
+    lod_bias: f32,
+    atlas: GlyphAtlas,
+    shadow: ShadowBlur,
+    outline: OutlineDilate,
+    // 아틀라스 이미지에 대한 디스크립터 셋과 그 시점의 아틀라스 세대
+    descriptor_set: Option<Arc<PersistentDescriptorSet>>,
+    atlas_generation: u64,
+    // 마지막으로 레이아웃한 텍스트와 정점 버퍼 링
+    // (frames_in_flight개의 슬롯을 돌아가며 써서 GPU가 읽는 중인 버퍼를 덮어쓰지 않음)
+    current_text: Option<StyledText>,
+    quad_vertices: Subbuffer<[QuadVertex]>,
+    quad_indices: Subbuffer<[u16]>,
+    instance_buffers: Vec<Option<Subbuffer<[GlyphInstance]>>>,
+    instance_slot: usize,
+    // 효과 파라미터 UBO 링과 각 버퍼의 디스크립터 셋 (descriptor set 1)
+    effect_buffers: Vec<Option<(Subbuffer<EffectParams>, Arc<PersistentDescriptorSet>)>>,
+    effect_slot: usize,
+    // 마지막 record에서 쓴 효과 파라미터 디스크립터 셋 (발광 마스크도 같은 값으로 그림)
+    effect_set: Option<Arc<PersistentDescriptorSet>>,
+    instance_count: u32,
+    // 마지막 레이아웃의 인스턴스 (애니메이션이 알파를 바꿔 다시 올릴 때의 원본)와 각 글리프가 속한 글자의 바이트 위치
+    instances: Vec<GlyphInstance>,
+    clusters: Vec<usize>,
+    // 기본 텍스트가 쓰는 아틀라스 글리프 (레이아웃 패스마다 쓴 것으로 표시해 내보내지 않음)
+    glyph_keys: Vec<GlyphRasterConfig>,
+    // 기본 텍스트 뒤에 이어 그리는 텍스트 객체 (추가한 순서)
+    objects: Vec<SceneObject>,
+    next_object_id: u64,
+    // 마지막으로 올린 기본 텍스트의 모델 행렬 (`TextParams::transform`)
+    transform: Mat4,
+    // 기본 텍스트나 텍스트 객체가 바뀌어 인스턴스 버퍼를 다시 올려야 하는지
+    scene_changed: bool,
+    // 장면 밖에서 그리는 결과가 바뀔 때마다 증가 (강조 사각형, 파이프라인, 샘플러)
+    revision: u64,
+    // 마지막으로 그린 파라미터와 그 시점의 revision (is_unchanged 판단용)
+    drawn: Option<(TextParams, u64)>,
+    // 진행 중인 타자기 애니메이션과 시작 시각 (시작 후 첫 record의 TextParams::time)
+    typewriter: Option<(Typewriter, Option<f32>)>,
+    // 노래방 효과와 시작 시각, 마지막으로 칠한 시점 (시작 후 초, 다 칠했으면 더 늘지 않음)
+    karaoke: Option<(Karaoke, Option<f32>)>,
+    sung: f32,
+    text_extent: [f32; 2],
+    lines: Vec<LineMetrics>,
+    // 기본 텍스트의 바이트 위치와 커서 위치 대응 (논리 픽셀)
+    carets: CaretMap,
+    // 기본 텍스트 위에 그리는 커서와 선택 영역 (set_highlights)
+    highlights: Vec<TextHighlight>,
+    // 내장 텍스트 프래그먼트 셰이더 대신 쓰는 사용자 셰이더
+    custom_shader: Option<Arc<ShaderModule>>,
+    // 디스크의 GLSL 셰이더 감시 (watch_shaders로 시작)
+    #[cfg(feature = "hot-reload")]
+    shader_watcher: Option<ShaderWatcher>,
+}
+
+impl TextRenderer {
+    /// 내장 폰트(Noto Sans KR, 48px)로 렌더러를 생성합니다.
+    ///
+    /// `target`(render pass의 서브패스나 dynamic rendering 대상)에 맞춰 파이프라인을 만듭니다.
+    /// `Arc<RenderPass>`를 넘기면 첫 번째 서브패스에 맞춥니다.
+    pub fn new(
+        device: Arc<Device>,
+        queue: Arc<Queue>,
+        target: impl Into<RenderTarget>,
+    ) -> Result<Self, RendererError> {
+        Self::with_font_data(
+            device,
+            queue,
+            target,
+            FontData::new(DEFAULT_FONT.to_vec()),
+            DEFAULT_FONT_SIZE,
+            RendererConfig::default(),
+        )
+    }
+
+    /// 지정한 폰트와 크기로 렌더러를 생성합니다.
+    pub fn with_font(
+        device: Arc<Device>,
+        queue: Arc<Queue>,
+        target: impl Into<RenderTarget>,
+        font: Font,
+        font_size: f32,
+    ) -> Result<Self, RendererError> {
+        Self::with_config(
+            device,
+            queue,
+            target,
+            font,
+            font_size,
+            RendererConfig::default(),
+        )
+    }
+
+    /// 폰트, 크기, 렌더러 설정을 모두 지정해 생성합니다.
+    ///
+    /// fontdue `Font`만으로는 셰이핑을 할 수 없으므로 복잡한 문자(아랍어, 데바나가리 등)가
+    /// 필요하면 `with_font_data`를 사용하세요.
+    pub fn with_config(
+        device: Arc<Device>,
+        queue: Arc<Queue>,
+        target: impl Into<RenderTarget>,
+        font: Font,
+        font_size: f32,
+        config: RendererConfig,
+    ) -> Result<Self, RendererError> {
+        Self::with_face(
+            device,
+            queue,
+            target,
+            FontFace::from_font(font),
+            font_size,
+            config,
+        )
+    }
+
+    /// 원본 폰트 데이터로 생성합니다. rustybuzz 셰이핑과 컬러 글리프가 적용됩니다.
+    pub fn with_font_data(
+        device: Arc<Device>,
+        queue: Arc<Queue>,
+        target: impl Into<RenderTarget>,
+        data: FontData,
+        font_size: f32,
+        config: RendererConfig,
+    ) -> Result<Self, RendererError> {
+        let face = FontFace::from_data(data)?;
+        Self::with_face(device, queue, target, face, font_size, config)
+    }
+
+    fn with_face(
+        device: Arc<Device>,
+        queue: Arc<Queue>,
+        target: impl Into<RenderTarget>,
+        face: FontFace,
+        font_size: f32,
+        config: RendererConfig,
+    ) -> Result<Self, RendererError> {
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let descriptor_set_allocator =
+            StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+
+        // 서브픽셀 모드는 dual-source blending이 켜진 장치에서만 사용
+        let antialiasing = match config.antialiasing {
+            Antialiasing::Subpixel if !device.enabled_features().dual_src_blend => {
+                Antialiasing::Grayscale
+            }
+            antialiasing => antialiasing,
+        };
+
+        let atlas = GlyphAtlas::new(
+            memory_allocator.clone(),
+            antialiasing == Antialiasing::Subpixel,
+            config.mipmaps,
+        )?;
+        let sampler = create_sampler(&device, atlas.mip_levels(), 0.0)?;
+
+        let shadow = ShadowBlur::new(device.clone(), memory_allocator.clone())?;
+        let outline = OutlineDilate::new(device.clone(), memory_allocator.clone())?;
+
+        let target = target.into();
+        let pipeline = create_pipeline(
+            device.clone(),
+            target.clone(),
+            antialiasing,
+            text_shaders(&device, antialiasing)?,
+        )?;
+        let background_pipeline = create_background_pipeline(
+            device.clone(),
+            target.clone(),
+            background_shaders(&device)?,
+        )?;
+
+        // 단위 사각형은 한 번만 만들어 모든 글리프 인스턴스가 공유
+        let quad_vertices = create_static_buffer(
+            &memory_allocator,
+            BufferUsage::VERTEX_BUFFER,
+            QUAD_VERTICES,
+        )?;
+        let quad_indices =
+            create_static_buffer(&memory_allocator, BufferUsage::INDEX_BUFFER, QUAD_INDICES)?;
+
+        Ok(Self {
+            queue,
+            compute_queue: None,
+            memory_allocator,
+            command_buffer_allocator,
+            descriptor_set_allocator,
+            fonts: vec![face],
+            font_size,
+            scale_factor: 1.0,
+            layout_options: LayoutOptions::default(),
+            antialiasing,
+            config: RendererConfig {
+                antialiasing,
+                ..config
+            },
+            color: DEFAULT_COLOR,
+            effects: TextEffects::NONE,
+            target,
+            pipeline,
+            background_pipeline,
+            glow_pipeline: None,
+            sampler,
+            lod_bias: 0.0,
+            atlas,
+            shadow,
+            outline,
+            descriptor_set: None,
+            atlas_generation: 0,
+            current_text: None,
+            quad_vertices,
+            quad_indices,
+            instance_buffers: vec![None; config.frames_in_flight.max(1)],
+            instance_slot: 0,
+            effect_buffers: vec![None; config.frames_in_flight.max(1)],
+            effect_slot: 0,
+            effect_set: None,
+            instance_count: 0,
+            instances: Vec::new(),
+            clusters: Vec::new(),
+            glyph_keys: Vec::new(),
+            objects: Vec::new(),
+            next_object_id: 0,
+            transform: Mat4::IDENTITY,
+            scene_changed: false,
+            revision: 0,
+            drawn: None,
+            typewriter: None,
+            karaoke: None,
+            sung: 0.0,
+            text_extent: [0.0, 0.0],
+            lines: Vec::new(),
+            carets: CaretMap::default(),
+            highlights: Vec::new(),
+            custom_shader: None,
+            #[cfg(feature = "hot-reload")]
+            shader_watcher: None,
+        })
+    }
+
+    /// 표시할 텍스트를 바꿉니다.
+    ///
+    /// 이전과 같은 텍스트면 아무 것도 하지 않습니다. 다르면 레이아웃을 다시 하고
+    /// 아틀라스에 없는 글리프만 래스터라이즈해 업로드합니다.
+    /// render pass 밖에서 미리 호출해 두면 `draw` 중에 업로드가 일어나지 않습니다.
+    pub fn set_text(&mut self, text: &str) -> Result<(), RendererError> {
+        let unchanged = self
+            .current_text
+            .as_ref()
+            .is_some_and(|current| current.text == text && current.spans.is_empty());
+        if !unchanged {
+            self.layout_text(StyledText::new(text))?;
+        }
+
+        Ok(())
+    }
+
+    /// 현재 표시 중인 텍스트
+    pub fn text(&self) -> &str {
+        self.current_text
+            .as_ref()
+            .map_or("", |current| current.text.as_str())
+    }
+
+    /// 구간별 스타일이 있는 텍스트로 바꿉니다. (`set_text`와 같이 바뀐 경우에만 레이아웃)
+    pub fn set_styled_text(&mut self, text: &StyledText) -> Result<(), RendererError> {
+        if self.current_text.as_ref() != Some(text) {
+            self.layout_text(text.clone())?;
+        }
+
+        Ok(())
+    }
+
+    /// 장면에 텍스트 객체를 추가합니다.
+    ///
+    /// `set_text`와 같이 바로 레이아웃하고 새 글리프를 업로드하므로 render pass 밖에서 호출하세요.
+    pub fn add_object(&mut self, object: TextObject) -> Result<TextObjectId, RendererError> {
+        let id = TextObjectId(self.next_object_id);
+        self.next_object_id += 1;
+
+        let object = self.layout_object(id, object)?;
+        self.objects.push(object);
+        self.scene_changed = true;
+
+        Ok(id)
+    }
+
+    /// 텍스트 객체를 바꿉니다. 텍스트가 같으면 레이아웃 없이 변환, 투명도, 효과만 바뀝니다.
+    ///
+    /// 그런 객체가 없으면 `false`를 돌려줍니다.
+    pub fn update_object(
+        &mut self,
+        id: TextObjectId,
+        object: TextObject,
+    ) -> Result<bool, RendererError> {
+        let Some(index) = self.object_index(id) else {
+            return Ok(false);
+        };
+        if self.objects[index].object == object {
+            return Ok(true);
+        }
+
+        if self.objects[index].object.text == object.text {
+            self.objects[index].object = object;
+        } else {
+            self.objects[index] = self.layout_object(id, object)?;
+        }
+        self.scene_changed = true;
+
+        Ok(true)
+    }
+
+    /// 텍스트 객체를 장면에서 빼고 돌려줍니다.
+    pub fn remove_object(&mut self, id: TextObjectId) -> Option<TextObject> {
+        let index = self.object_index(id)?;
+        self.scene_changed = true;
+        Some(self.objects.remove(index).object)
+    }
+
+    /// 모든 텍스트 객체를 뺍니다 (기본 텍스트는 그대로).
+    pub fn clear_objects(&mut self) {
+        if !self.objects.is_empty() {
+            self.objects.clear();
+            self.scene_changed = true;
+        }
+    }
+
+    pub fn object(&self, id: TextObjectId) -> Option<&TextObject> {
+        self.object_index(id)
+            .map(|index| &self.objects[index].object)
+    }
+
+    /// 마지막으로 레이아웃한 텍스트 객체의 크기 (픽셀)
+    pub fn object_extent(&self, id: TextObjectId) -> Option<[f32; 2]> {
+        self.object_index(id)
+            .map(|index| self.objects[index].extent)
+    }
+
+    fn object_index(&self, id: TextObjectId) -> Option<usize> {
+        self.objects.iter().position(|object| object.id == id)
+    }
+
+    /// 마지막으로 레이아웃한 텍스트 블록의 크기 (픽셀)
+    pub fn text_extent(&self) -> [f32; 2] {
+        self.text_extent
+    }
+
+    /// 마지막으로 레이아웃한 텍스트의 줄별 메트릭
+    pub fn lines(&self) -> &[LineMetrics] {
+        &self.lines
+    }
+
+    /// 기본 텍스트에서 바이트 위치 `offset` 앞에 놓인 커서의 사각형
+    ///
+    /// `[x, y, 너비, 높이]`는 `TextHighlight::rect`와 같은 텍스트 블록 중심 기준 픽셀이고, 높이는 줄 간격입니다.
+    /// 세로쓰기에서는 열 너비만큼 넓고 `width`만큼 높은 가로 막대입니다.
+    pub fn caret_rect(&self, offset: usize, width: f32) -> [f32; 4] {
+        let line = self.carets.line_at(offset);
+        let x = self.carets.caret_x(&self.lines, line, offset);
+        self.line_rect(line, x - width / 2.0, width)
+    }
+
+    /// 기본 텍스트의 바이트 범위를 덮는 줄마다의 사각형 (선택 영역 표시용)
+    ///
+    /// 한 줄 안에서 방향이 섞여 있으면 양 끝 사이를 하나의 사각형으로 덮습니다.
+    pub fn selection_rects(&self, range: Range<usize>) -> Vec<[f32; 4]> {
+        let mut rects = Vec::new();
+        for (line, line_range) in self.carets.line_ranges.iter().enumerate() {
+            if range.start > line_range.end || range.end <= line_range.start {
+                continue;
+            }
+            let start = range.start.max(line_range.start);
+            let end = range.end.min(line_range.end);
+            let x0 = self.carets.caret_x(&self.lines, line, start);
+            let x1 = self.carets.caret_x(&self.lines, line, end);
+            let (_, line_height) = self.line_span(line);
+            // 줄바꿈만 선택된 빈 줄도 보이도록 최소 너비를 줌
+            let length = (x1 - x0).abs().max(line_height * 0.25);
+            rects.push(self.line_rect(line, x0.min(x1), length));
+        }
+        rects
+    }
+
+    /// 기본 텍스트에서 `offset`이 있는 줄의 바이트 범위 (Home/End 이동용)
+    pub fn line_range(&self, offset: usize) -> Range<usize> {
+        let line = self.carets.line_at(offset);
+        self.carets
+            .line_ranges
+            .get(line)
+            .cloned()
+            .unwrap_or(offset..offset)
+    }
+
+    /// `offset`의 커서에서 `lines`줄 아래(음수면 위)의 가장 가까운 위치
+    ///
+    /// 첫 줄이나 마지막 줄을 넘어가면 그 줄의 처음이나 끝으로 갑니다.
+    pub fn offset_below(&self, offset: usize, lines: isize) -> usize {
+        let ranges = &self.carets.line_ranges;
+        let line = self.carets.line_at(offset);
+        let target = line as isize + lines;
+        if target < 0 {
+            return ranges.first().map_or(0, |range| range.start);
+        }
+        if target >= ranges.len() as isize {
+            return ranges.last().map_or(offset, |range| range.end);
+        }
+        let x = self.carets.caret_x(&self.lines, line, offset);
+        self.carets.offset_at(&self.lines, target as usize, x)
+    }
+
+    /// 기본 텍스트 위에 겹쳐 그릴 사각형 (다음 그리기부터 적용, 빈 슬라이스면 지움)
+    pub fn set_highlights(&mut self, highlights: &[TextHighlight]) {
+        if self.highlights != highlights {
+            self.highlights.clear();
+            self.highlights.extend_from_slice(highlights);
+            self.revision += 1;
+        }
+    }
+
+    // 줄 안에서 start부터 length만큼의 구간을 텍스트 블록 중심 기준 사각형으로 (세로쓰기면 열 안의 세로 구간)
+    fn line_rect(&self, line: usize, start: f32, length: f32) -> [f32; 4] {
+        let [width, height] = self.text_extent;
+        let (top, thickness) = self.line_span(line);
+        match self.layout_options.writing_mode {
+            WritingMode::Horizontal => [start - width / 2.0, top - height / 2.0, length, thickness],
+            // 첫 열이 블록 오른쪽 끝
+            WritingMode::VerticalRightToLeft => [
+                width / 2.0 - top - thickness,
+                start - height / 2.0,
+                thickness,
+                length,
+            ],
+        }
+    }
+
+    // 줄의 위쪽 y와 높이 (텍스트 블록 위쪽 기준, 세로쓰기면 블록 오른쪽에서 열까지의 거리와 열 너비)
+    fn line_span(&self, line: usize) -> (f32, f32) {
+        let top = self.lines[..line.min(self.lines.len())]
+            .iter()
+            .map(|metrics| metrics.height)
+            .sum();
+        let height = self.lines.get(line).map_or(0.0, |metrics| metrics.height);
+        (top, height)
+    }
+
+    /// 실제로 사용 중인 안티앨리어싱 방식 (장치가 지원하지 않으면 설정과 다를 수 있음)
+    pub fn antialiasing(&self) -> Antialiasing {
+        self.antialiasing
+    }
+
+    /// 생성할 때의 렌더러 설정 (`FramesInFlight::new`에 같은 값을 넘기세요)
+    pub fn config(&self) -> RendererConfig {
+        self.config
+    }
+
+    /// 기본 글자 크기 (논리 픽셀, 마크업의 `<size>`는 따로)
+    pub fn font_size(&self) -> f32 {
+        self.font_size
+    }
+
+    /// 스타일에 색이 없는 글자의 색 (RGBA8)
+    pub fn color(&self) -> [u8; 4] {
+        self.color
+    }
+
+    /// `TextRendererBuilder::effect`로 고른 효과를 켠 기본 그리기 파라미터
+    pub fn params(&self) -> TextParams {
+        TextParams {
+            effects: self.effects,
+            ..TextParams::default()
+        }
+    }
+
+    // 빌더가 텍스트를 올리기 전에 기본 색과 효과를 정함
+    pub(crate) fn set_defaults(&mut self, color: [u8; 4], effects: TextEffects) {
+        self.color = color;
+        self.effects = effects;
+    }
+
+    pub fn layout_options(&self) -> LayoutOptions {
+        self.layout_options
+    }
+
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// HiDPI 배율을 바꿉니다 (보통 winit `Window::scale_factor`).
+    ///
+    /// 글리프를 `폰트 크기 × 배율` 물리 픽셀로 다시 래스터라이즈하고, 레이아웃 결과와 크기는
+    /// 논리 픽셀로 돌려줍니다. `TextParams::projection`도 논리 픽셀 크기(물리 크기 ÷ 배율)로 만들면
+    /// 화면에서 글리프 텍셀 하나가 물리 픽셀 하나에 맞습니다.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) -> Result<(), RendererError> {
+        let scale_factor = scale_factor.max(0.1);
+        if self.scale_factor == scale_factor {
+            return Ok(());
+        }
+
+        self.scale_factor = scale_factor;
+        self.relayout()
+    }
+
+    /// 줄바꿈 너비와 줄 간격을 바꿉니다.
+    ///
+    /// 값이 달라졌으면 현재 텍스트를 바로 다시 레이아웃합니다.
+    pub fn set_layout_options(&mut self, options: LayoutOptions) -> Result<(), RendererError> {
+        if self.layout_options == options {
+            return Ok(());
+        }
+
+        self.layout_options = options;
+        self.relayout()
+    }
+
+    pub fn lod_bias(&self) -> f32 {
+        self.lod_bias
+    }
+
+    /// 아틀라스 밉맵 단계 선택에 더할 값을 바꿉니다.
+    ///
+    /// 양수면 축소한 텍스트가 더 부드러워지고, 음수면 더 선명하지만 계단 현상이 생길 수 있습니다.
+    /// 장치의 `max_sampler_lod_bias` 범위로 제한합니다. 밉맵을 끄면 효과가 없습니다.
+    pub fn set_lod_bias(&mut self, bias: f32) -> Result<(), RendererError> {
+        let limit = self
+            .queue
+            .device()
+            .physical_device()
+            .properties()
+            .max_sampler_lod_bias;
+        let bias = bias.clamp(-limit, limit);
+        if self.lod_bias == bias {
+            return Ok(());
+        }
+
+        self.sampler = create_sampler(self.queue.device(), self.atlas.mip_levels(), bias)?;
+        self.lod_bias = bias;
+        // 아틀라스 디스크립터 셋이 이전 샘플러를 잡고 있음
+        self.descriptor_set = None;
+        self.revision += 1;
+        Ok(())
+    }
+
+    /// 글리프 아틀라스의 캐시 적중, 실패, 제거 횟수
+    ///
+    /// 아틀라스가 최대 크기(8192×8192)까지 차면 화면에 없는 글리프 중 가장 오래전에 쓴 것부터
+    /// 내보내고 그 자리를 다시 씁니다. 시계나 로그처럼 계속 새 글자가 나오는 텍스트에서도 커지지 않습니다.
+    pub fn glyph_cache_stats(&self) -> GlyphCacheStats {
+        self.atlas.stats()
+    }
+
+    /// 글리프 업로드의 복사를 전송 전용 큐에서 실행합니다 (`None`이면 렌더러의 그래픽 큐만 사용).
+    ///
+    /// 그래픽 큐와 같은 패밀리면 무시합니다. 다른 패밀리면 아틀라스를 여러 패밀리가 함께 쓰도록
+    /// 다시 만들고, 복사 뒤 밉맵 blit과 그리기는 세마포어로 복사를 기다립니다.
+    pub fn set_transfer_queue(
+        &mut self,
+        transfer_queue: Option<Arc<Queue>>,
+    ) -> Result<(), RendererError> {
+        let transfer_queue = transfer_queue.filter(|queue| !self.is_graphics_family(queue));
+        self.atlas.set_transfer_queue(transfer_queue);
+        self.update_queue_families()
+    }
+
+    /// 그림자/외곽선 컴퓨트 패스를 비동기 컴퓨트 큐에서 실행합니다 (`None`이면 그래픽 큐).
+    ///
+    /// 그래픽 큐와 같은 패밀리면 무시합니다. 그리기는 세마포어로 컴퓨트 패스를 기다립니다.
+    pub fn set_compute_queue(
+        &mut self,
+        compute_queue: Option<Arc<Queue>>,
+    ) -> Result<(), RendererError> {
+        self.compute_queue = compute_queue.filter(|queue| !self.is_graphics_family(queue));
+        self.update_queue_families()
+    }
+
+    /// 아직 기다리지 않은 글리프 업로드와 그림자/외곽선 컴퓨트 패스의 future
+    ///
+    /// 글리프 업로드와 컴퓨트 패스는 기다리지 않고 제출되므로, 이 future를 프레임의 future에
+    /// `join`한 뒤 그리기 명령 버퍼를 실행해야 새 글리프와 효과 아틀라스가 준비된 다음 그려집니다.
+    /// 가져가지 않으면 다음 업로드나 컴퓨트 패스가 그 뒤에 이어 실행됩니다.
+    pub fn take_upload_future(&mut self) -> Option<Box<dyn GpuFuture>> {
+        self.atlas.take_pending()
+    }
+
+    /// 이 렌더러로 그린 프레임을 제출한 뒤 그 프레임의 펜스를 넘김 (`FramesInFlight::last_fence`)
+    ///
+    /// 아틀라스가 가득 차 내보낸 글리프의 영역은 그 글리프를 그렸을 수 있는 프레임이 모두 끝난 뒤에 다시 씁니다.
+    /// 프레임마다 펜스를 기다린 뒤 다음 프레임을 그린다면 넘기지 않아도 됩니다.
+    pub fn frame_submitted(&mut self, fence: FrameFence) {
+        self.atlas.frame_submitted(fence);
+    }
+
+    fn is_graphics_family(&self, queue: &Queue) -> bool {
+        queue.queue_family_index() == self.queue.queue_family_index()
+    }
+
+    // 아틀라스와 그림자/외곽선 이미지를 함께 쓰는 큐 패밀리 (바뀌면 이미지를 다시 만듦)
+    fn update_queue_families(&mut self) -> Result<(), RendererError> {
+        let mut families = vec![self.queue.queue_family_index()];
+        let others = [self.atlas.transfer_queue(), self.compute_queue.as_ref()];
+        for family in others.into_iter().flatten().map(|q| q.queue_family_index()) {
+            if !families.contains(&family) {
+                families.push(family);
+            }
+        }
+        self.atlas.set_queue_families(families)
+    }
+
+    /// 같은 `params`로 다시 그려도 마지막으로 그린 결과와 같은지
+    ///
+    /// 장면, 강조 사각형, 셰이더가 그대로이고 시간에 따라 바뀌는 효과(물결, 글리치, 흐르는 그라데이션,
+    /// 타자기, 노래방, 사용자 셰이더)가 없으면 `params.time`은 비교하지 않습니다.
+    /// `TextLayer`가 정적인 텍스트를 다시 그리지 않고 이전 프레임의 이미지를 재사용할 때 씁니다.
+    pub fn is_unchanged(&self, params: &TextParams) -> bool {
+        !self.scene_changed
+            && !self.is_animated(params)
+            && self.drawn == Some((static_params(params), self.revision))
+    }
+
+    /// `params.time`에 따라 그리는 결과가 달라지는지 (사용자 셰이더는 시간을 쓸 수 있으므로 항상)
+    ///
+    /// 바뀐 것이 있을 때만 다시 그리는 애플리케이션이 계속 그려야 하는지 판단할 때 씁니다.
+    pub fn is_animated(&self, params: &TextParams) -> bool {
+        self.typewriter.is_some()
+            || self.is_singing()
+            || self.custom_shader.is_some()
+            || self.uses_effect(params, TextEffects::WAVE)
+            || self.uses_effect(params, TextEffects::GLITCH)
+            || (params.gradient.speed != 0.0 && self.uses_effect(params, TextEffects::GRADIENT))
+    }
+
+    // 노래방 효과를 아직 다 칠하지 않았는지 (마지막으로 그린 시점 기준)
+    fn is_singing(&self) -> bool {
+        self.karaoke
+            .as_ref()
+            .is_some_and(|(karaoke, _)| self.sung < karaoke.duration())
+    }
+
+    /// 대체 폰트를 추가합니다.
+    ///
+    /// 기본 폰트에 없는 글자는 추가한 순서대로 대체 폰트에서 찾아 그립니다.
+    pub fn add_fallback_font(&mut self, font: Font) -> Result<(), RendererError> {
+        self.fonts.push(FontFace::from_font(font));
+        self.relayout()
+    }
+
+    /// 원본 폰트 데이터로 대체 폰트를 추가합니다.
+    ///
+    /// rustybuzz로 셰이핑하고, 컬러 글리프 테이블(CBDT/sbix/COLR)이 있으면
+    /// 이모지 등을 RGBA 그대로 그립니다.
+    pub fn add_fallback_font_data(&mut self, data: FontData) -> Result<(), RendererError> {
+        self.fonts.push(FontFace::from_data(data)?);
+        self.relayout()
+    }
+
+    /// 타자기 애니메이션을 처음부터 시작합니다.
+    ///
+    /// 다음 `draw` 호출의 `TextParams::time`부터 글자가 하나씩 나타나고,
+    /// 진행 중에 텍스트가 바뀌어도 같은 시간표로 이어집니다.
+    pub fn start_typewriter(&mut self, typewriter: Typewriter) {
+        self.typewriter = Some((typewriter, None));
+    }
+
+    /// 타자기 애니메이션을 멈추고 텍스트 전체를 바로 보여줍니다.
+    pub fn stop_typewriter(&mut self) -> Result<(), RendererError> {
+        if self.typewriter.take().is_some() {
+            self.scene_changed = true;
+        }
+        Ok(())
+    }
+
+    /// 타자기 애니메이션이 진행 중인지
+    pub fn is_typing(&self) -> bool {
+        self.typewriter.is_some()
+    }
+
+    /// 노래방 효과를 처음부터 시작합니다 (이전 효과는 지움).
+    ///
+    /// 다음 `draw` 호출의 `TextParams::time`부터 음절 시간표대로 칠하며, 다 칠한 뒤에도
+    /// `stop_karaoke`를 부를 때까지 강조 색이 남습니다.
+    pub fn start_karaoke(&mut self, karaoke: Karaoke) {
+        self.karaoke = Some((karaoke, None));
+        self.sung = 0.0;
+        self.scene_changed = true;
+    }
+
+    /// 노래방 효과를 멈추고 글자를 원래 색으로 되돌립니다.
+    pub fn stop_karaoke(&mut self) {
+        if self.karaoke.take().is_some() {
+            self.scene_changed = true;
+        }
+    }
+
+    /// 텍스트 프래그먼트 셰이더를 사용자 효과 셰이더로 바꿉니다 (`None`이면 내장 셰이더로 되돌림).
+    ///
+    /// 셰이더가 올바르지 않으면 기존 파이프라인을 유지하고 오류를 돌려줍니다.
+    pub fn set_custom_shader(
+        &mut self,
+        shader: Option<&CustomShader>,
+    ) -> Result<(), RendererError> {
+        let custom = shader
+            .map(|shader| shader.load(self.queue.device()))
+            .transpose()?;
+        let previous = std::mem::replace(&mut self.custom_shader, custom);
+        if let Err(e) = self.rebuild_text_pipeline() {
+            self.custom_shader = previous;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    // 현재 셰이더 구성(내장 또는 감시 중인 셰이더 + 사용자 셰이더)으로 텍스트 파이프라인을 다시 만듦
+    fn rebuild_text_pipeline(&mut self) -> Result<(), RendererError> {
+        #[cfg(feature = "hot-reload")]
+        if let Some(watcher) = self.shader_watcher.take() {
+            let result = self.rebuild_pipelines(&watcher);
+            self.shader_watcher = Some(watcher);
+            return result;
+        }
+
+        let device = self.queue.device();
+        let [vs, fs] = text_shaders(device, self.antialiasing)?;
+        self.pipeline = self.create_text_pipeline(vs, fs)?;
+        self.reset_descriptor_sets();
+        Ok(())
+    }
+
+    // 사용자 셰이더가 있으면 fs 대신 사용 (dual-source 출력이 없으므로 일반 블렌딩)
+    fn create_text_pipeline(
+        &self,
+        vs: Arc<ShaderModule>,
+        fs: Arc<ShaderModule>,
+    ) -> Result<Arc<GraphicsPipeline>, RendererError> {
+        let device = self.queue.device().clone();
+        let target = self.target.clone();
+        match &self.custom_shader {
+            Some(custom) => create_pipeline(
+                device,
+                target,
+                Antialiasing::Grayscale,
+                [vs, custom.clone()],
+            ),
+            None => create_pipeline(device, target, self.antialiasing, [vs, fs]),
+        }
+    }
+
+    // 파이프라인 레이아웃이 바뀌었을 수 있으므로 디스크립터 셋을 다시 만들게 함
+    fn reset_descriptor_sets(&mut self) {
+        self.descriptor_set = None;
+        self.effect_buffers.fill(None);
+        self.effect_set = None;
+        self.glow_pipeline = None;
+        self.revision += 1;
+    }
+
+    /// `dir`의 GLSL 셰이더 파일을 감시해 바뀔 때마다 파이프라인을 다시 만들도록 합니다.
+    ///
+    /// 파일 이름은 crate의 `shaders/` 디렉터리와 같아야 합니다
+    /// (`text.vert`, `text.frag`, `text_subpixel.frag`, `background.vert`, `background.frag`).
+    /// 시작할 때 한 번 컴파일해 적용하므로 셰이더에 오류가 있으면 여기서 실패합니다.
+    #[cfg(feature = "hot-reload")]
+    pub fn watch_shaders(&mut self, dir: impl Into<PathBuf>) -> Result<(), RendererError> {
+        let watcher = ShaderWatcher::new(dir)?;
+        self.rebuild_pipelines(&watcher)?;
+        self.shader_watcher = Some(watcher);
+        Ok(())
+    }
+
+    /// 감시 중인 셰이더 파일이 바뀌었으면 다시 컴파일해 파이프라인을 교체하고 `true`를 돌려줍니다.
+    ///
+    /// 프레임마다 커맨드 버퍼를 기록하기 전에 호출하세요.
+    /// 컴파일이나 파이프라인 생성에 실패하면 기존 파이프라인을 그대로 쓰고 오류를 돌려줍니다.
+    #[cfg(feature = "hot-reload")]
+    pub fn reload_changed_shaders(&mut self) -> Result<bool, RendererError> {
+        let Some(watcher) = self.shader_watcher.take() else {
+            return Ok(false);
+        };
+        let result = if watcher.poll() {
+            self.rebuild_pipelines(&watcher).map(|()| true)
+        } else {
+            Ok(false)
+        };
+        self.shader_watcher = Some(watcher);
+        result
+    }
+
+    // 디스크의 셰이더로 텍스트와 배경 파이프라인을 새로 만듦 (둘 다 성공해야 교체)
+    #[cfg(feature = "hot-reload")]
+    fn rebuild_pipelines(&mut self, watcher: &ShaderWatcher) -> Result<(), RendererError> {
+        let device = self.queue.device();
+        let fs = match self.antialiasing {
+            Antialiasing::Grayscale => "text.frag",
+            Antialiasing::Subpixel => "text_subpixel.frag",
+        };
+
+        let pipeline = self.create_text_pipeline(
+            watcher.load(device, "text.vert")?,
+            watcher.load(device, fs)?,
+        )?;
+        let background_pipeline = create_background_pipeline(
+            device.clone(),
+            self.target.clone(),
+            [
+                watcher.load(device, "background.vert")?,
+                watcher.load(device, "background.frag")?,
+            ],
+        )?;
+
+        self.pipeline = pipeline;
+        self.background_pipeline = background_pipeline;
+        self.reset_descriptor_sets();
+        Ok(())
+    }
+
+    // 현재 텍스트와 텍스트 객체를 바뀐 설정으로 다시 레이아웃
+    fn relayout(&mut self) -> Result<(), RendererError> {
+        if let Some(text) = self.current_text.take() {
+            self.layout_text(text)?;
+        }
+        for index in 0..self.objects.len() {
+            let id = self.objects[index].id;
+            let object = self.objects[index].object.clone();
+            self.objects[index] = self.layout_object(id, object)?;
+        }
+        self.scene_changed = true;
+
+        Ok(())
+    }
+
+    /// 현재 기록 중인 render pass 안에 텍스트를 그립니다.
+    ///
+    /// viewport는 동적 상태이므로 호출 전에 `set_viewport`로 설정해야 합니다.
+    /// 텍스트가 이전 호출과 다르면 `set_text`와 같이 레이아웃을 갱신합니다.
+    pub fn draw<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        text: &str,
+        params: &TextParams,
+    ) -> Result<(), RendererError> {
+        self.set_text(text)?;
+        self.record(builder, params)
+    }
+
+    /// 구간별 스타일이 있는 텍스트를 그립니다. 모든 구간이 드로우 콜 한 번으로 그려집니다.
+    pub fn draw_styled<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        text: &StyledText,
+        params: &TextParams,
+    ) -> Result<(), RendererError> {
+        self.set_styled_text(text)?;
+        self.record(builder, params)
+    }
+
+    /// 기본 텍스트를 바꾸지 않고 현재 장면(기본 텍스트와 텍스트 객체)을 그립니다.
+    pub fn draw_scene<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        params: &TextParams,
+    ) -> Result<(), RendererError> {
+        self.record(builder, params)
+    }
+
+    // 현재 레이아웃된 텍스트와 텍스트 객체의 드로우 명령 기록
+    fn record<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        params: &TextParams,
+    ) -> Result<(), RendererError> {
+        self.drawn = Some((static_params(params), self.revision));
+        let descriptor_set = match self.descriptor_set.clone() {
+            Some(set) if self.atlas_generation == self.atlas.generation() => set,
+            _ => self.update_descriptor_set()?,
+        };
+
+        let mut revealed = None;
+        if let Some((typewriter, started)) = self.typewriter {
+            let start = started.unwrap_or(params.time);
+            let elapsed = params.time - start;
+            self.typewriter =
+                (!typewriter.is_finished(elapsed)).then_some((typewriter, Some(start)));
+            revealed = Some((typewriter, elapsed));
+        }
+        if let Some((karaoke, started)) = &mut self.karaoke {
+            let start = *started.get_or_insert(params.time);
+            // 다 칠한 뒤에는 시간을 멈춰 더 올리지 않음
+            let elapsed = (params.time - start).clamp(0.0, karaoke.duration());
+            if elapsed != self.sung {
+                self.sung = elapsed;
+                self.scene_changed = true;
+            }
+        }
+        if params.transform != self.transform {
+            self.transform = params.transform;
+            self.scene_changed = true;
+        }
+        if revealed.is_some() || self.scene_changed {
+            self.upload_scene(revealed)?;
+        }
+
+        // 배경 상자와 커서/선택 영역은 글리프 아래에 (텍스트가 비어 있어도 커서는 그림)
+        self.record_rects(builder, params)?;
+
+        let Some(instance_buffer) = self.instance_buffers[self.instance_slot].clone() else {
+            return Ok(());
+        };
+        if self.instance_count == 0 {
+            return Ok(());
+        }
+
+        // 그림자/외곽선 컴퓨트 패스는 비동기 컴퓨트 큐가 있으면 거기서 실행
+        let compute_queue = self
+            .compute_queue
+            .clone()
+            .unwrap_or_else(|| self.queue.clone());
+        // 그림자를 쓰는 경우에만 아틀라스를 블러 (내용이나 sigma가 바뀌었을 때만 실행됨)
+        // 사용자 셰이더는 shadowSampler를 쓸 수 있으므로 항상 준비
+        if self.custom_shader.is_some() || self.uses_effect(params, TextEffects::SHADOW) {
+            self.shadow.update(
+                &mut self.atlas,
+                params.shadow_blur.max(0.0) * self.scale_factor,
+                &self.descriptor_set_allocator,
+                &self.command_buffer_allocator,
+                compute_queue.clone(),
+            )?;
+        }
+        // 외곽선도 같은 방식으로 아틀라스를 팽창 (두께와 흐림 폭은 아틀라스 텍셀 단위)
+        if self.custom_shader.is_some() || self.uses_effect(params, TextEffects::OUTLINE) {
+            self.outline.update(
+                &mut self.atlas,
+                params.outline_width * self.scale_factor,
+                params.outline_softness * self.scale_factor,
+                &self.descriptor_set_allocator,
+                &self.command_buffer_allocator,
+                compute_queue,
+            )?;
+        }
+
+        let effect_set =
+            self.upload_effect_params(params.effect_params(self.text_extent, self.scale_factor))?;
+        self.effect_set = Some(effect_set.clone());
+
+        builder
+            .bind_pipeline_graphics(self.pipeline.clone())
+            .map_err(RendererError::command)?
+            .bind_descriptor_sets(
+                PipelineBindPoint::Graphics,
+                self.pipeline.layout().clone(),
+                0,
+                vec![descriptor_set, effect_set],
+            )
+            .map_err(RendererError::command)?
+            .push_constants(self.pipeline.layout().clone(), 0, params.push_constants())
+            .map_err(RendererError::command)?
+            .bind_vertex_buffers(0, (self.quad_vertices.clone(), instance_buffer))
+            .map_err(RendererError::command)?
+            .bind_index_buffer(self.quad_indices.clone())
+            .map_err(RendererError::command)?
+            .draw_indexed(QUAD_INDICES.len() as u32, self.instance_count, 0, 0, 0)
+            .map_err(RendererError::command)?;
+
+        Ok(())
+    }
+
+    /// 발광(사용자 셰이더를 쓰면 그리지 않음)을 쓰는지
+    pub(crate) fn uses_glow(&self, params: &TextParams) -> bool {
+        self.custom_shader.is_none() && self.uses_effect(params, TextEffects::GLOW)
+    }
+
+    /// 마지막 record와 같은 장면에서 발광을 켠 글리프의 커버리지만 `subpass`에 그림
+    ///
+    /// 같은 `params`로 `draw_scene`을 기록한 다음 블룸 원본 render pass 안에서 호출합니다.
+    pub(crate) fn record_glow_mask<L>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        subpass: &Subpass,
+        params: &TextParams,
+    ) -> Result<(), RendererError> {
+        let (Some(instance_buffer), Some(descriptor_set), Some(effect_set)) = (
+            self.instance_buffers[self.instance_slot].clone(),
+            self.descriptor_set.clone(),
+            self.effect_set.clone(),
+        ) else {
+            return Ok(());
+        };
+        if self.instance_count == 0 || !self.uses_glow(params) {
+            return Ok(());
+        }
+
+        let pipeline = match &self.glow_pipeline {
+            Some(pipeline)
+                if matches!(
+                    pipeline.subpass(),
+                    PipelineSubpassType::BeginRenderPass(built)
+                        if Arc::ptr_eq(built.render_pass(), subpass.render_pass())
+                ) =>
+            {
+                pipeline.clone()
+            }
+            _ => {
+                let pipeline = self.create_glow_pipeline(subpass.clone())?;
+                self.glow_pipeline = Some(pipeline.clone());
+                pipeline
+            }
+        };
+
+        builder
+            .bind_pipeline_graphics(pipeline.clone())
+            .map_err(RendererError::command)?
+            .bind_descriptor_sets(
+                PipelineBindPoint::Graphics,
+                pipeline.layout().clone(),
+                0,
+                vec![descriptor_set, effect_set],
+            )
+            .map_err(RendererError::command)?
+            .push_constants(pipeline.layout().clone(), 0, params.push_constants())
+            .map_err(RendererError::command)?
+            .bind_vertex_buffers(0, (self.quad_vertices.clone(), instance_buffer))
+            .map_err(RendererError::command)?
+            .bind_index_buffer(self.quad_indices.clone())
+            .map_err(RendererError::command)?
+            .draw_indexed(QUAD_INDICES.len() as u32, self.instance_count, 0, 0, 0)
+            .map_err(RendererError::command)?;
+
+        Ok(())
+    }
+
+    // 텍스트 파이프라인의 레이아웃을 그대로 써서 디스크립터 셋을 공유
+    fn create_glow_pipeline(
+        &self,
+        subpass: Subpass,
+    ) -> Result<Arc<GraphicsPipeline>, RendererError> {
+        let device = self.queue.device().clone();
+        let vs = shaders::vs::load(device.clone())
+            .map_err(|e| RendererError::shader("vertex", e))?
+            .entry_point("main")
+            .ok_or_else(|| RendererError::shader("vertex", "main 진입점 없음"))?;
+        let fs = shaders::glow_mask_fs::load(device.clone())
+            .map_err(|e| RendererError::shader("glow mask fragment", e))?
+            .entry_point("main")
+            .ok_or_else(|| RendererError::shader("glow mask fragment", "main 진입점 없음"))?;
+
+        let vertex_input_state = [QuadVertex::per_vertex(), GlyphInstance::per_instance()]
+            .definition(&vs.info().input_interface)
+            .map_err(RendererError::pipeline)?;
+
+        build_pipeline_in_layout(
+            device,
+            subpass.into(),
+            [vs, fs],
+            vertex_input_state,
+            premultiplied_blend(),
+            self.pipeline.layout().clone(),
+        )
+    }
+
+    // 배경 상자와 강조 사각형은 같은 단위 사각형을 늘려 그림
+    fn record_rects<L>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L>,
+        params: &TextParams,
+    ) -> Result<(), RendererError> {
+        let background = params
+            .background_push_constants(self.text_extent)
+            .filter(|_| !self.instances.is_empty());
+        let rects: Vec<_> = background
+            .into_iter()
+            .chain(
+                self.highlights
+                    .iter()
+                    .map(|highlight| params.highlight_push_constants(highlight)),
+            )
+            .collect();
+        if rects.is_empty() {
+            return Ok(());
+        }
+
+        builder
+            .bind_pipeline_graphics(self.background_pipeline.clone())
+            .map_err(RendererError::command)?
+            .bind_vertex_buffers(0, self.quad_vertices.clone())
+            .map_err(RendererError::command)?
+            .bind_index_buffer(self.quad_indices.clone())
+            .map_err(RendererError::command)?;
+        for rect in rects {
+            builder
+                .push_constants(self.background_pipeline.layout().clone(), 0, rect)
+                .map_err(RendererError::command)?
+                .draw_indexed(QUAD_INDICES.len() as u32, 1, 0, 0, 0)
+                .map_err(RendererError::command)?;
+        }
+
+        Ok(())
+    }
+
+    // 기본 텍스트를 레이아웃 (인스턴스 버퍼는 다음 record에서 갱신)
+    fn layout_text(&mut self, text: StyledText) -> Result<(), RendererError> {
+        let (instances, clusters, layout) = self.layout_instances(&text)?;
+        self.instances = instances;
+        self.clusters = clusters;
+        self.glyph_keys = layout.glyphs.iter().map(|glyph| glyph.key).collect();
+        self.text_extent = [layout.width, layout.height];
+        self.lines = layout.lines;
+        self.carets = layout.carets;
+        self.current_text = Some(text);
+        self.scene_changed = true;
+
+        Ok(())
+    }
+
+    fn layout_object(
+        &mut self,
+        id: TextObjectId,
+        object: TextObject,
+    ) -> Result<SceneObject, RendererError> {
+        let (instances, _, layout) = self.layout_instances(&object.text)?;
+        Ok(SceneObject {
+            id,
+            object,
+            instances,
+            keys: layout.glyphs.iter().map(|glyph| glyph.key).collect(),
+            extent: [layout.width, layout.height],
+        })
+    }
+
+    // 텍스트를 레이아웃해 글리프마다 인스턴스와 그 글자(grapheme)의 바이트 위치를 만들고 새 글리프를 아틀라스에 업로드
+    // (레이아웃과 래스터라이즈는 물리 픽셀, 인스턴스와 돌려주는 레이아웃은 텍스트 블록 중심 기준 논리 픽셀)
+    fn layout_instances(
+        &mut self,
+        text: &StyledText,
+    ) -> Result<(Vec<GlyphInstance>, Vec<usize>, TextLayout), RendererError> {
+        let scale = self.scale_factor;
+        let mut layout = layout::layout_text(
+            &self.fonts,
+            self.font_size,
+            scale,
+            text,
+            &self.layout_options,
+        );
+
+        // 텍스트 블록의 중심을 원점으로
+        let origin = [layout.width / 2.0, layout.height / 2.0];
+
+        // 화면에 남아 있는 글리프는 이 레이아웃에서 새 글리프가 자리를 찾더라도 내보내지 않음
+        let live = self.objects.iter().flat_map(|object| &object.keys);
+        self.atlas.begin_pass(self.glyph_keys.iter().chain(live));
+
+        // 새 글리프가 필요한 만큼 아틀라스를 먼저 한 번에 확보
+        self.atlas.reserve(
+            layout
+                .glyphs
+                .iter()
+                .map(|glyph| (&self.fonts[glyph.font_index].font, glyph.key)),
+        )?;
+
+        let padding = GLYPH_PADDING as f32;
+        let mut instances = Vec::with_capacity(layout.glyphs.len());
+        let mut clusters = Vec::with_capacity(layout.glyphs.len());
+        // 글자(확장 grapheme cluster)의 시작 위치 (결합 문자나 이모지 시퀀스의 글리프를 한 글자로 묶음)
+        let graphemes: Vec<usize> = text
+            .text
+            .grapheme_indices(true)
+            .map(|(index, _)| index)
+            .collect();
+
+        for glyph in &layout.glyphs {
+            let entry = self.glyph_entry(glyph)?;
+            if entry.is_empty() {
+                continue;
+            }
+
+            // 컬러 글리프는 원래 색을 유지하고 투명도만 적용
+            let style = text.style_at(glyph.cluster);
+            let [r, g, b, a] = match style.color {
+                _ if entry.colored => DEFAULT_COLOR,
+                Some(color) => color,
+                None => self.color,
+            };
+            let alpha = a as f32 * style.opacity.unwrap_or(1.0).clamp(0.0, 1.0);
+
+            instances.push(GlyphInstance {
+                position: [
+                    (glyph.origin[0] + entry.offset[0] - padding - origin[0]) / scale,
+                    (glyph.origin[1] + entry.offset[1] - padding - origin[1]) / scale,
+                ],
+                size: [entry.width as f32 / scale, entry.height as f32 / scale],
+                uv_rect: [
+                    entry.x as f32,
+                    entry.y as f32,
+                    entry.width as f32,
+                    entry.height as f32,
+                ],
+                color: [r, g, b, alpha.round() as u8],
+                effect: style.effects.map_or(-1, TextEffects::bits),
+                colored: entry.colored as u32,
+                transform: Mat4::IDENTITY.to_cols_array_2d(),
+                extent: [layout.width / scale, layout.height / scale],
+                character: 0,
+                layer: entry.layer,
+            });
+            clusters.push(animation::grapheme_start(&graphemes, glyph.cluster));
+        }
+        let (order, _) = animation::character_order(&clusters);
+        for (instance, order) in instances.iter_mut().zip(order) {
+            instance.character = order as u32;
+        }
+
+        self.atlas
+            .flush(&self.command_buffer_allocator, self.queue.clone())?;
+
+        layout.width /= scale;
+        layout.height /= scale;
+        layout.carets.scale(1.0 / scale);
+        for line in &mut layout.lines {
+            line.x /= scale;
+            line.baseline /= scale;
+            line.width /= scale;
+            line.height /= scale;
+        }
+
+        Ok((instances, clusters, layout))
+    }
+
+    // 아틀라스에서 글리프를 찾고, 없으면 컬러/외곽선 글리프로 래스터라이즈해 추가
+    fn glyph_entry(&mut self, glyph: &PositionedGlyph) -> Result<AtlasEntry, RendererError> {
+        let key = glyph.key;
+        if let Some(entry) = self.atlas.get(&key) {
+            return Ok(entry);
+        }
+
+        let face = &self.fonts[glyph.font_index];
+        let color_bitmap = face
+            .color
+            .as_ref()
+            .and_then(|color| color.rasterize(&face.font, key.glyph_index, key.px));
+
+        match color_bitmap {
+            Some(bitmap) => self.atlas.insert_color(key, &bitmap, glyph.sideways),
+            None => {
+                let embolden = if glyph.bold {
+                    layout::bold_strength(key.px)
+                } else {
+                    0
+                };
+                self.atlas
+                    .glyph(face, key, &glyph.variation, embolden, glyph.sideways)
+            }
+        }
+    }
+
+    // 기본 텍스트와 텍스트 객체의 인스턴스를 모아 올림
+    // (타자기 애니메이션 중이면 기본 텍스트의 알파를 글자 순서대로 줄임, 끝났으면 원래 알파 그대로)
+    fn upload_scene(&mut self, revealed: Option<(Typewriter, f32)>) -> Result<(), RendererError> {
+        let (order, count) = animation::character_order(&self.clusters);
+        let transform = self.transform.to_cols_array_2d();
+        let sung = self
+            .karaoke
+            .as_ref()
+            .map(|(karaoke, _)| (karaoke.color, karaoke.fill(&self.clusters, self.sung)));
+        let mut instances: Vec<GlyphInstance> = self
+            .instances
+            .iter()
+            .enumerate()
+            .map(|(index, instance)| {
+                let mut instance = *instance;
+                instance.transform = transform;
+                if let Some((typewriter, elapsed)) = revealed {
+                    let alpha = typewriter.alpha(order[index], count, elapsed);
+                    instance.color[3] = (instance.color[3] as f32 * alpha).round() as u8;
+                }
+                if let Some((color, fill)) = sung.as_ref().filter(|_| instance.colored == 0) {
+                    for (channel, &to) in instance.color.iter_mut().zip(color).take(3) {
+                        let from = *channel as f32;
+                        *channel = (from + (to as f32 - from) * fill[index]).round() as u8;
+                    }
+                }
+                instance
+            })
+            .collect();
+
+        for SceneObject {
+            object,
+            instances: object_instances,
+            ..
+        } in &self.objects
+        {
+            let opacity = object.opacity.clamp(0.0, 1.0);
+            let effect = object.effects.map_or(-1, TextEffects::bits);
+            let transform = object.transform.to_cols_array_2d();
+            instances.extend(object_instances.iter().map(|instance| {
+                let mut instance = *instance;
+                instance.transform = transform;
+                instance.color[3] = (instance.color[3] as f32 * opacity).round() as u8;
+                if instance.effect < 0 {
+                    instance.effect = effect;
+                }
+                instance
+            }));
+        }
+
+        self.upload_instances(&instances)?;
+        self.scene_changed = false;
+
+        Ok(())
+    }
+
+    // 다음 링 슬롯의 버퍼에 인스턴스를 쓰고, 용량이 모자라거나 GPU가 아직 사용 중이면 새로 할당
+    fn upload_instances(&mut self, instances: &[GlyphInstance]) -> Result<(), RendererError> {
+        self.instance_count = instances.len() as u32;
+        if instances.is_empty() {
+            return Ok(());
+        }
+
+        self.instance_slot = (self.instance_slot + 1) % self.instance_buffers.len();
+
+        if let Some(buffer) = &self.instance_buffers[self.instance_slot] {
+            if buffer.len() >= instances.len() as u64 {
+                if let Ok(mut contents) = buffer.write() {
+                    contents[..instances.len()].copy_from_slice(instances);
+                    return Ok(());
+                }
+            }
+        }
+
+        // 글자를 조금 더 입력해도 재할당하지 않도록 여유 용량 확보
+        let capacity = instances.len().next_power_of_two().max(64);
+        let buffer = Buffer::new_slice::<GlyphInstance>(
+            self.memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            capacity as u64,
+        )
+        .map_err(RendererError::buffer)?;
+
+        buffer.write().map_err(RendererError::buffer)?[..instances.len()]
+            .copy_from_slice(instances);
+        self.instance_buffers[self.instance_slot] = Some(buffer);
+
+        Ok(())
+    }
+
+    // 다음 링 슬롯의 UBO에 효과 파라미터를 쓰고 그 디스크립터 셋을 돌려줌
+    // (GPU가 아직 사용 중이면 새 버퍼와 디스크립터 셋을 만듦)
+    fn upload_effect_params(
+        &mut self,
+        params: EffectParams,
+    ) -> Result<Arc<PersistentDescriptorSet>, RendererError> {
+        self.effect_slot = (self.effect_slot + 1) % self.effect_buffers.len();
+
+        if let Some((buffer, set)) = &self.effect_buffers[self.effect_slot] {
+            if let Ok(mut contents) = buffer.write() {
+                *contents = params;
+                return Ok(set.clone());
+            }
+        }
+
+        let buffer = Buffer::from_data(
+            self.memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::UNIFORM_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            params,
+        )
+        .map_err(RendererError::buffer)?;
+        let set = PersistentDescriptorSet::new(
+            &self.descriptor_set_allocator,
+            self.pipeline.layout().set_layouts()[1].clone(),
+            [WriteDescriptorSet::buffer(0, buffer.clone())],
+            [],
+        )
+        .map_err(RendererError::buffer)?;
+
+        self.effect_buffers[self.effect_slot] = Some((buffer, set.clone()));
+        Ok(set)
+    }
+
+    // 전체, 텍스트 객체 또는 구간 효과로 effect가 쓰이는지
+    fn uses_effect(&self, params: &TextParams, effect: TextEffects) -> bool {
+        let in_spans = |text: &StyledText| {
+            text.spans.iter().any(|span| {
+                span.style
+                    .effects
+                    .is_some_and(|effects| effects.contains(effect))
+            })
+        };
+
+        params.effects.contains(effect)
+            || self.current_text.as_ref().is_some_and(in_spans)
+            || self.objects.iter().any(|SceneObject { object, .. }| {
+                object
+                    .effects
+                    .is_some_and(|effects| effects.contains(effect))
+                    || in_spans(&object.text)
+            })
+    }
+
+    // 아틀라스 이미지가 새로 만들어졌으면 디스크립터 셋도 다시 생성
+    fn update_descriptor_set(&mut self) -> Result<Arc<PersistentDescriptorSet>, RendererError> {
+        let shadow_view = self
+            .shadow
+            .view(&self.atlas, &self.descriptor_set_allocator)?;
+        let outline_view = self
+            .outline
+            .view(&self.atlas, &self.descriptor_set_allocator)?;
+        let layout = self.pipeline.layout().set_layouts()[0].clone();
+        // 사용자 셰이더는 샘플러 중 일부만 쓸 수 있으므로 레이아웃에 있는 바인딩만 씀
+        let writes = [
+            WriteDescriptorSet::image_view_sampler(0, self.atlas.view(), self.sampler.clone()),
+            WriteDescriptorSet::image_view_sampler(1, shadow_view, self.sampler.clone()),
+            WriteDescriptorSet::image_view_sampler(2, outline_view, self.sampler.clone()),
+        ]
+        .into_iter()
+        .filter(|write| layout.bindings().contains_key(&write.binding()))
+        .collect::<Vec<_>>();
+        let descriptor_set =
+            PersistentDescriptorSet::new(&self.descriptor_set_allocator, layout, writes, [])
+                .map_err(RendererError::texture)?;
+
+        self.descriptor_set = Some(descriptor_set.clone());
+        self.atlas_generation = self.atlas.generation();
+
+        Ok(descriptor_set)
+    }
+}
+
+// 내용이 바뀌지 않는 작은 버퍼 (단위 사각형 정점/인덱스)
+fn create_static_buffer<T, I>(
+    memory_allocator: &Arc<StandardMemoryAllocator>,
+    usage: BufferUsage,
+    data: I,
+) -> Result<Subbuffer<[T]>, RendererError>
+where
+    T: BufferContents,
+    I: IntoIterator<Item = T>,
+    I::IntoIter: ExactSizeIterator,
+{
+    Buffer::from_iter(
+        memory_allocator.clone(),
+        BufferCreateInfo {
+            usage,
+            ..Default::default()
+        },
+        AllocationCreateInfo {
+            memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+            ..Default::default()
+        },
+        data,
+    )
+    .map_err(RendererError::buffer)
+}
+
+// 시간을 빼고 비교하도록 (시간에 따라 바뀌는 효과가 있으면 is_animated가 먼저 걸러냄)
+fn static_params(params: &TextParams) -> TextParams {
+    TextParams {
+        time: 0.0,
+        ..*params
+    }
+}
+
+// 아틀라스와 그림자 아틀라스 공용 샘플러 (밉맵이 있으면 단계 사이도 선형 보간)
+fn create_sampler(
+    device: &Arc<Device>,
+    mip_levels: u32,
+    lod_bias: f32,
+) -> Result<Arc<Sampler>, RendererError> {
+    Sampler::new(
+        device.clone(),
+        SamplerCreateInfo {
+            mag_filter: Filter::Linear,
+            min_filter: Filter::Linear,
+            mipmap_mode: SamplerMipmapMode::Linear,
+            address_mode: [SamplerAddressMode::ClampToEdge; 3],
+            mip_lod_bias: lod_bias,
+            lod: 0.0..=(mip_levels - 1) as f32,
+            ..Default::default()
+        },
+    )
+    .map_err(RendererError::texture)
+}
+
+// 빌드 시 컴파일되어 내장된 텍스트 셰이더 [정점, 프래그먼트]
+fn text_shaders(
+    device: &Arc<Device>,
+    antialiasing: Antialiasing,
+) -> Result<[Arc<ShaderModule>; 2], RendererError> {
+    let vs = shaders::vs::load(device.clone()).map_err(|e| RendererError::shader("vertex", e))?;
+    let fs = match antialiasing {
+        Antialiasing::Grayscale => shaders::fs::load(device.clone()),
+        Antialiasing::Subpixel => shaders::fs_subpixel::load(device.clone()),
+    }
+    .map_err(|e| RendererError::shader("fragment", e))?;
+    Ok([vs, fs])
+}
+
+fn background_shaders(device: &Arc<Device>) -> Result<[Arc<ShaderModule>; 2], RendererError> {
+    let vs = shaders::background_vs::load(device.clone())
+        .map_err(|e| RendererError::shader("background vertex", e))?;
+    let fs = shaders::background_fs::load(device.clone())
+        .map_err(|e| RendererError::shader("background fragment", e))?;
+    Ok([vs, fs])
+}
+
+fn create_pipeline(
+    device: Arc<Device>,
+    target: RenderTarget,
+    antialiasing: Antialiasing,
+    [vs, fs]: [Arc<ShaderModule>; 2],
+) -> Result<Arc<GraphicsPipeline>, RendererError> {
+    let vs = vs
+        .entry_point("main")
+        .ok_or_else(|| RendererError::shader("vertex", "main 진입점 없음"))?;
+    let fs = specialize_fragment(&fs, &target)?;
+
+    let vertex_input_state = [QuadVertex::per_vertex(), GlyphInstance::per_instance()]
+        .definition(&vs.info().input_interface)
+        .map_err(RendererError::pipeline)?;
+
+    // 서브픽셀 모드는 셰이더의 두 번째 출력을 채널별 블렌드 계수로 사용
+    let blend = match antialiasing {
+        Antialiasing::Grayscale => premultiplied_blend(),
+        Antialiasing::Subpixel => AttachmentBlend {
+            src_color_blend_factor: BlendFactor::Src1Color,
+            dst_color_blend_factor: BlendFactor::OneMinusSrc1Color,
+            color_blend_op: BlendOp::Add,
+            src_alpha_blend_factor: BlendFactor::Src1Alpha,
+            dst_alpha_blend_factor: BlendFactor::OneMinusSrc1Alpha,
+            alpha_blend_op: BlendOp::Add,
+        },
+    };
+
+    build_pipeline(device, target, [vs, fs], vertex_input_state, blend)
+}
+
+// 텍스트 뒤 배경 상자 (단위 사각형 하나를 SDF 둥근 사각형으로 그림)
+fn create_background_pipeline(
+    device: Arc<Device>,
+    target: RenderTarget,
+    [vs, fs]: [Arc<ShaderModule>; 2],
+) -> Result<Arc<GraphicsPipeline>, RendererError> {
+    let vs = vs
+        .entry_point("main")
+        .ok_or_else(|| RendererError::shader("background vertex", "main 진입점 없음"))?;
+    let fs = specialize_fragment(&fs, &target)?;
+
+    let vertex_input_state = QuadVertex::per_vertex()
+        .definition(&vs.info().input_interface)
+        .map_err(RendererError::pipeline)?;
+
+    build_pipeline(
+        device,
+        target,
+        [vs, fs],
+        vertex_input_state,
+        premultiplied_blend(),
+    )
+}
+
+// sRGB 대상이면 셰이더가 선형 색을 출력해 블렌딩이 선형 공간에서 이루어지도록 함
+// 부동소수점 대상(HDR, 확장 sRGB 선형)이면 선형 색에 더해 1.0을 넘는 밝기도 출력 (HDR_OUTPUT)
+pub(crate) fn specialize_fragment(
+    module: &Arc<ShaderModule>,
+    target: &RenderTarget,
+) -> Result<EntryPoint, RendererError> {
+    let numeric_format = target
+        .color_attachment()
+        .ok()
+        .and_then(|(format, _)| format.numeric_format_color());
+    let hdr_output = numeric_format == Some(NumericFormat::SFLOAT);
+    let linear_output = hdr_output || numeric_format == Some(NumericFormat::SRGB);
+
+    // 사용자 셰이더는 LINEAR_OUTPUT, HDR_OUTPUT 상수를 선언하지 않았을 수 있음
+    module
+        .specialize(
+            [
+                (0, SpecializationConstant::Bool(linear_output)),
+                (1, SpecializationConstant::Bool(hdr_output)),
+            ]
+            .into_iter()
+            .filter(|(id, _)| module.specialization_constants().contains_key(id))
+            .collect(),
+        )
+        .map_err(|e| RendererError::shader("fragment", e))?
+        .entry_point("main")
+        .ok_or_else(|| RendererError::shader("fragment", "main 진입점 없음"))
+}
+
+// 셰이더 출력과 아틀라스는 premultiplied alpha
+pub(crate) fn premultiplied_blend() -> AttachmentBlend {
+    AttachmentBlend {
+        src_color_blend_factor: BlendFactor::One,
+        dst_color_blend_factor: BlendFactor::OneMinusSrcAlpha,
+        color_blend_op: BlendOp::Add,
+        src_alpha_blend_factor: BlendFactor::One,
+        dst_alpha_blend_factor: BlendFactor::OneMinusSrcAlpha,
+        alpha_blend_op: BlendOp::Add,
+    }
+}
+
+pub(crate) fn build_pipeline(
+    device: Arc<Device>,
+    target: RenderTarget,
+    [vs, fs]: [EntryPoint; 2],
+    vertex_input_state: VertexInputState,
+    blend: AttachmentBlend,
+) -> Result<Arc<GraphicsPipeline>, RendererError> {
+    let stages = [
+        PipelineShaderStageCreateInfo::new(vs.clone()),
+        PipelineShaderStageCreateInfo::new(fs.clone()),
+    ];
+
+    let layout = PipelineLayout::new(
+        device.clone(),
+        PipelineDescriptorSetLayoutCreateInfo::from_stages(&stages)
+            .into_pipeline_layout_create_info(device.clone())
+            .map_err(RendererError::pipeline)?,
+    )
+    .map_err(RendererError::pipeline)?;
+
+    build_pipeline_in_layout(device, target, [vs, fs], vertex_input_state, blend, layout)
+}
+
+// 다른 파이프라인과 디스크립터 셋을 같이 쓰도록 레이아웃을 지정해 만듦
+// (셰이더가 쓰는 바인딩이 레이아웃에 모두 있어야 함)
+fn build_pipeline_in_layout(
+    device: Arc<Device>,
+    target: RenderTarget,
+    [vs, fs]: [EntryPoint; 2],
+    vertex_input_state: VertexInputState,
+    blend: AttachmentBlend,
+    layout: Arc<PipelineLayout>,
+) -> Result<Arc<GraphicsPipeline>, RendererError> {
+    let stages = [
+        PipelineShaderStageCreateInfo::new(vs),
+        PipelineShaderStageCreateInfo::new(fs),
+    ];
+
+    // 블렌딩 활성화 (투명도 지원)
+    let mut color_blend_state = ColorBlendState::with_attachment_states(
+        target.color_attachment_count(),
+        ColorBlendAttachmentState::default(),
+    );
+    color_blend_state.attachments[0].blend = Some(blend);
+
+    // 멀티샘플 대상이면 같은 샘플 수로 래스터화 (MSAA)
+    let multisample_state = MultisampleState {
+        rasterization_samples: target.samples(),
+        ..MultisampleState::default()
+    };
+
+    GraphicsPipeline::new(
+        device,
+        None,
+        GraphicsPipelineCreateInfo {
+            stages: stages.into_iter().collect(),
+            vertex_input_state: Some(vertex_input_state),
+            input_assembly_state: Some(InputAssemblyState::default()),
+            viewport_state: Some(ViewportState::default()),
+            rasterization_state: Some(RasterizationState::default()),
+            multisample_state: Some(multisample_state),
+            color_blend_state: Some(color_blend_state),
+            dynamic_state: [DynamicState::Viewport].into_iter().collect(),
+            subpass: Some(target.pipeline_subpass()),
+            ..GraphicsPipelineCreateInfo::layout(layout)
+        },
+    )
+    .map_err(RendererError::pipeline)
+}
@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::{user_config_dir, Color, Effects};
+
+/// 저장한 상태 파일 오류
+#[derive(Debug, thiserror::Error)]
+pub enum StateError {
+    #[error("사용자 설정 디렉터리를 찾을 수 없습니다")]
+    NoConfigDir,
+
+    #[error("상태 파일을 읽을 수 없습니다 ({path}): {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("상태 파일을 저장할 수 없습니다 ({path}): {source}")]
+    Write {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("상태 파일 형식 오류: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("상태를 TOML로 바꿀 수 없습니다: {0}")]
+    Serialize(#[from] toml::ser::Error),
+}
+
+/// 종료할 때 저장해 다음 실행 때 복원하는 창과 표시 상태
+///
+/// 생략한 항목은 복원하지 않으므로 필요 없는 줄은 지워도 됩니다.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedState {
+    /// 표시하던 텍스트 (텍스트 파일이나 표준 입력을 쓰면 복원하지 않음)
+    pub text: Option<String>,
+    pub color: Option<Color>,
+    pub opacity: Option<f32>,
+    pub effects: Option<Effects>,
+    pub outline_color: Option<Color>,
+    pub outline_width: Option<f32>,
+    pub outline_softness: Option<f32>,
+    pub glow_color: Option<Color>,
+    pub glow_radius: Option<f32>,
+    pub glow_intensity: Option<f32>,
+    pub shadow_color: Option<Color>,
+    pub shadow_blur: Option<f32>,
+    pub background: Option<bool>,
+    pub click_through: Option<bool>,
+    /// 창 중앙 기준 텍스트 위치 (논리 픽셀), 회전 (도), 크기 배율
+    pub text_position: Option<[f32; 2]>,
+    pub text_rotation: Option<f32>,
+    pub text_scale: Option<f32>,
+    pub window: Option<SavedWindow>,
+}
+
+/// 창의 바깥 위치와 안쪽 크기 (물리 픽셀, 배율이 다른 모니터에서도 같은 자리로 돌아가도록)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SavedWindow {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 사용자 설정 디렉터리의 상태 파일
+pub struct StateFile {
+    path: PathBuf,
+}
+
+impl StateFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// `user_config_dir()`의 state.toml
+    pub fn in_config_dir() -> Result<Self, StateError> {
+        let dir = user_config_dir().ok_or(StateError::NoConfigDir)?;
+        Ok(Self::new(dir.join("state.toml")))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 저장한 상태 (처음 실행해 파일이 없으면 None)
+    pub fn load(&self) -> Result<Option<SavedState>, StateError> {
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => {
+                return Err(StateError::Read {
+                    path: self.path.display().to_string(),
+                    source,
+                })
+            }
+        };
+        Ok(Some(toml::from_str(&text)?))
+    }
+
+    pub fn save(&self, state: &SavedState) -> Result<(), StateError> {
+        let text = toml::to_string_pretty(state)?;
+        let write = |source| StateError::Write {
+            path: self.path.display().to_string(),
+            source,
+        };
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(write)?;
+        }
+        std::fs::write(&self.path, text).map_err(write)
+    }
+}
@@ -0,0 +1,61 @@
+use glam::{Mat4, Vec3};
+
+use crate::{effect::TextEffects, style::StyledText};
+
+/// 렌더러의 기본 텍스트와 별도로 장면에 두는 텍스트
+///
+/// 모든 텍스트 객체는 기본 텍스트와 같은 인스턴스 버퍼에 모여 드로우 콜 한 번으로 그려집니다.
+/// 효과 파라미터(색, 두께 등)는 `TextParams`를 함께 쓰고, 변환, 투명도, 효과 조합만 객체마다 정합니다.
+///
+/// ```ignore
+/// let id = text_renderer.add_object(TextObject::new("LIVE").at([80.0, 40.0]))?;
+/// let tilted = TextObject::new("NEW!").with_transform(
+///     Mat4::from_translation(Vec3::new(600.0, 150.0, 0.0)) * Mat4::from_rotation_z(-0.3),
+/// );
+/// text_renderer.update_object(id, TextObject::new("OFFLINE").at([80.0, 40.0]))?;
+/// text_renderer.remove_object(id);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextObject {
+    pub text: StyledText,
+    /// 모델 행렬 (텍스트 블록 중심이 원점인 픽셀 좌표 → 화면 픽셀, 왼쪽 위가 원점이고 Y는 아래 방향)
+    ///
+    /// 회전과 크기 조절은 텍스트 블록 중심을 기준으로 합니다. 물결 효과는 변환 전에 적용됩니다.
+    pub transform: Mat4,
+    /// 투명도 (`TextParams::opacity`와 구간 투명도에 곱해짐)
+    pub opacity: f32,
+    /// 효과 조합 (`None`이면 `TextParams::effects`, 구간 효과가 있으면 구간 효과가 우선)
+    pub effects: Option<TextEffects>,
+}
+
+impl TextObject {
+    /// 화면 왼쪽 위(원점)에 중심이 놓인 불투명한 텍스트 (`at`으로 위치 지정)
+    pub fn new(text: impl Into<StyledText>) -> Self {
+        Self {
+            text: text.into(),
+            transform: Mat4::IDENTITY,
+            opacity: 1.0,
+            effects: None,
+        }
+    }
+
+    /// 회전이나 크기 조절 없이 텍스트 블록 중심을 화면 픽셀 `[x, y]`에 둠
+    pub fn at(self, [x, y]: [f32; 2]) -> Self {
+        self.with_transform(Mat4::from_translation(Vec3::new(x, y, 0.0)))
+    }
+
+    pub fn with_transform(mut self, transform: Mat4) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+impl Default for TextObject {
+    fn default() -> Self {
+        Self::new(StyledText::default())
+    }
+}
+
+/// 장면에 추가한 텍스트 객체의 핸들 (제거된 객체의 id는 다시 쓰지 않음)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextObjectId(pub(crate) u64);
@@ -0,0 +1,202 @@
+use std::{
+    cell::RefCell,
+    path::PathBuf,
+    rc::Rc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use rhai::{Engine, EvalAltResult, Scope, AST, FLOAT, INT};
+use tracing::{info, warn};
+use transparent_text_vulkan::parse_color;
+
+use crate::file_watch::FileWatcher;
+
+/// `--script`도 설정 파일의 `script`도 없을 때 현재 디렉터리에서 찾는 스크립트
+pub const DEFAULT_SCRIPT_PATH: &str = "overlay.rhai";
+
+// 무한 루프가 이벤트 루프를 멈추지 않도록 한 프레임에 실행할 수 있는 최대 연산 수
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// 스크립트가 이번 프레임에 정한 값 (부르지 않은 함수의 항목은 None)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScriptOutput {
+    pub text: Option<String>,
+    pub opacity: Option<f32>,
+    pub color: Option<[u8; 4]>,
+    pub position: Option<[f32; 2]>,
+    pub rotation: Option<f32>,
+    pub scale: Option<f32>,
+}
+
+/// 프레임마다 실행하는 rhai 스크립트 (scripting 기능)
+///
+/// 스크립트 전체를 프레임마다 처음부터 실행하며, 다음 변수를 읽을 수 있습니다.
+///
+/// - `time`: 애니메이션 시계 (초, P로 일시 정지, Z/X로 배속)
+/// - `dt`: 지난 실행 이후 실제 시간 (초)
+/// - `unix_time`: 1970-01-01 UTC부터의 초 (시계, 카운트다운용)
+/// - `width`, `height`: 창 크기 (논리 픽셀)
+///
+/// `set_text`, `set_opacity`, `set_color`, `set_position`, `set_rotation`, `set_scale`로 값을 정하고,
+/// 부르지 않은 값은 키나 설정 파일로 정한 값이 그대로 남습니다.
+/// 파일을 저장하면 다시 컴파일하며, 컴파일이나 실행 오류는 출력만 하고 이전 스크립트를 유지합니다.
+///
+/// ```rhai
+/// let seconds = unix_time.to_int() % 60;
+/// set_text(`<b>${seconds}</b>초`);
+/// set_position(200.0 * (time * 0.5).sin(), 0.0);
+/// ```
+pub struct OverlayScript {
+    engine: Engine,
+    ast: Option<AST>,
+    // 스크립트 함수가 값을 쓰고 run이 가져감
+    output: Rc<RefCell<ScriptOutput>>,
+    watcher: Option<FileWatcher>,
+    path: PathBuf,
+    last_run: Option<Instant>,
+    // 같은 실행 오류를 프레임마다 출력하지 않도록 마지막 오류를 기억
+    last_error: Option<String>,
+}
+
+impl OverlayScript {
+    /// 스크립트를 컴파일하고 감시를 시작합니다 (컴파일에 실패해도 저장하면 다시 시도).
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let output = Rc::new(RefCell::new(ScriptOutput::default()));
+        let watcher = FileWatcher::new(&path)
+            .map_err(|e| warn!("스크립트 감시 실패 ({}): {e}", path.display()))
+            .ok();
+        let mut script = Self {
+            engine: create_engine(&output),
+            ast: None,
+            output,
+            watcher,
+            path,
+            last_run: None,
+            last_error: None,
+        };
+        script.compile();
+        script
+    }
+
+    /// 파일이 바뀌었으면 다시 컴파일하고 true
+    pub fn poll(&mut self) -> bool {
+        let changed = self.watcher.as_ref().is_some_and(FileWatcher::changed);
+        if changed {
+            self.compile();
+        }
+        changed
+    }
+
+    /// 스크립트를 한 번 실행하고 정한 값을 돌려줌 (컴파일된 스크립트가 없거나 실행에 실패하면 None)
+    pub fn run(&mut self, time: f32, [width, height]: [f32; 2]) -> Option<ScriptOutput> {
+        let ast = self.ast.as_ref()?;
+        let now = Instant::now();
+        let dt = self
+            .last_run
+            .replace(now)
+            .map_or(0.0, |last| now.duration_since(last).as_secs_f64());
+        let unix_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |elapsed| elapsed.as_secs_f64());
+
+        let mut scope = Scope::new();
+        scope.push_constant("time", time as FLOAT);
+        scope.push_constant("dt", dt as FLOAT);
+        scope.push_constant("unix_time", unix_time as FLOAT);
+        scope.push_constant("width", width as FLOAT);
+        scope.push_constant("height", height as FLOAT);
+
+        self.output.take();
+        match self.engine.run_ast_with_scope(&mut scope, ast) {
+            Ok(()) => {
+                self.last_error = None;
+                Some(self.output.take())
+            }
+            Err(e) => {
+                let message = e.to_string();
+                if self.last_error.as_ref() != Some(&message) {
+                    warn!("스크립트 실행 오류 ({}): {message}", self.path.display());
+                    self.last_error = Some(message);
+                }
+                None
+            }
+        }
+    }
+
+    // 읽거나 컴파일하지 못하면 이전 스크립트 유지 (저장하는 중이면 다음 변경 때 다시 읽음)
+    fn compile(&mut self) {
+        let source = match std::fs::read_to_string(&self.path) {
+            Ok(source) => source,
+            Err(e) => {
+                warn!("스크립트를 읽을 수 없습니다 ({}): {e}", self.path.display());
+                return;
+            }
+        };
+        match self.engine.compile(source) {
+            Ok(ast) => {
+                info!("스크립트: {}", self.path.display());
+                self.ast = Some(ast);
+                self.last_error = None;
+            }
+            Err(e) => warn!("스크립트 컴파일 오류 ({}): {e}", self.path.display()),
+        }
+    }
+}
+
+// 값을 정하는 함수 등록 (숫자 인자는 정수로 써도 되도록 FLOAT과 INT 둘 다)
+fn create_engine(output: &Rc<RefCell<ScriptOutput>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.on_print(|text| info!("스크립트: {text}"));
+
+    let out = output.clone();
+    engine.register_fn("set_text", move |text: &str| {
+        out.borrow_mut().text = Some(text.to_owned());
+    });
+    let out = output.clone();
+    engine.register_fn(
+        "set_color",
+        move |value: &str| -> Result<(), Box<EvalAltResult>> {
+            let color = parse_color(value).ok_or_else(|| format!("잘못된 색: {value}"))?;
+            out.borrow_mut().color = Some(color);
+            Ok(())
+        },
+    );
+
+    register_number(&mut engine, output, "set_opacity", |output, value| {
+        output.opacity = Some(value.clamp(0.0, 1.0));
+    });
+    register_number(&mut engine, output, "set_rotation", |output, value| {
+        output.rotation = Some(value);
+    });
+    register_number(&mut engine, output, "set_scale", |output, value| {
+        output.scale = Some(value);
+    });
+
+    let out = output.clone();
+    engine.register_fn("set_position", move |x: FLOAT, y: FLOAT| {
+        out.borrow_mut().position = Some([x as f32, y as f32]);
+    });
+    let out = output.clone();
+    engine.register_fn("set_position", move |x: INT, y: INT| {
+        out.borrow_mut().position = Some([x as f32, y as f32]);
+    });
+    engine
+}
+
+fn register_number(
+    engine: &mut Engine,
+    output: &Rc<RefCell<ScriptOutput>>,
+    name: &str,
+    set: fn(&mut ScriptOutput, f32),
+) {
+    let out = output.clone();
+    engine.register_fn(name, move |value: FLOAT| {
+        set(&mut out.borrow_mut(), value as f32)
+    });
+    let out = output.clone();
+    engine.register_fn(name, move |value: INT| {
+        set(&mut out.borrow_mut(), value as f32)
+    });
+}
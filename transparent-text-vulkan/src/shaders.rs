@@ -0,0 +1,119 @@
+// 텍스트 셰이더 정의
+// 모든 스테이지가 같은 push constant 블록과 효과 파라미터 UBO를 선언합니다
+// (effect::PushConstants, effect::EffectParams와 동일한 레이아웃).
+
+use std::sync::Arc;
+use vulkano::{
+    device::Device,
+    shader::{ShaderModule, ShaderModuleCreateInfo},
+};
+
+use crate::error::RendererError;
+
+pub(crate) mod vs {
+    vulkano_shaders::shader! {
+        ty: "vertex",
+        path: "shaders/text.vert",
+    }
+}
+
+pub(crate) mod fs {
+    vulkano_shaders::shader! {
+        ty: "fragment",
+        path: "shaders/text.frag",
+    }
+}
+
+// 서브픽셀 안티앨리어싱용 (dual-source blending)
+// 아틀라스의 RGB는 채널별 커버리지이고, 두 번째 출력이 채널별 블렌드 계수가 됩니다.
+// 결과 = outColor * outBlend + 대상 * (1 - outBlend)
+pub(crate) mod fs_subpixel {
+    vulkano_shaders::shader! {
+        ty: "fragment",
+        path: "shaders/text_subpixel.frag",
+    }
+}
+
+// 텍스트 뒤 배경 상자 (effect::BackgroundPushConstants와 동일한 레이아웃)
+pub(crate) mod background_vs {
+    vulkano_shaders::shader! {
+        ty: "vertex",
+        path: "shaders/background.vert",
+    }
+}
+
+pub(crate) mod background_fs {
+    vulkano_shaders::shader! {
+        ty: "fragment",
+        path: "shaders/background.frag",
+    }
+}
+
+// 오프스크린 텍스트 레이어를 대상 render pass에 합성 (layer::CompositePushConstants와 동일한 레이아웃)
+pub(crate) mod composite_vs {
+    vulkano_shaders::shader! {
+        ty: "vertex",
+        path: "shaders/composite.vert",
+    }
+}
+
+pub(crate) mod composite_fs {
+    vulkano_shaders::shader! {
+        ty: "fragment",
+        path: "shaders/composite.frag",
+    }
+}
+
+// 발광을 켠 글리프의 커버리지만 블룸 원본에 그림 (text.vert와 함께 사용)
+pub(crate) mod glow_mask_fs {
+    vulkano_shaders::shader! {
+        ty: "fragment",
+        path: "shaders/glow_mask.frag",
+    }
+}
+
+// 블룸 축소/확대 체인 (bloom::BloomParams와 동일한 레이아웃)
+pub(crate) mod bloom_cs {
+    vulkano_shaders::shader! {
+        ty: "compute",
+        path: "shaders/bloom.comp",
+    }
+}
+
+// 블룸 결과를 발광 색으로 대상에 더함 (composite.vert와 함께 사용, bloom::GlowPushConstants와 동일한 레이아웃)
+pub(crate) mod glow_fs {
+    vulkano_shaders::shader! {
+        ty: "fragment",
+        path: "shaders/glow.frag",
+    }
+}
+
+// 그림자용 분리형 가우시안 블러 (가로 한 번, 세로 한 번)
+// 아틀라스의 알파만 블러해 같은 배치의 그림자 아틀라스에 씁니다.
+pub(crate) mod blur_cs {
+    vulkano_shaders::shader! {
+        ty: "compute",
+        path: "shaders/blur.comp",
+    }
+}
+
+// 외곽선용 팽창 (반지름 안의 커버리지 최댓값)
+// 아틀라스의 알파를 넓혀 같은 배치의 외곽선 아틀라스에 씁니다.
+pub(crate) mod dilate_cs {
+    vulkano_shaders::shader! {
+        ty: "compute",
+        path: "shaders/dilate.comp",
+    }
+}
+
+// 런타임에 읽거나 컴파일한 SPIR-V로 셰이더 모듈 생성 (사용자 셰이더, 핫 리로드)
+pub(crate) fn load_spirv(
+    device: &Arc<Device>,
+    words: &[u32],
+    name: &'static str,
+) -> Result<Arc<ShaderModule>, RendererError> {
+    // SAFETY: vulkano가 모듈을 만들면서 SPIR-V를 파싱해 인터페이스를 반사하고,
+    // 파이프라인 생성 시 스테이지 사이의 인터페이스를 검증함
+    unsafe { ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(words)) }
+        .map_err(|e| RendererError::shader(name, e))
+}
@@ -0,0 +1,257 @@
+use std::sync::Arc;
+use vulkano::{
+    buffer::BufferContents,
+    command_buffer::{
+        allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+    },
+    descriptor_set::{
+        allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
+    },
+    device::{Device, Queue},
+    format::Format,
+    image::{
+        sampler::{ComponentMapping, Sampler, SamplerCreateInfo},
+        view::ImageView,
+        Image, ImageCreateInfo, ImageType, ImageUsage,
+    },
+    memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    pipeline::{
+        compute::ComputePipelineCreateInfo, layout::PipelineDescriptorSetLayoutCreateInfo,
+        ComputePipeline, Pipeline, PipelineBindPoint, PipelineLayout,
+        PipelineShaderStageCreateInfo,
+    },
+    sync::{self, GpuFuture},
+};
+use tracing::debug_span;
+
+use crate::{
+    atlas::{array_view, image_sharing, GlyphAtlas},
+    error::RendererError,
+    shaders,
+};
+
+const WORKGROUP_SIZE: u32 = 16;
+
+// blur_cs의 push constant 블록과 같은 레이아웃
+#[derive(BufferContents, Clone, Copy)]
+#[repr(C)]
+struct BlurParams {
+    direction: [i32; 2],
+    sigma: f32,
+}
+
+// 블러 결과와 그 시점의 입력 (다시 블러할 필요가 있는지 판단용)
+struct BlurTargets {
+    size: u32,
+    layers: u32,
+    // 중간(가로 블러) 이미지는 디스크립터 셋이 잡고 있음
+    output: Arc<ImageView>,
+    horizontal: Arc<PersistentDescriptorSet>,
+    vertical: Arc<PersistentDescriptorSet>,
+    // 마지막으로 블러한 (아틀라스 세대, 아틀라스 리비전, sigma)
+    blurred: Option<(u64, u64, f32)>,
+}
+
+/// 글리프 아틀라스를 가우시안 블러한 그림자 아틀라스
+///
+/// 아틀라스와 같은 배치이므로 텍스트 셰이더는 같은 좌표에 오프셋만 더해 그림자를 샘플링합니다.
+/// 아틀라스 내용이나 sigma가 바뀔 때만 컴퓨트 셰이더로 다시 블러합니다.
+pub(crate) struct ShadowBlur {
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    pipeline: Arc<ComputePipeline>,
+    sampler: Arc<Sampler>,
+    targets: Option<BlurTargets>,
+    atlas_generation: u64,
+}
+
+impl ShadowBlur {
+    pub fn new(
+        device: Arc<Device>,
+        memory_allocator: Arc<StandardMemoryAllocator>,
+    ) -> Result<Self, RendererError> {
+        let cs = shaders::blur_cs::load(device.clone())
+            .map_err(|e| RendererError::shader("blur compute", e))?
+            .entry_point("main")
+            .ok_or_else(|| RendererError::shader("blur compute", "main 진입점 없음"))?;
+        let sampler = create_fetch_sampler(&device)?;
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
+                .into_pipeline_layout_create_info(device.clone())
+                .map_err(RendererError::pipeline)?,
+        )
+        .map_err(RendererError::pipeline)?;
+        let pipeline = ComputePipeline::new(
+            device,
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .map_err(RendererError::pipeline)?;
+
+        Ok(Self {
+            memory_allocator,
+            pipeline,
+            sampler,
+            targets: None,
+            atlas_generation: 0,
+        })
+    }
+
+    /// 그림자 아틀라스 (아틀라스가 커졌으면 같은 크기로 다시 만듦)
+    pub fn view(
+        &mut self,
+        atlas: &GlyphAtlas,
+        descriptor_set_allocator: &StandardDescriptorSetAllocator,
+    ) -> Result<Arc<ImageView>, RendererError> {
+        Ok(self
+            .targets(atlas, descriptor_set_allocator)?
+            .output
+            .clone())
+    }
+
+    /// 아틀라스 내용이나 sigma가 바뀌었으면 다시 블러 (기다리지 않고 제출)
+    ///
+    /// `queue`는 비동기 컴퓨트 큐이거나 그래픽 큐이며, 완료는 아틀라스의 `take_pending`으로 기다립니다.
+    pub fn update(
+        &mut self,
+        atlas: &mut GlyphAtlas,
+        sigma: f32,
+        descriptor_set_allocator: &StandardDescriptorSetAllocator,
+        command_buffer_allocator: &StandardCommandBufferAllocator,
+        queue: Arc<Queue>,
+    ) -> Result<(), RendererError> {
+        let pipeline = self.pipeline.clone();
+        let targets = self.targets(atlas, descriptor_set_allocator)?;
+        let state = (atlas.generation(), atlas.revision(), sigma);
+        if targets.blurred == Some(state) {
+            return Ok(());
+        }
+        let _span = debug_span!(
+            "shadow_blur",
+            size = targets.size,
+            layers = targets.layers,
+            sigma
+        )
+        .entered();
+
+        let groups = targets.size.div_ceil(WORKGROUP_SIZE);
+        let mut builder = AutoCommandBufferBuilder::primary(
+            command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .map_err(RendererError::command)?;
+
+        builder
+            .bind_pipeline_compute(pipeline.clone())
+            .map_err(RendererError::command)?;
+        for (set, direction) in [
+            (targets.horizontal.clone(), [1, 0]),
+            (targets.vertical.clone(), [0, 1]),
+        ] {
+            builder
+                .bind_descriptor_sets(
+                    PipelineBindPoint::Compute,
+                    pipeline.layout().clone(),
+                    0,
+                    set,
+                )
+                .map_err(RendererError::command)?
+                .push_constants(
+                    pipeline.layout().clone(),
+                    0,
+                    BlurParams { direction, sigma },
+                )
+                .map_err(RendererError::command)?
+                .dispatch([groups, groups, targets.layers])
+                .map_err(RendererError::command)?;
+        }
+
+        let command_buffer = builder.build().map_err(RendererError::command)?;
+        // 아직 기다리지 않은 아틀라스 업로드가 있으면 그 뒤에 실행하고, 그리기는 세마포어로 이것을 기다림
+        let future = atlas
+            .take_pending()
+            .unwrap_or_else(|| sync::now(queue.device().clone()).boxed())
+            .then_execute(queue, command_buffer)
+            .map_err(RendererError::command)?
+            .then_signal_semaphore_and_flush()
+            .map_err(RendererError::command)?;
+        atlas.set_pending(future.boxed());
+
+        targets.blurred = Some(state);
+        Ok(())
+    }
+
+    // 아틀라스 이미지가 다시 만들어졌으면 중간/결과 이미지와 디스크립터 셋도 다시 만듦
+    fn targets(
+        &mut self,
+        atlas: &GlyphAtlas,
+        descriptor_set_allocator: &StandardDescriptorSetAllocator,
+    ) -> Result<&mut BlurTargets, RendererError> {
+        if self.targets.is_none() || self.atlas_generation != atlas.generation() {
+            let (size, layers) = (atlas.size(), atlas.layers());
+            let families = atlas.queue_families();
+            let temp = create_storage_image(&self.memory_allocator, size, layers, families)?;
+            let output = create_storage_image(&self.memory_allocator, size, layers, families)?;
+            let set_layout = self.pipeline.layout().set_layouts()[0].clone();
+            let descriptor_set = |source: Arc<ImageView>, target: Arc<ImageView>| {
+                PersistentDescriptorSet::new(
+                    descriptor_set_allocator,
+                    set_layout.clone(),
+                    [
+                        WriteDescriptorSet::image_view_sampler(0, source, self.sampler.clone()),
+                        WriteDescriptorSet::image_view(1, target),
+                    ],
+                    [],
+                )
+                .map_err(RendererError::texture)
+            };
+
+            self.targets = Some(BlurTargets {
+                size,
+                layers,
+                horizontal: descriptor_set(atlas.base_view(), temp.clone())?,
+                vertical: descriptor_set(temp, output.clone())?,
+                output,
+                blurred: None,
+            });
+            self.atlas_generation = atlas.generation();
+        }
+
+        self.targets
+            .as_mut()
+            .ok_or_else(|| RendererError::texture("그림자 아틀라스 생성 실패"))
+    }
+}
+
+// 컴퓨트 셰이더가 아틀라스와 중간 이미지를 texelFetch로 읽을 때 쓰는 샘플러 (필터링하지 않음)
+pub(crate) fn create_fetch_sampler(device: &Arc<Device>) -> Result<Arc<Sampler>, RendererError> {
+    Sampler::new(device.clone(), SamplerCreateInfo::default()).map_err(RendererError::texture)
+}
+
+// 아틀라스와 같은 크기와 레이어 수의 컴퓨트 셰이더 출력 이미지 (그림자, 외곽선 아틀라스)
+// 비동기 컴퓨트 큐에서 쓰고 그래픽 큐에서 읽으므로 아틀라스와 같은 큐 패밀리로 공유
+pub(crate) fn create_storage_image(
+    memory_allocator: &Arc<StandardMemoryAllocator>,
+    size: u32,
+    layers: u32,
+    queue_families: &[u32],
+) -> Result<Arc<ImageView>, RendererError> {
+    let image = Image::new(
+        memory_allocator.clone(),
+        ImageCreateInfo {
+            image_type: ImageType::Dim2d,
+            format: Format::R8G8B8A8_UNORM,
+            extent: [size, size, 1],
+            array_layers: layers,
+            usage: ImageUsage::STORAGE | ImageUsage::SAMPLED,
+            sharing: image_sharing(queue_families),
+            ..Default::default()
+        },
+        AllocationCreateInfo::default(),
+    )
+    .map_err(RendererError::texture)?;
+
+    array_view(image, 1, ComponentMapping::identity())
+}
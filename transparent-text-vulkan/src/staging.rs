@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use vulkano::{
+    buffer::{Buffer, BufferCreateInfo, BufferUsage, Subbuffer},
+    memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+};
+
+use crate::error::RendererError;
+
+// 작은 업로드가 이어져도 재할당하지 않도록 두는 최소 용량 (바이트)
+const MIN_STAGING_SIZE: u64 = 64 * 1024;
+
+/// 텍스처 업로드에 돌려 쓰는 호스트 메모리 버퍼 링
+///
+/// 업로드마다 다음 슬롯의 버퍼를 다시 쓰고, 용량이 모자라거나 GPU가 아직 복사 중이면
+/// 그 슬롯만 새로 할당합니다 (인스턴스 버퍼 링과 같은 방식).
+pub(crate) struct StagingRing {
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    buffers: Vec<Option<Subbuffer<[u8]>>>,
+    slot: usize,
+}
+
+impl StagingRing {
+    pub fn new(memory_allocator: Arc<StandardMemoryAllocator>, slots: usize) -> Self {
+        Self {
+            memory_allocator,
+            buffers: vec![None; slots.max(1)],
+            slot: 0,
+        }
+    }
+
+    /// `len` 바이트를 CPU에서 쓸 수 있는 스테이징 버퍼를 돌려줌
+    pub fn allocate(&mut self, len: u64) -> Result<Subbuffer<[u8]>, RendererError> {
+        self.slot = (self.slot + 1) % self.buffers.len();
+
+        if let Some(buffer) = &self.buffers[self.slot] {
+            // write()가 실패하면 이전 업로드가 아직 이 버퍼를 읽는 중
+            if buffer.len() >= len && buffer.write().is_ok() {
+                return Ok(buffer.clone().slice(0..len));
+            }
+        }
+
+        let capacity = len.next_power_of_two().max(MIN_STAGING_SIZE);
+        let buffer = Buffer::new_slice::<u8>(
+            self.memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            capacity,
+        )
+        .map_err(RendererError::buffer)?;
+        self.buffers[self.slot] = Some(buffer.clone());
+
+        Ok(buffer.slice(0..len))
+    }
+
+    /// 모든 슬롯을 버림 (업로드 큐 패밀리가 바뀌었을 때)
+    pub fn clear(&mut self) {
+        self.buffers.fill(None);
+    }
+}
@@ -0,0 +1,132 @@
+use std::time::{Duration, Instant};
+
+use transparent_text_vulkan::{FrameStats, GlyphCacheStats};
+
+// 통계를 모아 평균을 내는 간격 (숫자가 읽을 수 있을 만큼만 바뀌도록)
+const UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 한 갱신 간격 동안의 평균 프레임 통계
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameSummary {
+    pub fps: f32,
+    /// 프레임 사이 간격 (밀리초)
+    pub frame_ms: f32,
+    /// 명령을 기록하고 제출하는 데 걸린 CPU 시간 (밀리초, 펜스와 스왑체인 대기 제외)
+    pub cpu_ms: f32,
+    /// 구간별 GPU 시간 (밀리초, 타임스탬프를 지원하지 않으면 비어 있음)
+    pub gpu_passes: Vec<(&'static str, f32)>,
+    /// 요약을 만들 때의 글리프 아틀라스 캐시 통계
+    pub glyph_cache: GlyphCacheStats,
+}
+
+impl FrameSummary {
+    /// 통계 표시에 쓰는 요약 (GPU 시간이 있으면 둘째 줄에 구간별로, 마지막 줄은 글리프 캐시)
+    pub fn text(&self) -> String {
+        let mut text = format!(
+            "FPS {:.0} · 프레임 {:.2} ms · CPU {:.2} ms",
+            self.fps, self.frame_ms, self.cpu_ms
+        );
+        if !self.gpu_passes.is_empty() {
+            let total: f32 = self.gpu_passes.iter().map(|(_, ms)| ms).sum();
+            let passes: Vec<String> = self
+                .gpu_passes
+                .iter()
+                .map(|(name, ms)| format!("{name} {ms:.2}"))
+                .collect();
+            text.push_str(&format!("\nGPU {total:.2} ms ({})", passes.join(" · ")));
+        }
+        let cache = &self.glyph_cache;
+        let lookups = (cache.hits + cache.misses).max(1);
+        text.push_str(&format!(
+            "\n글리프 {}개 · 적중 {:.1}% · 제거 {}",
+            cache.glyphs,
+            cache.hits as f32 * 100.0 / lookups as f32,
+            cache.evictions
+        ));
+        text
+    }
+}
+
+/// 그린 프레임 수와 CPU/GPU 시간을 모아 일정 간격마다 평균을 냄 (통계 표시용)
+pub struct FrameCounter {
+    since: Instant,
+    frames: u32,
+    cpu_time: Duration,
+    // 구간별 GPU 시간 합과 GPU 시간을 받은 프레임 수
+    gpu_passes: Vec<(&'static str, Duration)>,
+    gpu_frames: u32,
+    summary: Option<FrameSummary>,
+}
+
+impl FrameCounter {
+    pub fn new() -> Self {
+        Self {
+            since: Instant::now(),
+            frames: 0,
+            cpu_time: Duration::ZERO,
+            gpu_passes: Vec::new(),
+            gpu_frames: 0,
+            summary: None,
+        }
+    }
+
+    /// 프레임 하나를 기록하고, 갱신 간격이 지났으면 새 요약을 만듦
+    ///
+    /// `gpu`는 `GpuProfiler::stats` (몇 프레임 늦은 값이어도 평균에는 영향이 작음)
+    /// `glyph_cache`는 요약에 그대로 담아 표시가 요약 간격마다만 바뀌게 함
+    pub fn record(
+        &mut self,
+        cpu_time: Duration,
+        gpu: Option<&FrameStats>,
+        glyph_cache: GlyphCacheStats,
+    ) {
+        self.frames += 1;
+        self.cpu_time += cpu_time;
+        if let Some(gpu) = gpu {
+            for pass in &gpu.passes {
+                let total = self
+                    .gpu_passes
+                    .iter_mut()
+                    .find(|(name, _)| *name == pass.name);
+                match total {
+                    Some((_, total)) => *total += pass.gpu_time,
+                    None => self.gpu_passes.push((pass.name, pass.gpu_time)),
+                }
+            }
+            self.gpu_frames += 1;
+        }
+
+        let elapsed = self.since.elapsed();
+        if elapsed < UPDATE_INTERVAL {
+            return;
+        }
+        let frames = self.frames as f32;
+        let gpu_frames = self.gpu_frames.max(1) as f32;
+        self.summary = Some(FrameSummary {
+            fps: frames / elapsed.as_secs_f32(),
+            frame_ms: elapsed.as_secs_f32() * 1000.0 / frames,
+            cpu_ms: self.cpu_time.as_secs_f32() * 1000.0 / frames,
+            gpu_passes: self
+                .gpu_passes
+                .iter()
+                .map(|&(name, total)| (name, total.as_secs_f32() * 1000.0 / gpu_frames))
+                .collect(),
+            glyph_cache,
+        });
+        self.reset();
+    }
+
+    /// 마지막 요약 (첫 갱신 간격이 지나기 전에는 None)
+    pub fn summary(&self) -> Option<&FrameSummary> {
+        self.summary.as_ref()
+    }
+
+    /// 모은 값을 버리고 다시 셈 (표시를 켤 때 꺼져 있던 동안의 간격이 섞이지 않도록)
+    pub fn reset(&mut self) {
+        self.since = Instant::now();
+        self.frames = 0;
+        self.cpu_time = Duration::ZERO;
+        self.gpu_passes.clear();
+        self.gpu_frames = 0;
+    }
+}
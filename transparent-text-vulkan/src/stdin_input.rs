@@ -0,0 +1,65 @@
+use std::{
+    io::BufRead,
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+/// 덧붙이기 모드에서 남겨 둘 최대 줄 수 (오래된 줄부터 버림)
+pub const MAX_APPENDED_LINES: usize = 20;
+
+/// 표준 입력의 한 줄이 표시 텍스트를 바꾸는 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StdinMode {
+    /// 새 줄이 텍스트 전체를 대신함
+    Replace,
+    /// 새 줄을 끝에 덧붙임 (`tail -f`처럼 최근 줄만 유지)
+    Append,
+}
+
+/// 별도 스레드에서 표준 입력을 줄 단위로 읽어 이벤트 루프로 넘김
+///
+/// 읽기는 블로킹이므로 스레드에서 하고, 이벤트 루프는 프레임마다 쌓인 줄만 가져갑니다.
+/// 입력이 끝나면(EOF) 스레드가 끝나고 마지막 텍스트가 그대로 남습니다.
+pub struct StdinLines {
+    mode: StdinMode,
+    lines: Receiver<String>,
+}
+
+impl StdinLines {
+    pub fn spawn(mode: StdinMode) -> Self {
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { mode, lines }
+    }
+
+    /// 마지막 확인 이후 들어온 줄을 텍스트에 반영하고, 바뀌었는지 돌려줌
+    pub fn apply(&self, text: &mut String) -> bool {
+        let mut changed = false;
+        for line in self.lines.try_iter() {
+            match self.mode {
+                StdinMode::Replace => text.clear(),
+                StdinMode::Append if !text.is_empty() => text.push('\n'),
+                StdinMode::Append => {}
+            }
+            text.push_str(line.trim_end_matches('\r'));
+            changed = true;
+        }
+
+        if changed && self.mode == StdinMode::Append {
+            let excess = text.lines().count().saturating_sub(MAX_APPENDED_LINES);
+            if let Some((cut, _)) = excess
+                .checked_sub(1)
+                .and_then(|skip| text.match_indices('\n').nth(skip))
+            {
+                text.drain(..=cut);
+            }
+        }
+        changed
+    }
+}
@@ -0,0 +1,124 @@
+use std::ops::Range;
+
+use crate::effect::TextEffects;
+
+/// 구간에 적용할 스타일 (`None`인 항목은 `TextParams`의 값을 따름)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SpanStyle {
+    /// 글자 색 (RGBA8, 기본 흰색)
+    pub color: Option<[u8; 4]>,
+    /// 구간 투명도 (`TextParams::opacity`에 곱해짐)
+    pub opacity: Option<f32>,
+    /// 효과 조합 (구간 안에서는 `TextParams::effects`를 대신함)
+    pub effects: Option<TextEffects>,
+    /// 글자 크기 (픽셀, 기본은 렌더러의 폰트 크기)
+    pub size: Option<f32>,
+    /// 굵게 (외곽선을 가로로 넓혀 흉내냄)
+    pub bold: Option<bool>,
+    /// 자간 (글자 크기에 대한 배율, 0.1이면 글자마다 크기의 10%를 더 띄움)
+    pub letter_spacing: Option<f32>,
+    /// 줄 간격 배율 (기본은 `LayoutOptions::line_spacing`)
+    pub line_height: Option<f32>,
+    /// 가변 폰트의 굵기 축 `wght` (보통 100 ~ 900, `FontData`로 등록한 가변 폰트에만 적용)
+    pub weight: Option<f32>,
+    /// 가변 폰트의 너비 축 `wdth` (보통 일반 너비에 대한 백분율, 75 ~ 125)
+    pub width: Option<f32>,
+    /// 가변 폰트의 기울기 축 `slnt` (도, 음수면 오른쪽으로 기울어짐)
+    pub slant: Option<f32>,
+}
+
+impl SpanStyle {
+    pub fn color(color: [u8; 4]) -> Self {
+        Self {
+            color: Some(color),
+            ..Self::default()
+        }
+    }
+
+    // 지정된 항목만 덮어씀
+    fn merge(&mut self, other: &SpanStyle) {
+        self.color = other.color.or(self.color);
+        self.opacity = other.opacity.or(self.opacity);
+        self.effects = other.effects.or(self.effects);
+        self.size = other.size.or(self.size);
+        self.bold = other.bold.or(self.bold);
+        self.letter_spacing = other.letter_spacing.or(self.letter_spacing);
+        self.line_height = other.line_height.or(self.line_height);
+        self.weight = other.weight.or(self.weight);
+        self.width = other.width.or(self.width);
+        self.slant = other.slant.or(self.slant);
+    }
+}
+
+/// 텍스트의 바이트 범위와 그 스타일
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleSpan {
+    pub range: Range<usize>,
+    pub style: SpanStyle,
+}
+
+/// 구간별로 색, 투명도, 효과, 크기, 굵기를 다르게 줄 수 있는 텍스트
+///
+/// 구간이 겹치면 나중에 추가한 구간이 우선합니다.
+///
+/// ```ignore
+/// let mut text = StyledText::default();
+/// text.push("투명도: ", SpanStyle::default());
+/// text.push("100%", SpanStyle::color([255, 200, 0, 255]));
+/// text_renderer.set_styled_text(&text)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StyledText {
+    pub text: String,
+    pub spans: Vec<StyleSpan>,
+}
+
+impl StyledText {
+    /// 스타일이 없는 텍스트
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            spans: Vec::new(),
+        }
+    }
+
+    /// 끝에 텍스트를 이어 붙이고 그 구간에 스타일 적용
+    pub fn push(&mut self, text: &str, style: SpanStyle) -> &mut Self {
+        let start = self.text.len();
+        self.text.push_str(text);
+        if style != SpanStyle::default() {
+            self.spans.push(StyleSpan {
+                range: start..self.text.len(),
+                style,
+            });
+        }
+        self
+    }
+
+    /// 기존 텍스트의 바이트 범위에 스타일 적용
+    pub fn span(mut self, range: Range<usize>, style: SpanStyle) -> Self {
+        self.spans.push(StyleSpan { range, style });
+        self
+    }
+
+    /// 바이트 위치의 최종 스타일
+    pub fn style_at(&self, index: usize) -> SpanStyle {
+        let mut style = SpanStyle::default();
+        for span in self.spans.iter().filter(|span| span.range.contains(&index)) {
+            style.merge(&span.style);
+        }
+        style
+    }
+}
+
+impl From<&str> for StyledText {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for StyledText {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
@@ -0,0 +1,434 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use tracing::{info, warn};
+use transparent_text_vulkan::{parse_color, KaraokeSyllable};
+
+use crate::file_watch::FileWatcher;
+
+/// `--subtitle-fade`도 설정 파일의 `subtitle_fade`도 없을 때 자막이 나타나고 사라지는 시간 (초)
+pub const DEFAULT_SUBTITLE_FADE: f32 = 0.25;
+
+/// 자막 하나 (시각은 자막 시계 기준)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleCue {
+    pub start: Duration,
+    pub end: Duration,
+    /// 표시할 텍스트 (마크업)
+    pub text: String,
+    /// `{\k}` 음절 시간 (자막이 나타난 때부터, 범위는 마크업을 해석한 뒤의 텍스트 기준, 없으면 비어 있음)
+    pub karaoke: Vec<KaraokeSyllable>,
+}
+
+/// 시간에 맞춰 자막을 하나씩 보여 주는 대기열 (`--subtitles <파일.srt>`, 제어 메시지 `subtitle`)
+///
+/// 자막 시계는 대기열을 만들 때 0에서 시작합니다. 시작 시각에 자막을 띄우고 끝 시각에 지우며,
+/// 시작과 끝에서 `fade` 동안 서서히 나타나고 사라집니다. 겹치는 자막은 나중에 시작한 것을 보여 줍니다.
+/// SRT 파일은 저장하면 자막 시계를 그대로 두고 다시 읽습니다.
+pub struct SubtitleQueue {
+    // 시작 시각 순서
+    cues: Vec<SubtitleCue>,
+    started: Instant,
+    fade: Duration,
+    // 지금 보이는 자막 (cues의 번호)
+    current: Option<usize>,
+    watcher: Option<FileWatcher>,
+}
+
+impl SubtitleQueue {
+    /// 빈 대기열 (제어 메시지로 자막을 받을 때)
+    ///
+    /// `fade`가 `Duration`으로 나타낼 수 없을 만큼 크면 기본값을 씁니다.
+    pub fn new(fade: f32) -> Self {
+        let fade = Duration::try_from_secs_f32(fade.max(0.0)).unwrap_or_else(|_| {
+            warn!("자막 페이드 시간이 너무 깁니다 ({fade}초), 기본값을 씁니다");
+            Duration::from_secs_f32(DEFAULT_SUBTITLE_FADE)
+        });
+        Self {
+            cues: Vec::new(),
+            started: Instant::now(),
+            fade,
+            current: None,
+            watcher: None,
+        }
+    }
+
+    /// SRT 파일을 읽고 감시를 시작합니다.
+    pub fn load(path: impl Into<PathBuf>, fade: f32) -> Result<Self, String> {
+        let path = path.into();
+        let mut queue = Self::new(fade);
+        queue.cues = read_srt(&path)?;
+        info!("자막: {} ({}개)", path.display(), queue.cues.len());
+        queue.watcher = FileWatcher::new(&path)
+            .map_err(|e| warn!("자막 파일 감시 실패 ({}): {e}", path.display()))
+            .ok();
+        Ok(queue)
+    }
+
+    /// 지금부터 `delay` 뒤에 `duration` 동안 보일 자막을 추가 (이미 끝난 자막은 버림)
+    ///
+    /// 시간이 `Duration`으로 나타낼 수 없을 만큼 크면 자막을 버립니다.
+    pub fn push(&mut self, text: String, delay: f32, duration: f32) {
+        let seconds = |value: f32| Duration::try_from_secs_f32(value.max(0.0));
+        let (Ok(delay), Ok(duration)) = (seconds(delay), seconds(duration)) else {
+            warn!("자막 시간이 너무 깁니다 (delay {delay}초, duration {duration}초)");
+            return;
+        };
+        let elapsed = self.started.elapsed();
+        let start = elapsed.saturating_add(delay);
+        let cue = SubtitleCue {
+            start,
+            end: start.saturating_add(duration),
+            text,
+            karaoke: Vec::new(),
+        };
+        self.cues.retain(|cue| cue.end > elapsed);
+        let index = self.cues.partition_point(|other| other.start <= cue.start);
+        self.cues.insert(index, cue);
+        // 번호가 바뀌었으므로 다음 poll에서 다시 찾음
+        self.current = None;
+    }
+
+    /// 파일이 바뀌었으면 다시 읽고, 보여 줄 자막이 바뀌었으면 true (새 자막은 `current`)
+    pub fn poll(&mut self) -> bool {
+        let reload = self.watcher.as_ref().filter(|watcher| watcher.changed());
+        if let Some(watcher) = reload {
+            // 쓰는 중이라 읽지 못하면 이전 자막 유지 (다음 변경 때 다시 읽음)
+            match read_srt(watcher.path()) {
+                Ok(cues) => {
+                    info!("자막 파일을 다시 읽었습니다 ({}개)", cues.len());
+                    self.cues = cues;
+                    self.current = None;
+                }
+                Err(e) => warn!("{e}"),
+            }
+        }
+
+        let elapsed = self.started.elapsed();
+        let active = self
+            .cues
+            .iter()
+            .rposition(|cue| cue.start <= elapsed && elapsed < cue.end);
+        if active == self.current && reload.is_none() {
+            return false;
+        }
+        self.current = active;
+        true
+    }
+
+    /// 지금 보이는 자막 (자막 사이에는 None)
+    pub fn current(&self) -> Option<&SubtitleCue> {
+        self.current.map(|index| &self.cues[index])
+    }
+
+    /// 지금 보이는 자막의 투명도 (나타나거나 사라지는 중이 아니면 1)
+    pub fn alpha(&self) -> f32 {
+        let Some(cue) = self.current() else {
+            return 1.0;
+        };
+        if self.fade.is_zero() {
+            return 1.0;
+        }
+        let elapsed = self.started.elapsed();
+        let shown = elapsed.saturating_sub(cue.start);
+        let left = cue.end.saturating_sub(elapsed);
+        (shown.min(left).as_secs_f32() / self.fade.as_secs_f32()).min(1.0)
+    }
+
+    /// 자막이 서서히 나타나거나 사라지는 중인지 (프레임마다 다시 그려야 함)
+    pub fn is_fading(&self) -> bool {
+        self.alpha() < 1.0
+    }
+
+    /// 다음에 자막이 나타나거나 사라질 시각
+    ///
+    /// 남은 자막이 없거나 그 시각을 `Instant`로 나타낼 수 없을 만큼 멀면 None입니다.
+    pub fn next_change(&self) -> Option<Instant> {
+        let elapsed = self.started.elapsed();
+        self.cues
+            .iter()
+            .flat_map(|cue| [cue.start, cue.end])
+            .filter(|&time| time > elapsed)
+            .min()
+            .and_then(|time| self.started.checked_add(time))
+    }
+}
+
+fn read_srt(path: &Path) -> Result<Vec<SubtitleCue>, String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("자막 파일을 읽을 수 없습니다 ({}): {e}", path.display()))?;
+    parse_srt(&source).map_err(|e| format!("{} ({})", e, path.display()))
+}
+
+/// SRT 자막 (번호, `00:00:01,000 --> 00:00:04,000`, 텍스트 줄, 빈 줄의 반복)
+///
+/// 번호 줄은 없어도 되고, 블록 사이의 빈 줄이 빠져도 다음 시간 줄에서 새 자막을 시작합니다.
+/// `<b>`와 `<font color="...">`는 마크업으로 바꾸며 그 밖의 태그는 버립니다.
+/// ASS 노래방 지정 `{\k50}`(센티초)은 뒤따르는 음절의 길이로 읽고, 그 밖의 `{...}` 지정은 버립니다.
+pub fn parse_srt(source: &str) -> Result<Vec<SubtitleCue>, String> {
+    let source = source.trim_start_matches('\u{feff}');
+    let lines: Vec<&str> = source.lines().map(str::trim_end).collect();
+    let mut cues = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        // 블록 사이의 빈 줄 건너뜀
+        if lines[index].is_empty() {
+            index += 1;
+            continue;
+        }
+        if !is_timing(lines[index]) {
+            index += 1;
+        }
+        let timing = lines.get(index).copied().unwrap_or_default();
+        index += 1;
+        let number = cues.len() + 1;
+        let (start, end) = parse_timing(timing)
+            .ok_or_else(|| format!("{number}번째 자막의 시간을 읽을 수 없습니다: {timing}"))?;
+        let mut cue = CueBuilder::default();
+        while let Some(&line) = lines.get(index) {
+            if line.is_empty() || starts_cue(&lines[index..]) {
+                break;
+            }
+            cue.push_line(line);
+            index += 1;
+        }
+        cues.push(cue.finish(start, end));
+    }
+    cues.sort_by_key(|cue| cue.start);
+    Ok(cues)
+}
+
+fn is_timing(line: &str) -> bool {
+    line.contains("-->")
+}
+
+// 다음 자막의 시간 줄이나 그 앞의 번호 줄인지 (빈 줄 없이 이어진 자막)
+fn starts_cue(lines: &[&str]) -> bool {
+    match lines {
+        [line, ..] if is_timing(line) => true,
+        [line, next, ..] => is_timing(next) && line.trim().parse::<u32>().is_ok(),
+        _ => false,
+    }
+}
+
+// `00:00:01,000 --> 00:00:04,000` (끝 시각 뒤의 위치 지정은 무시)
+fn parse_timing(line: &str) -> Option<(Duration, Duration)> {
+    let (start, end) = line.split_once("-->")?;
+    let end = end.split_whitespace().next()?;
+    Some((parse_time(start.trim())?, parse_time(end)?))
+}
+
+// `시:분:초,밀리초` (밀리초 앞에 `.`도 허용, 소수 부분은 1~3자리로 `,5`는 500밀리초)
+fn parse_time(text: &str) -> Option<Duration> {
+    let (clock, fraction) = text.split_once([',', '.']).unwrap_or((text, "0"));
+    let mut parts = clock.split(':').map(|part| part.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() {
+        return None;
+    }
+    if !(1..=3).contains(&fraction.len()) || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let millis = fraction.parse::<u64>().ok()? * 10u64.pow(3 - fraction.len() as u32);
+    let seconds = hours
+        .checked_mul(3600)?
+        .checked_add(minutes.checked_mul(60)?)?
+        .checked_add(seconds)?;
+    Duration::from_secs(seconds).checked_add(Duration::from_millis(millis))
+}
+
+// 자막 한 개의 SRT 줄을 마크업으로 바꾸며 `{\k}` 음절 시간을 모음
+#[derive(Default)]
+struct CueBuilder {
+    markup: String,
+    // 마크업을 해석한 뒤의 텍스트 길이 (바이트, 음절 범위의 기준)
+    plain: usize,
+    karaoke: Vec<KaraokeSyllable>,
+    // 열린 음절의 시작 위치와 길이 (초), 그 음절이 시작하는 시각
+    syllable: Option<(usize, f32)>,
+    time: f32,
+    // 열린 font 태그마다 color 태그로 바꿨는지 (닫는 태그의 짝을 맞추기 위해)
+    fonts: Vec<bool>,
+    // 줄을 하나 이상 넣었는지 (다음 줄 앞에 줄바꿈)
+    has_lines: bool,
+}
+
+impl CueBuilder {
+    fn push_line(&mut self, line: &str) {
+        if self.has_lines {
+            self.push_text("\n");
+        }
+        self.has_lines = true;
+        let mut rest = line;
+        while let Some(start) = rest.find(['<', '{']) {
+            self.push_text(&rest[..start]);
+            let close = if rest[start..].starts_with('<') {
+                '>'
+            } else {
+                '}'
+            };
+            let Some(end) = rest[start..].find(close).map(|end| start + end) else {
+                rest = &rest[start..];
+                break;
+            };
+            let tag = rest[start + 1..end].trim().to_ascii_lowercase();
+            if close == '}' {
+                self.push_overrides(&rest[start + 1..end]);
+            } else if tag == "b" || tag == "/b" {
+                self.markup.push_str(&format!("<{tag}>"));
+            } else if tag == "/font" {
+                if self.fonts.pop() == Some(true) {
+                    self.markup.push_str("</color>");
+                }
+            } else if let Some(attributes) = tag.strip_prefix("font") {
+                let color = font_color(attributes);
+                if let Some(color) = color {
+                    self.markup.push_str(&format!("<color={color}>"));
+                }
+                self.fonts.push(color.is_some());
+            }
+            rest = &rest[end + 1..];
+        }
+        self.push_text(rest);
+    }
+
+    fn push_text(&mut self, text: &str) {
+        self.markup.push_str(text);
+        self.plain += text.len();
+    }
+
+    // `\k50\an8`처럼 이어진 ASS 지정 중 `\k`, `\kf`, `\ko`, `\K`만 읽음
+    fn push_overrides(&mut self, overrides: &str) {
+        for item in overrides.split('\\') {
+            let digits = ["kf", "ko", "k", "K"]
+                .iter()
+                .find_map(|prefix| item.strip_prefix(prefix));
+            if let Some(centiseconds) = digits.and_then(|digits| digits.trim().parse::<f32>().ok())
+            {
+                self.end_syllable();
+                self.syllable = Some((self.plain, centiseconds.max(0.0) / 100.0));
+            }
+        }
+    }
+
+    fn end_syllable(&mut self) {
+        if let Some((start, duration)) = self.syllable.take() {
+            self.karaoke.push(KaraokeSyllable {
+                range: start..self.plain,
+                start: self.time,
+                end: self.time + duration,
+            });
+            self.time += duration;
+        }
+    }
+
+    fn finish(mut self, start: Duration, end: Duration) -> SubtitleCue {
+        self.end_syllable();
+        SubtitleCue {
+            start,
+            end,
+            text: self.markup,
+            karaoke: self.karaoke,
+        }
+    }
+}
+
+// font 태그의 `color="#ff0000"` (마크업이 읽을 수 없는 색 이름은 무시)
+fn font_color(attributes: &str) -> Option<&str> {
+    let (_, value) = attributes.split_once("color=")?;
+    let color = value
+        .trim_start_matches(['"', '\''])
+        .split(['"', '\'', ' '])
+        .next()?;
+    parse_color(color).map(|_| color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(cues: &[SubtitleCue]) -> Vec<&str> {
+        cues.iter().map(|cue| cue.text.as_str()).collect()
+    }
+
+    #[test]
+    fn fraction_is_scaled_to_milliseconds() {
+        let millis = |text| parse_time(text).map(|time| time.as_millis());
+        assert_eq!(millis("00:00:01,5"), Some(1500));
+        assert_eq!(millis("00:00:01,05"), Some(1050));
+        assert_eq!(millis("00:00:01.123"), Some(1123));
+        assert_eq!(millis("01:02:03"), Some(3_723_000));
+        assert_eq!(millis("00:00:01,1234"), None);
+        assert_eq!(millis("00:00:01,"), None);
+        assert_eq!(millis("00:00:01,+5"), None);
+    }
+
+    #[test]
+    fn malformed_timestamps_are_rejected() {
+        assert_eq!(parse_time("00:01,000"), None);
+        assert_eq!(parse_time("00:00:00:01,000"), None);
+        assert_eq!(parse_time("aa:00:01,000"), None);
+        assert_eq!(parse_time("99999999999999999999:00:00"), None);
+        assert!(parse_srt("1\n00:00:01,000 -> 00:00:02,000\nA\n").is_err());
+        assert!(parse_srt("1\n00:00:01,000 --> later\nA\n").is_err());
+    }
+
+    #[test]
+    fn crlf_input() {
+        let source = "\u{feff}1\r\n00:00:01,000 --> 00:00:02,500\r\nHello\r\nworld\r\n\r\n\
+                      2\r\n00:00:03,000 --> 00:00:04,000\r\nBye\r\n";
+        let cues = parse_srt(source).unwrap();
+        assert_eq!(texts(&cues), ["Hello\nworld", "Bye"]);
+        assert_eq!(cues[0].start, Duration::from_millis(1000));
+        assert_eq!(cues[0].end, Duration::from_millis(2500));
+    }
+
+    #[test]
+    fn missing_blank_lines_start_a_new_cue() {
+        let source = "1\n00:00:01,000 --> 00:00:02,000\nA\n\
+                      2\n00:00:03,000 --> 00:00:04,000\nB\n\
+                      00:00:05,000 --> 00:00:06,000\nC";
+        let cues = parse_srt(source).unwrap();
+        assert_eq!(texts(&cues), ["A", "B", "C"]);
+        assert_eq!(cues[2].start, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn cues_are_sorted_by_start() {
+        let source = "00:00:05,000 --> 00:00:06,000\nB\n\n00:00:01,000 --> 00:00:02,000\nA\n";
+        assert_eq!(texts(&parse_srt(source).unwrap()), ["A", "B"]);
+    }
+
+    #[test]
+    fn push_keeps_cues_sorted_and_drops_expired() {
+        let mut queue = SubtitleQueue::new(0.0);
+        queue.push("old".into(), 0.0, 1.0);
+        // 시계를 5초 앞으로 돌려 첫 자막을 끝난 것으로 만듦
+        queue.started = queue.started.checked_sub(Duration::from_secs(5)).unwrap();
+        queue.push("late".into(), 10.0, 1.0);
+        queue.push("soon".into(), 1.0, 1.0);
+        assert_eq!(texts(&queue.cues), ["soon", "late"]);
+    }
+
+    #[test]
+    fn out_of_range_times_do_not_panic() {
+        let mut queue = SubtitleQueue::new(f32::INFINITY);
+        assert_eq!(queue.fade, Duration::from_secs_f32(DEFAULT_SUBTITLE_FADE));
+        queue.push("inf".into(), f32::INFINITY, 1.0);
+        queue.push("huge".into(), 1e30, 1.0);
+        queue.push("long".into(), 0.0, f32::MAX);
+        queue.push("nan".into(), f32::NAN, 1.0);
+        assert_eq!(texts(&queue.cues), ["nan"]);
+    }
+
+    #[test]
+    fn far_future_cues_have_no_next_change() {
+        let mut queue = SubtitleQueue::new(0.0);
+        queue.push("delay".into(), 1e19, 1.0);
+        queue.push("duration".into(), 0.0, 1.8e19);
+        assert_eq!(texts(&queue.cues), ["duration", "delay"]);
+        assert_eq!(queue.next_change(), None);
+    }
+}
@@ -0,0 +1,150 @@
+use std::time::{Duration, SystemTime};
+
+use starship_battery::{units::ratio::percent, Manager, State};
+use sysinfo::System;
+
+use tracing::warn;
+
+use crate::text_source::TextSource;
+
+/// `--stats-interval`도 설정 파일의 `system_stats_interval`도 없을 때의 갱신 간격 (초)
+pub const DEFAULT_STATS_INTERVAL: f32 = 1.0;
+
+// CPU 사용률은 두 번 갱신한 사이의 값이므로 너무 짧으면 의미가 없음
+const MIN_STATS_INTERVAL: f32 = 0.25;
+
+// 한 시간보다 길면 통계라고 보기 어려우므로 잘라냄
+const MAX_STATS_INTERVAL: f32 = 3600.0;
+
+// 템플릿에 쓸 수 있는 이름
+const PLACEHOLDERS: [&str; 6] = [
+    "cpu",
+    "mem_used",
+    "mem_total",
+    "mem_percent",
+    "battery",
+    "battery_state",
+];
+
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// 템플릿의 `{이름}`을 시스템 통계로 바꾼 텍스트 (system-stats 기능)
+///
+/// - `{cpu}`: 전체 CPU 사용률 (%)
+/// - `{mem_used}`, `{mem_total}`: 사용 중인 메모리와 전체 메모리 (GiB, `7.8G`)
+/// - `{mem_percent}`: 메모리 사용률 (%)
+/// - `{battery}`, `{battery_state}`: 첫 번째 배터리의 충전량 (%)과 상태 (배터리가 없으면 `-`)
+///
+/// 예: `"CPU {cpu}%  RAM {mem_used}/{mem_total}"`
+pub struct SystemStatsSource {
+    template: String,
+    interval: Duration,
+    system: System,
+    // 템플릿에 배터리 이름이 있을 때만 (배터리 정보를 읽을 수 없으면 None)
+    battery: Option<Manager>,
+}
+
+impl SystemStatsSource {
+    /// 템플릿에 알 수 없는 이름이 있으면 오류 (`interval`은 초)
+    pub fn new(template: impl Into<String>, interval: f32) -> Result<Self, String> {
+        let template = template.into();
+        let names = placeholders(&template);
+        if let Some(name) = names
+            .iter()
+            .find(|name| !PLACEHOLDERS.contains(&name.as_str()))
+        {
+            return Err(format!(
+                "알 수 없는 통계 이름입니다: {{{name}}} (사용할 수 있는 이름: {})",
+                PLACEHOLDERS.join(", ")
+            ));
+        }
+        let uses_battery = names.iter().any(|name| name.starts_with("battery"));
+
+        // NaN이면 clamp를 지나도 NaN이므로 기본 간격
+        let interval =
+            Duration::try_from_secs_f32(interval.clamp(MIN_STATS_INTERVAL, MAX_STATS_INTERVAL))
+                .unwrap_or_else(|_| {
+                    warn!("통계 갱신 간격이 올바르지 않아 기본값을 씁니다: {interval}");
+                    Duration::from_secs_f32(DEFAULT_STATS_INTERVAL)
+                });
+
+        let mut system = System::new();
+        system.refresh_cpu();
+        Ok(Self {
+            interval,
+            template,
+            system,
+            battery: uses_battery.then(|| Manager::new().ok()).flatten(),
+        })
+    }
+
+    // 첫 번째 배터리의 충전량 (%)과 상태
+    fn battery(&self) -> Option<(f32, &'static str)> {
+        let battery = self.battery.as_ref()?.batteries().ok()?.flatten().next()?;
+        let state = match battery.state() {
+            State::Charging => "충전 중",
+            State::Discharging => "방전 중",
+            State::Full => "완충",
+            State::Empty => "방전됨",
+            _ => "알 수 없음",
+        };
+        Some((battery.state_of_charge().get::<percent>(), state))
+    }
+}
+
+impl TextSource for SystemStatsSource {
+    fn text(&mut self) -> String {
+        self.system.refresh_cpu();
+        self.system.refresh_memory();
+        let cpu = self.system.global_cpu_info().cpu_usage();
+        let used = self.system.used_memory() as f64;
+        let total = self.system.total_memory() as f64;
+        let battery = self.battery();
+
+        interpolate(&self.template, |name| match name {
+            "cpu" => format!("{cpu:.0}"),
+            "mem_used" => format!("{:.1}G", used / GIB),
+            "mem_total" => format!("{:.1}G", total / GIB),
+            "mem_percent" if total > 0.0 => format!("{:.0}", used / total * 100.0),
+            "battery" => battery.map_or("-".to_owned(), |(charge, _)| format!("{charge:.0}")),
+            "battery_state" => battery.map_or("-", |(_, state)| state).to_owned(),
+            _ => "-".to_owned(),
+        })
+    }
+
+    fn next_update(&self, now: SystemTime) -> SystemTime {
+        now + self.interval
+    }
+}
+
+// 템플릿의 `{이름}`들 (interpolate와 같은 규칙)
+fn placeholders(template: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    interpolate(template, |name| {
+        names.push(name.to_owned());
+        String::new()
+    });
+    names
+}
+
+// `{이름}`을 값으로 바꿈 (닫는 중괄호가 없는 `{`는 글자 그대로)
+fn interpolate(template: &str, mut value: impl FnMut(&str) -> String) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                text.push_str(&value(&after[..end]));
+                rest = &after[end + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
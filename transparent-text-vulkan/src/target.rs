@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use vulkano::{
+    format::Format,
+    image::SampleCount,
+    pipeline::graphics::subpass::{PipelineRenderingCreateInfo, PipelineSubpassType},
+    render_pass::{RenderPass, Subpass},
+};
+
+use crate::error::RendererError;
+
+/// 파이프라인을 맞출 그리기 대상
+///
+/// render pass의 서브패스에 그리거나, dynamic rendering(`begin_rendering`)으로
+/// render pass와 framebuffer 없이 색 첨부 하나에 바로 그립니다.
+/// `Arc<RenderPass>`는 첫 번째 서브패스로 바뀝니다.
+#[derive(Debug, Clone)]
+pub enum RenderTarget {
+    /// render pass의 서브패스
+    Subpass(Subpass),
+    /// dynamic rendering으로 그리는 색 첨부 하나의 포맷과 샘플 수
+    ///
+    /// 장치에서 `dynamic_rendering` 기능을 켜야 합니다.
+    Dynamic {
+        format: Format,
+        samples: SampleCount,
+    },
+}
+
+impl RenderTarget {
+    /// 첫 번째 색 첨부의 포맷과 샘플 수
+    pub fn color_attachment(&self) -> Result<(Format, SampleCount), RendererError> {
+        match self {
+            RenderTarget::Subpass(subpass) => {
+                let attachments = subpass.render_pass().attachments();
+                subpass
+                    .subpass_desc()
+                    .color_attachments
+                    .first()
+                    .and_then(|reference| reference.as_ref())
+                    .map(|reference| &attachments[reference.attachment as usize])
+                    .map(|attachment| (attachment.format, attachment.samples))
+                    .ok_or_else(|| RendererError::pipeline("서브패스에 색 첨부가 없습니다"))
+            }
+            RenderTarget::Dynamic { format, samples } => Ok((*format, *samples)),
+        }
+    }
+
+    /// dynamic rendering 대상인지
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self, RenderTarget::Dynamic { .. })
+    }
+
+    pub(crate) fn color_attachment_count(&self) -> u32 {
+        match self {
+            RenderTarget::Subpass(subpass) => subpass.num_color_attachments(),
+            RenderTarget::Dynamic { .. } => 1,
+        }
+    }
+
+    /// 래스터화 샘플 수 (멀티샘플 대상이면 같은 수로 MSAA)
+    pub fn samples(&self) -> SampleCount {
+        match self {
+            RenderTarget::Subpass(subpass) => subpass.num_samples().unwrap_or(SampleCount::Sample1),
+            RenderTarget::Dynamic { samples, .. } => *samples,
+        }
+    }
+
+    /// `GraphicsPipelineCreateInfo::subpass`에 넣을 값 (직접 파이프라인을 만들 때)
+    pub fn pipeline_subpass(&self) -> PipelineSubpassType {
+        match self {
+            RenderTarget::Subpass(subpass) => subpass.clone().into(),
+            RenderTarget::Dynamic { format, .. } => PipelineRenderingCreateInfo {
+                color_attachment_formats: vec![Some(*format)],
+                ..Default::default()
+            }
+            .into(),
+        }
+    }
+}
+
+impl From<Subpass> for RenderTarget {
+    fn from(subpass: Subpass) -> Self {
+        RenderTarget::Subpass(subpass)
+    }
+}
+
+impl From<Arc<RenderPass>> for RenderTarget {
+    fn from(render_pass: Arc<RenderPass>) -> Self {
+        RenderTarget::Subpass(render_pass.first_subpass())
+    }
+}
@@ -0,0 +1,392 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use chrono::{
+    format::{Item, StrftimeItems},
+    Local,
+};
+
+/// 시간이 지나면 바뀌는 표시 텍스트 (시계, 시스템 통계 등)
+///
+/// 이벤트 루프가 `next_update` 시각이 지나면 `text`로 새 텍스트를 받아 표시합니다.
+/// 텍스트 파일이나 표준 입력처럼 설정 파일의 `text`보다 우선합니다.
+pub trait TextSource {
+    /// 지금 표시할 텍스트 (마크업 사용 가능)
+    fn text(&mut self) -> String;
+
+    /// `now` 뒤에 텍스트가 처음 바뀔 수 있는 시각
+    fn next_update(&self, now: SystemTime) -> SystemTime;
+
+    /// 타이머를 시작하거나 일시 정지 (타이머가 아니면 무시하고 false)
+    fn toggle_timer(&mut self) -> bool {
+        false
+    }
+
+    /// 타이머를 처음 상태로 되돌리고 멈춤 (타이머가 아니면 무시하고 false)
+    fn reset_timer(&mut self) -> bool {
+        false
+    }
+}
+
+/// strftime 형식으로 만든 현재 지역 시각 (`%H:%M:%S`, `%Y-%m-%d (%a)` 등)
+///
+/// 형식에 마크업을 섞을 수 있고 (`<b>%H:%M</b>:%S`), 매초 시계의 초가 바뀔 때 갱신합니다.
+pub struct ClockSource {
+    format: String,
+}
+
+impl ClockSource {
+    /// 형식에 알 수 없는 `%` 지정자가 있으면 오류
+    pub fn new(format: impl Into<String>) -> Result<Self, String> {
+        let format = format.into();
+        // chrono는 틀린 형식을 출력할 때 패닉하므로 미리 확인
+        if StrftimeItems::new(&format).any(|item| matches!(item, Item::Error)) {
+            return Err(format!("잘못된 시계 형식입니다: {format}"));
+        }
+        Ok(Self { format })
+    }
+}
+
+impl TextSource for ClockSource {
+    fn text(&mut self) -> String {
+        Local::now().format(&self.format).to_string()
+    }
+
+    // 다음 정각 초 (초 단위 형식이 없어도 분이 바뀌는 순간을 놓치지 않도록)
+    fn next_update(&self, now: SystemTime) -> SystemTime {
+        let subsec = now
+            .duration_since(UNIX_EPOCH)
+            .map_or(Duration::ZERO, |elapsed| {
+                Duration::from_nanos(elapsed.subsec_nanos() as u64)
+            });
+        now + (Duration::from_secs(1) - subsec)
+    }
+}
+
+/// 소수점 아래 자릿수의 최댓값 (밀리초)
+pub const MAX_TIMER_DECIMALS: u32 = 3;
+
+// 멈춘 타이머는 키를 눌러야 바뀌므로 가끔만 확인
+const IDLE_TIMER_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 카운트다운 (`--countdown 10:00`) 또는 스톱워치 (`--stopwatch`)
+///
+/// `분:초.밀리초` 형식으로 표시하고 (한 시간 이상이면 `시:분:초`), 표시하는 문자열이 바뀌는
+/// 순간에만 갱신합니다. 카운트다운은 남은 시간을 올림해 0이 되는 순간 끝나고 멈춥니다.
+/// 시작할 때 바로 흐르며, 리셋하면 처음 상태로 돌아가 멈춥니다.
+pub struct TimerSource {
+    // 카운트다운이면 전체 시간
+    countdown: Option<Duration>,
+    decimals: u32,
+    // 마지막으로 일시 정지하기 전까지 흐른 시간과 다시 시작한 시각 (멈춰 있으면 None)
+    accumulated: Duration,
+    started: Option<Instant>,
+}
+
+impl TimerSource {
+    /// `시:분:초`, `분:초`, `초` 형식의 시간부터 세는 카운트다운 (마지막 자리는 소수 가능)
+    pub fn countdown(duration: &str, decimals: u32) -> Result<Self, String> {
+        let total = parse_duration(duration).ok_or_else(|| {
+            format!("잘못된 카운트다운 시간입니다: {duration} (예: 10:00, 1:30:00, 90)")
+        })?;
+        Ok(Self::new(Some(total), decimals))
+    }
+
+    pub fn stopwatch(decimals: u32) -> Self {
+        Self::new(None, decimals)
+    }
+
+    fn new(countdown: Option<Duration>, decimals: u32) -> Self {
+        Self {
+            countdown,
+            decimals: decimals.min(MAX_TIMER_DECIMALS),
+            accumulated: Duration::ZERO,
+            started: Some(Instant::now()),
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        let elapsed =
+            self.accumulated + self.started.map_or(Duration::ZERO, |start| start.elapsed());
+        self.countdown.map_or(elapsed, |total| elapsed.min(total))
+    }
+
+    // 표시 단위 (소수점 아래 마지막 자리, 나노초)
+    fn unit(&self) -> u128 {
+        10u128.pow(9 - self.decimals)
+    }
+
+    // 흐르는 중이면 표시하는 문자열이 다음에 바뀔 때까지 남은 시간
+    fn until_change(&self) -> Option<Duration> {
+        self.started?;
+        let unit = self.unit();
+        let elapsed = self.elapsed().as_nanos();
+        let nanos = match self.countdown {
+            // 남은 시간을 올림해 표시하므로 한 단위 아래로 내려가는 순간
+            Some(total) => {
+                let remaining = total.as_nanos() - elapsed;
+                remaining - (remaining.div_ceil(unit).checked_sub(1)? * unit)
+            }
+            None => (elapsed / unit + 1) * unit - elapsed,
+        };
+        Some(Duration::from_nanos(nanos as u64))
+    }
+}
+
+impl TextSource for TimerSource {
+    fn text(&mut self) -> String {
+        let elapsed = self.elapsed();
+        let unit = self.unit();
+        let (ticks, hours) = match self.countdown {
+            Some(total) => {
+                // 끝났으면 0에서 멈춤
+                if elapsed >= total && self.started.is_some() {
+                    self.accumulated = total;
+                    self.started = None;
+                }
+                let remaining = (total - elapsed).as_nanos();
+                (remaining.div_ceil(unit), total.as_secs() >= 3600)
+            }
+            None => (elapsed.as_nanos() / unit, elapsed.as_secs() >= 3600),
+        };
+        format_timer(ticks * unit, self.decimals, hours)
+    }
+
+    fn next_update(&self, now: SystemTime) -> SystemTime {
+        now + self.until_change().unwrap_or(IDLE_TIMER_INTERVAL)
+    }
+
+    fn toggle_timer(&mut self) -> bool {
+        match self.started.take() {
+            Some(start) => self.accumulated += start.elapsed(),
+            // 끝난 카운트다운은 처음부터 다시
+            None => {
+                if self
+                    .countdown
+                    .is_some_and(|total| self.accumulated >= total)
+                {
+                    self.accumulated = Duration::ZERO;
+                }
+                self.started = Some(Instant::now());
+            }
+        }
+        true
+    }
+
+    fn reset_timer(&mut self) -> bool {
+        self.accumulated = Duration::ZERO;
+        self.started = None;
+        true
+    }
+}
+
+// `시:분:초`, `분:초`, `초` (마지막 자리만 소수 가능)
+fn parse_duration(text: &str) -> Option<Duration> {
+    let parts: Vec<&str> = text.trim().split(':').collect();
+    let (last, rest) = parts.split_last()?;
+    if rest.len() > 2 {
+        return None;
+    }
+    let mut seconds = 0.0;
+    for part in rest {
+        seconds = (seconds + part.parse::<u64>().ok()? as f64) * 60.0;
+    }
+    let last: f64 = last.parse().ok()?;
+    if !last.is_finite() || last < 0.0 {
+        return None;
+    }
+    Duration::try_from_secs_f64(seconds + last).ok()
+}
+
+// 나노초를 `[시:]분:초[.소수]`로
+fn format_timer(nanos: u128, decimals: u32, hours: bool) -> String {
+    let seconds = (nanos / 1_000_000_000) as u64;
+    let (h, m, s) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    let mut text = if hours {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{:02}:{s:02}", seconds / 60)
+    };
+    if decimals > 0 {
+        let fraction = nanos % 1_000_000_000 / 10u128.pow(9 - decimals);
+        text.push_str(&format!(".{fraction:0width$}", width = decimals as usize));
+    }
+    text
+}
+
+/// 여러 소스의 텍스트를 한 줄씩 이어 붙임 (`--clock`, `--system-stats`, 타이머를 함께 줄 때)
+///
+/// 소스마다 자기 갱신 시각에만 텍스트를 다시 만들고, 그 사이에는 마지막 텍스트를 씁니다.
+pub struct SourceLines {
+    // 소스, 마지막 텍스트, 다음 갱신 시각
+    sources: Vec<(Box<dyn TextSource>, String, SystemTime)>,
+}
+
+impl SourceLines {
+    pub fn new(sources: Vec<Box<dyn TextSource>>) -> Self {
+        Self {
+            sources: sources
+                .into_iter()
+                .map(|source| (source, String::new(), UNIX_EPOCH))
+                .collect(),
+        }
+    }
+
+    fn update_timers(&mut self, mut update: impl FnMut(&mut dyn TextSource) -> bool) -> bool {
+        let mut changed = false;
+        for (source, _, due) in &mut self.sources {
+            if update(source.as_mut()) {
+                *due = UNIX_EPOCH;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+impl TextSource for SourceLines {
+    fn text(&mut self) -> String {
+        let now = SystemTime::now();
+        for (source, text, due) in &mut self.sources {
+            if now >= *due {
+                *text = source.text();
+                *due = source.next_update(now);
+            }
+        }
+        let lines: Vec<&str> = self
+            .sources
+            .iter()
+            .map(|(_, text, _)| text.as_str())
+            .collect();
+        lines.join("\n")
+    }
+
+    fn next_update(&self, now: SystemTime) -> SystemTime {
+        self.sources
+            .iter()
+            .map(|(_, _, due)| *due)
+            .min()
+            .unwrap_or(now)
+    }
+
+    // 타이머가 바뀐 소스는 바로 다시 만들도록 갱신 시각을 지움
+    fn toggle_timer(&mut self) -> bool {
+        self.update_timers(|source| source.toggle_timer())
+    }
+
+    fn reset_timer(&mut self) -> bool {
+        self.update_timers(|source| source.reset_timer())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 멈춘 채로 elapsed만큼 흐른 타이머
+    fn paused(countdown: Option<Duration>, decimals: u32, elapsed: Duration) -> TimerSource {
+        TimerSource {
+            accumulated: elapsed,
+            started: None,
+            ..TimerSource::new(countdown, decimals)
+        }
+    }
+
+    // elapsed 전에 시작해 지금도 흐르는 타이머
+    fn running(countdown: Option<Duration>, elapsed: Duration) -> TimerSource {
+        TimerSource {
+            started: Some(Instant::now().checked_sub(elapsed).unwrap()),
+            ..TimerSource::new(countdown, 0)
+        }
+    }
+
+    #[test]
+    fn duration_forms() {
+        let secs = Duration::from_secs_f64;
+        assert_eq!(parse_duration("90"), Some(secs(90.0)));
+        assert_eq!(parse_duration("1.5"), Some(secs(1.5)));
+        assert_eq!(parse_duration("1:30"), Some(secs(90.0)));
+        assert_eq!(parse_duration(" 10:00.5 "), Some(secs(600.5)));
+        assert_eq!(parse_duration("1:30:00"), Some(secs(5400.0)));
+        assert_eq!(parse_duration("0:0:0"), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn malformed_durations_are_rejected() {
+        for text in [
+            "", ":", "1:", "1:2:3:4", "-1", "1:-1", "-1:00", "1.5:00", "a:10", "inf", "NaN",
+            "1e400",
+        ] {
+            assert_eq!(parse_duration(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn timer_decimals() {
+        let nanos = 61_234_567_890;
+        assert_eq!(format_timer(nanos, 0, false), "01:01");
+        assert_eq!(format_timer(nanos, 1, false), "01:01.2");
+        assert_eq!(format_timer(nanos, 2, false), "01:01.23");
+        assert_eq!(format_timer(nanos, 3, false), "01:01.234");
+        assert_eq!(format_timer(5_000_000, 3, false), "00:00.005");
+        assert_eq!(TimerSource::stopwatch(7).decimals, MAX_TIMER_DECIMALS);
+    }
+
+    #[test]
+    fn timer_hours() {
+        assert_eq!(format_timer(3_723_500_000_000, 1, true), "1:02:03.5");
+        assert_eq!(format_timer(3_723_500_000_000, 0, false), "62:03");
+        assert_eq!(format_timer(0, 0, true), "0:00:00");
+        let mut countdown = paused(Some(Duration::from_secs(3600)), 0, Duration::ZERO);
+        assert_eq!(countdown.text(), "1:00:00");
+    }
+
+    #[test]
+    fn countdown_rounds_up_the_remaining_time() {
+        let total = Some(Duration::from_secs(10));
+        assert_eq!(
+            paused(total, 1, Duration::from_millis(1250)).text(),
+            "00:08.8"
+        );
+        assert_eq!(paused(total, 0, Duration::from_millis(1)).text(), "00:10");
+    }
+
+    #[test]
+    fn countdown_stops_at_zero() {
+        let total = Duration::from_secs(10);
+        let mut timer = running(Some(total), Duration::from_secs(20));
+        assert_eq!(timer.text(), "00:00");
+        assert_eq!(timer.started, None);
+        assert_eq!(timer.accumulated, total);
+        assert_eq!(timer.until_change(), None);
+
+        // 끝난 카운트다운을 다시 시작하면 처음부터
+        assert!(timer.toggle_timer());
+        assert!(timer.started.is_some());
+        assert_eq!(timer.accumulated, Duration::ZERO);
+        assert_eq!(timer.text(), "00:10");
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let mut timer = running(None, Duration::from_secs(5));
+        assert!(timer.toggle_timer());
+        assert_eq!(timer.started, None);
+        assert!(timer.accumulated >= Duration::from_secs(5));
+        assert_eq!(timer.text(), "00:05");
+        assert_eq!(timer.until_change(), None);
+        let now = SystemTime::now();
+        assert_eq!(timer.next_update(now), now + IDLE_TIMER_INTERVAL);
+
+        // 다시 시작해도 멈춘 동안의 시간은 더하지 않음
+        let accumulated = timer.accumulated;
+        assert!(timer.toggle_timer());
+        assert!(timer.started.is_some());
+        assert_eq!(timer.accumulated, accumulated);
+        assert_eq!(timer.text(), "00:05");
+        assert!(timer.until_change().is_some());
+
+        assert!(timer.reset_timer());
+        assert_eq!(timer.started, None);
+        assert_eq!(timer.text(), "00:00");
+    }
+}
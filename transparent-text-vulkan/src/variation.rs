@@ -0,0 +1,172 @@
+use ab_glyph_rasterizer::{point, Point, Rasterizer};
+use ttf_parser::{Face, GlyphId, OutlineBuilder, Tag};
+
+const WEIGHT: Tag = Tag::from_bytes(b"wght");
+const WIDTH: Tag = Tag::from_bytes(b"wdth");
+const SLANT: Tag = Tag::from_bytes(b"slnt");
+
+// 가변 폰트의 축 좌표 (None인 축은 폰트 기본값)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct Variation {
+    pub weight: Option<f32>,
+    pub width: Option<f32>,
+    pub slant: Option<f32>,
+}
+
+impl Variation {
+    // 지정된 축 (태그, 좌표)
+    pub fn axes(&self) -> impl Iterator<Item = (Tag, f32)> {
+        [
+            (WEIGHT, self.weight),
+            (WIDTH, self.width),
+            (SLANT, self.slant),
+        ]
+        .into_iter()
+        .filter_map(|(tag, value)| Some((tag, value?)))
+    }
+
+    // 아틀라스에서 인스턴스마다 글리프를 구분하기 위해 font_hash에 섞는 값 (기본값이면 0)
+    pub fn key(&self) -> usize {
+        [self.weight, self.width, self.slant]
+            .into_iter()
+            .enumerate()
+            .fold(0, |hash, (axis, value)| {
+                let bits = value.map_or(0, |value| value.to_bits() as usize + 1);
+                hash ^ bits.wrapping_mul(0x9e37_79b9).rotate_left(axis as u32 * 17)
+            })
+    }
+}
+
+// 래스터라이즈한 커버리지 (fontdue Metrics와 같이 Y 위쪽이 양수인 비트맵 왼쪽 아래 위치)
+pub(crate) struct Coverage {
+    pub xmin: i32,
+    pub ymin: i32,
+    pub width: usize,
+    pub height: usize,
+    // 서브픽셀이면 픽셀마다 RGB 세 개
+    pub pixels: Vec<u8>,
+}
+
+// 축 좌표를 적용한 가변 폰트 인스턴스의 외곽선으로 래스터라이즈 (fontdue는 기본 인스턴스만 읽음)
+// subpixel이면 가로로 세 배 촘촘하게 그려 픽셀마다 RGB 커버리지를 만듦
+pub(crate) fn rasterize(
+    face: &Face,
+    glyph_index: u16,
+    px: f32,
+    variation: &Variation,
+    subpixel: bool,
+) -> Coverage {
+    let mut face = face.clone();
+    for (tag, value) in variation.axes() {
+        face.set_variation(tag, value);
+    }
+
+    let mut outline = Outline::default();
+    let bounds = face.outline_glyph(GlyphId(glyph_index), &mut outline);
+    let scale = px / face.units_per_em() as f32;
+    // 공백처럼 외곽선이 없는 글리프
+    let Some(bounds) = bounds else {
+        return Coverage {
+            xmin: 0,
+            ymin: 0,
+            width: 0,
+            height: 0,
+            pixels: Vec::new(),
+        };
+    };
+
+    let xmin = (bounds.x_min as f32 * scale).floor() as i32;
+    let ymin = (bounds.y_min as f32 * scale).floor() as i32;
+    let xmax = (bounds.x_max as f32 * scale).ceil() as i32;
+    let ymax = (bounds.y_max as f32 * scale).ceil() as i32;
+    let width = (xmax - xmin).max(0) as usize;
+    let height = (ymax - ymin).max(0) as usize;
+    let columns = if subpixel { 3 } else { 1 };
+
+    // 폰트 단위 (Y 위쪽) → 비트맵 픽셀 (Y 아래쪽)
+    let to_pixel = |[x, y]: [f32; 2]| {
+        point(
+            (x * scale - xmin as f32) * columns as f32,
+            ymax as f32 - y * scale,
+        )
+    };
+    let mut rasterizer = Rasterizer::new(width * columns, height);
+    let mut start = Point::default();
+    let mut last = Point::default();
+    for segment in &outline.segments {
+        match *segment {
+            Segment::Move(p) => {
+                start = to_pixel(p);
+                last = start;
+            }
+            Segment::Line(p) => {
+                let p = to_pixel(p);
+                rasterizer.draw_line(last, p);
+                last = p;
+            }
+            Segment::Quad(c, p) => {
+                let p = to_pixel(p);
+                rasterizer.draw_quad(last, to_pixel(c), p);
+                last = p;
+            }
+            Segment::Cubic(c0, c1, p) => {
+                let p = to_pixel(p);
+                rasterizer.draw_cubic(last, to_pixel(c0), to_pixel(c1), p);
+                last = p;
+            }
+            Segment::Close => {
+                rasterizer.draw_line(last, start);
+                last = start;
+            }
+        }
+    }
+
+    let mut pixels = vec![0u8; width * columns * height];
+    rasterizer.for_each_pixel(|index, alpha| {
+        pixels[index] = (alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+    });
+    Coverage {
+        xmin,
+        ymin,
+        width,
+        height,
+        pixels,
+    }
+}
+
+enum Segment {
+    Move([f32; 2]),
+    Line([f32; 2]),
+    Quad([f32; 2], [f32; 2]),
+    Cubic([f32; 2], [f32; 2], [f32; 2]),
+    Close,
+}
+
+// ttf-parser가 내보내는 외곽선 (폰트 단위)
+#[derive(Default)]
+struct Outline {
+    segments: Vec<Segment>,
+}
+
+impl OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.segments.push(Segment::Move([x, y]));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.segments.push(Segment::Line([x, y]));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.segments.push(Segment::Quad([x1, y1], [x, y]));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.segments
+            .push(Segment::Cubic([x1, y1], [x2, y2], [x, y]));
+    }
+
+    fn close(&mut self) {
+        self.segments.push(Segment::Close);
+    }
+}
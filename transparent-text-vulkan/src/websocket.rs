@@ -0,0 +1,42 @@
+use std::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc::Sender,
+};
+
+use tungstenite::Message;
+
+use crate::control::{handle_message, ControlMessage, ControlServer};
+
+impl ControlServer {
+    /// WebSocket 연결에서 텍스트 메시지마다 JSON 하나씩 받음 (`ws://0.0.0.0:9000` 형식)
+    pub fn listen_websocket(&self, url: &str) -> std::io::Result<()> {
+        let address = url
+            .strip_prefix("ws://")
+            .unwrap_or(url)
+            .trim_end_matches('/');
+        let listener = TcpListener::bind(address)?;
+        self.spawn(
+            move || listener.accept().map(|(stream, _)| stream),
+            serve_websocket,
+        );
+        Ok(())
+    }
+}
+
+// 핸드셰이크 후 연결이 닫히거나 이벤트 루프가 끝날 때까지 처리 (ping/pong은 tungstenite가 응답)
+fn serve_websocket(stream: TcpStream, sender: Sender<ControlMessage>) {
+    let Ok(mut socket) = tungstenite::accept(stream) else {
+        return;
+    };
+    while let Ok(message) = socket.read() {
+        let Message::Text(json) = message else {
+            continue;
+        };
+        let Some(reply) = handle_message(&json, &sender) else {
+            break;
+        };
+        if socket.send(Message::Text(reply.to_string())).is_err() {
+            break;
+        }
+    }
+}
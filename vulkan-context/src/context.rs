@@ -0,0 +1,147 @@
+use std::sync::Arc;
+use vulkano::{
+    device::{physical::PhysicalDevice, Device, DeviceExtensions, Features},
+    image::Image,
+    instance::{Instance, InstanceCreateFlags, InstanceCreateInfo, InstanceExtensions},
+    swapchain::{Surface, Swapchain},
+    VulkanLibrary,
+};
+use tracing::info;
+use winit::window::Window;
+
+use crate::{
+    device::{create_device, select_physical_device, GpuSelector, Queues},
+    error::ContextError,
+    present::{create_swapchain, resize_swapchain, select_present_mode, PresentModePreference},
+};
+
+/// `VulkanContext::with_options`의 설정
+#[derive(Debug, Clone, Default)]
+pub struct ContextOptions {
+    /// 지원하면 켤 instance 확장 (창에 그리는 데 필요한 확장은 항상 켬)
+    pub instance_extensions: InstanceExtensions,
+    /// 장치가 지원해야 하는 확장 (`khr_swapchain`은 항상 켬)
+    pub device_extensions: DeviceExtensions,
+    /// 사용할 GPU (None이면 외장 GPU를 우선)
+    pub gpu: Option<GpuSelector>,
+    pub present_mode: PresentModePreference,
+    /// 지원하면 scRGB(HDR) 스왑체인을 만듦
+    pub hdr: bool,
+}
+
+/// 창 하나에 그리는 데 필요한 instance, 장치, 큐와 스왑체인
+///
+/// 창 크기가 바뀌면 `resize`로 스왑체인을 다시 만들고, framebuffer는 `create_framebuffers`로
+/// 새 `images`에 맞춰 만듭니다.
+pub struct VulkanContext {
+    pub instance: Arc<Instance>,
+    pub device: Arc<Device>,
+    pub queues: Queues,
+    pub swapchain: Arc<Swapchain>,
+    pub images: Vec<Arc<Image>>,
+    window: Arc<Window>,
+}
+
+impl VulkanContext {
+    /// 기본 설정(수직 동기화, SDR)으로 `window`에 그리는 context
+    pub fn new(window: Arc<Window>) -> Result<Self, ContextError> {
+        Self::with_options(window, ContextOptions::default(), |_, _, _| {})
+    }
+
+    /// `options`로 만들되, 장치를 고른 뒤 `configure`로 켤 장치 확장과 기능을 더 정함
+    pub fn with_options(
+        window: Arc<Window>,
+        options: ContextOptions,
+        configure: impl FnOnce(&PhysicalDevice, &mut DeviceExtensions, &mut Features),
+    ) -> Result<Self, ContextError> {
+        let library = VulkanLibrary::new()?;
+        // HDR 색 공간은 instance 확장을 켜야 surface 포맷 목록에 나옴
+        let requested_extensions = InstanceExtensions {
+            ext_swapchain_colorspace: options.hdr,
+            ..options.instance_extensions
+        };
+        let enabled_extensions = requested_extensions
+            .intersection(library.supported_extensions())
+            .union(&Surface::required_extensions(&*window));
+        let instance = Instance::new(
+            library,
+            InstanceCreateInfo {
+                flags: InstanceCreateFlags::ENUMERATE_PORTABILITY,
+                enabled_extensions,
+                ..Default::default()
+            },
+        )
+        .map_err(ContextError::Instance)?;
+
+        let surface = Surface::from_window(instance.clone(), window.clone())
+            .map_err(|e| ContextError::Window(e.into()))?;
+
+        let mut device_extensions = DeviceExtensions {
+            khr_swapchain: true,
+            ..options.device_extensions
+        };
+        let (physical_device, queue_family_index) = select_physical_device(
+            &instance,
+            &surface,
+            &device_extensions,
+            options.gpu.as_ref(),
+        )?;
+        info!(
+            gpu = %physical_device.properties().device_name,
+            device_type = ?physical_device.properties().device_type,
+            "사용 중인 GPU"
+        );
+
+        let mut device_features = Features::empty();
+        configure(
+            &physical_device,
+            &mut device_extensions,
+            &mut device_features,
+        );
+        let (device, queues) = create_device(
+            physical_device,
+            queue_family_index,
+            device_extensions,
+            device_features,
+        )?;
+
+        let (swapchain, images) = create_swapchain(
+            &device,
+            surface,
+            window.inner_size().into(),
+            options.present_mode,
+            options.hdr,
+        )?;
+
+        Ok(Self {
+            instance,
+            device,
+            queues,
+            swapchain,
+            images,
+            window,
+        })
+    }
+
+    pub fn window(&self) -> &Arc<Window> {
+        &self.window
+    }
+
+    /// 창 크기에 맞춰 스왑체인을 다시 만듦 (창이 0 크기라 만들지 않았으면 false)
+    pub fn resize(&mut self, present_mode: PresentModePreference) -> Result<bool, ContextError> {
+        let window_extent: [u32; 2] = self.window.inner_size().into();
+        let present_mode = select_present_mode(
+            self.device.physical_device(),
+            self.swapchain.surface(),
+            present_mode,
+        );
+        let Some((swapchain, images)) =
+            resize_swapchain(&self.swapchain, window_extent, present_mode)?
+        else {
+            return Ok(false);
+        };
+        self.swapchain = swapchain;
+        self.images = images;
+        Ok(true)
+    }
+}
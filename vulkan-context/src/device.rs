@@ -0,0 +1,231 @@
+use std::{fmt, str::FromStr, sync::Arc};
+use vulkano::{
+    device::{
+        physical::{PhysicalDevice, PhysicalDeviceType},
+        Device, DeviceCreateInfo, DeviceExtensions, Features, Queue, QueueCreateInfo, QueueFlags,
+    },
+    instance::Instance,
+    swapchain::Surface,
+};
+use tracing::{info, warn};
+
+use crate::error::ContextError;
+
+/// `--gpu`를 주지 않았을 때 읽는 환경 변수
+pub const GPU_ENV: &str = "VK_DEVICE";
+
+/// 사용할 GPU (`print_devices`가 출력하는 번호나, 대소문자를 가리지 않는 장치 이름 일부)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GpuSelector {
+    Index(usize),
+    Name(String),
+}
+
+impl GpuSelector {
+    fn matches(&self, index: usize, device: &PhysicalDevice) -> bool {
+        match self {
+            GpuSelector::Index(selected) => *selected == index,
+            GpuSelector::Name(name) => device
+                .properties()
+                .device_name
+                .to_lowercase()
+                .contains(name.as_str()),
+        }
+    }
+}
+
+impl FromStr for GpuSelector {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err("GPU 번호나 이름이 비어 있습니다".to_string());
+        }
+        Ok(match value.parse() {
+            Ok(index) => GpuSelector::Index(index),
+            Err(_) => GpuSelector::Name(value.to_lowercase()),
+        })
+    }
+}
+
+impl fmt::Display for GpuSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuSelector::Index(index) => write!(f, "{index}"),
+            GpuSelector::Name(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+/// 장치 목록 (시작할 때 `--gpu`로 고를 수 있도록 번호와 함께 출력)
+pub fn enumerate_devices(
+    instance: &Arc<Instance>,
+) -> Result<Vec<Arc<PhysicalDevice>>, ContextError> {
+    let devices: Vec<_> = instance
+        .enumerate_physical_devices()
+        .map_err(|e| ContextError::Device(e.into()))?
+        .collect();
+    for (index, device) in devices.iter().enumerate() {
+        let properties = device.properties();
+        info!(
+            "GPU {index}: {} ({:?})",
+            properties.device_name, properties.device_type
+        );
+    }
+    Ok(devices)
+}
+
+/// 사용할 수 있는 장치 중 하나를 고름
+///
+/// `candidates`는 (`enumerate_devices`의 번호, 장치, 큐 패밀리)입니다.
+/// `selector`에 맞는 장치가 있으면 그것을, 없으면 외장 GPU, 내장 GPU 순서로 고릅니다.
+pub fn choose_device(
+    candidates: Vec<(usize, Arc<PhysicalDevice>, u32)>,
+    selector: Option<&GpuSelector>,
+) -> Result<(Arc<PhysicalDevice>, u32), ContextError> {
+    if let Some(selector) = selector {
+        let selected = candidates
+            .iter()
+            .find(|(index, device, _)| selector.matches(*index, device));
+        if let Some((_, device, queue_family_index)) = selected {
+            return Ok((device.clone(), *queue_family_index));
+        }
+        warn!("GPU '{selector}'를 찾지 못했거나 사용할 수 없어 기본 순서로 고릅니다");
+    }
+
+    candidates
+        .into_iter()
+        .map(|(_, device, queue_family_index)| (device, queue_family_index))
+        .min_by_key(|(device, _)| match device.properties().device_type {
+            PhysicalDeviceType::DiscreteGpu => 0,
+            PhysicalDeviceType::IntegratedGpu => 1,
+            PhysicalDeviceType::VirtualGpu => 2,
+            PhysicalDeviceType::Cpu => 3,
+            PhysicalDeviceType::Other => 4,
+            _ => 5,
+        })
+        .ok_or(ContextError::NoSuitableDevice)
+}
+
+/// `surface`에 표시할 수 있고 `device_extensions`를 지원하는 장치 중 하나를 고름 (`choose_device` 순서)
+///
+/// 그래픽 큐 패밀리도 함께 돌려줍니다.
+pub fn select_physical_device(
+    instance: &Arc<Instance>,
+    surface: &Surface,
+    device_extensions: &DeviceExtensions,
+    gpu: Option<&GpuSelector>,
+) -> Result<(Arc<PhysicalDevice>, u32), ContextError> {
+    let candidates = enumerate_devices(instance)?
+        .into_iter()
+        .enumerate()
+        .filter(|(_, p)| p.supported_extensions().contains(device_extensions))
+        .filter_map(|(index, p)| {
+            p.queue_family_properties()
+                .iter()
+                .enumerate()
+                .position(|(i, q)| {
+                    q.queue_flags.intersects(QueueFlags::GRAPHICS)
+                        && p.surface_support(i as u32, surface).unwrap_or(false)
+                })
+                .map(|i| (index, p, i as u32))
+        })
+        .collect();
+    choose_device(candidates, gpu)
+}
+
+/// 논리 장치와 큐를 만듦 (장치에 있으면 전송 전용 큐와 비동기 컴퓨트 큐도)
+pub fn create_device(
+    physical_device: Arc<PhysicalDevice>,
+    queue_family_index: u32,
+    enabled_extensions: DeviceExtensions,
+    enabled_features: Features,
+) -> Result<(Arc<Device>, Queues), ContextError> {
+    let queue_families = QueueFamilies::new(&physical_device, queue_family_index);
+    let (device, queues) = Device::new(
+        physical_device,
+        DeviceCreateInfo {
+            queue_create_infos: queue_families.create_infos(),
+            enabled_extensions,
+            enabled_features,
+            ..Default::default()
+        },
+    )
+    .map_err(ContextError::Device)?;
+    Ok((device, queue_families.split(queues)?))
+}
+
+/// 그래픽 큐와 함께 만드는 큐 패밀리
+#[derive(Debug, Clone, Copy)]
+pub struct QueueFamilies {
+    pub graphics: u32,
+    /// 그래픽과 컴퓨트가 없는 전송 전용 패밀리 (보통 GPU의 DMA 엔진, 글리프 업로드용)
+    pub transfer: Option<u32>,
+    /// 그래픽이 없는 비동기 컴퓨트 패밀리 (그림자/외곽선 컴퓨트 패스용)
+    pub compute: Option<u32>,
+}
+
+impl QueueFamilies {
+    pub fn new(device: &PhysicalDevice, graphics: u32) -> Self {
+        let find = |required: QueueFlags, excluded: QueueFlags| {
+            device
+                .queue_family_properties()
+                .iter()
+                .position(|family| {
+                    family.queue_flags.contains(required)
+                        && !family.queue_flags.intersects(excluded)
+                })
+                .map(|index| index as u32)
+        };
+        Self {
+            graphics,
+            transfer: find(
+                QueueFlags::TRANSFER,
+                QueueFlags::GRAPHICS | QueueFlags::COMPUTE,
+            ),
+            compute: find(QueueFlags::COMPUTE, QueueFlags::GRAPHICS),
+        }
+    }
+
+    /// 패밀리마다 큐 하나씩 (그래픽, 전송, 컴퓨트 순서)
+    pub fn create_infos(&self) -> Vec<QueueCreateInfo> {
+        [Some(self.graphics), self.transfer, self.compute]
+            .into_iter()
+            .flatten()
+            .map(|queue_family_index| QueueCreateInfo {
+                queue_family_index,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// `Device::new`가 `create_infos` 순서로 돌려준 큐를 나눔
+    pub fn split(
+        &self,
+        mut queues: impl Iterator<Item = Arc<Queue>>,
+    ) -> Result<Queues, ContextError> {
+        let graphics = queues.next().ok_or(ContextError::NoSuitableDevice)?;
+        let transfer = self.transfer.and_then(|_| queues.next());
+        let compute = self.compute.and_then(|_| queues.next());
+        info!(
+            graphics = self.graphics,
+            transfer = ?self.transfer,
+            compute = ?self.compute,
+            "큐 패밀리"
+        );
+        Ok(Queues {
+            graphics,
+            transfer,
+            compute,
+        })
+    }
+}
+
+/// 그래픽 큐와, 장치에 있으면 전송 전용 큐와 비동기 컴퓨트 큐
+#[derive(Clone)]
+pub struct Queues {
+    pub graphics: Arc<Queue>,
+    pub transfer: Option<Arc<Queue>>,
+    pub compute: Option<Arc<Queue>>,
+}
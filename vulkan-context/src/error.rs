@@ -0,0 +1,31 @@
+use vulkano::{LoadingError, Validated, VulkanError};
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Vulkan 초기화와 스왑체인, framebuffer를 만들 때 발생할 수 있는 오류
+#[derive(Debug, thiserror::Error)]
+pub enum ContextError {
+    #[error("Vulkan 라이브러리 로드 실패: {0}")]
+    Library(#[from] LoadingError),
+
+    #[error("Instance 생성 실패: {0}")]
+    Instance(#[source] Validated<VulkanError>),
+
+    #[error("윈도우 surface 생성 실패: {0}")]
+    Window(#[source] BoxError),
+
+    #[error("사용 가능한 GPU가 없습니다")]
+    NoSuitableDevice,
+
+    #[error("Device 생성 실패: {0}")]
+    Device(#[source] Validated<VulkanError>),
+
+    #[error("Swapchain 오류: {0}")]
+    Swapchain(#[source] Validated<VulkanError>),
+
+    #[error("Render pass 생성 실패: {0}")]
+    RenderPass(#[source] BoxError),
+
+    #[error("이미지 생성 실패: {0}")]
+    Image(#[source] BoxError),
+}
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+use vulkano::{
+    device::{physical::PhysicalDevice, Device},
+    format::{ClearValue, Format},
+    image::{view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage, SampleCount},
+    memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    render_pass::{AttachmentLoadOp, Framebuffer, FramebufferCreateInfo, RenderPass},
+};
+
+use crate::error::ContextError;
+
+/// 장치가 지원하는 샘플 수 중 `requested` 이하의 가장 큰 값
+pub fn select_sample_count(physical_device: &PhysicalDevice, requested: u32) -> SampleCount {
+    let supported = physical_device.properties().framebuffer_color_sample_counts;
+    let candidates = [
+        SampleCount::Sample8,
+        SampleCount::Sample4,
+        SampleCount::Sample2,
+    ];
+    candidates
+        .into_iter()
+        .find(|&samples| samples as u32 <= requested && supported.contains_enum(samples))
+        .unwrap_or(SampleCount::Sample1)
+}
+
+/// `format` 이미지에 그리는 단일 서브패스 render pass
+///
+/// MSAA면 첫 번째 첨부가 멀티샘플 이미지, 두 번째가 resolve 대상인 스왑체인 이미지입니다.
+pub fn create_render_pass(
+    device: &Arc<Device>,
+    format: Format,
+    samples: SampleCount,
+) -> Result<Arc<RenderPass>, ContextError> {
+    if samples == SampleCount::Sample1 {
+        return vulkano::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: format,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: Store,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .map_err(|e| ContextError::RenderPass(e.into()));
+    }
+
+    // 멀티샘플 이미지는 resolve 후 버리므로 저장하지 않음
+    vulkano::single_pass_renderpass!(
+        device.clone(),
+        attachments: {
+            multisampled: {
+                format: format,
+                samples: samples as u32,
+                load_op: Clear,
+                store_op: DontCare,
+            },
+            color: {
+                format: format,
+                samples: 1,
+                load_op: DontCare,
+                store_op: Store,
+            },
+        },
+        pass: {
+            color: [multisampled],
+            color_resolve: [color],
+            depth_stencil: {},
+        },
+    )
+    .map_err(|e| ContextError::RenderPass(e.into()))
+}
+
+/// Clear로 여는 첨부만 `color`로 지움 (MSAA의 resolve 대상은 None)
+pub fn clear_values(render_pass: &RenderPass, color: [f32; 4]) -> Vec<Option<ClearValue>> {
+    render_pass
+        .attachments()
+        .iter()
+        .map(|attachment| (attachment.load_op == AttachmentLoadOp::Clear).then(|| color.into()))
+        .collect()
+}
+
+/// MSAA로 그릴 `extent` 크기의 멀티샘플 이미지 (`samples`가 1이면 None)
+///
+/// resolve 후에는 내용이 필요 없으므로 모든 스왑체인 이미지가 하나를 공유합니다.
+pub fn multisampled_image(
+    memory_allocator: &Arc<StandardMemoryAllocator>,
+    format: Format,
+    samples: SampleCount,
+    extent: [u32; 3],
+) -> Result<Option<Arc<ImageView>>, ContextError> {
+    if samples == SampleCount::Sample1 {
+        return Ok(None);
+    }
+    let image = Image::new(
+        memory_allocator.clone(),
+        ImageCreateInfo {
+            image_type: ImageType::Dim2d,
+            format,
+            extent,
+            usage: ImageUsage::COLOR_ATTACHMENT | ImageUsage::TRANSIENT_ATTACHMENT,
+            samples,
+            ..Default::default()
+        },
+        AllocationCreateInfo::default(),
+    )
+    .map_err(|e| ContextError::Image(e.into()))?;
+    let view = ImageView::new_default(image).map_err(ContextError::Swapchain)?;
+    Ok(Some(view))
+}
+
+/// `images`마다 `create_render_pass`로 만든 render pass의 framebuffer를 만듦
+///
+/// render pass가 MSAA면(첫 번째 첨부가 멀티샘플) 멀티샘플 이미지도 함께 만들어 붙입니다.
+pub fn create_framebuffers(
+    render_pass: &Arc<RenderPass>,
+    images: &[Arc<Image>],
+    memory_allocator: &Arc<StandardMemoryAllocator>,
+) -> Result<Vec<Arc<Framebuffer>>, ContextError> {
+    let Some(image) = images.first() else {
+        return Ok(Vec::new());
+    };
+    let samples = render_pass
+        .attachments()
+        .first()
+        .map_or(SampleCount::Sample1, |attachment| attachment.samples);
+    let multisampled =
+        multisampled_image(memory_allocator, image.format(), samples, image.extent())?;
+    images
+        .iter()
+        .map(|image| {
+            let view = ImageView::new_default(image.clone()).map_err(ContextError::Swapchain)?;
+            Framebuffer::new(
+                render_pass.clone(),
+                FramebufferCreateInfo {
+                    attachments: multisampled.iter().cloned().chain([view]).collect(),
+                    ..Default::default()
+                },
+            )
+            .map_err(ContextError::Swapchain)
+        })
+        .collect()
+}
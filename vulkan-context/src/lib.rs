@@ -0,0 +1,25 @@
+//! 창에 Vulkan으로 그리는 데 필요한 공통 초기화
+//!
+//! instance와 장치 선택, 큐 생성, 투명한 창용 스왑체인과 다시 만들기, MSAA render pass와 framebuffer를
+//! `VulkanContext` 하나로 묶어 `transparent-text-vulkan`과 `rust-vulkan`이 함께 씁니다.
+
+mod context;
+mod device;
+mod error;
+mod framebuffer;
+mod present;
+
+pub use context::{ContextOptions, VulkanContext};
+pub use device::{
+    choose_device, create_device, enumerate_devices, select_physical_device, GpuSelector,
+    QueueFamilies, Queues, GPU_ENV,
+};
+pub use error::{BoxError, ContextError};
+pub use framebuffer::{
+    clear_values, create_framebuffers, create_render_pass, multisampled_image, select_sample_count,
+};
+pub use present::{
+    create_swapchain, resize_swapchain, select_hdr_surface_format, select_present_mode,
+    select_surface_format, swapchain_extent, PresentModePreference, SwapchainImages,
+    HDR_SURFACE_FORMAT, PREFERRED_SURFACE_FORMATS,
+};
@@ -0,0 +1,275 @@
+use std::sync::Arc;
+use vulkano::{
+    device::{physical::PhysicalDevice, Device, DeviceOwned},
+    format::{Format, NumericFormat},
+    image::{Image, ImageUsage},
+    swapchain::{
+        ColorSpace, CompositeAlpha, PresentMode, Surface, SurfaceCapabilities, Swapchain,
+        SwapchainCreateInfo,
+    },
+    Validated, VulkanError,
+};
+use tracing::{info, warn};
+
+use crate::error::ContextError;
+
+/// 스왑체인과 그 이미지 (`create_swapchain`, `resize_swapchain`)
+pub type SwapchainImages = (Arc<Swapchain>, Vec<Arc<Image>>);
+
+// 스왑체인을 다시 만들 때 surface가 오래되었다고(OutOfDate) 하면 다시 시도하는 횟수
+const RECREATE_ATTEMPTS: usize = 3;
+
+/// 원하는 프레젠트 방식 (지원되지 않으면 FIFO로 대체)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PresentModePreference {
+    /// FIFO (수직 동기화, 모든 장치에서 지원)
+    #[default]
+    Vsync,
+    /// Mailbox (티어링 없이 최신 프레임 표시)
+    Mailbox,
+    /// Immediate (동기화 없음, 티어링 가능)
+    Immediate,
+}
+
+impl PresentModePreference {
+    pub fn next(&self) -> Self {
+        match self {
+            PresentModePreference::Vsync => PresentModePreference::Mailbox,
+            PresentModePreference::Mailbox => PresentModePreference::Immediate,
+            PresentModePreference::Immediate => PresentModePreference::Vsync,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            PresentModePreference::Vsync => "수직 동기화 (FIFO)",
+            PresentModePreference::Mailbox => "Mailbox",
+            PresentModePreference::Immediate => "Immediate",
+        }
+    }
+
+    // 선호 순서대로 시도할 프레젠트 모드
+    fn candidates(&self) -> &'static [PresentMode] {
+        match self {
+            PresentModePreference::Vsync => &[PresentMode::Fifo],
+            PresentModePreference::Mailbox => &[PresentMode::Mailbox, PresentMode::Fifo],
+            PresentModePreference::Immediate => &[
+                PresentMode::Immediate,
+                PresentMode::Mailbox,
+                PresentMode::Fifo,
+            ],
+        }
+    }
+}
+
+/// surface가 지원하는 모드 중 선호에 가장 가까운 프레젠트 모드 선택
+///
+/// FIFO는 Vulkan 명세상 항상 지원되므로 최종 대체값으로 사용합니다.
+pub fn select_present_mode(
+    physical_device: &PhysicalDevice,
+    surface: &Surface,
+    preference: PresentModePreference,
+) -> PresentMode {
+    let supported: Vec<PresentMode> = physical_device
+        .surface_present_modes(surface, Default::default())
+        .map(|modes| modes.into_iter().collect())
+        .unwrap_or_default();
+
+    preference
+        .candidates()
+        .iter()
+        .copied()
+        .find(|mode| supported.contains(mode))
+        .unwrap_or(PresentMode::Fifo)
+}
+
+/// 선호하는 surface 포맷 (앞쪽일수록 우선, 모두 sRGB 비선형 색 공간에서)
+///
+/// 대부분의 Windows/Linux 드라이버는 BGRA, 일부 모바일과 MoltenVK는 RGBA를 먼저 내놓으므로
+/// 목록 순서대로 찾아 플랫폼마다 감마가 달라지지 않게 합니다.
+pub const PREFERRED_SURFACE_FORMATS: [Format; 4] = [
+    Format::B8G8R8A8_SRGB,
+    Format::R8G8B8A8_SRGB,
+    Format::B8G8R8A8_UNORM,
+    Format::R8G8B8A8_UNORM,
+];
+
+/// surface 포맷 선택 (`PREFERRED_SURFACE_FORMATS` 순서, 없으면 다른 sRGB 포맷, 그래도 없으면 첫 번째)
+///
+/// sRGB 포맷이면 블렌딩이 선형 공간에서 이루어져 반투명한 글자 가장자리가 어둡게 뭉개지지 않습니다.
+/// 렌더러는 render pass의 포맷을 보고 셰이더 출력을 알맞게 바꾸므로 UNORM 포맷이어도 색은 같습니다.
+pub fn select_surface_format(
+    physical_device: &PhysicalDevice,
+    surface: &Surface,
+) -> Result<(Format, ColorSpace), ContextError> {
+    let formats = physical_device
+        .surface_formats(surface, Default::default())
+        .map_err(ContextError::Swapchain)?;
+    let nonlinear: Vec<Format> = formats
+        .iter()
+        .filter(|&&(_, color_space)| color_space == ColorSpace::SrgbNonLinear)
+        .map(|&(format, _)| format)
+        .collect();
+
+    PREFERRED_SURFACE_FORMATS
+        .into_iter()
+        .find(|preferred| nonlinear.contains(preferred))
+        .or_else(|| {
+            nonlinear
+                .iter()
+                .copied()
+                .find(|format| format.numeric_format_color() == Some(NumericFormat::SRGB))
+        })
+        .map(|format| (format, ColorSpace::SrgbNonLinear))
+        .or_else(|| formats.first().copied())
+        .ok_or(ContextError::Swapchain(Validated::Error(
+            VulkanError::FormatNotSupported,
+        )))
+}
+
+/// HDR surface 포맷 (scRGB: 16비트 부동소수점, 확장 sRGB 선형 색 공간)
+///
+/// 1.0이 SDR 흰색이고 그보다 큰 값은 HDR 디스플레이에서 더 밝게 표시됩니다.
+pub const HDR_SURFACE_FORMAT: (Format, ColorSpace) =
+    (Format::R16G16B16A16_SFLOAT, ColorSpace::ExtendedSrgbLinear);
+
+/// surface가 HDR 포맷을 지원하면 반환 (instance에 `ext_swapchain_colorspace`를 켜야 목록에 나옴)
+///
+/// 지원하지 않으면 `None`이고, `select_surface_format`의 SDR 포맷을 쓰면 발광은 톤 매핑되어 1.0 안에 들어갑니다.
+pub fn select_hdr_surface_format(
+    physical_device: &PhysicalDevice,
+    surface: &Surface,
+) -> Result<Option<(Format, ColorSpace)>, ContextError> {
+    let formats = physical_device
+        .surface_formats(surface, Default::default())
+        .map_err(ContextError::Swapchain)?;
+    Ok(formats
+        .contains(&HDR_SURFACE_FORMAT)
+        .then_some(HDR_SURFACE_FORMAT))
+}
+
+/// 창 크기를 surface가 허용하는 스왑체인 크기로 맞춤 (최소화 등으로 그릴 수 없으면 `None`)
+///
+/// surface가 크기를 정해 두었으면(`current_extent`) 그 크기를 쓰고,
+/// 아니면 창 크기를 `min_image_extent`..=`max_image_extent` 범위로 자릅니다.
+pub fn swapchain_extent(
+    capabilities: &SurfaceCapabilities,
+    window_extent: [u32; 2],
+) -> Option<[u32; 2]> {
+    if window_extent.contains(&0) {
+        return None;
+    }
+    let extent = capabilities.current_extent.unwrap_or(window_extent);
+    let min = capabilities.min_image_extent;
+    let max = capabilities.max_image_extent;
+    let extent = [0, 1].map(|i| extent[i].clamp(min[i], max[i].max(min[i])));
+    (!extent.contains(&0)).then_some(extent)
+}
+
+/// 스왑체인을 창 크기와 `present_mode`로 다시 만듦 (그릴 수 없는 크기면 `None`, 다음 프레임에 다시 시도)
+///
+/// 크기는 `swapchain_extent`로 맞추고, 드라이버가 만드는 도중 surface가 오래되었다고(OutOfDate) 하면
+/// capabilities를 다시 읽어 몇 번 더 시도합니다. 이전 스왑체인 이미지를 쓰는 프레임은 호출하기 전에 기다리세요.
+pub fn resize_swapchain(
+    swapchain: &Arc<Swapchain>,
+    window_extent: [u32; 2],
+    present_mode: PresentMode,
+) -> Result<Option<SwapchainImages>, ContextError> {
+    let physical_device = swapchain.device().physical_device();
+    for _ in 0..RECREATE_ATTEMPTS {
+        let capabilities = physical_device
+            .surface_capabilities(swapchain.surface(), Default::default())
+            .map_err(ContextError::Swapchain)?;
+        let Some(image_extent) = swapchain_extent(&capabilities, window_extent) else {
+            return Ok(None);
+        };
+        match swapchain.recreate(SwapchainCreateInfo {
+            image_extent,
+            present_mode,
+            ..swapchain.create_info()
+        }) {
+            Ok(recreated) => return Ok(Some(recreated)),
+            Err(Validated::Error(VulkanError::OutOfDate)) => continue,
+            Err(e) => return Err(ContextError::Swapchain(e)),
+        }
+    }
+    Ok(None)
+}
+
+/// `surface`에 창 크기(`window_extent`)의 스왑체인을 만듦 (투명한 창용)
+///
+/// `hdr`이고 지원하면 scRGB, 아니면 `select_surface_format`의 포맷을 쓰고,
+/// 알파 합성은 premultiplied alpha를 우선합니다 (지원하지 않으면 불투명 합성일 수 있으니 `composite_alpha()`를 확인하세요).
+/// 창이 아직 0 크기면(최소화 상태로 시작 등) 가장 작은 크기로 만들므로 나중에 `resize_swapchain`으로 다시 만드세요.
+/// 스크린샷처럼 복사할 수 있도록 지원하면 `TRANSFER_SRC` 용도도 켭니다.
+pub fn create_swapchain(
+    device: &Arc<Device>,
+    surface: Arc<Surface>,
+    window_extent: [u32; 2],
+    present_mode: PresentModePreference,
+    hdr: bool,
+) -> Result<SwapchainImages, ContextError> {
+    let surface_capabilities = device
+        .physical_device()
+        .surface_capabilities(&surface, Default::default())
+        .map_err(ContextError::Swapchain)?;
+
+    // HDR을 요청했고 지원하면 scRGB, 아니면 sRGB 포맷을 우선해 블렌딩이 선형 공간에서 이루어지도록 함
+    let hdr_format = if hdr {
+        select_hdr_surface_format(device.physical_device(), &surface)?
+    } else {
+        None
+    };
+    if hdr && hdr_format.is_none() {
+        warn!("HDR 포맷을 지원하지 않아 SDR로 그립니다");
+    }
+    let (image_format, image_color_space) = match hdr_format {
+        Some(format) => format,
+        None => select_surface_format(device.physical_device(), &surface)?,
+    };
+    info!(format = ?image_format, color_space = ?image_color_space, "Surface Format");
+
+    // premultiplied alpha로 그리므로 PreMultiplied를 우선 (PostMultiplied면 알파가 두 번 곱해짐)
+    let supported_composite_alpha = surface_capabilities.supported_composite_alpha;
+    let composite_alpha = [
+        CompositeAlpha::PreMultiplied,
+        CompositeAlpha::Inherit,
+        CompositeAlpha::PostMultiplied,
+    ]
+    .into_iter()
+    .find(|&alpha| supported_composite_alpha.contains_enum(alpha))
+    .or_else(|| supported_composite_alpha.into_iter().next())
+    .unwrap_or(CompositeAlpha::Opaque);
+    info!(composite_alpha = ?composite_alpha, "Composite Alpha");
+
+    let selected_present_mode =
+        select_present_mode(device.physical_device(), &surface, present_mode);
+    info!(
+        present_mode = ?selected_present_mode,
+        requested = present_mode.name(),
+        "Present Mode"
+    );
+
+    let min_image_extent = surface_capabilities
+        .min_image_extent
+        .map(|length| length.max(1));
+    let image_extent =
+        swapchain_extent(&surface_capabilities, window_extent).unwrap_or(min_image_extent);
+
+    Swapchain::new(
+        device.clone(),
+        surface,
+        SwapchainCreateInfo {
+            min_image_count: surface_capabilities.min_image_count.max(2),
+            image_format,
+            image_color_space,
+            image_extent,
+            image_usage: ImageUsage::COLOR_ATTACHMENT
+                | (surface_capabilities.supported_usage_flags & ImageUsage::TRANSFER_SRC),
+            composite_alpha,
+            present_mode: selected_present_mode,
+            ..Default::default()
+        },
+    )
+    .map_err(ContextError::Swapchain)
+}